@@ -46,6 +46,9 @@ codegen_ssa_incompatible_linking_modifiers = link modifiers combination `+bundle
 
 codegen_ssa_insufficient_vs_code_product = VS Code is a different product, and is not sufficient.
 
+codegen_ssa_invalid_inline_budget = invalid argument for `inline_budget`
+    .note = an unsuffixed integer value, e.g., `200`, is expected
+
 codegen_ssa_invalid_link_ordinal_nargs = incorrect number of arguments to `#[link_ordinal]`
     .note = the attribute requires exactly one argument
 
@@ -314,6 +314,9 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: LocalDefId) -> CodegenFnAttrs {
                     codegen_fn_attrs.link_ordinal = ordinal;
                 }
             }
+            sym::inline_budget => {
+                codegen_fn_attrs.inline_budget = check_inline_budget(tcx, attr);
+            }
             sym::no_sanitize => {
                 no_sanitize_span = Some(attr.span);
                 if let Some(list) = attr.meta_item_list() {
@@ -431,7 +434,10 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: LocalDefId) -> CodegenFnAttrs {
         match attr.meta_kind() {
             Some(MetaItemKind::Word) => InlineAttr::Hint,
             Some(MetaItemKind::List(ref items)) => {
-                inline_span = Some(attr.span);
+                // `#[inline(never_callees)]` neither requests nor forbids inlining the function.
+                if !list_contains_name(&items, sym::never_callees) {
+                    inline_span = Some(attr.span);
+                }
                 if items.len() != 1 {
                     struct_span_err!(
                         tcx.sess.diagnostic(),
@@ -445,6 +451,21 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: LocalDefId) -> CodegenFnAttrs {
                     InlineAttr::Always
                 } else if list_contains_name(&items, sym::never) {
                     InlineAttr::Never
+                } else if list_contains_name(&items, sym::never_callees) {
+                    if !tcx.features().inline_never_callees {
+                        feature_err(
+                            &tcx.sess.parse_sess,
+                            sym::inline_never_callees,
+                            attr.span,
+                            "`#[inline(never_callees)]` is currently unstable",
+                        )
+                        .emit();
+                    }
+                    // This only affects inlining into the function, not inlining of the function
+                    // itself, so it is combined with another `#[inline]` attribute instead of
+                    // overriding it. `check_attr` doesn't report such pairs as duplicates.
+                    codegen_fn_attrs.flags |= CodegenFnAttrFlags::NO_INLINE_CALLEES;
+                    ia
                 } else {
                     struct_span_err!(
                         tcx.sess.diagnostic(),
@@ -590,6 +611,19 @@ fn should_inherit_track_caller(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
     false
 }
 
+fn check_inline_budget(tcx: TyCtxt<'_>, attr: &ast::Attribute) -> Option<usize> {
+    use rustc_ast::{LitIntType, LitKind, MetaItemLit};
+    if let Some([item]) = attr.meta_item_list().as_deref()
+        && let Some(MetaItemLit { kind: LitKind::Int(budget, LitIntType::Unsuffixed), .. }) =
+            item.lit()
+    {
+        Some(usize::try_from(*budget).unwrap_or(usize::MAX))
+    } else {
+        tcx.sess.emit_err(errors::InvalidInlineBudget { span: attr.span });
+        None
+    }
+}
+
 fn check_link_ordinal(tcx: TyCtxt<'_>, attr: &ast::Attribute) -> Option<u16> {
     use rustc_ast::{LitIntType, LitKind, MetaItemLit};
     let meta_item_list = attr.meta_item_list();
@@ -998,6 +998,14 @@ pub struct InvalidNoSanitize {
     pub span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_invalid_inline_budget)]
+#[note]
+pub struct InvalidInlineBudget {
+    #[primary_span]
+    pub span: Span,
+}
+
 #[derive(Diagnostic)]
 #[diag(codegen_ssa_invalid_link_ordinal_nargs)]
 #[note]
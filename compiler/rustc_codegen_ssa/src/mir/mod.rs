@@ -163,21 +163,31 @@ pub fn codegen_mir<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
     let start_llbb = Bx::append_block(cx, llfn, "start");
     let mut start_bx = Bx::build(cx, start_llbb);
 
-    if mir.basic_blocks.iter().any(|bb| {
-        bb.is_cleanup || matches!(bb.terminator().unwind(), Some(mir::UnwindAction::Terminate))
+    let reachable_blocks = monomorphic_reachable_blocks(cx.tcx(), instance, mir);
+
+    if mir.basic_blocks.iter_enumerated().any(|(bb, data)| {
+        reachable_blocks.contains(bb)
+            && (data.is_cleanup
+                || matches!(data.terminator().unwind(), Some(mir::UnwindAction::Terminate)))
     }) {
         start_bx.set_personality_fn(cx.eh_personality());
     }
 
     let cleanup_kinds = base::wants_msvc_seh(cx.tcx().sess).then(|| analyze::cleanup_kinds(&mir));
 
-    let cached_llbbs: IndexVec<mir::BasicBlock, CachedLlbb<Bx::BasicBlock>> =
-        mir.basic_blocks
-            .indices()
-            .map(|bb| {
-                if bb == mir::START_BLOCK { CachedLlbb::Some(start_llbb) } else { CachedLlbb::None }
-            })
-            .collect();
+    let cached_llbbs: IndexVec<mir::BasicBlock, CachedLlbb<Bx::BasicBlock>> = mir
+        .basic_blocks
+        .indices()
+        .map(|bb| {
+            if bb == mir::START_BLOCK {
+                CachedLlbb::Some(start_llbb)
+            } else if reachable_blocks.contains(bb) {
+                CachedLlbb::None
+            } else {
+                CachedLlbb::Skip
+            }
+        })
+        .collect();
 
     let mut fx = FunctionCx {
         instance,
@@ -270,6 +280,40 @@ pub fn codegen_mir<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
     }
 }
 
+/// Returns the blocks of `mir` that are reachable once it is monomorphized for `instance`.
+///
+/// `Drop` terminators of types without drop glue just branch to their target, see
+/// `codegen_drop_terminator`, so the cleanup blocks that only they unwind to are never reached,
+/// and do not need to be codegened.
+fn monomorphic_reachable_blocks<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    mir: &mir::Body<'tcx>,
+) -> BitSet<mir::BasicBlock> {
+    let mut reachable = BitSet::new_empty(mir.basic_blocks.len());
+    let mut stack = vec![mir::START_BLOCK];
+    while let Some(bb) = stack.pop() {
+        if !reachable.insert(bb) {
+            continue;
+        }
+        let terminator = mir[bb].terminator();
+        if let mir::TerminatorKind::Drop { place, target, .. } = terminator.kind {
+            let ty = instance.subst_mir_and_normalize_erasing_regions(
+                tcx,
+                ty::ParamEnv::reveal_all(),
+                ty::EarlyBinder::bind(place.ty(mir, tcx).ty),
+            );
+            if let ty::InstanceDef::DropGlue(_, None) = Instance::resolve_drop_in_place(tcx, ty).def
+            {
+                stack.push(target);
+                continue;
+            }
+        }
+        stack.extend(terminator.successors());
+    }
+    reachable
+}
+
 /// Produces, for each argument, a `Value` pointing at the
 /// argument's value. As arguments are places, these are always
 /// indirect.
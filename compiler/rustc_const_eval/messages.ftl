@@ -29,6 +29,8 @@ const_eval_closure_non_const =
     cannot call non-const closure in {const_eval_const_context}s
 const_eval_consider_dereferencing =
     consider dereferencing here
+const_eval_const_accesses_mut_global = constant accesses mutable global memory
+
 const_eval_const_accesses_static = constant accesses static
 
 const_eval_const_context = {$kind ->
@@ -215,6 +217,9 @@ const_eval_max_num_nodes_in_const = maximum number of nodes exceeded in constant
 const_eval_memory_access_test = memory access failed
 const_eval_memory_exhausted =
     tried to allocate more memory than available to compiler
+const_eval_memory_limit_reached =
+    constant evaluation exceeded the memory limit
+const_eval_memory_path = the offending memory is reached through `{$path}`
 const_eval_modified_global =
     modifying a static's initial value from another static's initializer
 
@@ -223,6 +228,12 @@ const_eval_mut_deref =
 
 const_eval_mutable_ref_in_const = {$front_matter}: encountered mutable reference in a `const`
 const_eval_never_val = {$front_matter}: encountered a value of the never type `!`
+const_eval_non_const_drop_impl =
+    the `Drop` impl for `{$ty}` is not `const`
+
+const_eval_non_const_drop_param =
+    `{$param}` is not known to be destructible at compile-time, consider adding a `~const Destruct` bound
+
 const_eval_non_const_fmt_macro_call =
     cannot call non-const formatting macro in {const_eval_const_context}s
 
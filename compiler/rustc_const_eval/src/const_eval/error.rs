@@ -15,6 +15,7 @@
 #[derive(Clone, Debug)]
 pub enum ConstEvalErrKind {
     ConstAccessesStatic,
+    ConstAccessesMutGlobal,
     ModifiedGlobal,
     AssertFailure(AssertKind<ConstInt>),
     Panic { msg: Symbol, line: u32, col: u32, file: Symbol },
@@ -27,6 +28,7 @@ fn diagnostic_message(&self) -> DiagnosticMessage {
         use ConstEvalErrKind::*;
         match self {
             ConstAccessesStatic => const_eval_const_accesses_static,
+            ConstAccessesMutGlobal => const_eval_const_accesses_mut_global,
             ModifiedGlobal => const_eval_modified_global,
             Panic { .. } => const_eval_panic,
             AssertFailure(x) => x.diagnostic_message(),
@@ -39,7 +41,7 @@ fn add_args(
     ) {
         use ConstEvalErrKind::*;
         match *self {
-            ConstAccessesStatic | ModifiedGlobal | Abort(_) => {}
+            ConstAccessesStatic | ConstAccessesMutGlobal | ModifiedGlobal | Abort(_) => {}
             AssertFailure(kind) => kind.add_args(adder),
             Panic { msg, line, col, file } => {
                 adder("msg".into(), msg.into_diagnostic_arg());
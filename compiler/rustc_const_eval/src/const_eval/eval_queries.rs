@@ -2,11 +2,18 @@
 use crate::errors::ConstEvalError;
 
 use either::{Left, Right};
+use std::collections::VecDeque;
 
+use rustc_data_structures::fx::FxHashSet;
 use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
 use rustc_middle::mir;
-use rustc_middle::mir::interpret::{ErrorHandled, InterpErrorInfo};
+use rustc_middle::mir::interpret::{
+    AllocId, ErrorHandled, GlobalAlloc, InterpErrorInfo, UndefinedBehaviorInfo,
+    ValidationErrorInfo, ValidationErrorKind,
+};
 use rustc_middle::mir::pretty::write_allocation_bytes;
+use rustc_middle::mir::Mutability;
 use rustc_middle::traits::Reveal;
 use rustc_middle::ty::layout::LayoutOf;
 use rustc_middle::ty::print::with_no_trimmed_paths;
@@ -20,7 +27,7 @@
 use crate::interpret::{
     intern_const_alloc_recursive, Allocation, ConstAlloc, ConstValue, CtfeValidationMode, GlobalId,
     Immediate, InternKind, InterpCx, InterpError, InterpResult, MPlaceTy, MemoryKind, OpTy,
-    RefTracking, StackPopCleanup,
+    RefTracking, StackPopCleanup, StaticPtrs,
 };
 
 // Returns a pointer to where the result lives
@@ -173,6 +180,13 @@ pub(super) fn op_to_const<'tcx>(
                 debug!("ScalarPair(a: {:?}, b: {:?})", a, b);
                 // We know `offset` is relative to the allocation, so we can use `into_parts`.
                 let (data, start) = match a.to_pointer(ecx).unwrap().into_parts() {
+                    // A slice pointing into a static cannot be a `ConstValue::Slice`, as that
+                    // needs to own its data. Keep the wide reference itself in memory instead.
+                    (Some(alloc_id), _)
+                        if matches!(ecx.tcx.global_alloc(alloc_id), GlobalAlloc::Static(_)) =>
+                    {
+                        return to_const_value(&op.assert_mem_place());
+                    }
                     (Some(alloc_id), offset) => {
                         (ecx.tcx.global_alloc(alloc_id).unwrap_memory(), offset.bytes())
                     }
@@ -362,10 +376,13 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
                     let mode = match tcx.static_mutability(cid.instance.def_id()) {
                         Some(_) if cid.promoted.is_some() => {
                             // Promoteds in statics are allowed to point to statics.
-                            CtfeValidationMode::Const { inner, allow_static_ptrs: true }
+                            CtfeValidationMode::Const { inner, static_ptrs: StaticPtrs::Allowed }
                         }
                         Some(_) => CtfeValidationMode::Regular, // a `static`
-                        None => CtfeValidationMode::Const { inner, allow_static_ptrs: false },
+                        None => CtfeValidationMode::Const {
+                            inner,
+                            static_ptrs: StaticPtrs::for_const(tcx),
+                        },
                     };
                     ecx.const_validate_operand(&mplace.into(), path, &mut ref_tracking, mode)?;
                     inner = true;
@@ -380,19 +397,38 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
 
                 let ub_note = matches!(error, InterpError::UndefinedBehavior(_)).then(|| {});
 
-                let alloc = ecx.tcx.global_alloc(alloc_id).unwrap_memory().inner();
-                let mut bytes = String::new();
-                if alloc.size() != abi::Size::ZERO {
-                    bytes = "\n".into();
-                    // FIXME(translation) there might be pieces that are translatable.
-                    write_allocation_bytes(*ecx.tcx, alloc, &mut bytes, "    ").unwrap();
-                }
-                let raw_bytes = errors::RawBytesNote {
-                    size: alloc.size().bytes(),
-                    align: alloc.align.bytes(),
-                    bytes,
+                // For references to statics or mutable memory, the path to that memory is more
+                // helpful than the raw bytes, which only show that there is a pointer.
+                let memory_path = match error {
+                    InterpError::UndefinedBehavior(UndefinedBehaviorInfo::Validation(
+                        ValidationErrorInfo {
+                            kind:
+                                ValidationErrorKind::PtrToStatic { .. }
+                                | ValidationErrorKind::PtrToMut { .. },
+                            ..
+                        },
+                    )) => mutable_memory_path(tcx, key.param_env, def, alloc_id),
+                    _ => None,
                 };
 
+                let raw_bytes = (memory_path.is_none()
+                    || tcx.sess.opts.unstable_opts.extra_const_ub_info)
+                    .then(|| {
+                        let alloc = ecx.tcx.global_alloc(alloc_id).unwrap_memory().inner();
+                        let mut bytes = String::new();
+                        if alloc.size() != abi::Size::ZERO {
+                            bytes = "\n".into();
+                            // FIXME(translation) there might be pieces that are translatable.
+                            write_allocation_bytes(*ecx.tcx, alloc, &mut bytes, "    ").unwrap();
+                        }
+                        errors::RawBytesNote {
+                            size: alloc.size().bytes(),
+                            align: alloc.align.bytes(),
+                            bytes,
+                        }
+                    });
+                let memory_path = memory_path.map(|path| errors::MemoryPathNote { path });
+
                 Err(super::report(
                     *ecx.tcx,
                     error,
@@ -402,6 +438,7 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
                         span,
                         ub_note,
                         frames,
+                        memory_path,
                         raw_bytes,
                     },
                 ))
@@ -412,3 +449,55 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
         }
     }
 }
+
+/// Renders the chain of references from the constant `def` to the first static or mutable memory
+/// it may not point to, e.g. `C -> &S_MUT -> static mut S_MUT`. `root` is the allocation holding
+/// the value of the constant.
+fn mutable_memory_path<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    def: DefId,
+    root: AllocId,
+) -> Option<String> {
+    let name = |def_id| {
+        tcx.opt_item_name(def_id).map_or_else(|| tcx.def_path_str(def_id), |name| name.to_string())
+    };
+
+    // Walk breadth-first, so that the shortest path is reported.
+    let mut queue = VecDeque::from([(root, vec![name(def)])]);
+    let mut visited = FxHashSet::default();
+    while let Some((alloc_id, path)) = queue.pop_front() {
+        if !visited.insert(alloc_id) {
+            continue;
+        }
+        let Some(GlobalAlloc::Memory(alloc)) = tcx.try_get_global_alloc(alloc_id) else {
+            continue;
+        };
+        for &(_, target) in alloc.inner().provenance().ptrs().iter() {
+            let mut path = path.clone();
+            match tcx.try_get_global_alloc(target) {
+                Some(GlobalAlloc::Static(did)) => {
+                    // Like validation, accept statics that can be read from constants.
+                    if StaticPtrs::for_const(tcx).permits(tcx, param_env, did) {
+                        continue;
+                    }
+                    let is_mutable = tcx.is_mutable_static(did);
+                    let name = name(did);
+                    path.push(format!("&{name}"));
+                    path.push(format!("static {}{name}", if is_mutable { "mut " } else { "" }));
+                    return Some(path.join(" -> "));
+                }
+                Some(GlobalAlloc::Memory(target_alloc)) => {
+                    path.push("&<anonymous memory>".to_string());
+                    if target_alloc.inner().mutability == Mutability::Mut {
+                        path.push("mutable memory".to_string());
+                        return Some(path.join(" -> "));
+                    }
+                    queue.push_back((target, path));
+                }
+                Some(GlobalAlloc::Function(_) | GlobalAlloc::VTable(..)) | None => {}
+            }
+        }
+    }
+    None
+}
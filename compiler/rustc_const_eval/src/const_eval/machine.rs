@@ -22,8 +22,8 @@
 
 use crate::errors::{LongRunning, LongRunningWarn};
 use crate::interpret::{
-    self, compile_time_machine, AllocId, ConstAllocation, FnVal, Frame, ImmTy, InterpCx,
-    InterpResult, OpTy, PlaceTy, Pointer, Scalar,
+    self, compile_time_machine, AllocId, AllocRange, ConstAllocation, FnVal, Frame, ImmTy,
+    InterpCx, InterpResult, OpTy, PlaceTy, Pointer, Scalar,
 };
 use crate::{errors, fluent_generated as fluent};
 
@@ -61,6 +61,10 @@ pub struct CompileTimeInterpreter<'mir, 'tcx> {
 
     /// Whether to check alignment during evaluation.
     pub(super) check_alignment: CheckAlignment,
+
+    /// The number of bytes currently allocated by the evaluation, which is limited by
+    /// `-Zconst-eval-memory-limit`.
+    pub(super) allocated_bytes: u64,
 }
 
 #[derive(Copy, Clone)]
@@ -90,6 +94,7 @@ pub(crate) fn new(can_access_statics: bool, check_alignment: CheckAlignment) ->
             stack: Vec::new(),
             can_access_statics,
             check_alignment,
+            allocated_bytes: 0,
         }
     }
 }
@@ -683,7 +688,7 @@ fn stack_mut<'a>(
     }
 
     fn before_access_global(
-        _tcx: TyCtxt<'tcx>,
+        tcx: TyCtxt<'tcx>,
         machine: &Self,
         alloc_id: AllocId,
         alloc: ConstAllocation<'tcx>,
@@ -703,23 +708,60 @@ fn before_access_global(
                 // Machine configuration allows us read from anything (e.g., `static` initializer).
                 Ok(())
             } else if static_def_id.is_some() {
-                // Machine configuration does not allow us to read statics
-                // (e.g., `const` initializer).
-                // See const_eval::machine::MemoryExtra::can_access_statics for why
-                // this check is so important: if we could read statics, we could read pointers
-                // to mutable allocations *inside* statics. These allocations are not themselves
-                // statics, so pointers to them can get around the check in `validity.rs`.
-                Err(ConstEvalErrKind::ConstAccessesStatic.into())
+                if tcx.features().const_refs_to_static && alloc.mutability == Mutability::Not {
+                    // The static is neither a `static mut` nor has interior mutability, so its
+                    // value is the same now and at run-time. Pointers read from it may still
+                    // point to mutable memory, which is rejected below once they are read from.
+                    Ok(())
+                } else {
+                    // Machine configuration does not allow us to read statics
+                    // (e.g., `const` initializer).
+                    // See const_eval::machine::MemoryExtra::can_access_statics for why
+                    // this check is so important: if we could read statics, we could read
+                    // pointers to mutable allocations *inside* statics. These allocations are
+                    // not themselves statics, so pointers to them can get around the check in
+                    // `validity.rs`.
+                    Err(ConstEvalErrKind::ConstAccessesStatic.into())
+                }
+            } else if alloc.mutability == Mutability::Mut {
+                // Never accept a read from something mutable, that would be unsound. The reason
+                // is that as the content of this allocation may be different now and at
+                // run-time, so if we permit reading now we might return the wrong value.
+                // This is only reachable through a pointer read from an immutable static.
+                Err(ConstEvalErrKind::ConstAccessesMutGlobal.into())
             } else {
                 // Immutable global, this read is fine.
-                // But make sure we never accept a read from something mutable, that would be
-                // unsound. The reason is that as the content of this allocation may be different
-                // now and at run-time, so if we permit reading now we might return the wrong value.
-                assert_eq!(alloc.mutability, Mutability::Not);
                 Ok(())
             }
         }
     }
+
+    fn before_memory_allocation(
+        tcx: TyCtxt<'tcx>,
+        machine: &mut Self,
+        size: Size,
+        _kind: interpret::MemoryKind<Self::MemoryKind>,
+    ) -> InterpResult<'tcx> {
+        let allocated_bytes = machine.allocated_bytes.saturating_add(size.bytes());
+        if let Some(limit) = tcx.sess.opts.unstable_opts.const_eval_memory_limit
+            && allocated_bytes > limit
+        {
+            throw_exhaust!(MemoryLimitReached)
+        }
+        machine.allocated_bytes = allocated_bytes;
+        Ok(())
+    }
+
+    fn before_memory_deallocation(
+        _tcx: TyCtxt<'tcx>,
+        machine: &mut Self,
+        _alloc_extra: &mut Self::AllocExtra,
+        _prov: (AllocId, Self::ProvenanceExtra),
+        range: AllocRange,
+    ) -> InterpResult<'tcx> {
+        machine.allocated_bytes = machine.allocated_bytes.saturating_sub(range.size.bytes());
+        Ok(())
+    }
 }
 
 // Please do not add any code below the above `Machine` trait impl. I (oli-obk) plan more cleanups
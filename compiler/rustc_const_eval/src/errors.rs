@@ -271,6 +271,12 @@ pub struct RawBytesNote {
     pub bytes: String,
 }
 
+#[derive(Subdiagnostic)]
+#[note(const_eval_memory_path)]
+pub struct MemoryPathNote {
+    pub path: String,
+}
+
 #[derive(Diagnostic)]
 #[diag(const_eval_for_loop_into_iter_non_const, code = "E0015")]
 pub struct NonConstForLoopIntoIter<'tcx> {
@@ -382,6 +388,20 @@ pub struct LiveDrop<'tcx> {
     pub dropped_ty: Ty<'tcx>,
     #[label(const_eval_dropped_at_label)]
     pub dropped_at: Option<Span>,
+    #[subdiagnostic]
+    pub note: Option<NonConstDropNote<'tcx>>,
+}
+
+#[derive(Subdiagnostic)]
+pub enum NonConstDropNote<'tcx> {
+    #[note(const_eval_non_const_drop_impl)]
+    Impl {
+        #[primary_span]
+        span: Span,
+        ty: Ty<'tcx>,
+    },
+    #[note(const_eval_non_const_drop_param)]
+    Param { param: Ty<'tcx> },
 }
 
 #[derive(LintDiagnostic)]
@@ -422,7 +442,9 @@ pub struct UndefinedBehavior {
     #[subdiagnostic]
     pub frames: Vec<FrameNote>,
     #[subdiagnostic]
-    pub raw_bytes: RawBytesNote,
+    pub memory_path: Option<MemoryPathNote>,
+    #[subdiagnostic]
+    pub raw_bytes: Option<RawBytesNote>,
 }
 
 pub trait ReportErrorExt {
@@ -861,6 +883,7 @@ fn diagnostic_message(&self) -> DiagnosticMessage {
         match self {
             ResourceExhaustionInfo::StackFrameLimitReached => const_eval_stack_frame_limit_reached,
             ResourceExhaustionInfo::MemoryExhausted => const_eval_memory_exhausted,
+            ResourceExhaustionInfo::MemoryLimitReached => const_eval_memory_limit_reached,
             ResourceExhaustionInfo::AddressSpaceFull => const_eval_address_space_full,
         }
     }
@@ -384,6 +384,17 @@ fn before_memory_write(
         Ok(())
     }
 
+    /// Hook for performing extra checks on a memory allocation, before the memory is allocated.
+    #[inline(always)]
+    fn before_memory_allocation(
+        _tcx: TyCtxt<'tcx>,
+        _machine: &mut Self,
+        _size: Size,
+        _kind: MemoryKind<Self::MemoryKind>,
+    ) -> InterpResult<'tcx> {
+        Ok(())
+    }
+
     /// Hook for performing extra operations on a memory deallocation.
     #[inline(always)]
     fn before_memory_deallocation(
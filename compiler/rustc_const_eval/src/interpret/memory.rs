@@ -201,6 +201,7 @@ pub fn allocate_ptr(
         align: Align,
         kind: MemoryKind<M::MemoryKind>,
     ) -> InterpResult<'tcx, Pointer<M::Provenance>> {
+        M::before_memory_allocation(*self.tcx, &mut self.machine, size, kind)?;
         let alloc = if M::PANIC_ON_ALLOC_FAIL {
             Allocation::uninit(size, align)
         } else {
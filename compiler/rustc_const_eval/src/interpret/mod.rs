@@ -26,7 +26,7 @@
 pub use self::memory::{AllocKind, AllocRef, AllocRefMut, FnVal, Memory, MemoryKind};
 pub use self::operand::{ImmTy, Immediate, OpTy, Operand};
 pub use self::place::{MPlaceTy, MemPlace, MemPlaceMeta, Place, PlaceTy};
-pub use self::validity::{CtfeValidationMode, RefTracking};
+pub use self::validity::{CtfeValidationMode, RefTracking, StaticPtrs};
 pub use self::visitor::{MutValueVisitor, Value, ValueVisitor};
 
 pub(crate) use self::intrinsics::eval_nullary_intrinsic;
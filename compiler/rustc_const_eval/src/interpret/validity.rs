@@ -12,12 +12,13 @@
 use rustc_ast::Mutability;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_hir as hir;
+use rustc_hir::def_id::DefId;
 use rustc_middle::mir::interpret::{
     ExpectedKind, InterpError, InvalidMetaKind, PointerKind, ValidationErrorInfo,
     ValidationErrorKind, ValidationErrorKind::*,
 };
-use rustc_middle::ty;
 use rustc_middle::ty::layout::{LayoutOf, TyAndLayout};
+use rustc_middle::ty::{self, TyCtxt};
 use rustc_span::symbol::{sym, Symbol};
 use rustc_target::abi::{
     Abi, FieldIdx, Scalar as ScalarAbi, Size, VariantIdx, Variants, WrappingRange,
@@ -126,8 +127,45 @@ pub enum CtfeValidationMode {
     /// `inner` says if this is an inner, indirect allocation (as opposed to the top-level const
     /// allocation). Being an inner allocation makes a difference because the top-level allocation
     /// of a `const` is copied for each use, but the inner allocations are implicitly shared.
-    /// `allow_static_ptrs` says if pointers to statics are permitted (which is the case for promoteds in statics).
-    Const { inner: bool, allow_static_ptrs: bool },
+    /// `static_ptrs` says which statics the value may point to.
+    Const { inner: bool, static_ptrs: StaticPtrs },
+}
+
+/// Which statics the value of a `const` may point to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StaticPtrs {
+    /// No statics at all.
+    Forbidden,
+    /// Statics that are neither `static mut` nor have interior mutability. Their value is the
+    /// same at compile time and at run time, so constants may refer to them with
+    /// `const_refs_to_static`.
+    Frozen,
+    /// All statics (which is the case for promoteds in statics).
+    Allowed,
+}
+
+impl StaticPtrs {
+    /// The statics that a `const` or a promoted outside of a static may point to.
+    pub fn for_const(tcx: TyCtxt<'_>) -> Self {
+        if tcx.features().const_refs_to_static { StaticPtrs::Frozen } else { StaticPtrs::Forbidden }
+    }
+
+    /// Whether a pointer to the static `did` is permitted.
+    pub fn permits<'tcx>(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ty::ParamEnv<'tcx>,
+        did: DefId,
+    ) -> bool {
+        match self {
+            StaticPtrs::Forbidden => false,
+            StaticPtrs::Frozen => {
+                !tcx.is_mutable_static(did)
+                    && tcx.type_of(did).subst_identity().is_freeze(tcx, param_env)
+            }
+            StaticPtrs::Allowed => true,
+        }
+    }
 }
 
 /// State for tracking recursive validation of references
@@ -411,10 +449,9 @@ fn check_safe_pointer(
                         // Special handling for pointers to statics (irrespective of their type).
                         assert!(!self.ecx.tcx.is_thread_local_static(did));
                         assert!(self.ecx.tcx.is_static(did));
-                        if matches!(
-                            self.ctfe_mode,
-                            Some(CtfeValidationMode::Const { allow_static_ptrs: false, .. })
-                        ) {
+                        if let Some(CtfeValidationMode::Const { static_ptrs, .. }) = self.ctfe_mode
+                            && !static_ptrs.permits(*self.ecx.tcx, self.ecx.param_env, did)
+                        {
                             // See const_eval::machine::MemoryExtra::can_access_statics for why
                             // this check is so important.
                             // This check is reachable when the const just referenced the static,
@@ -194,6 +194,10 @@ pub struct Checker<'mir, 'tcx> {
 
     error_emitted: Option<ErrorGuaranteed>,
     secondary_errors: Vec<Diagnostic>,
+
+    /// Whether to only record that an illegal operation was found instead of emitting an error.
+    dry_run: bool,
+    found_illegal_op: bool,
 }
 
 impl<'mir, 'tcx> Deref for Checker<'mir, 'tcx> {
@@ -213,9 +217,25 @@ pub fn new(ccx: &'mir ConstCx<'mir, 'tcx>) -> Self {
             local_has_storage_dead: None,
             error_emitted: None,
             secondary_errors: Vec::new(),
+            dry_run: false,
+            found_illegal_op: false,
         }
     }
 
+    /// Creates a checker that does not emit any errors, and only records whether the body contains
+    /// an operation that is not allowed in its const context. This is used to find out whether a
+    /// non-const item could be made const, by checking it with a `ConstCx` of the desired
+    /// `const_kind`.
+    pub fn new_dry_run(ccx: &'mir ConstCx<'mir, 'tcx>) -> Self {
+        Checker { dry_run: true, ..Checker::new(ccx) }
+    }
+
+    /// Returns `true` if a checker created with [`Checker::new_dry_run`] found an operation that
+    /// is not allowed in its const context.
+    pub fn found_illegal_op(&self) -> bool {
+        self.found_illegal_op
+    }
+
     pub fn check_body(&mut self) {
         let ConstCx { tcx, body, .. } = *self.ccx;
         let def_id = self.ccx.def_id();
@@ -312,6 +332,11 @@ pub fn check_op_spanned<O: NonConstOp<'tcx>>(&mut self, op: O, span: Span) {
             Status::Forbidden => None,
         };
 
+        if self.dry_run {
+            self.found_illegal_op = true;
+            return;
+        }
+
         if self.tcx.sess.opts.unstable_opts.unleash_the_miri_inside_of_you {
             self.tcx.sess.miri_unleashed_feature(span, gate);
             return;
@@ -334,7 +359,9 @@ fn check_static(&mut self, def_id: DefId, span: Span) {
         if self.tcx.is_thread_local_static(def_id) {
             self.tcx.sess.delay_span_bug(span, "tls access is checked in `Rvalue::ThreadLocalRef`");
         }
-        self.check_op_spanned(ops::StaticAccess, span)
+        let mutable = self.tcx.is_mutable_static(def_id)
+            || !self.tcx.type_of(def_id).subst_identity().is_freeze(self.tcx, self.param_env);
+        self.check_op_spanned(ops::StaticAccess { mutable }, span)
     }
 
     fn check_local_or_return_ty(&mut self, ty: Ty<'tcx>, local: Local) {
@@ -988,7 +1015,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
             TerminatorKind::Drop { place: dropped_place, .. } => {
                 // If we are checking live drops after drop-elaboration, don't emit duplicate
                 // errors here.
-                if super::post_drop_elaboration::checking_enabled(self.ccx) {
+                if !self.dry_run && super::post_drop_elaboration::checking_enabled(self.ccx) {
                     return;
                 }
 
@@ -1,7 +1,10 @@
 //! Concrete error types for all operations which may be invalid in a certain const context.
 
+use std::collections::VecDeque;
+
 use hir::def_id::LocalDefId;
 use hir::{ConstContext, LangItem};
+use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::{error_code, DiagnosticBuilder, ErrorGuaranteed};
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
@@ -10,7 +13,9 @@
 use rustc_middle::mir;
 use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::subst::{GenericArgKind, SubstsRef};
-use rustc_middle::ty::{suggest_constraining_type_param, Adt, Closure, FnDef, FnPtr, Param, Ty};
+use rustc_middle::ty::{
+    suggest_constraining_type_param, Adt, Array, Closure, FnDef, FnPtr, Param, Slice, Tuple, Ty,
+};
 use rustc_middle::ty::{Binder, TraitRef};
 use rustc_middle::util::{call_kind, CallDesugaringKind, CallKind};
 use rustc_session::parse::feature_err;
@@ -18,6 +23,7 @@
 use rustc_span::{BytePos, Pos, Span, Symbol};
 use rustc_trait_selection::traits::SelectionContext;
 
+use super::qualifs::{NeedsNonConstDrop, Qualif};
 use super::ConstCx;
 use crate::errors;
 
@@ -416,15 +422,58 @@ fn build_error(
         ccx: &ConstCx<'_, 'tcx>,
         span: Span,
     ) -> DiagnosticBuilder<'tcx, ErrorGuaranteed> {
+        // Pointing at the culprit only helps if it can actually be made `const`.
+        let note = if ccx.tcx.features().const_trait_impl {
+            non_const_drop_note(ccx, self.dropped_ty)
+        } else {
+            None
+        };
         ccx.tcx.sess.create_err(errors::LiveDrop {
             span,
             dropped_ty: self.dropped_ty,
             kind: ccx.const_kind(),
             dropped_at: self.dropped_at,
+            note,
         })
     }
 }
 
+/// Finds the type in the drop glue of `dropped_ty` that keeps it from being dropped at
+/// compile-time: either an ADT with a `Drop` impl that is not `const`, or a type parameter
+/// without a `~const Destruct` bound.
+fn non_const_drop_note<'tcx>(
+    ccx: &ConstCx<'_, 'tcx>,
+    dropped_ty: Ty<'tcx>,
+) -> Option<errors::NonConstDropNote<'tcx>> {
+    let tcx = ccx.tcx;
+    let mut visited = FxHashSet::default();
+    let mut queue = VecDeque::from([dropped_ty]);
+    while let Some(ty) = queue.pop_front() {
+        if !visited.insert(ty) || !NeedsNonConstDrop::in_any_value_of_ty(ccx, ty) {
+            continue;
+        }
+        match *ty.kind() {
+            Adt(adt, substs) => {
+                if let Some(dtor) = adt.destructor(tcx)
+                    && dtor.constness == hir::Constness::NotConst
+                {
+                    return Some(errors::NonConstDropNote::Impl {
+                        span: tcx.def_span(tcx.parent(dtor.did)),
+                        ty,
+                    });
+                }
+                queue.extend(adt.all_fields().map(|field| field.ty(tcx, substs)));
+            }
+            Tuple(tys) => queue.extend(tys),
+            Array(ty, _) | Slice(ty) => queue.push_back(ty),
+            Closure(_, substs) => queue.extend(substs.as_closure().upvar_tys()),
+            Param(_) => return Some(errors::NonConstDropNote::Param { param: ty }),
+            _ => {}
+        }
+    }
+    None
+}
+
 #[derive(Debug)]
 /// A borrow of a type that contains an `UnsafeCell` somewhere. The borrow never escapes to
 /// the final value of the constant.
@@ -634,13 +683,19 @@ fn build_error(
 
 /// An access to a (non-thread-local) `static`.
 #[derive(Debug)]
-pub struct StaticAccess;
+pub struct StaticAccess {
+    /// Whether the `static` is a `static mut` or has interior mutability. Constants can never
+    /// refer to such statics, as their value may change at runtime.
+    pub mutable: bool,
+}
 impl<'tcx> NonConstOp<'tcx> for StaticAccess {
     fn status_in_item(&self, ccx: &ConstCx<'_, 'tcx>) -> Status {
         if let hir::ConstContext::Static(_) = ccx.const_kind() {
             Status::Allowed
-        } else {
+        } else if self.mutable {
             Status::Forbidden
+        } else {
+            Status::Unstable(sym::const_refs_to_static)
         }
     }
 
@@ -649,11 +704,16 @@ fn build_error(
         ccx: &ConstCx<'_, 'tcx>,
         span: Span,
     ) -> DiagnosticBuilder<'tcx, ErrorGuaranteed> {
-        ccx.tcx.sess.create_err(errors::StaticAccessErr {
+        let err = errors::StaticAccessErr {
             span,
             kind: ccx.const_kind(),
             teach: ccx.tcx.sess.teach(&error_code!(E0013)).then_some(()),
-        })
+        };
+        if self.mutable {
+            ccx.tcx.sess.create_err(err)
+        } else {
+            ccx.tcx.sess.create_feature_err(err, sym::const_refs_to_static)
+        }
     }
 }
 
@@ -1,6 +1,7 @@
 //! Validates the MIR to ensure that invariants are upheld.
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def::DefKind;
 use rustc_index::bit_set::BitSet;
 use rustc_index::IndexVec;
 use rustc_infer::traits::Reveal;
@@ -11,7 +12,7 @@
     MirPass, MirPhase, NonDivergingIntrinsic, NullOp, Operand, Place, PlaceElem, PlaceRef,
     ProjectionElem, RetagKind, RuntimePhase, Rvalue, SourceScope, Statement, StatementKind,
     Terminator, TerminatorKind, UnOp, UnwindAction, VarDebugInfo, VarDebugInfoContents,
-    START_BLOCK,
+    OUTERMOST_SOURCE_SCOPE, START_BLOCK,
 };
 use rustc_middle::ty::{self, InstanceDef, ParamEnv, Ty, TyCtxt, TypeVisitableExt};
 use rustc_mir_dataflow::impls::MaybeStorageLive;
@@ -72,6 +73,7 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         };
         checker.visit_body(body);
         checker.check_cleanup_control_flow();
+        checker.check_source_scopes();
 
         if let MirPhase::Runtime(_) = body.phase {
             if let ty::InstanceDef::Item(_) = body.source.instance {
@@ -233,6 +235,71 @@ fn check_cleanup_control_flow(&self) {
         }
     }
 
+    /// Checks that the `parent_scope`, `inlined` and `inlined_parent_scope` links of the source
+    /// scopes are consistent. Debuginfo relies on them to attribute code to the right inlined
+    /// function, so breaking them, e.g. in the MIR inliner, results in bogus backtraces.
+    fn check_source_scopes(&self) {
+        // Scopes don't have a location of their own, so report them at the start of the body.
+        let fail = |scope: SourceScope, msg: String| {
+            self.fail(START_BLOCK.start_location(), format!("source scope {:?}: {}", scope, msg));
+        };
+
+        for (scope, scope_data) in self.body.source_scopes.iter_enumerated() {
+            if scope == OUTERMOST_SOURCE_SCOPE {
+                if scope_data.parent_scope.is_some()
+                    || scope_data.inlined.is_some()
+                    || scope_data.inlined_parent_scope.is_some()
+                {
+                    fail(
+                        scope,
+                        "the outermost scope must neither be inlined nor have a parent".to_string(),
+                    );
+                }
+                continue;
+            }
+
+            let Some(parent) = scope_data.parent_scope else {
+                fail(scope, "only the outermost scope can be without a parent".to_string());
+                continue;
+            };
+            if parent >= scope {
+                fail(scope, format!("parent scope {:?} must precede its children", parent));
+                continue;
+            }
+
+            // The nearest inlined scope is the parent, or the one nearest to the parent.
+            let parent_data = &self.body.source_scopes[parent];
+            let expected_inlined_parent = if parent_data.inlined.is_some() {
+                Some(parent)
+            } else {
+                parent_data.inlined_parent_scope
+            };
+            if scope_data.inlined_parent_scope != expected_inlined_parent {
+                fail(
+                    scope,
+                    format!(
+                        "inlined parent scope is {:?}, but the nearest inlined ancestor is {:?}",
+                        scope_data.inlined_parent_scope, expected_inlined_parent,
+                    ),
+                );
+            }
+
+            if let Some((callee, _)) = scope_data.inlined {
+                let has_body = match callee.def {
+                    InstanceDef::Intrinsic(_) | InstanceDef::Virtual(..) => false,
+                    InstanceDef::Item(def_id) => {
+                        let def_kind = self.tcx.def_kind(def_id);
+                        def_kind.is_fn_like() || matches!(def_kind, DefKind::Ctor(..))
+                    }
+                    _ => true,
+                };
+                if !has_body {
+                    fail(scope, format!("inlined callee {} is not a function with a body", callee));
+                }
+            }
+        }
+    }
+
     fn check_unwind_edge(&mut self, location: Location, unwind: UnwindAction) {
         let is_cleanup = self.body.basic_blocks[location.block].is_cleanup;
         match unwind {
@@ -438,6 +505,22 @@ fn visit_projection_elem(
     }
 
     fn visit_var_debug_info(&mut self, debuginfo: &VarDebugInfo<'tcx>) {
+        // Arguments are declared in the outermost scope of their function, which is the scope
+        // marked as inlined for the arguments of inlined callees.
+        let scope = debuginfo.source_info.scope;
+        if debuginfo.argument_index.is_some()
+            && let Some(scope_data) = self.body.source_scopes.get(scope)
+            && scope != OUTERMOST_SOURCE_SCOPE
+            && scope_data.inlined.is_none()
+        {
+            self.fail(
+                START_BLOCK.start_location(),
+                format!(
+                    "argument debuginfo for {:?} is not in the outermost scope of a function",
+                    debuginfo.name
+                ),
+            );
+        }
         let check_place = |place: Place<'_>| {
             if place.projection.iter().any(|p| !p.can_use_in_debuginfo()) {
                 self.fail(
@@ -4,6 +4,9 @@ driver_impl_ice_exclude_cargo_defaults = some of the compiler flags provided by
 
 driver_impl_ice_flags = compiler flags: {$flags}
 driver_impl_ice_version = rustc {$version} running on {$triple}
+driver_impl_inline_mir_report_incremental = the MIR inlining report doesn't include the functions whose MIR was reused from the incremental cache
+driver_impl_inline_mir_report_write_fail = failed to write MIR inlining report to `{$path}` due to error `{$err}`
+
 driver_impl_rlink_empty_version_number = The input does not contain version number
 
 driver_impl_rlink_encoding_version_mismatch = .rlink file was produced with encoding version `{$version_array}`, but the current version is `{$rlink_version}`
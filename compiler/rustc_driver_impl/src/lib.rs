@@ -54,7 +54,7 @@
 use std::fs;
 use std::io::{self, IsTerminal, Read, Write};
 use std::panic::{self, catch_unwind};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{self, Command, Stdio};
 use std::str;
 use std::sync::OnceLock;
@@ -80,8 +80,9 @@
 mod session_diagnostics;
 
 use crate::session_diagnostics::{
-    RLinkEmptyVersionNumber, RLinkEncodingVersionMismatch, RLinkRustcVersionMismatch,
-    RLinkWrongFileType, RlinkNotAFile, RlinkUnableToRead,
+    InlineMirReportIncremental, InlineMirReportWriteFail, RLinkEmptyVersionNumber,
+    RLinkEncodingVersionMismatch, RLinkRustcVersionMismatch, RLinkWrongFileType, RlinkNotAFile,
+    RlinkUnableToRead,
 };
 
 fluent_messages! { "../messages.ftl" }
@@ -437,6 +438,14 @@ fn run_compiler(
                 sess.code_stats.print_vtable_sizes(crate_name);
             }
 
+            if sess.opts.unstable_opts.dump_elaboration {
+                sess.code_stats.print_elaboration_stats();
+            }
+
+            if let Some(path) = &sess.opts.unstable_opts.inline_mir_report {
+                write_inline_mir_report(sess, path);
+            }
+
             let linker = queries.linker()?;
             Ok(Some(linker))
         })?;
@@ -462,6 +471,39 @@ fn run_compiler(
     })
 }
 
+/// Writes the decisions recorded by the MIR inliner as a JSON array, for `-Zinline-mir-report`.
+///
+/// The decisions are recorded as a side effect of computing the optimized MIR, so they are
+/// missing for every function whose optimized MIR is loaded from the incremental cache instead.
+fn write_inline_mir_report(sess: &Session, path: &Path) {
+    if sess.opts.incremental.is_some() {
+        sess.emit_warning(InlineMirReportIncremental);
+    }
+    let decisions: Vec<_> = sess
+        .code_stats
+        .take_inline_decisions()
+        .into_iter()
+        .map(|info| {
+            serde_json::json!({
+                "caller": info.caller,
+                "callee": info.callee,
+                "inlined": info.rejection.is_none(),
+                "reason": info.rejection,
+                "cost": info.cost,
+                "threshold": info.threshold,
+                "caller_inlined_cost": info.caller_inlined_cost,
+            })
+        })
+        .collect();
+    let report = serde_json::to_string_pretty(&decisions).unwrap();
+    if let Err(err) = fs::write(path, report) {
+        sess.emit_err(InlineMirReportWriteFail {
+            path: path.display().to_string(),
+            err: err.to_string(),
+        });
+    }
+}
+
 // Extract output directory and file from matches.
 fn make_output(matches: &getopts::Matches) -> (Option<PathBuf>, Option<OutFileName>) {
     let odir = matches.opt_str("out-dir").map(|o| PathBuf::from(&o));
@@ -39,6 +39,17 @@ pub(crate) struct UnprettyDumpFail {
     pub err: String,
 }
 
+#[derive(Diagnostic)]
+#[diag(driver_impl_inline_mir_report_incremental)]
+pub(crate) struct InlineMirReportIncremental;
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_inline_mir_report_write_fail)]
+pub(crate) struct InlineMirReportWriteFail {
+    pub path: String,
+    pub err: String,
+}
+
 #[derive(Diagnostic)]
 #[diag(driver_impl_ice)]
 pub(crate) struct Ice;
@@ -154,8 +154,14 @@ pub fn set(&self, features: &mut Features, span: Span) {
     (active, compiler_builtins, "1.13.0", None, None),
     /// Allows writing custom MIR
     (active, custom_mir, "1.65.0", None, None),
+    /// Allows `#[do_not_elaborate]` on type parameters to keep their bounds from being elaborated.
+    (active, do_not_elaborate, "CURRENT_RUSTC_VERSION", None, None),
     /// Outputs useful `assert!` messages
     (active, generic_assert, "1.63.0", None, None),
+    /// Allows `#[inline_budget(N)]` to set the MIR inlining threshold of a function.
+    (active, inline_budget, "CURRENT_RUSTC_VERSION", None, None),
+    /// Allows `#[inline(never_callees)]` to prevent the MIR inliner from inlining into a function.
+    (active, inline_never_callees, "CURRENT_RUSTC_VERSION", None, None),
     /// Allows using the `rust-intrinsic`'s "ABI".
     (active, intrinsics, "1.0.0", None, None),
     /// Allows using `#[lang = ".."]` attribute for linking items to special compiler logic.
@@ -363,6 +369,8 @@ pub fn set(&self, features: &mut Features, span: Span) {
     (active, const_precise_live_drops, "1.46.0", Some(73255), None),
     /// Allows references to types with interior mutability within constants
     (active, const_refs_to_cell, "1.51.0", Some(80384), None),
+    /// Allows constants to refer to immutable statics without interior mutability.
+    (active, const_refs_to_static, "CURRENT_RUSTC_VERSION", Some(119618), None),
     /// Allows `impl const Trait for T` syntax.
     (active, const_trait_impl, "1.42.0", Some(67792), None),
     /// Allows the `?` operator in const contexts.
@@ -454,6 +454,10 @@ pub struct BuiltinAttribute {
         optimize, Normal, template!(List: "size|speed"), ErrorPreceding, optimize_attribute,
         experimental!(optimize),
     ),
+    gated!(
+        inline_budget, Normal, template!(List: "N"), ErrorFollowing, @only_local: true,
+        experimental!(inline_budget),
+    ),
 
     gated!(
         ffi_returns_twice, Normal, template!(Word), WarnFollowing, experimental!(ffi_returns_twice)
@@ -491,6 +495,11 @@ pub struct BuiltinAttribute {
     // RFC 2397
     gated!(do_not_recommend, Normal, template!(Word), WarnFollowing, experimental!(do_not_recommend)),
 
+    gated!(
+        do_not_elaborate, Normal, template!(Word), WarnFollowing, @only_local: true,
+        experimental!(do_not_elaborate),
+    ),
+
     // `#[cfi_encoding = ""]`
     gated!(
         cfi_encoding, Normal, template!(NameValueStr: "encoding"), ErrorPreceding,
@@ -44,6 +44,16 @@ hir_analysis_const_param_ty_impl_on_non_adt =
     the trait `ConstParamTy` may not be implemented for this type
     .label = type is not a structure or enumeration
 
+hir_analysis_const_refs_to_static_mut = constant takes a reference to the mutable static `{$name}`
+    .note = the value of a `static mut` can change at runtime, so constants cannot refer to it
+    .help = to get a raw pointer to `{$name}` without creating a reference, use `std::ptr::{$macro_name}!` outside of constants
+
+hir_analysis_const_refs_to_static_mut_atomic =
+    consider making `{$name}` a `static` of type `std::sync::atomic::{$atomic}` instead
+
+hir_analysis_const_refs_to_static_mut_cell =
+    consider making `{$name}` a `static` of type `std::cell::SyncUnsafeCell` instead
+
 hir_analysis_const_specialize = cannot specialize on const impl with non-const impl
 
 hir_analysis_copy_impl_on_non_adt =
@@ -62,6 +72,10 @@ hir_analysis_drop_impl_on_wrong_item =
 
 hir_analysis_drop_impl_reservation = reservation `Drop` impls are not supported
 
+hir_analysis_dyn_principal_implied_bound =
+    the trait object in `{$ty}` requires `{$predicate}`, which is not satisfied
+    .note = this where clause of the trait object's principal trait will be required for the type to be well-formed
+
 hir_analysis_empty_specialization = specialization impl does not specialize any associated items
     .note = impl is a specialization of this impl
 
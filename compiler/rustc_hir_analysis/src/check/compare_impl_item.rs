@@ -224,7 +224,8 @@ fn compare_method_predicate_entailment<'tcx>(
         Reveal::UserFacing,
         hir::Constness::NotConst,
     );
-    let param_env = traits::normalize_param_env_or_error(tcx, param_env, normalize_cause);
+    let param_env =
+        traits::normalize_param_env_or_error(tcx, param_env, Vec::new(), normalize_cause);
 
     let infcx = &tcx.infer_ctxt().build();
     let ocx = ObligationCtxt::new(infcx);
@@ -1837,7 +1838,8 @@ fn compare_type_predicate_entailment<'tcx>(
         Reveal::UserFacing,
         hir::Constness::NotConst,
     );
-    let param_env = traits::normalize_param_env_or_error(tcx, param_env, normalize_cause);
+    let param_env =
+        traits::normalize_param_env_or_error(tcx, param_env, Vec::new(), normalize_cause);
     let infcx = tcx.infer_ctxt().build();
     let ocx = ObligationCtxt::new(&infcx);
 
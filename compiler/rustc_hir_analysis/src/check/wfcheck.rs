@@ -1,5 +1,6 @@
 use crate::autoderef::Autoderef;
 use crate::constrained_generic_params::{identify_constrained_generic_params, Parameter};
+use crate::errors::DynPrincipalImpliedBound;
 
 use rustc_ast as ast;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexSet};
@@ -19,6 +20,7 @@
     TypeVisitable, TypeVisitableExt, TypeVisitor,
 };
 use rustc_middle::ty::{GenericArgKind, InternalSubsts};
+use rustc_session::lint::builtin::DYN_PRINCIPAL_IMPLIED_BOUNDS;
 use rustc_session::parse::feature_err;
 use rustc_span::symbol::{sym, Ident, Symbol};
 use rustc_span::{Span, DUMMY_SP};
@@ -30,7 +32,7 @@
     self, ObligationCause, ObligationCauseCode, ObligationCtxt, WellFormedLoc,
 };
 
-use std::cell::LazyCell;
+use std::cell::{LazyCell, RefCell};
 use std::ops::{ControlFlow, Deref};
 
 pub(super) struct WfCheckingCtxt<'a, 'tcx> {
@@ -38,6 +40,9 @@ pub(super) struct WfCheckingCtxt<'a, 'tcx> {
     span: Span,
     body_def_id: LocalDefId,
     param_env: ty::ParamEnv<'tcx>,
+    /// The types containing trait objects that were required to be WF, for the
+    /// `dyn_principal_implied_bounds` lint.
+    dyn_wf_args: RefCell<Vec<(Span, ty::GenericArg<'tcx>)>>,
 }
 impl<'a, 'tcx> Deref for WfCheckingCtxt<'a, 'tcx> {
     type Target = ObligationCtxt<'a, 'tcx>;
@@ -83,6 +88,10 @@ fn register_wf_obligation(
             param_env,
             ty::Binder::dummy(ty::PredicateKind::WellFormed(arg)),
         ));
+        if arg.walk().any(|arg| matches!(arg.as_type().map(|ty| ty.kind()), Some(ty::Dynamic(..))))
+        {
+            self.dyn_wf_args.borrow_mut().push((span, arg));
+        }
     }
 }
 
@@ -98,7 +107,8 @@ pub(super) fn enter_wf_checking_ctxt<'tcx, F>(
     let infcx = &tcx.infer_ctxt().build();
     let ocx = ObligationCtxt::new(infcx);
 
-    let mut wfcx = WfCheckingCtxt { ocx, span, body_def_id, param_env };
+    let mut wfcx =
+        WfCheckingCtxt { ocx, span, body_def_id, param_env, dyn_wf_args: RefCell::new(vec![]) };
 
     if !tcx.features().trivial_bounds {
         wfcx.check_false_global_bounds()
@@ -116,7 +126,54 @@ pub(super) fn enter_wf_checking_ctxt<'tcx, F>(
 
     let outlives_env = OutlivesEnvironment::with_bounds(param_env, implied_bounds);
 
-    let _ = wfcx.ocx.resolve_regions_and_report_errors(body_def_id, &outlives_env);
+    if wfcx.ocx.resolve_regions_and_report_errors(body_def_id, &outlives_env).is_ok() {
+        check_dyn_principal_implied_bounds(tcx, body_def_id, param_env, wfcx.dyn_wf_args.take());
+    }
+}
+
+/// Lints the where clauses of the principal traits of trait objects in `wf_args` that don't hold,
+/// see `wf::dyn_principal_implied_predicates`. These will become part of the WF requirements of
+/// trait object types.
+fn check_dyn_principal_implied_bounds<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body_def_id: LocalDefId,
+    param_env: ty::ParamEnv<'tcx>,
+    wf_args: Vec<(Span, ty::GenericArg<'tcx>)>,
+) {
+    let hir_id = tcx.hir().local_def_id_to_hir_id(body_def_id);
+    let param_env = param_env.without_const();
+    let mut checked = FxHashSet::default();
+    for (span, arg) in wf_args {
+        // Inference variables from normalizing `arg` don't exist outside of the `InferCtxt` it
+        // was normalized in.
+        if arg.has_infer() {
+            continue;
+        }
+        for predicate in traits::wf::dyn_principal_implied_predicates(tcx, arg) {
+            if !checked.insert((span, predicate)) {
+                continue;
+            }
+            let infcx = tcx.infer_ctxt().build();
+            let ocx = ObligationCtxt::new(&infcx);
+            let cause =
+                ObligationCause::new(span, body_def_id, ObligationCauseCode::WellFormed(None));
+            ocx.register_obligation(traits::Obligation::new(tcx, cause, param_env, predicate));
+            let assumed_wf_types = ocx.assumed_wf_types(param_env, span, body_def_id);
+            let implied_bounds = infcx.implied_bounds_tys(param_env, body_def_id, assumed_wf_types);
+            let outlives_env = OutlivesEnvironment::with_bounds(param_env, implied_bounds);
+            if ocx.select_all_or_error().is_empty()
+                && infcx.resolve_regions(&outlives_env).is_empty()
+            {
+                continue;
+            }
+            tcx.emit_spanned_lint(
+                DYN_PRINCIPAL_IMPLIED_BOUNDS,
+                hir_id,
+                span,
+                DynPrincipalImpliedBound { ty: arg, predicate },
+            );
+        }
+    }
 }
 
 fn check_well_formed(tcx: TyCtxt<'_>, def_id: hir::OwnerId) {
@@ -1380,16 +1437,23 @@ fn visit_const(&mut self, c: ty::Const<'tcx>) -> ControlFlow<Self::BreakTy> {
 
     let predicates = wfcx.normalize(span, None, predicates);
 
+    // The bounds on `#[do_not_elaborate]` parameters are not elaborated in the param-env,
+    // but they still imply their supertraits, which their own well-formedness requires.
+    let param_env = wfcx.param_env.without_const();
+    let param_env = if traits::unelaborated_params(tcx, def_id.to_def_id()).is_empty() {
+        param_env
+    } else {
+        ty::ParamEnv::new(
+            tcx.mk_predicates_from_iter(traits::elaborate(tcx, param_env.caller_bounds())),
+            param_env.reveal(),
+            param_env.constness(),
+        )
+    };
+
     debug!(?predicates.predicates);
     assert_eq!(predicates.predicates.len(), predicates.spans.len());
     let wf_obligations = predicates.into_iter().flat_map(|(p, sp)| {
-        traits::wf::predicate_obligations(
-            infcx,
-            wfcx.param_env.without_const(),
-            wfcx.body_def_id,
-            p,
-            sp,
-        )
+        traits::wf::predicate_obligations(infcx, param_env, wfcx.body_def_id, p, sp)
     });
     let obligations: Vec<_> = wf_obligations.chain(default_obligations).collect();
     wfcx.register_obligations(obligations);
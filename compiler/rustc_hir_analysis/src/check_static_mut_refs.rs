@@ -0,0 +1,104 @@
+//! Lints on constants taking references to `static mut`s.
+//!
+//! Constant evaluation rejects these too, but only after type checking, and with an error about
+//! statics in general. Linting on the HIR lets us point out the actual problem first.
+
+use rustc_hir as hir;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::DefId;
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_infer::infer::TyCtxtInferExt;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_session::lint::builtin::CONST_REFS_TO_STATIC_MUT;
+use rustc_span::sym;
+use rustc_trait_selection::traits::type_known_to_meet_bound_modulo_regions;
+
+use crate::errors::{ConstRefsToStaticMut, ConstRefsToStaticMutAtomic, ConstRefsToStaticMutCell};
+
+pub fn check_crate(tcx: TyCtxt<'_>) {
+    let mut visitor = StaticMutRefVisitor { tcx };
+    for def_id in tcx.hir().body_owners() {
+        if tcx.hir().body_const_context(def_id) != Some(hir::ConstContext::Const) {
+            continue;
+        }
+        let body = tcx.hir().body(tcx.hir().body_owned_by(def_id));
+        visitor.visit_body(body);
+    }
+}
+
+struct StaticMutRefVisitor<'tcx> {
+    tcx: TyCtxt<'tcx>,
+}
+
+impl<'tcx> Visitor<'tcx> for StaticMutRefVisitor<'tcx> {
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let hir::ExprKind::AddrOf(hir::BorrowKind::Ref, mutbl, place) = expr.kind {
+            self.check_ref(expr, mutbl, place);
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+impl<'tcx> StaticMutRefVisitor<'tcx> {
+    fn check_ref(
+        &mut self,
+        expr: &'tcx hir::Expr<'tcx>,
+        mutbl: hir::Mutability,
+        place: &'tcx hir::Expr<'tcx>,
+    ) {
+        // Also catch references to fields and elements of the static, like `&S.0`.
+        let mut base = place;
+        while let hir::ExprKind::Field(inner, _) | hir::ExprKind::Index(inner, _) = base.kind {
+            base = inner;
+        }
+        let hir::ExprKind::Path(hir::QPath::Resolved(None, path)) = base.kind else { return };
+        let Res::Def(DefKind::Static(hir::Mutability::Mut), def_id) = path.res else { return };
+
+        let atomic = match *self.tcx.type_of(def_id).subst_identity().kind() {
+            ty::Bool => Some("AtomicBool".to_string()),
+            ty::Int(int) if int != ty::IntTy::I128 => Some(atomic_name(int.name_str())),
+            ty::Uint(uint) if uint != ty::UintTy::U128 => Some(atomic_name(uint.name_str())),
+            _ => None,
+        };
+        let cell = suggests_sync_unsafe_cell(self.tcx, def_id).then_some(ConstRefsToStaticMutCell);
+
+        self.tcx.emit_spanned_lint(
+            CONST_REFS_TO_STATIC_MUT,
+            expr.hir_id,
+            expr.span,
+            ConstRefsToStaticMut {
+                name: self.tcx.item_name(def_id),
+                macro_name: match mutbl {
+                    hir::Mutability::Not => "addr_of",
+                    hir::Mutability::Mut => "addr_of_mut",
+                },
+                atomic: atomic.map(|atomic| ConstRefsToStaticMutAtomic { atomic }),
+                cell,
+            },
+        );
+    }
+}
+
+/// Turns `i32` into `AtomicI32`.
+fn atomic_name(int: &str) -> String {
+    let (first, rest) = int.split_at(1);
+    format!("Atomic{}{rest}", first.to_ascii_uppercase())
+}
+
+/// Whether to suggest turning the local `static mut` `def_id` into a `static` of type
+/// `SyncUnsafeCell`, which is only done if the crate already uses the unstable `SyncUnsafeCell`.
+///
+/// The constant still can't refer to the static then, so this only points at the type instead of
+/// rewriting the static and its uses.
+fn suggests_sync_unsafe_cell(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    if !tcx.features().active(sym::sync_unsafe_cell) || !def_id.is_local() {
+        return false;
+    }
+
+    // Unlike a `static mut`, a `static` has to be `Sync`.
+    let Some(sync_trait) = tcx.lang_items().sync_trait() else { return false };
+    let infcx = tcx.infer_ctxt().build();
+    let param_env = tcx.param_env(def_id);
+    let static_ty = tcx.type_of(def_id).subst_identity();
+    type_known_to_meet_bound_modulo_regions(&infcx, param_env, static_ty, sync_trait)
+}
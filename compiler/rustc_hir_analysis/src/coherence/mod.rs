@@ -171,8 +171,7 @@ fn check_object_overlap<'tcx>(
                 // With the feature enabled, the trait is not implemented automatically,
                 // so this is valid.
             } else {
-                let mut supertrait_def_ids = traits::supertrait_def_ids(tcx, component_def_id);
-                if supertrait_def_ids.any(|d| d == trait_def_id) {
+                if traits::is_supertrait_of(tcx, component_def_id, trait_def_id) {
                     let span = tcx.def_span(impl_def_id);
                     struct_span_err!(
                         tcx.sess,
@@ -892,13 +892,18 @@ fn adt_def(tcx: TyCtxt<'_>, def_id: LocalDefId) -> ty::AdtDef<'_> {
 fn trait_def(tcx: TyCtxt<'_>, def_id: LocalDefId) -> ty::TraitDef {
     let item = tcx.hir().expect_item(def_id);
 
-    let (is_auto, unsafety, items) = match item.kind {
-        hir::ItemKind::Trait(is_auto, unsafety, .., items) => {
-            (is_auto == hir::IsAuto::Yes, unsafety, items)
+    let (is_auto, unsafety, generics, supertraits, items) = match item.kind {
+        hir::ItemKind::Trait(is_auto, unsafety, generics, supertraits, items) => {
+            (is_auto == hir::IsAuto::Yes, unsafety, generics, supertraits, items)
+        }
+        hir::ItemKind::TraitAlias(generics, supertraits) => {
+            (false, hir::Unsafety::Normal, generics, supertraits, &[][..])
         }
-        hir::ItemKind::TraitAlias(..) => (false, hir::Unsafety::Normal, &[][..]),
         _ => span_bug!(item.span, "trait_def_of_item invoked on non-trait"),
     };
+    // The implied predicates are lowered from the supertraits and the where clauses,
+    // see `implied_predicates_with_filter`.
+    let has_no_implied_predicates = supertraits.is_empty() && generics.predicates.is_empty();
 
     let paren_sugar = tcx.has_attr(def_id, sym::rustc_paren_sugar);
     if paren_sugar && !tcx.features().unboxed_closures {
@@ -1001,6 +1006,7 @@ fn trait_def(tcx: TyCtxt<'_>, def_id: LocalDefId) -> ty::TraitDef {
         skip_array_during_method_dispatch,
         specialization_kind,
         must_implement_one_of,
+        has_no_implied_predicates,
     }
 }
 
@@ -5,7 +5,7 @@
     error_code, Applicability, DiagnosticBuilder, ErrorGuaranteed, Handler, IntoDiagnostic,
     MultiSpan,
 };
-use rustc_macros::{Diagnostic, Subdiagnostic};
+use rustc_macros::{Diagnostic, LintDiagnostic, Subdiagnostic};
 use rustc_middle::ty::{self, print::TraitRefPrintOnlyTraitPath, Ty};
 use rustc_span::{symbol::Ident, Span, Symbol};
 
@@ -875,3 +875,34 @@ pub(crate) enum ReturnTypeNotationIllegalParam {
         param_span: Span,
     },
 }
+
+#[derive(LintDiagnostic)]
+#[diag(hir_analysis_const_refs_to_static_mut)]
+#[note]
+#[help]
+pub(crate) struct ConstRefsToStaticMut {
+    pub name: Symbol,
+    pub macro_name: &'static str,
+    #[subdiagnostic]
+    pub atomic: Option<ConstRefsToStaticMutAtomic>,
+    #[subdiagnostic]
+    pub cell: Option<ConstRefsToStaticMutCell>,
+}
+
+#[derive(Subdiagnostic)]
+#[help(hir_analysis_const_refs_to_static_mut_atomic)]
+pub(crate) struct ConstRefsToStaticMutAtomic {
+    pub atomic: String,
+}
+
+#[derive(Subdiagnostic)]
+#[help(hir_analysis_const_refs_to_static_mut_cell)]
+pub(crate) struct ConstRefsToStaticMutCell;
+
+#[derive(LintDiagnostic)]
+#[diag(hir_analysis_dyn_principal_implied_bound)]
+#[note]
+pub(crate) struct DynPrincipalImpliedBound<'tcx> {
+    pub ty: ty::GenericArg<'tcx>,
+    pub predicate: ty::Predicate<'tcx>,
+}
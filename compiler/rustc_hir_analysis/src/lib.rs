@@ -83,6 +83,7 @@
 pub mod astconv;
 pub mod autoderef;
 mod bounds;
+mod check_static_mut_refs;
 mod check_unused;
 mod coherence;
 // FIXME: This module shouldn't be public.
@@ -494,6 +495,7 @@ pub fn check_crate(tcx: TyCtxt<'_>) -> Result<(), ErrorGuaranteed> {
         tcx.hir().for_each_module(|module| tcx.ensure().check_mod_item_types(module))
     });
 
+    check_static_mut_refs::check_crate(tcx);
     check_unused::check_crate(tcx);
     check_for_entry_fn(tcx);
 
@@ -110,7 +110,12 @@
 /// Impl `DepNode`s.
 const LABELS_TRAIT: &[&[&str]] = &[
     BASE_HIR,
-    &[label_strs::associated_item_def_ids, label_strs::predicates_of, label_strs::generics_of],
+    &[
+        label_strs::associated_item_def_ids,
+        label_strs::predicates_of,
+        label_strs::generics_of,
+        label_strs::elaborated_implied_predicates,
+    ],
 ];
 
 /// Impl `DepNode`s.
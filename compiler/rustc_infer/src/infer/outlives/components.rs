@@ -5,7 +5,8 @@
 use rustc_data_structures::sso::SsoHashSet;
 use rustc_middle::ty::subst::{GenericArg, GenericArgKind};
 use rustc_middle::ty::{self, Ty, TyCtxt, TypeVisitableExt};
-use smallvec::{smallvec, SmallVec};
+use smallvec::SmallVec;
+use std::ops::ControlFlow;
 
 #[derive(Debug)]
 pub enum Component<'tcx> {
@@ -54,17 +55,32 @@ pub fn push_outlives_components<'tcx>(
     ty0: Ty<'tcx>,
     out: &mut SmallVec<[Component<'tcx>; 4]>,
 ) {
-    let mut visited = SsoHashSet::new();
-    compute_components(tcx, ty0, out, &mut visited);
+    let _: ControlFlow<!> = visit_outlives_components(tcx, ty0, |component| {
+        out.push(component);
+        ControlFlow::Continue(())
+    });
     debug!("components({:?}) = {:?}", ty0, out);
 }
 
-fn compute_components<'tcx>(
+/// Calls `f` on all the things that must outlive `'a` for the condition
+/// `ty0: 'a` to hold, stopping as soon as `f` breaks. Unlike
+/// [push_outlives_components], this doesn't collect the components.
+/// Note that `ty0` must be a **fully resolved type**.
+pub fn visit_outlives_components<'tcx, B>(
+    tcx: TyCtxt<'tcx>,
+    ty0: Ty<'tcx>,
+    mut f: impl FnMut(Component<'tcx>) -> ControlFlow<B>,
+) -> ControlFlow<B> {
+    let mut visited = SsoHashSet::new();
+    compute_components(tcx, ty0, &mut f, &mut visited)
+}
+
+fn compute_components<'tcx, B>(
     tcx: TyCtxt<'tcx>,
     ty: Ty<'tcx>,
-    out: &mut SmallVec<[Component<'tcx>; 4]>,
+    out: &mut impl FnMut(Component<'tcx>) -> ControlFlow<B>,
     visited: &mut SsoHashSet<GenericArg<'tcx>>,
-) {
+) -> ControlFlow<B> {
     // Descend through the types, looking for the various "base"
     // components and collecting them into `out`. This is not written
     // with `collect()` because of the need to sometimes skip subtrees
@@ -82,11 +98,11 @@ fn compute_components<'tcx>(
                 for child in substs {
                     match child.unpack() {
                         GenericArgKind::Type(ty) => {
-                            compute_components(tcx, ty, out, visited);
+                            compute_components(tcx, ty, out, visited)?;
                         }
                         GenericArgKind::Lifetime(_) => {}
                         GenericArgKind::Const(_) => {
-                            compute_components_recursive(tcx, child, out, visited);
+                            compute_components_recursive(tcx, child, out, visited)?;
                         }
                     }
                 }
@@ -94,18 +110,18 @@ fn compute_components<'tcx>(
 
             ty::Array(element, _) => {
                 // Don't look into the len const as it doesn't affect regions
-                compute_components(tcx, element, out, visited);
+                compute_components(tcx, element, out, visited)?;
             }
 
             ty::Closure(_, ref substs) => {
                 let tupled_ty = substs.as_closure().tupled_upvars_ty();
-                compute_components(tcx, tupled_ty, out, visited);
+                compute_components(tcx, tupled_ty, out, visited)?;
             }
 
             ty::Generator(_, ref substs, _) => {
                 // Same as the closure case
                 let tupled_ty = substs.as_generator().tupled_upvars_ty();
-                compute_components(tcx, tupled_ty, out, visited);
+                compute_components(tcx, tupled_ty, out, visited)?;
 
                 // We ignore regions in the generator interior as we don't
                 // want these to affect region inference
@@ -117,7 +133,7 @@ fn compute_components<'tcx>(
             // OutlivesTypeParameterEnv -- the actual checking that `X:'a`
             // is implied by the environment is done in regionck.
             ty::Param(p) => {
-                out.push(Component::Param(p));
+                out(Component::Param(p))?;
             }
 
             // For projections, we prefer to generate an obligation like
@@ -136,15 +152,12 @@ fn compute_components<'tcx>(
                     // the rules OutlivesProjectionEnv,
                     // OutlivesProjectionTraitDef, and
                     // OutlivesProjectionComponents to regionck.
-                    out.push(Component::Alias(alias_ty));
+                    out(Component::Alias(alias_ty))?;
                 } else {
                     // fallback case: hard code
                     // OutlivesProjectionComponents. Continue walking
                     // through and constrain Pi.
-                    let mut subcomponents = smallvec![];
-                    let mut subvisited = SsoHashSet::new();
-                    compute_alias_components_recursive(tcx, ty, &mut subcomponents, &mut subvisited);
-                    out.push(Component::EscapingAlias(subcomponents.into_iter().collect()));
+                    out(Component::EscapingAlias(escaping_alias_components(tcx, ty)))?;
                 }
             }
 
@@ -152,7 +165,7 @@ fn compute_components<'tcx>(
             // So, if we encounter an inference variable, just record
             // the unresolved variable as a component.
             ty::Infer(infer_ty) => {
-                out.push(Component::UnresolvedInferenceVariable(infer_ty));
+                out(Component::UnresolvedInferenceVariable(infer_ty))?;
             }
 
             // Most types do not introduce any region binders, nor
@@ -184,21 +197,41 @@ fn compute_components<'tcx>(
                 // the "bound regions list". In our representation, no such
                 // list is maintained explicitly, because bound regions
                 // themselves can be readily identified.
-                compute_components_recursive(tcx, ty.into(), out, visited);
+                compute_components_recursive(tcx, ty.into(), out, visited)?;
             }
         }
+    ControlFlow::Continue(())
+}
+
+/// Collects the [Component]s of the substs of an alias with escaping bound vars.
+///
+/// This is not generic over the callback of `compute_components`, as the
+/// collecting closure would otherwise be instantiated with itself.
+fn escaping_alias_components<'tcx>(tcx: TyCtxt<'tcx>, alias_ty: Ty<'tcx>) -> Vec<Component<'tcx>> {
+    let mut subcomponents = vec![];
+    let mut subvisited = SsoHashSet::new();
+    let _: ControlFlow<!> = compute_alias_components_recursive(
+        tcx,
+        alias_ty,
+        &mut |component| {
+            subcomponents.push(component);
+            ControlFlow::Continue(())
+        },
+        &mut subvisited,
+    );
+    subcomponents
 }
 
 /// Collect [Component]s for *all* the substs of `parent`.
 ///
 /// This should not be used to get the components of `parent` itself.
 /// Use [push_outlives_components] instead.
-pub(super) fn compute_alias_components_recursive<'tcx>(
+pub(super) fn compute_alias_components_recursive<'tcx, B>(
     tcx: TyCtxt<'tcx>,
     alias_ty: Ty<'tcx>,
-    out: &mut SmallVec<[Component<'tcx>; 4]>,
+    out: &mut impl FnMut(Component<'tcx>) -> ControlFlow<B>,
     visited: &mut SsoHashSet<GenericArg<'tcx>>,
-) {
+) -> ControlFlow<B> {
     let ty::Alias(kind, alias_ty) = alias_ty.kind() else { bug!() };
     let opt_variances = if *kind == ty::Opaque { tcx.variances_of(alias_ty.def_id) } else { &[] };
     for (index, child) in alias_ty.substs.iter().enumerate() {
@@ -210,45 +243,47 @@ pub(super) fn compute_alias_components_recursive<'tcx>(
         }
         match child.unpack() {
             GenericArgKind::Type(ty) => {
-                compute_components(tcx, ty, out, visited);
+                compute_components(tcx, ty, out, visited)?;
             }
             GenericArgKind::Lifetime(lt) => {
                 // Ignore late-bound regions.
                 if !lt.is_late_bound() {
-                    out.push(Component::Region(lt));
+                    out(Component::Region(lt))?;
                 }
             }
             GenericArgKind::Const(_) => {
-                compute_components_recursive(tcx, child, out, visited);
+                compute_components_recursive(tcx, child, out, visited)?;
             }
         }
     }
+    ControlFlow::Continue(())
 }
 
 /// Collect [Component]s for *all* the substs of `parent`.
 ///
 /// This should not be used to get the components of `parent` itself.
 /// Use [push_outlives_components] instead.
-fn compute_components_recursive<'tcx>(
+fn compute_components_recursive<'tcx, B>(
     tcx: TyCtxt<'tcx>,
     parent: GenericArg<'tcx>,
-    out: &mut SmallVec<[Component<'tcx>; 4]>,
+    out: &mut impl FnMut(Component<'tcx>) -> ControlFlow<B>,
     visited: &mut SsoHashSet<GenericArg<'tcx>>,
-) {
+) -> ControlFlow<B> {
     for child in parent.walk_shallow(visited) {
         match child.unpack() {
             GenericArgKind::Type(ty) => {
-                compute_components(tcx, ty, out, visited);
+                compute_components(tcx, ty, out, visited)?;
             }
             GenericArgKind::Lifetime(lt) => {
                 // Ignore late-bound regions.
                 if !lt.is_late_bound() {
-                    out.push(Component::Region(lt));
+                    out(Component::Region(lt))?;
                 }
             }
             GenericArgKind::Const(_) => {
-                compute_components_recursive(tcx, child, out, visited);
+                compute_components_recursive(tcx, child, out, visited)?;
             }
         }
     }
+    ControlFlow::Continue(())
 }
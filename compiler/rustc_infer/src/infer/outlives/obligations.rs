@@ -59,7 +59,7 @@
 //! might later infer `?U` to something like `&'b u32`, which would
 //! imply that `'b: 'a`.
 
-use crate::infer::outlives::components::{push_outlives_components, Component};
+use crate::infer::outlives::components::{visit_outlives_components, Component};
 use crate::infer::outlives::env::RegionBoundPairs;
 use crate::infer::outlives::verify::VerifyBoundCx;
 use crate::infer::{
@@ -70,7 +70,7 @@
 use rustc_middle::mir::ConstraintCategory;
 use rustc_middle::ty::subst::GenericArgKind;
 use rustc_middle::ty::{self, Region, SubstsRef, Ty, TyCtxt, TypeVisitableExt};
-use smallvec::smallvec;
+use std::ops::ControlFlow;
 
 use super::env::OutlivesEnvironment;
 
@@ -225,41 +225,41 @@ pub fn type_must_outlive(
     ) {
         assert!(!ty.has_escaping_bound_vars());
 
-        let mut components = smallvec![];
-        push_outlives_components(self.tcx, ty, &mut components);
-        self.components_must_outlive(origin, &components, region, category);
+        let _: ControlFlow<!> = visit_outlives_components(self.tcx, ty, |component| {
+            self.component_must_outlive(origin.clone(), &component, region, category);
+            ControlFlow::Continue(())
+        });
     }
 
-    fn components_must_outlive(
+    fn component_must_outlive(
         &mut self,
         origin: infer::SubregionOrigin<'tcx>,
-        components: &[Component<'tcx>],
+        component: &Component<'tcx>,
         region: ty::Region<'tcx>,
         category: ConstraintCategory<'tcx>,
     ) {
-        for component in components.iter() {
-            let origin = origin.clone();
-            match component {
-                Component::Region(region1) => {
-                    self.delegate.push_sub_region_constraint(origin, region, *region1, category);
-                }
-                Component::Param(param_ty) => {
-                    self.param_ty_must_outlive(origin, region, *param_ty);
-                }
-                Component::Alias(alias_ty) => self.alias_ty_must_outlive(origin, region, *alias_ty),
-                Component::EscapingAlias(subcomponents) => {
-                    self.components_must_outlive(origin, &subcomponents, region, category);
-                }
-                Component::UnresolvedInferenceVariable(v) => {
-                    // ignore this, we presume it will yield an error
-                    // later, since if a type variable is not resolved by
-                    // this point it never will be
-                    self.tcx.sess.delay_span_bug(
-                        origin.span(),
-                        format!("unresolved inference variable in outlives: {:?}", v),
-                    );
+        match component {
+            Component::Region(region1) => {
+                self.delegate.push_sub_region_constraint(origin, region, *region1, category);
+            }
+            Component::Param(param_ty) => {
+                self.param_ty_must_outlive(origin, region, *param_ty);
+            }
+            Component::Alias(alias_ty) => self.alias_ty_must_outlive(origin, region, *alias_ty),
+            Component::EscapingAlias(subcomponents) => {
+                for subcomponent in subcomponents {
+                    self.component_must_outlive(origin.clone(), subcomponent, region, category);
                 }
             }
+            Component::UnresolvedInferenceVariable(v) => {
+                // ignore this, we presume it will yield an error
+                // later, since if a type variable is not resolved by
+                // this point it never will be
+                self.tcx.sess.delay_span_bug(
+                    origin.span(),
+                    format!("unresolved inference variable in outlives: {:?}", v),
+                );
+            }
         }
     }
 
@@ -5,8 +5,7 @@
 use rustc_data_structures::sso::SsoHashSet;
 use rustc_middle::ty::GenericArg;
 use rustc_middle::ty::{self, OutlivesPredicate, Ty, TyCtxt};
-
-use smallvec::smallvec;
+use std::ops::ControlFlow;
 
 /// The `TypeOutlives` struct has the job of "lowering" a `T: 'a`
 /// obligation into a series of `'a: 'b` constraints and "verifys", as
@@ -129,11 +128,14 @@ pub fn alias_bound(
 
         // see the extensive comment in projection_must_outlive
         let recursive_bound = {
-            let mut components = smallvec![];
-            compute_alias_components_recursive(
+            let mut components = vec![];
+            let _: ControlFlow<!> = compute_alias_components_recursive(
                 self.tcx,
                 alias_ty_as_ty.into(),
-                &mut components,
+                &mut |component| {
+                    components.push(component);
+                    ControlFlow::Continue(())
+                },
                 visited,
             );
             self.bound_from_components(&components, visited)
@@ -1,60 +1,22 @@
-use smallvec::smallvec;
-
-use crate::infer::outlives::components::{push_outlives_components, Component};
+use crate::infer::outlives::components::{visit_outlives_components, Component};
 use crate::traits::{self, Obligation, PredicateObligation};
-use rustc_data_structures::fx::{FxHashSet, FxIndexSet};
-use rustc_middle::ty::{self, ToPredicate, TyCtxt};
-use rustc_span::symbol::Ident;
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{self, ToPredicate, TyCtxt, TypeVisitableExt};
+use rustc_span::symbol::{sym, Ident};
 use rustc_span::Span;
+use rustc_type_ir::elaborate::{ElaborationInterner, Filter};
+use std::collections::VecDeque;
+use std::ops::ControlFlow;
 
 pub fn anonymize_predicate<'tcx>(
     tcx: TyCtxt<'tcx>,
     pred: ty::Predicate<'tcx>,
 ) -> ty::Predicate<'tcx> {
-    let new = tcx.anonymize_bound_vars(pred.kind());
-    tcx.reuse_or_mk_predicate(pred, new)
-}
-
-pub struct PredicateSet<'tcx> {
-    tcx: TyCtxt<'tcx>,
-    set: FxHashSet<ty::Predicate<'tcx>>,
+    tcx.anonymize_predicate(pred)
 }
 
-impl<'tcx> PredicateSet<'tcx> {
-    pub fn new(tcx: TyCtxt<'tcx>) -> Self {
-        Self { tcx, set: Default::default() }
-    }
-
-    pub fn insert(&mut self, pred: ty::Predicate<'tcx>) -> bool {
-        // We have to be careful here because we want
-        //
-        //    for<'a> Foo<&'a i32>
-        //
-        // and
-        //
-        //    for<'b> Foo<&'b i32>
-        //
-        // to be considered equivalent. So normalize all late-bound
-        // regions before we throw things into the underlying set.
-        self.set.insert(anonymize_predicate(self.tcx, pred))
-    }
-}
-
-impl<'tcx> Extend<ty::Predicate<'tcx>> for PredicateSet<'tcx> {
-    fn extend<I: IntoIterator<Item = ty::Predicate<'tcx>>>(&mut self, iter: I) {
-        for pred in iter {
-            self.insert(pred);
-        }
-    }
-
-    fn extend_one(&mut self, pred: ty::Predicate<'tcx>) {
-        self.insert(pred);
-    }
-
-    fn extend_reserve(&mut self, additional: usize) {
-        Extend::<ty::Predicate<'tcx>>::extend_reserve(&mut self.set, additional);
-    }
-}
+pub type PredicateSet<'tcx> = rustc_type_ir::elaborate::PredicateSet<TyCtxt<'tcx>>;
 
 ///////////////////////////////////////////////////////////////////////////
 // `Elaboration` iterator
@@ -67,9 +29,62 @@ fn extend_reserve(&mut self, additional: usize) {
 /// holds as well. Similarly, if we have `trait Foo: 'static`, and we know that
 /// `T: Foo`, then we know that `T: 'static`.
 pub struct Elaborator<'tcx, O> {
-    stack: Vec<O>,
+    /// The obligations still to be yielded, each paired with the frame it was
+    /// derived in. This is used as a stack, unless a depth limit is set, see
+    /// [`Elaborator::with_depth_limit`].
+    stack: VecDeque<(O, Frame)>,
     visited: PredicateSet<'tcx>,
-    only_self: bool,
+    mode: Filter,
+    /// The indices of the type parameters whose trait bounds are not elaborated,
+    /// see [`Elaborator::without_elaborating_params`].
+    unelaborated_params: Vec<u32>,
+    /// See [`Elaborator::with_elaborating_const_evaluatable`].
+    elaborate_const_evaluatable: bool,
+    depth_limit: Option<usize>,
+    reached_depth_limit: bool,
+    /// Only collected with `-Zdump-elaboration`.
+    stats: Option<ElaborationStats<'tcx>>,
+}
+
+/// Where an obligation on the stack of an [`Elaborator`] came from.
+#[derive(Copy, Clone, Debug)]
+struct Frame {
+    /// How many elaboration steps separate the obligation from its root obligation.
+    depth: usize,
+    /// The index of the root obligation, only meaningful if statistics are collected.
+    root: usize,
+}
+
+/// How many predicates each root predicate of an [`Elaborator`] implied, recorded
+/// in the session's code stats once the elaborator is dropped, to be printed at the
+/// end of the compilation.
+struct ElaborationStats<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    roots: Vec<RootStats<'tcx>>,
+}
+
+struct RootStats<'tcx> {
+    predicate: ty::Predicate<'tcx>,
+    /// All implied predicates, including the deduplicated and filtered ones.
+    generated: usize,
+    /// Implied predicates that were dropped because they were already visited.
+    deduped: usize,
+    /// Implied predicates that were elaborated, but not yielded because of the
+    /// elaborator's [`Filter`].
+    filtered: usize,
+}
+
+impl Drop for ElaborationStats<'_> {
+    fn drop(&mut self) {
+        for root in self.roots.iter().filter(|root| root.generated > 0) {
+            self.tcx.sess.code_stats.record_elaboration(
+                with_no_trimmed_paths!(root.predicate.to_string()),
+                root.generated,
+                root.deduped,
+                root.filtered,
+            );
+        }
+    }
 }
 
 /// Describes how to elaborate an obligation into a sub-obligation.
@@ -171,31 +186,140 @@ pub fn elaborate<'tcx, O: Elaboratable<'tcx>>(
     tcx: TyCtxt<'tcx>,
     obligations: impl IntoIterator<Item = O>,
 ) -> Elaborator<'tcx, O> {
-    let mut elaborator =
-        Elaborator { stack: Vec::new(), visited: PredicateSet::new(tcx), only_self: false };
-    elaborator.extend_deduped(obligations);
+    let mut elaborator = Elaborator {
+        stack: VecDeque::new(),
+        visited: PredicateSet::new(tcx),
+        mode: Filter::All,
+        unelaborated_params: Vec::new(),
+        elaborate_const_evaluatable: false,
+        depth_limit: None,
+        reached_depth_limit: false,
+        stats: tcx
+            .sess
+            .opts
+            .unstable_opts
+            .dump_elaboration
+            .then(|| ElaborationStats { tcx, roots: Vec::new() }),
+    };
+    for obligation in obligations {
+        let root = match &mut elaborator.stats {
+            Some(stats) => {
+                let predicate = obligation.predicate();
+                stats.roots.push(RootStats { predicate, generated: 0, deduped: 0, filtered: 0 });
+                stats.roots.len() - 1
+            }
+            None => 0,
+        };
+        elaborator.extend_deduped([obligation], Frame { depth: 0, root });
+    }
     elaborator
 }
 
 impl<'tcx, O: Elaboratable<'tcx>> Elaborator<'tcx, O> {
-    fn extend_deduped(&mut self, obligations: impl IntoIterator<Item = O>) {
+    fn extend_deduped(&mut self, obligations: impl IntoIterator<Item = O>, frame: Frame) {
         // Only keep those bounds that we haven't already seen.
         // This is necessary to prevent infinite recursion in some
         // cases. One common case is when people define
         // `trait Sized: Sized { }` rather than `trait Sized { }`.
-        // let visited = &mut self.visited;
-        self.stack.extend(obligations.into_iter().filter(|o| self.visited.insert(o.predicate())));
+        let visited = &mut self.visited;
+        let mut root_stats = match &mut self.stats {
+            Some(stats) if frame.depth > 0 => Some(&mut stats.roots[frame.root]),
+            _ => None,
+        };
+        self.stack.extend(
+            obligations
+                .into_iter()
+                .filter(|o| {
+                    let is_new = visited.insert(o.predicate());
+                    if let Some(root_stats) = &mut root_stats {
+                        root_stats.generated += 1;
+                        root_stats.deduped += usize::from(!is_new);
+                    }
+                    is_new
+                })
+                .map(|o| (o, frame)),
+        );
     }
 
     /// Filter to only the supertraits of trait predicates, i.e. only the predicates
     /// that have `Self` as their self type, instead of all implied predicates.
     pub fn filter_only_self(mut self) -> Self {
-        self.only_self = true;
+        self.mode = Filter::OnlySelf;
+        self
+    }
+
+    /// Filter to only the region and type outlives predicates that are implied,
+    /// e.g. `T: 'a` from `T: Foo<'a>` where `trait Foo<'a>: 'a`. Other predicates
+    /// are still elaborated, but never yielded.
+    pub fn filter_only_outlives(mut self) -> Self {
+        self.mode = Filter::OnlyOutlives;
+        self
+    }
+
+    /// Do not elaborate the trait predicates whose self type is one of the type
+    /// parameters with the given indices, usually the ones marked `#[do_not_elaborate]`,
+    /// see [`unelaborated_params`]. Such predicates are still yielded, but none of the
+    /// supertraits they imply are.
+    pub fn without_elaborating_params(mut self, params: Vec<u32>) -> Self {
+        self.unelaborated_params = params;
+        self
+    }
+
+    /// Also elaborate `ConstEvaluatable` predicates into the subexpressions of their
+    /// constants. This is off by default, as it needs the abstract representation of
+    /// the constants, which can't be built while computing the param env of the item
+    /// they appear in: that would require type-checking the constants, which requires
+    /// that param env.
+    pub fn with_elaborating_const_evaluatable(mut self) -> Self {
+        self.elaborate_const_evaluatable = true;
+        self
+    }
+
+    /// Stop elaborating obligations that are `limit` elaboration steps away from
+    /// the root obligations. Obligations at the limit are still yielded, but their
+    /// implied predicates are not, and if there are any, [`Elaborator::reached_depth_limit`]
+    /// will return `true` afterwards. This allows callers to report overflow instead of
+    /// walking pathologically deep supertrait hierarchies.
+    ///
+    /// With a depth limit, obligations are elaborated breadth-first, so that each
+    /// predicate is first reached, and thus elaborated, at its smallest depth.
+    /// Elaborating depth-first would skip the implied predicates of a predicate
+    /// that is first reached through a long path and later deduplicated when it
+    /// is reached again through a shorter one.
+    pub fn with_depth_limit(mut self, limit: usize) -> Self {
+        self.depth_limit = Some(limit);
         self
     }
 
-    fn elaborate(&mut self, elaboratable: &O) {
-        let tcx = self.visited.tcx;
+    /// Whether some predicates were not yielded because the obligations implying them hit
+    /// the limit set by [`Elaborator::with_depth_limit`].
+    pub fn reached_depth_limit(&self) -> bool {
+        self.reached_depth_limit
+    }
+
+    /// Takes the next obligation to yield, breadth-first if there is a depth limit,
+    /// depth-first otherwise.
+    fn pop(&mut self) -> Option<(O, Frame)> {
+        if self.depth_limit.is_some() { self.stack.pop_front() } else { self.stack.pop_back() }
+    }
+
+    /// Whether elaborating an obligation at the depth limit would have implied predicates
+    /// that were not visited yet, i.e. whether stopping there cut anything off.
+    ///
+    /// The implied predicates are marked as visited, which is fine as every obligation
+    /// left on the stack is at the depth limit too, since elaboration is breadth-first.
+    fn implies_new_predicates(&mut self, elaboratable: &O, frame: Frame) -> bool {
+        let len = self.stack.len();
+        let stats = self.stats.take();
+        self.elaborate(elaboratable, Frame { depth: frame.depth + 1, ..frame });
+        self.stats = stats;
+        let implies_new_predicates = self.stack.len() > len;
+        self.stack.truncate(len);
+        implies_new_predicates
+    }
+
+    fn elaborate(&mut self, elaboratable: &O, frame: Frame) {
+        let tcx = self.visited.interner();
 
         let bound_predicate = elaboratable.predicate().kind();
         match bound_predicate.skip_binder() {
@@ -204,8 +328,18 @@ fn elaborate(&mut self, elaboratable: &O) {
                 if data.polarity == ty::ImplPolarity::Negative {
                     return;
                 }
+                if let ty::Param(param) = data.self_ty().kind()
+                    && self.unelaborated_params.contains(&param.index)
+                {
+                    return;
+                }
+                // Most marker traits don't imply anything, so don't bother querying their
+                // predicates.
+                if tcx.trait_def(data.def_id()).has_no_implied_predicates {
+                    return;
+                }
                 // Get predicates implied by the trait, or only super predicates if we only care about self predicates.
-                let predicates = if self.only_self {
+                let predicates = if self.mode == Filter::OnlySelf {
                     tcx.super_predicates_of(data.def_id())
                 } else {
                     tcx.implied_predicates_of(data.def_id())
@@ -225,7 +359,7 @@ fn elaborate(&mut self, elaboratable: &O) {
                         )
                     });
                 debug!(?data, ?obligations, "super_predicates");
-                self.extend_deduped(obligations);
+                self.extend_deduped(obligations, frame);
             }
             ty::PredicateKind::WellFormed(..) => {
                 // Currently, we do not elaborate WF predicates,
@@ -249,9 +383,53 @@ fn elaborate(&mut self, elaboratable: &O) {
             ty::PredicateKind::ClosureKind(..) => {
                 // Nothing to elaborate when waiting for a closure's kind to be inferred.
             }
-            ty::PredicateKind::ConstEvaluatable(..) => {
-                // Currently, we do not elaborate const-evaluatable
-                // predicates.
+            ty::PredicateKind::ConstEvaluatable(ct) => {
+                // If `N + M * 2` is evaluatable, then so are all of its
+                // subexpressions. We only care about those which could fail
+                // to evaluate though, i.e. nested expressions and unevaluated
+                // constants, which only exist with `generic_const_exprs`.
+                if !tcx.features().generic_const_exprs || !self.elaborate_const_evaluatable {
+                    return;
+                }
+
+                let mut nested = vec![];
+                let mut stack = vec![tcx.expand_abstract_consts(ct)];
+                while let Some(ct) = stack.pop() {
+                    let ty::ConstKind::Expr(expr) = ct.kind() else {
+                        continue;
+                    };
+                    let operands_start = stack.len();
+                    match expr {
+                        ty::Expr::Binop(_, lhs, rhs) => stack.extend([lhs, rhs]),
+                        ty::Expr::UnOp(_, operand) | ty::Expr::Cast(_, operand, _) => {
+                            stack.push(operand)
+                        }
+                        ty::Expr::FunctionCall(func, args) => {
+                            stack.push(func);
+                            stack.extend(args.iter());
+                        }
+                    }
+                    // Fully concrete expressions can't be evaluated yet, so checking
+                    // them would only ICE in `is_const_evaluatable`.
+                    nested.extend(stack[operands_start..].iter().copied().filter(|operand| {
+                        match operand.kind() {
+                            ty::ConstKind::Unevaluated(_) => true,
+                            ty::ConstKind::Expr(_) => operand.has_non_region_param(),
+                            _ => false,
+                        }
+                    }));
+                }
+
+                self.extend_deduped(
+                    nested.into_iter().map(|ct| {
+                        elaboratable.child(
+                            bound_predicate
+                                .rebind(ty::PredicateKind::ConstEvaluatable(ct))
+                                .to_predicate(tcx),
+                        )
+                    }),
+                    frame,
+                );
             }
             ty::PredicateKind::ConstEquate(..) => {
                 // Currently, we do not elaborate const-equate
@@ -282,50 +460,46 @@ fn elaborate(&mut self, elaboratable: &O) {
                     return;
                 }
 
-                let mut components = smallvec![];
-                push_outlives_components(tcx, ty_max, &mut components);
-                self.extend_deduped(
-                    components
-                        .into_iter()
-                        .filter_map(|component| match component {
-                            Component::Region(r) => {
-                                if r.is_late_bound() {
-                                    None
-                                } else {
-                                    Some(ty::PredicateKind::Clause(ty::Clause::RegionOutlives(
-                                        ty::OutlivesPredicate(r, r_min),
-                                    )))
-                                }
+                let _: ControlFlow<!> = visit_outlives_components(tcx, ty_max, |component| {
+                    let predicate_kind = match component {
+                        Component::Region(r) => {
+                            if r.is_late_bound() {
+                                return ControlFlow::Continue(());
                             }
+                            ty::PredicateKind::Clause(ty::Clause::RegionOutlives(
+                                ty::OutlivesPredicate(r, r_min),
+                            ))
+                        }
 
-                            Component::Param(p) => {
-                                let ty = tcx.mk_ty_param(p.index, p.name);
-                                Some(ty::PredicateKind::Clause(ty::Clause::TypeOutlives(
-                                    ty::OutlivesPredicate(ty, r_min),
-                                )))
-                            }
+                        Component::Param(p) => {
+                            let ty = tcx.mk_ty_param(p.index, p.name);
+                            ty::PredicateKind::Clause(ty::Clause::TypeOutlives(
+                                ty::OutlivesPredicate(ty, r_min),
+                            ))
+                        }
 
-                            Component::UnresolvedInferenceVariable(_) => None,
+                        Component::UnresolvedInferenceVariable(_) => {
+                            return ControlFlow::Continue(());
+                        }
 
-                            Component::Alias(alias_ty) => {
-                                // We might end up here if we have `Foo<<Bar as Baz>::Assoc>: 'a`.
-                                // With this, we can deduce that `<Bar as Baz>::Assoc: 'a`.
-                                Some(ty::PredicateKind::Clause(ty::Clause::TypeOutlives(
-                                    ty::OutlivesPredicate(alias_ty.to_ty(tcx), r_min),
-                                )))
-                            }
+                        Component::Alias(alias_ty) => {
+                            // We might end up here if we have `Foo<<Bar as Baz>::Assoc>: 'a`.
+                            // With this, we can deduce that `<Bar as Baz>::Assoc: 'a`.
+                            ty::PredicateKind::Clause(ty::Clause::TypeOutlives(
+                                ty::OutlivesPredicate(alias_ty.to_ty(tcx), r_min),
+                            ))
+                        }
 
-                            Component::EscapingAlias(_) => {
-                                // We might be able to do more here, but we don't
-                                // want to deal with escaping vars right now.
-                                None
-                            }
-                        })
-                        .map(|predicate_kind| {
-                            bound_predicate.rebind(predicate_kind).to_predicate(tcx)
-                        })
-                        .map(|predicate| elaboratable.child(predicate)),
-                );
+                        Component::EscapingAlias(_) => {
+                            // We might be able to do more here, but we don't
+                            // want to deal with escaping vars right now.
+                            return ControlFlow::Continue(());
+                        }
+                    };
+                    let predicate = bound_predicate.rebind(predicate_kind).to_predicate(tcx);
+                    self.extend_deduped([elaboratable.child(predicate)], frame);
+                    ControlFlow::Continue(())
+                });
             }
             ty::PredicateKind::TypeWellFormedFromEnv(..) => {
                 // Nothing to elaborate
@@ -345,20 +519,69 @@ impl<'tcx, O: Elaboratable<'tcx>> Iterator for Elaborator<'tcx, O> {
     type Item = O;
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.stack.len(), None)
+        match self.mode {
+            Filter::All | Filter::OnlySelf => (self.stack.len(), None),
+            // Anything on the stack may end up being filtered out.
+            Filter::OnlyOutlives => (0, None),
+        }
     }
 
     fn next(&mut self) -> Option<Self::Item> {
         // Extract next item from top-most stack frame, if any.
-        if let Some(obligation) = self.stack.pop() {
-            self.elaborate(&obligation);
-            Some(obligation)
-        } else {
-            None
+        while let Some((obligation, frame)) = self.pop() {
+            if self.depth_limit.is_some_and(|limit| frame.depth >= limit) {
+                if !self.reached_depth_limit {
+                    self.reached_depth_limit = self.implies_new_predicates(&obligation, frame);
+                }
+            } else {
+                self.elaborate(&obligation, Frame { depth: frame.depth + 1, ..frame });
+            }
+
+            if !self.mode.yields(self.visited.interner(), &obligation.predicate()) {
+                if let Some(stats) = &mut self.stats
+                    && frame.depth > 0
+                {
+                    stats.roots[frame.root].filtered += 1;
+                }
+                continue;
+            }
+
+            return Some(obligation);
         }
+
+        None
     }
 }
 
+/// Returns the indices of the type parameters of `def_id` and its parents that are
+/// marked `#[do_not_elaborate]`, whose trait bounds should not be elaborated in the
+/// param-env of `def_id`.
+///
+/// The attribute is only respected in the crate that uses it, other crates elaborate
+/// all the bounds of the items they see, which is always correct, just slower.
+pub fn unelaborated_params(tcx: TyCtxt<'_>, def_id: DefId) -> Vec<u32> {
+    let mut params = Vec::new();
+    if !def_id.is_local() || !tcx.features().do_not_elaborate {
+        return params;
+    }
+    let mut generics = tcx.generics_of(def_id);
+    loop {
+        params.extend(
+            generics
+                .params
+                .iter()
+                .filter(|param| {
+                    matches!(param.kind, ty::GenericParamDefKind::Type { .. })
+                        && tcx.has_attr(param.def_id, sym::do_not_elaborate)
+                })
+                .map(|param| param.index),
+        );
+        let Some(parent) = generics.parent else { break };
+        generics = tcx.generics_of(parent);
+    }
+    params
+}
+
 ///////////////////////////////////////////////////////////////////////////
 // Supertrait iterator
 ///////////////////////////////////////////////////////////////////////////
@@ -384,33 +607,14 @@ pub fn transitive_bounds<'tcx>(
 /// `super_predicates_that_define_assoc_item` query to avoid enumerating super-predicates that
 /// aren't related to `assoc_item`. This is used when resolving types like `Self::Item` or
 /// `T::Item` and helps to avoid cycle errors (see e.g. #35237).
+///
+/// See [`rustc_type_ir::elaborate::transitive_bounds_that_define_assoc_item`].
 pub fn transitive_bounds_that_define_assoc_item<'tcx>(
     tcx: TyCtxt<'tcx>,
     bounds: impl Iterator<Item = ty::PolyTraitRef<'tcx>>,
     assoc_name: Ident,
 ) -> impl Iterator<Item = ty::PolyTraitRef<'tcx>> {
-    let mut stack: Vec<_> = bounds.collect();
-    let mut visited = FxIndexSet::default();
-
-    std::iter::from_fn(move || {
-        while let Some(trait_ref) = stack.pop() {
-            let anon_trait_ref = tcx.anonymize_bound_vars(trait_ref);
-            if visited.insert(anon_trait_ref) {
-                let super_predicates =
-                    tcx.super_predicates_that_define_assoc_item((trait_ref.def_id(), assoc_name));
-                for (super_predicate, _) in super_predicates.predicates {
-                    let subst_predicate = super_predicate.subst_supertrait(tcx, &trait_ref);
-                    if let Some(binder) = subst_predicate.to_opt_poly_trait_pred() {
-                        stack.push(binder.map_bound(|t| t.trait_ref));
-                    }
-                }
-
-                return Some(trait_ref);
-            }
-        }
-
-        return None;
-    })
+    rustc_type_ir::elaborate::transitive_bounds_that_define_assoc_item(tcx, bounds, assoc_name)
 }
 
 ///////////////////////////////////////////////////////////////////////////
@@ -663,6 +663,7 @@ macro_rules! untracked {
     untracked!(dont_buffer_diagnostics, true);
     untracked!(dump_dep_graph, true);
     untracked!(dump_drop_tracking_cfg, Some("cfg.dot".to_string()));
+    untracked!(dump_elaboration, true);
     untracked!(dump_mir, Some(String::from("abc")));
     untracked!(dump_mir_dataflow, true);
     untracked!(dump_mir_dir, String::from("abc"));
@@ -671,6 +672,7 @@ macro_rules! untracked {
     untracked!(dump_mir_spanview, Some(MirSpanview::Statement));
     untracked!(dump_mono_stats, SwitchWithOptPath::Enabled(Some("mono-items-dir/".into())));
     untracked!(dump_mono_stats_format, DumpMonoStatsFormat::Json);
+    untracked!(dump_supertrait_graph, Some(String::from("abc")));
     untracked!(dylib_lto, true);
     untracked!(emit_stack_sizes, true);
     untracked!(future_incompat_test, true);
@@ -678,6 +680,7 @@ macro_rules! untracked {
     untracked!(identify_regions, true);
     untracked!(incremental_info, true);
     untracked!(incremental_verify_ich, true);
+    untracked!(inline_mir_report, Some(PathBuf::from("inline.json")));
     untracked!(input_stats, true);
     untracked!(keep_hygiene_data, true);
     untracked!(link_native_libraries, false);
@@ -745,7 +748,9 @@ macro_rules! tracked {
         })
     );
     tracked!(codegen_backend, Some("abc".to_string()));
+    tracked!(const_eval_memory_limit, Some(1 << 20));
     tracked!(crate_attr, vec!["abc".to_string()]);
+    tracked!(cross_crate_inline_threshold, Some(10));
     tracked!(debug_info_for_profiling, true);
     tracked!(debug_macros, true);
     tracked!(dep_info_omit_d_target, true);
@@ -754,6 +759,7 @@ macro_rules! tracked {
     tracked!(dwarf_version, Some(5));
     tracked!(emit_thin_lto, false);
     tracked!(export_executable_symbols, true);
+    tracked!(extra_const_ub_info, true);
     tracked!(fewer_names, Some(true));
     tracked!(flatten_format_args, false);
     tracked!(force_unstable_if_unmarked, true);
@@ -763,8 +769,10 @@ macro_rules! tracked {
     tracked!(incremental_ignore_spans, true);
     tracked!(inline_in_all_cgus, Some(true));
     tracked!(inline_mir, Some(true));
+    tracked!(inline_mir_cycle_depth, 3);
     tracked!(inline_mir_hint_threshold, Some(123));
     tracked!(inline_mir_threshold, Some(123));
+    tracked!(inline_mir_total_threshold, Some(123));
     tracked!(instrument_coverage, Some(InstrumentCoverage::All));
     tracked!(instrument_mcount, true);
     tracked!(instrument_xray, Some(InstrumentXRay::default()));
@@ -777,6 +785,7 @@ macro_rules! tracked {
     tracked!(mir_emit_retag, true);
     tracked!(mir_enable_passes, vec![("DestProp".to_string(), false)]);
     tracked!(mir_keep_place_mention, true);
+    tracked!(mir_loop_invariant_code_motion, Some(false));
     tracked!(mir_opt_level, Some(4));
     tracked!(move_size_limit, Some(4096));
     tracked!(mutable_noalias, false);
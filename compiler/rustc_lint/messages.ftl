@@ -248,6 +248,10 @@ lint_identifier_uncommon_codepoints = identifier contains uncommon Unicode codep
 
 lint_ignored_unless_crate_specified = {$level}({$name}) is ignored unless specified at crate level
 
+lint_implied_static_bound = bound `{$bound}` implicitly requires `{$ty}: 'static`
+    .note = `{$trait_name}` requires its implementors to be `'static` here
+    .suggestion = consider making the `'static` requirement explicit
+
 lint_improper_ctypes = `extern` {$desc} uses type `{$ty}`, which is not FFI-safe
     .label = not FFI-safe
     .note = the type is defined here
@@ -331,6 +335,12 @@ lint_map_unit_fn = `Iterator::map` call that discard the iterator's values
     .map_label = after this call to map, the resulting iterator is `impl Iterator<Item = ()>`, which means the only information carried by the iterator is the number of items
     .suggestion = you might have meant to use `Iterator::for_each`
 
+lint_missing_const_fn = this function could be a `const fn`
+    .suggestion = make it a `const fn`
+
+lint_missing_const_impl = this impl could be an `impl const`
+    .suggestion = make it an `impl const`
+
 lint_mixed_script_confusables =
     the usage of Script Group `{$set}` in this crate consists solely of mixed script confusables
     .includes_note = the usage includes {$includes}
@@ -471,11 +481,28 @@ lint_redundant_semicolons =
         *[false] this semicolon
     }
 
+lint_redundant_supertrait_bound = bound `{$redundant}` is redundant, it is already implied by `{$implied_by}`
+    .label = `{$redundant}` is implied by the supertraits of this bound
+    .suggestion = remove the redundant bound
+
+lint_redundant_supertrait_bound_chain = `{$redundant_trait}` is a supertrait of `{$implied_by_trait}` through {$through}
+
+lint_redundant_supertrait_bound_overflow = reached the recursion limit while checking whether bound `{$predicate}` is redundant
+    .label = the supertraits of `{$elaborated}` were only searched up to the recursion limit
+    .help = consider increasing the recursion limit by adding a `#![recursion_limit = "{$suggested_limit}"]` attribute to your crate (`{$crate_name}`)
+
+lint_redundant_supertrait_elaboration = supertrait bound `{$predicate}` is declared more than once
+    .label = `{$predicate}` is first declared here
+    .suggestion = remove the repeated bound
+
 lint_renamed_or_removed_lint = {$msg}
     .suggestion = use the new name
 
 lint_requested_level = requested on the command line with `{$level} {$lint_name}`
 
+lint_static_could_be_const = this static could be a `const`
+    .help = its value has no interior mutability or destructor, so it does not need to be a `static`
+
 lint_supertrait_as_deref_target = `{$t}` implements `Deref` with supertrait `{$target_principal}` as target
     .label = target type is set here
 
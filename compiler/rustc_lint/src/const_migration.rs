@@ -0,0 +1,189 @@
+use crate::late::unerased_lint_store;
+use crate::lints::{MissingConstFn, MissingConstImpl, StaticCouldBeConst};
+use crate::{LateContext, LateLintPass, LintContext};
+
+use rustc_hir as hir;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::LocalDefId;
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::query::Providers;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::lint::builtin::{MISSING_CONST_FN, MISSING_CONST_IMPL, STATIC_COULD_BE_CONST};
+use rustc_session::lint::{Level, LintId};
+use rustc_span::{sym, Span};
+
+declare_lint_pass!(
+    /// Lints for items that could be made const, see the `const_migration` lint group.
+    ConstMigration => [MISSING_CONST_FN, MISSING_CONST_IMPL, STATIC_COULD_BE_CONST]
+);
+
+impl<'tcx> LateLintPass<'tcx> for ConstMigration {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::Item<'tcx>) {
+        if item.span.from_expansion() {
+            return;
+        }
+
+        match item.kind {
+            hir::ItemKind::Fn(sig, ..) => check_fn(cx, item.owner_id.def_id, item.vis_span, &sig),
+            hir::ItemKind::Impl(impl_) => check_impl(cx, impl_),
+            hir::ItemKind::Static(_, hir::Mutability::Not, _) => check_static(cx, item),
+            _ => {}
+        }
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::ImplItem<'tcx>) {
+        // The methods of a trait impl can only be const if the whole impl is, which is
+        // checked in `check_impl`.
+        if let hir::ImplItemKind::Fn(sig, _) = item.kind
+            && !item.span.from_expansion()
+            && let Some(impl_def_id) = cx.tcx.impl_of_method(item.owner_id.to_def_id())
+            && cx.tcx.trait_id_of_impl(impl_def_id).is_none()
+        {
+            check_fn(cx, item.owner_id.def_id, item.vis_span, &sig);
+        }
+    }
+}
+
+pub(crate) fn provide(providers: &mut Providers) {
+    *providers = Providers { const_fn_candidate_lints_enabled, ..*providers };
+}
+
+/// Whether `missing_const_fn` or `missing_const_impl` is enabled anywhere in the crate, either on
+/// the command line or by a lint attribute.
+///
+/// This only looks at the names in the attributes instead of computing the lint levels, as the
+/// answer is needed while building the MIR of every body, see `is_const_fn_candidate`.
+fn const_fn_candidate_lints_enabled(tcx: TyCtxt<'_>, (): ()) -> bool {
+    if tcx.sess.opts.lint_cap == Some(Level::Allow) {
+        return false;
+    }
+
+    let store = unerased_lint_store(tcx);
+    let enables_lint = |name: &str, level: Level| {
+        level != Level::Allow
+            && store.find_lints(name).is_ok_and(|lints| {
+                lints.iter().any(|&lint| {
+                    lint == LintId::of(MISSING_CONST_FN) || lint == LintId::of(MISSING_CONST_IMPL)
+                })
+            })
+    };
+
+    if tcx.sess.opts.lint_opts.iter().any(|(name, level)| enables_lint(name, *level)) {
+        return true;
+    }
+
+    let crate_items = tcx.hir_crate_items(());
+    std::iter::once(hir::CRATE_OWNER_ID)
+        .chain(crate_items.owners())
+        .flat_map(|owner| tcx.hir_attrs(owner).map.values())
+        .flat_map(|attrs| attrs.iter())
+        .filter_map(|attr| Some((Level::from_attr(attr)?, attr.meta_item_list()?)))
+        .any(|(level, metas)| {
+            metas
+                .iter()
+                .any(|meta| meta.ident().is_some_and(|ident| enables_lint(ident.as_str(), level)))
+        })
+}
+
+/// Whether the body of the non-const `fn` `def_id` passes const-checking.
+///
+/// `is_const_fn_candidate` can only be computed while the MIR it checks is still available,
+/// which is only done ahead of time if one of the lints using it is enabled in the crate.
+fn is_const_fn_candidate(cx: &LateContext<'_>, def_id: LocalDefId) -> bool {
+    cx.tcx.const_fn_candidate_lints_enabled(()) && cx.tcx.is_const_fn_candidate(def_id)
+}
+
+fn check_fn(cx: &LateContext<'_>, def_id: LocalDefId, vis_span: Span, sig: &hir::FnSig<'_>) {
+    if sig.header.is_const()
+        || cx.tcx.entry_fn(()).is_some_and(|(entry_def_id, _)| entry_def_id == def_id.to_def_id())
+    {
+        return;
+    }
+
+    if is_const_fn_candidate(cx, def_id) {
+        // The signature includes the visibility, `const` goes right after it.
+        let suggestion = cx
+            .sess()
+            .source_map()
+            .span_take_while(sig.span.with_lo(vis_span.hi()), |c| c.is_whitespace())
+            .shrink_to_hi();
+        cx.emit_spanned_lint(MISSING_CONST_FN, sig.span, MissingConstFn { suggestion });
+    }
+}
+
+fn check_impl(cx: &LateContext<'_>, impl_: &hir::Impl<'_>) {
+    let Some(trait_ref) = impl_.of_trait else { return };
+    if impl_.constness == hir::Constness::Const
+        || impl_.polarity != hir::ImplPolarity::Positive
+        || !cx.tcx.features().const_trait_impl
+    {
+        return;
+    }
+
+    let Some(trait_def_id) = trait_ref.trait_def_id() else { return };
+    if !cx.tcx.has_attr(trait_def_id, sym::const_trait) {
+        return;
+    }
+
+    let all_items_const = impl_.items.iter().all(|item| match item.kind {
+        hir::AssocItemKind::Fn { .. } => is_const_fn_candidate(cx, item.id.owner_id.def_id),
+        hir::AssocItemKind::Const | hir::AssocItemKind::Type => true,
+    });
+    if all_items_const {
+        cx.emit_spanned_lint(
+            MISSING_CONST_IMPL,
+            trait_ref.path.span,
+            MissingConstImpl { suggestion: trait_ref.path.span.shrink_to_lo() },
+        );
+    }
+}
+
+fn check_static(cx: &LateContext<'_>, item: &hir::Item<'_>) {
+    let hir::ItemKind::Static(_, _, body_id) = item.kind else { return };
+    let def_id = item.owner_id.def_id;
+
+    // Statics that are exported or otherwise need to exist at a specific place in the binary
+    // can't be consts.
+    let attrs = cx.tcx.codegen_fn_attrs(def_id);
+    if attrs.contains_extern_indicator()
+        || attrs.link_section.is_some()
+        || attrs.flags.intersects(
+            CodegenFnAttrFlags::USED
+                | CodegenFnAttrFlags::USED_LINKER
+                | CodegenFnAttrFlags::THREAD_LOCAL,
+        )
+    {
+        return;
+    }
+
+    // A `const` with interior mutability or a destructor behaves differently from a static,
+    // since each use of it creates a new value.
+    let qualifs = cx.tcx.mir_const_qualif(def_id);
+    if qualifs.has_mut_interior || qualifs.needs_drop || qualifs.tainted_by_errors.is_some() {
+        return;
+    }
+
+    // Constants can't refer to statics, so neither can the value of the static.
+    let mut finder = StaticRefFinder { found: false };
+    finder.visit_body(cx.tcx.hir().body(body_id));
+    if finder.found {
+        return;
+    }
+
+    cx.emit_spanned_lint(STATIC_COULD_BE_CONST, item.ident.span, StaticCouldBeConst);
+}
+
+/// Finds the uses of statics, including thread locals, in a body.
+struct StaticRefFinder {
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for StaticRefFinder {
+    fn visit_path(&mut self, path: &hir::Path<'tcx>, _: hir::HirId) {
+        if let Res::Def(DefKind::Static(_), _) = path.res {
+            self.found = true;
+        }
+        intravisit::walk_path(self, path);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::lints::ImpliedStaticBound;
+use crate::{LateContext, LateLintPass, LintContext};
+
+use rustc_hir as hir;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_infer::traits::util::elaborate;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_span::Span;
+
+declare_lint! {
+    /// The `implied_static_bounds` lint detects trait bounds that require a type parameter to be
+    /// `'static` through a `'static` supertrait bound, without the requirement being written out.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![warn(implied_static_bounds)]
+    ///
+    /// trait Service: Send + 'static {}
+    ///
+    /// fn spawn<S: Service>(_service: S) {}
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Every implementor of `Service` has to be `'static`, so the bound `S: Service` also requires
+    /// `S: 'static`. Nothing at the use site hints at this, which makes the borrow checker errors
+    /// about `S` not living long enough hard to understand. Spelling out the `'static` bound makes
+    /// the requirement visible.
+    pub IMPLIED_STATIC_BOUNDS,
+    Allow,
+    "detects bounds that require a type parameter to be `'static` through a supertrait"
+}
+
+declare_lint_pass!(ImpliedStaticBounds => [IMPLIED_STATIC_BOUNDS]);
+
+impl<'tcx> LateLintPass<'tcx> for ImpliedStaticBounds {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::Item<'tcx>) {
+        match item.kind {
+            hir::ItemKind::Trait(_, _, generics, supertraits, _)
+            | hir::ItemKind::TraitAlias(generics, supertraits) => {
+                check_bounds(cx, item.owner_id.def_id, generics, supertraits)
+            }
+            hir::ItemKind::Fn(_, generics, _)
+            | hir::ItemKind::Impl(&hir::Impl { generics, .. })
+            | hir::ItemKind::Enum(_, generics)
+            | hir::ItemKind::Struct(_, generics)
+            | hir::ItemKind::Union(_, generics) => {
+                check_bounds(cx, item.owner_id.def_id, generics, &[])
+            }
+            _ => {}
+        }
+    }
+
+    fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::TraitItem<'tcx>) {
+        check_bounds(cx, item.owner_id.def_id, item.generics, &[]);
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::ImplItem<'tcx>) {
+        check_bounds(cx, item.owner_id.def_id, item.generics, &[]);
+    }
+}
+
+fn check_bounds<'tcx>(
+    cx: &LateContext<'tcx>,
+    def_id: LocalDefId,
+    generics: &'tcx hir::Generics<'tcx>,
+    supertraits: hir::GenericBounds<'tcx>,
+) {
+    let mut bound_spans: Vec<_> = supertraits.iter().map(|bound| bound.span()).collect();
+    for predicate in generics.predicates {
+        let hir::WherePredicate::BoundPredicate(predicate) = predicate else { continue };
+        bound_spans.extend(predicate.bounds.iter().map(|bound| bound.span()));
+    }
+
+    let tcx = cx.tcx;
+    let predicates = tcx.explicit_predicates_of(def_id).predicates;
+
+    // Type parameters that are already required to be `'static` by a `'static` bound the user
+    // wrote, e.g. `T: 'static` or `Vec<T>: 'static`, don't need to be reported.
+    let explicit_static_bounds = predicates
+        .iter()
+        .filter(|(predicate, _)| static_outlives_ty(*predicate).is_some())
+        .map(|&(predicate, _)| predicate);
+    let mut static_params: Vec<_> =
+        elaborate(tcx, explicit_static_bounds).filter_map(static_outlives_ty).collect();
+
+    for &(predicate, span) in predicates {
+        if span.from_expansion()
+            || !bound_spans.contains(&span)
+            || predicate.to_opt_poly_trait_pred().is_none()
+        {
+            continue;
+        }
+        // Elaboration turns the `Self: 'static` supertrait bound into a `'static` requirement
+        // on the self type of the bound, and then into one on each of its components. The bound
+        // itself is a trait predicate, so it is filtered out as well.
+        for ty in elaborate(tcx, [predicate]).filter_only_outlives().filter_map(static_outlives_ty)
+        {
+            if !matches!(ty.kind(), ty::Param(_)) || static_params.contains(&ty) {
+                continue;
+            }
+            let Some((trait_def_id, static_span)) = static_supertrait(tcx, predicate) else {
+                continue;
+            };
+            static_params.push(ty);
+            cx.emit_spanned_lint(
+                IMPLIED_STATIC_BOUNDS,
+                span,
+                ImpliedStaticBound {
+                    bound: predicate,
+                    ty,
+                    trait_name: tcx.item_name(trait_def_id),
+                    static_span,
+                    suggestion: span.shrink_to_hi(),
+                },
+            );
+        }
+    }
+}
+
+/// Returns `T` if `predicate` is `T: 'static`.
+fn static_outlives_ty(predicate: ty::Predicate<'_>) -> Option<Ty<'_>> {
+    match predicate.kind().skip_binder() {
+        ty::PredicateKind::Clause(ty::Clause::TypeOutlives(ty::OutlivesPredicate(ty, region)))
+            if region.is_static() =>
+        {
+            Some(ty)
+        }
+        _ => None,
+    }
+}
+
+/// Finds the trait among the bound `predicate` and its supertraits that declares a
+/// `Self: 'static` bound, along with the span of that bound.
+fn static_supertrait<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    predicate: ty::Predicate<'tcx>,
+) -> Option<(DefId, Span)> {
+    elaborate(tcx, [predicate]).filter_map(|predicate| predicate.to_opt_poly_trait_pred()).find_map(
+        |trait_predicate| {
+            let def_id = trait_predicate.def_id();
+            tcx.super_predicates_of(def_id).predicates.iter().find_map(|&(predicate, span)| {
+                let ty = static_outlives_ty(predicate)?;
+                ty.is_param(0).then_some((def_id, span))
+            })
+        },
+    )
+}
@@ -51,6 +51,7 @@
 mod array_into_iter;
 pub mod builtin;
 mod cast_ref_to_mut;
+mod const_migration;
 mod context;
 mod deref_into_dyn_supertrait;
 mod drop_forget_useless;
@@ -60,6 +61,7 @@
 mod expect;
 mod for_loops_over_fallibles;
 pub mod hidden_unicode_codepoints;
+mod implied_static_bounds;
 mod internal;
 mod invalid_from_utf8;
 mod late;
@@ -77,6 +79,8 @@
 mod pass_by_value;
 mod passes;
 mod redundant_semicolon;
+mod redundant_supertrait_bounds;
+mod redundant_supertrait_elaboration;
 mod traits;
 mod types;
 mod unused;
@@ -99,11 +103,13 @@
 use array_into_iter::ArrayIntoIter;
 use builtin::*;
 use cast_ref_to_mut::*;
+use const_migration::*;
 use deref_into_dyn_supertrait::*;
 use drop_forget_useless::*;
 use enum_intrinsics_non_enums::EnumIntrinsicsNonEnums;
 use for_loops_over_fallibles::*;
 use hidden_unicode_codepoints::*;
+use implied_static_bounds::*;
 use internal::*;
 use invalid_from_utf8::*;
 use let_underscore::*;
@@ -117,6 +123,8 @@
 use opaque_hidden_inferred_bound::*;
 use pass_by_value::*;
 use redundant_semicolon::*;
+use redundant_supertrait_bounds::*;
+use redundant_supertrait_elaboration::*;
 use traits::*;
 use types::*;
 use unused::*;
@@ -137,6 +145,7 @@
 pub fn provide(providers: &mut Providers) {
     levels::provide(providers);
     expect::provide(providers);
+    const_migration::provide(providers);
     *providers = Providers { lint_mod, ..*providers };
 }
 
@@ -251,6 +260,10 @@ fn lint_mod(tcx: TyCtxt<'_>, module_def_id: LocalDefId) {
             OpaqueHiddenInferredBound: OpaqueHiddenInferredBound,
             MultipleSupertraitUpcastable: MultipleSupertraitUpcastable,
             MapUnitFn: MapUnitFn,
+            RedundantSupertraitBounds: RedundantSupertraitBounds,
+            RedundantSupertraitElaboration: RedundantSupertraitElaboration,
+            ImpliedStaticBounds: ImpliedStaticBounds,
+            ConstMigration: ConstMigration,
         ]
     ]
 );
@@ -316,6 +329,8 @@ macro_rules! add_lint_group {
 
     add_lint_group!("let_underscore", LET_UNDERSCORE_DROP, LET_UNDERSCORE_LOCK);
 
+    add_lint_group!("const_migration", MISSING_CONST_FN, MISSING_CONST_IMPL, STATIC_COULD_BE_CONST);
+
     add_lint_group!(
         "rust_2018_idioms",
         BARE_TRAIT_OBJECTS,
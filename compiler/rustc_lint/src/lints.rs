@@ -12,7 +12,7 @@
 use rustc_middle::ty::{
     inhabitedness::InhabitedPredicate, PolyExistentialTraitRef, Predicate, Ty, TyCtxt,
 };
-use rustc_session::parse::ParseSess;
+use rustc_session::{parse::ParseSess, Limit};
 use rustc_span::{edition::Edition, sym, symbol::Ident, Span, Symbol};
 
 use crate::{
@@ -564,6 +564,26 @@ pub struct BuiltinUnexpectedCliConfigValue {
     pub value: Symbol,
 }
 
+// const_migration.rs
+#[derive(LintDiagnostic)]
+#[diag(lint_missing_const_fn)]
+pub struct MissingConstFn {
+    #[suggestion(code = "const ", applicability = "maybe-incorrect", style = "verbose")]
+    pub suggestion: Span,
+}
+
+#[derive(LintDiagnostic)]
+#[diag(lint_missing_const_impl)]
+pub struct MissingConstImpl {
+    #[suggestion(code = "const ", applicability = "maybe-incorrect", style = "verbose")]
+    pub suggestion: Span,
+}
+
+#[derive(LintDiagnostic)]
+#[diag(lint_static_could_be_const)]
+#[help]
+pub struct StaticCouldBeConst;
+
 // deref_into_dyn_supertrait.rs
 #[derive(LintDiagnostic)]
 #[diag(lint_supertrait_as_deref_target)]
@@ -828,6 +848,19 @@ fn add_to_diagnostic_with<F>(self, diag: &mut rustc_errors::Diagnostic, _: F)
     }
 }
 
+// implied_static_bounds.rs
+#[derive(LintDiagnostic)]
+#[diag(lint_implied_static_bound)]
+pub struct ImpliedStaticBound<'a> {
+    pub bound: Predicate<'a>,
+    pub ty: Ty<'a>,
+    pub trait_name: Symbol,
+    #[note]
+    pub static_span: Span,
+    #[suggestion(code = " + 'static", applicability = "maybe-incorrect", style = "verbose")]
+    pub suggestion: Span,
+}
+
 // map_unit_fn.rs
 #[derive(LintDiagnostic)]
 #[diag(lint_map_unit_fn)]
@@ -1260,6 +1293,51 @@ pub struct RedundantSemicolonsDiag {
     pub suggestion: Span,
 }
 
+// redundant_supertrait_bounds.rs
+#[derive(LintDiagnostic)]
+#[diag(lint_redundant_supertrait_bound)]
+pub struct RedundantSupertraitBound<'a> {
+    pub redundant: Predicate<'a>,
+    pub implied_by: Predicate<'a>,
+    #[label]
+    pub implied_by_span: Span,
+    #[subdiagnostic]
+    pub chain: Option<RedundantSupertraitBoundChain>,
+    #[suggestion(code = "", applicability = "machine-applicable", style = "verbose")]
+    pub suggestion: Span,
+}
+
+#[derive(Subdiagnostic)]
+#[note(lint_redundant_supertrait_bound_chain)]
+pub struct RedundantSupertraitBoundChain {
+    pub redundant_trait: Symbol,
+    pub implied_by_trait: Symbol,
+    pub through: String,
+}
+
+#[derive(LintDiagnostic)]
+#[diag(lint_redundant_supertrait_bound_overflow)]
+#[help]
+pub struct RedundantSupertraitBoundOverflow<'a> {
+    pub predicate: Predicate<'a>,
+    pub elaborated: Predicate<'a>,
+    #[label]
+    pub elaborated_span: Span,
+    pub suggested_limit: Limit,
+    pub crate_name: Symbol,
+}
+
+// redundant_supertrait_elaboration.rs
+#[derive(LintDiagnostic)]
+#[diag(lint_redundant_supertrait_elaboration)]
+pub struct RedundantSupertraitElaborationDiag<'a> {
+    pub predicate: Predicate<'a>,
+    #[label]
+    pub first_span: Span,
+    #[suggestion(code = "", applicability = "machine-applicable", style = "verbose")]
+    pub suggestion: Span,
+}
+
 // traits.rs
 pub struct DropTraitConstraintsDiag<'a> {
     pub predicate: Predicate<'a>,
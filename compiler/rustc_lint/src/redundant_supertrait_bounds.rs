@@ -0,0 +1,198 @@
+use crate::lints::{
+    RedundantSupertraitBound, RedundantSupertraitBoundChain, RedundantSupertraitBoundOverflow,
+};
+use crate::{LateContext, LateLintPass, LintContext};
+
+use rustc_hir as hir;
+use rustc_hir::def_id::{LocalDefId, LOCAL_CRATE};
+use rustc_infer::traits::util::elaborate;
+use rustc_span::{BytePos, Span};
+use rustc_trait_selection::traits::supertrait_def_ids_with_paths;
+
+declare_lint! {
+    /// The `redundant_supertrait_bounds` lint detects trait bounds that are already implied by
+    /// the supertraits of another bound on the same item.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![warn(redundant_supertrait_bounds)]
+    ///
+    /// trait Foo: Ord where Self: PartialOrd {}
+    ///
+    /// fn bar<T: Copy + Clone>() {}
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// `PartialOrd` is a supertrait of `Ord`, so every type implementing `Ord` also implements
+    /// `PartialOrd`, and the same holds for `Clone` and `Copy`. Spelling out the implied bound
+    /// has no effect and can be removed.
+    pub REDUNDANT_SUPERTRAIT_BOUNDS,
+    Allow,
+    "detects bounds that are implied by the supertraits of another bound"
+}
+
+declare_lint_pass!(RedundantSupertraitBounds => [REDUNDANT_SUPERTRAIT_BOUNDS]);
+
+impl<'tcx> LateLintPass<'tcx> for RedundantSupertraitBounds {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::Item<'tcx>) {
+        match item.kind {
+            hir::ItemKind::Trait(_, _, generics, supertraits, _)
+            | hir::ItemKind::TraitAlias(generics, supertraits) => {
+                check_bounds(cx, item.owner_id.def_id, generics, supertraits)
+            }
+            hir::ItemKind::Fn(_, generics, _)
+            | hir::ItemKind::Impl(&hir::Impl { generics, .. })
+            | hir::ItemKind::Enum(_, generics)
+            | hir::ItemKind::Struct(_, generics)
+            | hir::ItemKind::Union(_, generics) => {
+                check_bounds(cx, item.owner_id.def_id, generics, &[])
+            }
+            _ => {}
+        }
+    }
+
+    fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::TraitItem<'tcx>) {
+        check_bounds(cx, item.owner_id.def_id, item.generics, &[]);
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::ImplItem<'tcx>) {
+        check_bounds(cx, item.owner_id.def_id, item.generics, &[]);
+    }
+}
+
+/// Returns the span of each user-written bound in `supertraits` and the where clauses of
+/// `generics`, along with the span to remove it.
+pub(crate) fn bound_removal_spans(
+    cx: &LateContext<'_>,
+    generics: &hir::Generics<'_>,
+    supertraits: hir::GenericBounds<'_>,
+) -> Vec<(Span, Span)> {
+    let mut bounds = Vec::new();
+    for (pos, bound) in supertraits.iter().enumerate() {
+        let removal_span = if supertraits.len() == 1 {
+            // trait Foo: Bar {}
+            //          ^^^^^
+            let span = cx.sess().source_map().span_extend_to_prev_char(bound.span(), ':', true);
+            span.with_lo(span.lo() - BytePos(1))
+        } else if pos == 0 {
+            bound.span().to(supertraits[1].span().shrink_to_lo())
+        } else {
+            supertraits[pos - 1].span().shrink_to_hi().to(bound.span())
+        };
+        bounds.push((bound.span(), removal_span));
+    }
+    for (predicate_pos, predicate) in generics.predicates.iter().enumerate() {
+        let hir::WherePredicate::BoundPredicate(predicate) = predicate else { continue };
+        for (bound_pos, bound) in predicate.bounds.iter().enumerate() {
+            let mut removal_span = generics.span_for_bound_removal(predicate_pos, bound_pos);
+            if removal_span == generics.where_clause_span {
+                // trait Foo: Bar where Self: Baz {}
+                //               ^^^^^^^^^^^^^^^^
+                if let Ok(prev_source) = cx.sess().source_map().span_to_prev_source(removal_span) {
+                    let whitespace = prev_source.len() - prev_source.trim_end().len();
+                    removal_span =
+                        removal_span.with_lo(removal_span.lo() - BytePos(whitespace as u32));
+                }
+            }
+            bounds.push((bound.span(), removal_span));
+        }
+    }
+    bounds
+}
+
+fn check_bounds<'tcx>(
+    cx: &LateContext<'tcx>,
+    def_id: LocalDefId,
+    generics: &'tcx hir::Generics<'tcx>,
+    supertraits: hir::GenericBounds<'tcx>,
+) {
+    let bounds = bound_removal_spans(cx, generics, supertraits);
+
+    // Only consider plain trait bounds written by the user, not the implicit `Sized` bounds or
+    // bounds from macro expansions.
+    let predicates = cx.tcx.explicit_predicates_of(def_id).predicates;
+    let bounds: Vec<_> = bounds
+        .into_iter()
+        .filter(|(span, _)| !span.from_expansion())
+        .filter_map(|(span, removal_span)| {
+            let &(predicate, _) = predicates.iter().find(|&&(predicate, predicate_span)| {
+                predicate_span == span && predicate.to_opt_poly_trait_pred().is_some()
+            })?;
+            Some((predicate, span, removal_span))
+        })
+        .collect();
+
+    // Pathologically deep supertrait hierarchies are elaborated once for every pair of bounds,
+    // so only look as deep as the trait solver would before reporting overflow. Bounds that may
+    // be implied further down are reported as such.
+    let depth_limit = cx.tcx.recursion_limit();
+    let mut redundant = vec![false; bounds.len()];
+    for (i, &(predicate, span, removal_span)) in bounds.iter().enumerate() {
+        // Bounds are only implied through supertraits here, duplicated bounds are not redundant
+        // with each other. Bounds that were already found to be redundant can't imply others, as
+        // they'd both be removed otherwise.
+        let mut implied_by = None;
+        let mut reached_depth_limit = None;
+        for (j, &(other, other_span, _)) in bounds.iter().enumerate() {
+            if i == j || redundant[j] {
+                continue;
+            }
+            let mut elaborator = elaborate(cx.tcx, [other]).with_depth_limit(depth_limit.0);
+            if elaborator.by_ref().skip(1).any(|implied| implied == predicate) {
+                implied_by = Some((other, other_span));
+                break;
+            }
+            if elaborator.reached_depth_limit() && reached_depth_limit.is_none() {
+                reached_depth_limit = Some((other, other_span));
+            }
+        }
+        let Some((implied_by, implied_by_span)) = implied_by else {
+            if let Some((elaborated, elaborated_span)) = reached_depth_limit {
+                cx.emit_spanned_lint(
+                    REDUNDANT_SUPERTRAIT_BOUNDS,
+                    span,
+                    RedundantSupertraitBoundOverflow {
+                        predicate,
+                        elaborated,
+                        elaborated_span,
+                        suggested_limit: depth_limit * 2,
+                        crate_name: cx.tcx.crate_name(LOCAL_CRATE),
+                    },
+                );
+            }
+            continue;
+        };
+        redundant[i] = true;
+
+        // Name the supertraits in between if the bound isn't a direct supertrait.
+        let redundant_trait = predicate.to_opt_poly_trait_pred().unwrap().def_id();
+        let implied_by_trait = implied_by.to_opt_poly_trait_pred().unwrap().def_id();
+        let chain = supertrait_def_ids_with_paths(cx.tcx, implied_by_trait)
+            .find(|&(def_id, _)| def_id == redundant_trait)
+            .filter(|(_, path)| path.len() > 1)
+            .map(|(_, path)| RedundantSupertraitBoundChain {
+                redundant_trait: cx.tcx.item_name(redundant_trait),
+                implied_by_trait: cx.tcx.item_name(implied_by_trait),
+                through: path[1..]
+                    .iter()
+                    .map(|&def_id| format!("`{}`", cx.tcx.item_name(def_id)))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            });
+        cx.emit_spanned_lint(
+            REDUNDANT_SUPERTRAIT_BOUNDS,
+            span,
+            RedundantSupertraitBound {
+                redundant: predicate,
+                implied_by,
+                implied_by_span,
+                chain,
+                suggestion: removal_span,
+            },
+        );
+    }
+}
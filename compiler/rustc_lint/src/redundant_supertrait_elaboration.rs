@@ -0,0 +1,98 @@
+use crate::lints::RedundantSupertraitElaborationDiag;
+use crate::redundant_supertrait_bounds::bound_removal_spans;
+use crate::{LateContext, LateLintPass, LintContext};
+
+use rustc_hir as hir;
+use rustc_middle::ty;
+
+declare_lint! {
+    /// The `redundant_supertrait_elaboration` lint detects supertraits that are declared more
+    /// than once on the same trait.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![warn(redundant_supertrait_elaboration)]
+    ///
+    /// trait Foo: Clone + Clone {}
+    ///
+    /// trait Bar: Copy
+    /// where
+    ///     Self: Copy,
+    /// {
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// A repeated supertrait adds no information, the trait can be implemented by the same types
+    /// without it. The compiler still elaborates the supertrait once more wherever the trait is
+    /// used as a bound, only to find predicates it has already seen, and readers are left
+    /// wondering whether the bounds differ.
+    ///
+    /// Supertraits that refer back to the trait itself, like `trait Foo: Foo {}`, are rejected as
+    /// a cycle instead. Supertraits that are implied by another supertrait are detected by the
+    /// `redundant_supertrait_bounds` lint.
+    pub REDUNDANT_SUPERTRAIT_ELABORATION,
+    Allow,
+    "detects supertraits that are declared more than once"
+}
+
+declare_lint_pass!(RedundantSupertraitElaboration => [REDUNDANT_SUPERTRAIT_ELABORATION]);
+
+impl<'tcx> LateLintPass<'tcx> for RedundantSupertraitElaboration {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::Item<'tcx>) {
+        let (generics, supertraits) = match item.kind {
+            hir::ItemKind::Trait(_, _, generics, supertraits, _)
+            | hir::ItemKind::TraitAlias(generics, supertraits) => (generics, supertraits),
+            _ => return,
+        };
+
+        // The supertrait and where clause bounds on `Self`, leaving out bounds from macro
+        // expansions. Bounds that constrain associated items, like `Base<Output = i32>`, are
+        // left out as well: their constraints are separate predicates, so two such bounds on the
+        // same trait may differ even though their trait predicates are the same.
+        let super_predicates = cx.tcx.super_predicates_of(item.owner_id.to_def_id()).predicates;
+        let bounds: Vec<_> = bound_removal_spans(cx, generics, supertraits)
+            .into_iter()
+            .filter(|(span, _)| !span.from_expansion())
+            .filter(|&(span, _)| {
+                !super_predicates.iter().any(|&(_, predicate_span)| {
+                    predicate_span != span && span.contains(predicate_span)
+                })
+            })
+            .filter_map(|(span, removal_span)| {
+                let &(predicate, _) =
+                    super_predicates.iter().find(|&&(predicate, predicate_span)| {
+                        predicate_span == span
+                            && matches!(
+                                predicate.kind().skip_binder(),
+                                ty::PredicateKind::Clause(
+                                    ty::Clause::Trait(_) | ty::Clause::TypeOutlives(_)
+                                )
+                            )
+                    })?;
+                Some((predicate, span, removal_span))
+            })
+            .collect();
+
+        for (i, &(predicate, span, removal_span)) in bounds.iter().enumerate() {
+            let Some(&(_, first_span, _)) =
+                bounds[..i].iter().find(|&&(other, _, _)| other == predicate)
+            else {
+                continue;
+            };
+            cx.emit_spanned_lint(
+                REDUNDANT_SUPERTRAIT_ELABORATION,
+                span,
+                RedundantSupertraitElaborationDiag {
+                    predicate,
+                    first_span,
+                    suggestion: removal_span,
+                },
+            );
+        }
+    }
+}
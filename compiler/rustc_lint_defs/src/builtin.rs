@@ -1257,6 +1257,35 @@
     "detects attempts to mutate a `const` item",
 }
 
+declare_lint! {
+    /// The `const_refs_to_static_mut` lint detects constants whose initializer takes a
+    /// reference to a `static mut`.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// static mut COUNTER: i32 = 0;
+    ///
+    /// const C: &i32 = unsafe { &COUNTER };
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// The value of a `static mut` can change at runtime, so a constant can never refer to it:
+    /// the constant is evaluated once, at compile-time. Constant evaluation rejects such
+    /// initializers anyway, but this lint points out the problem before that, and more
+    /// directly. Either take the address of the static with `addr_of!` in code that runs at
+    /// runtime, or turn the `static mut` into a `static` with interior mutability, like an
+    /// [`atomic`] type.
+    ///
+    /// [`atomic`]: https://doc.rust-lang.org/std/sync/atomic/index.html
+    pub CONST_REFS_TO_STATIC_MUT,
+    Deny,
+    "detects constant initializers taking a reference to a `static mut`",
+}
+
 declare_lint! {
     /// The `patterns_in_fns_without_body` lint detects `mut` identifier
     /// patterns as a parameter in functions without a body.
@@ -2431,6 +2460,69 @@
     };
 }
 
+declare_lint! {
+    /// The `inline_always_cycle` lint detects calls to [`#[inline(always)]`][inline] functions
+    /// that could not be fully inlined by the MIR inliner, because the callee is part of a cycle
+    /// of calls.
+    ///
+    /// [inline]: https://doc.rust-lang.org/reference/attributes/codegen.html#the-inline-attribute
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (needs -Zinline-mir)
+    /// #![deny(inline_always_cycle)]
+    ///
+    /// #[inline(always)]
+    /// fn countdown(n: u32) {
+    ///     if n > 0 {
+    ///         countdown(n - 1);
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     countdown(3);
+    /// }
+    /// ```
+    ///
+    /// When MIR inlining is enabled, e.g. with `-Copt-level=3`, this will produce:
+    ///
+    /// ```text
+    /// error: `#[inline(always)]` function `countdown` was not fully inlined, as it is part of a call cycle
+    ///  --> src/main.rs:6:9
+    ///   |
+    /// 6 |         countdown(n - 1);
+    ///   |         ^^^^^^^^^^^^^^^^ this call cannot be fully inlined
+    ///   |
+    ///   = note: the call from `countdown` to `countdown` was left in place to break the cycle
+    /// note: the lint level is defined here
+    ///  --> src/main.rs:1:9
+    ///   |
+    /// 1 | #![deny(inline_always_cycle)]
+    ///   |         ^^^^^^^^^^^^^^^^^^^
+    ///
+    /// error: `#[inline(always)]` function `countdown` was not fully inlined, as it is part of a call cycle
+    ///   --> src/main.rs:11:5
+    ///    |
+    /// 11 |     countdown(3);
+    ///    |     ^^^^^^^^^^^^ this call cannot be fully inlined
+    ///    |
+    ///    = note: the call from `countdown` to `countdown` was left in place to break the cycle
+    ///    = help: use `-Zinline-mir-cycle-depth` to unroll the cycle further
+    /// ```
+    ///
+    /// ### Explanation
+    ///
+    /// Inlining a function that (directly or indirectly) calls itself would never terminate,
+    /// so the inliner unrolls such cycles only up to a fixed depth, which can be raised with
+    /// `-Zinline-mir-cycle-depth`, and leaves the remaining call in place. Calls that would make
+    /// the compiler optimize a function while it is already being optimized are not inlined at
+    /// all. This lint reports the call that was left in place to break the cycle, which may be
+    /// surprising for functions marked `#[inline(always)]`.
+    pub INLINE_ALWAYS_CYCLE,
+    Allow,
+    "detects `#[inline(always)]` functions whose calls are not fully inlined due to a cycle",
+}
+
 declare_lint! {
     /// The `inline_no_sanitize` lint detects incompatible use of
     /// [`#[inline(always)]`][inline] and [`#[no_sanitize(...)]`][no_sanitize].
@@ -3329,12 +3421,14 @@
         CONFLICTING_REPR_HINTS,
         CONST_EVALUATABLE_UNCHECKED,
         CONST_ITEM_MUTATION,
+        CONST_REFS_TO_STATIC_MUT,
         DEAD_CODE,
         DEPRECATED,
         DEPRECATED_CFG_ATTR_CRATE_TYPE_NAME,
         DEPRECATED_IN_FUTURE,
         DEPRECATED_WHERE_CLAUSE_LOCATION,
         DUPLICATE_MACRO_ATTRIBUTES,
+        DYN_PRINCIPAL_IMPLIED_BOUNDS,
         ELIDED_LIFETIMES_IN_PATHS,
         EXPORTED_PRIVATE_DEPENDENCIES,
         FFI_UNWIND_CALLS,
@@ -3348,6 +3442,7 @@
         INCOMPLETE_INCLUDE,
         INDIRECT_STRUCTURAL_MATCH,
         INEFFECTIVE_UNSTABLE_TRAIT_IMPL,
+        INLINE_ALWAYS_CYCLE,
         INLINE_NO_SANITIZE,
         INVALID_ALIGNMENT,
         INVALID_DOC_ATTRIBUTES,
@@ -4346,3 +4441,130 @@
     Allow,
     "effective visibility of a type is larger than the area in which it can be named"
 }
+
+declare_lint! {
+    /// The `missing_const_fn` lint detects functions that could be declared
+    /// as `const fn`.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// #![deny(missing_const_fn)]
+    ///
+    /// pub fn double(x: u32) -> u32 {
+    ///     x * 2
+    /// }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// A function whose body only performs operations and calls that are
+    /// allowed in a `const fn` can be made a `const fn`, which allows it to
+    /// be called from constants and statics. Note that making a function
+    /// `const` is a promise to callers: removing `const` again later is a
+    /// breaking change. This lint is part of the `const_migration` lint group.
+    pub MISSING_CONST_FN,
+    Allow,
+    "detects functions that could be `const fn`"
+}
+
+declare_lint! {
+    /// The `missing_const_impl` lint detects implementations of `#[const_trait]`
+    /// traits that could be `impl const` implementations.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// #![feature(const_trait_impl)]
+    /// #![deny(missing_const_impl)]
+    ///
+    /// #[const_trait]
+    /// pub trait Double {
+    ///     fn double(&self) -> Self;
+    /// }
+    ///
+    /// impl Double for u32 {
+    ///     fn double(&self) -> u32 {
+    ///         *self * 2
+    ///     }
+    /// }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// An implementation of a `#[const_trait]` trait whose methods could all
+    /// be `const fn`s can be made an `impl const`, which allows its methods to
+    /// be called in const contexts. This lint is part of the `const_migration`
+    /// lint group.
+    pub MISSING_CONST_IMPL,
+    Allow,
+    "detects trait implementations that could be `impl const`"
+}
+
+declare_lint! {
+    /// The `static_could_be_const` lint detects immutable statics that could
+    /// be declared as constants.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// #![deny(static_could_be_const)]
+    ///
+    /// pub static LIMIT: u32 = 100;
+    /// # fn main() {}
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// A static whose value has no interior mutability and no destructor can
+    /// usually be a `const` instead, which lets it be used in const contexts
+    /// such as array lengths and patterns. This lint does not fire for statics
+    /// that are exported or otherwise need a unique address, but the address
+    /// of a `const` is not guaranteed to be unique, so code relying on the
+    /// identity of the static needs to keep it. This lint is part of the
+    /// `const_migration` lint group.
+    pub STATIC_COULD_BE_CONST,
+    Allow,
+    "detects statics that could be `const` items"
+}
+
+declare_lint! {
+    /// The `dyn_principal_implied_bounds` lint detects trait object types whose principal trait,
+    /// or one of its supertraits, has a where clause that doesn't hold for the trait's generic
+    /// arguments.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// trait Trait<T: Copy> {}
+    ///
+    /// fn f(_: &dyn Trait<String>) {}
+    /// # fn main() {}
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// The bounds of a trait on its own generic parameters were not checked for trait object
+    /// types, so `dyn Trait<String>` was accepted even though no type can implement
+    /// `Trait<String>`. This is a [future-incompatible] lint to transition this to a hard error
+    /// in the future. See [issue #27579] for more details.
+    ///
+    /// [future-incompatible]: ../index.md#future-incompatible-lints
+    /// [issue #27579]: https://github.com/rust-lang/rust/issues/27579
+    pub DYN_PRINCIPAL_IMPLIED_BOUNDS,
+    Warn,
+    "detects trait object types that don't satisfy the where clauses of their principal trait",
+    @future_incompatible = FutureIncompatibleInfo {
+        reference: "issue #27579 <https://github.com/rust-lang/rust/issues/27579>",
+    };
+}
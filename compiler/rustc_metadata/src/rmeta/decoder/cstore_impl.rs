@@ -228,6 +228,7 @@ fn into_args(self) -> (DefId, SimplifiedType) {
     lookup_deprecation_entry => { table }
     params_in_repr => { table }
     unused_generic_params => { cdata.root.tables.unused_generic_params.get(cdata, def_id.index) }
+    cross_crate_inlinable => { cdata.root.tables.cross_crate_inlinable.get(cdata, def_id.index) }
     opt_def_kind => { table_direct }
     impl_parent => { table }
     impl_polarity => { table_direct }
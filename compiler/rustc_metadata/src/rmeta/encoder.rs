@@ -1025,9 +1025,8 @@ fn should_encode_mir(tcx: TyCtxt<'_>, def_id: LocalDefId) -> (bool, bool) {
         // Full-fledged functions + closures
         DefKind::AssocFn | DefKind::Fn | DefKind::Closure => {
             let generics = tcx.generics_of(def_id);
-            let needs_inline = (generics.requires_monomorphization(tcx)
-                || tcx.codegen_fn_attrs(def_id).requests_inline())
-                && tcx.sess.opts.output_types.should_codegen();
+            let needs_inline = tcx.sess.opts.output_types.should_codegen()
+                && (generics.requires_monomorphization(tcx) || tcx.cross_crate_inlinable(def_id));
             // The function has a `const` modifier or is in a `#[const_trait]`.
             let is_const_fn = tcx.is_const_fn_raw(def_id.to_def_id())
                 || tcx.is_const_default_method(def_id.to_def_id());
@@ -1521,6 +1520,12 @@ fn encode_mir(&mut self) {
             if encode_opt {
                 record!(self.tables.optimized_mir[def_id.to_def_id()] <- tcx.optimized_mir(def_id));
 
+                if let DefKind::AssocFn | DefKind::Fn | DefKind::Closure = tcx.def_kind(def_id) {
+                    self.tables
+                        .cross_crate_inlinable
+                        .set(def_id.local_def_index, tcx.cross_crate_inlinable(def_id));
+                }
+
                 if tcx.sess.opts.unstable_opts.drop_tracking_mir
                     && let DefKind::Generator = self.tcx.def_kind(def_id)
                     && let Some(witnesses) = tcx.mir_generator_witnesses(def_id)
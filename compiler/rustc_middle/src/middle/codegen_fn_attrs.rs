@@ -10,6 +10,9 @@ pub struct CodegenFnAttrs {
     pub inline: InlineAttr,
     /// Parsed representation of the `#[optimize]` attribute
     pub optimize: OptimizeAttr,
+    /// The `#[inline_budget(N)]` attribute, overriding the cost threshold used by the MIR
+    /// inliner when deciding whether to inline calls to this function.
+    pub inline_budget: Option<usize>,
     /// The `#[export_name = "..."]` attribute, indicating a custom symbol a
     /// function should be exported under
     pub export_name: Option<Symbol>,
@@ -100,6 +103,9 @@ pub struct CodegenFnAttrFlags: u32 {
         const REALLOCATOR               = 1 << 18;
         /// `#[rustc_allocator_zeroed]`: a hint to LLVM that the function only allocates zeroed memory.
         const ALLOCATOR_ZEROED          = 1 << 19;
+        /// `#[inline(never_callees)]`: the MIR inliner does not inline any calls
+        /// into this function.
+        const NO_INLINE_CALLEES         = 1 << 20;
     }
 }
 
@@ -111,6 +117,7 @@ pub const fn new() -> CodegenFnAttrs {
             flags: CodegenFnAttrFlags::empty(),
             inline: InlineAttr::None,
             optimize: OptimizeAttr::None,
+            inline_budget: None,
             export_name: None,
             link_name: None,
             link_ordinal: None,
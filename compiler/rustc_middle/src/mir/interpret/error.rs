@@ -445,6 +445,8 @@ pub enum ResourceExhaustionInfo {
     StackFrameLimitReached,
     /// There is not enough memory (on the host) to perform an allocation.
     MemoryExhausted,
+    /// The interpreted program has more memory allocated than the configured limit allows.
+    MemoryLimitReached,
     /// The address space (of the target) is full.
     AddressSpaceFull,
 }
@@ -478,6 +478,18 @@
         separate_provide_extern
     }
 
+    /// Whether any of the `const_migration` lints that use `is_const_fn_candidate`
+    /// is enabled anywhere in the crate.
+    query const_fn_candidate_lints_enabled(_: ()) -> bool {
+        desc { "checking whether `is_const_fn_candidate` is needed" }
+    }
+
+    /// Whether the MIR of a non-const `fn` passes const-checking as if the
+    /// `fn` was a `const fn`. Used by the `const_migration` lints.
+    query is_const_fn_candidate(key: LocalDefId) -> bool {
+        desc { |tcx| "checking if `{}` could be a `const fn`", tcx.def_path_str(key) }
+    }
+
     /// Fetch the MIR for a given `DefId` right after it's built - this includes
     /// unreachable code.
     query mir_built(key: LocalDefId) -> &'tcx Steal<mir::Body<'tcx>> {
@@ -659,6 +671,15 @@
         separate_provide_extern
     }
 
+    /// The predicates implied by the identity trait ref `Self: Trait<P0, .., Pn>` of the trait
+    /// `key`, including itself, fully elaborated and deduplicated, with anonymized bound vars.
+    /// Substitute another trait ref of the trait into them with `subst_supertrait` to get the
+    /// predicates it implies, without walking the supertraits again.
+    query elaborated_implied_predicates(key: DefId) -> &'tcx ty::List<ty::Predicate<'tcx>> {
+        desc { |tcx| "elaborating the implied predicates of `{}`", tcx.def_path_str(key) }
+        cache_on_disk_if { key.is_local() }
+    }
+
     /// The `Option<Ident>` is the name of an associated type. If it is `None`, then this query
     /// returns the full set of predicates. If `Some<Ident>`, then the query returns only the
     /// subset of super-predicates that reference traits that define the given associated type.
@@ -1239,6 +1260,15 @@
         separate_provide_extern
     }
 
+    /// Returns `true` if the given function should get a CGU-internal copy, and its MIR should be
+    /// encoded, so that other crates can inline it. This is always the case for functions with
+    /// `#[inline]`, and for small leaf functions under `-Zcross-crate-inline-threshold`.
+    query cross_crate_inlinable(def_id: DefId) -> bool {
+        desc { |tcx| "whether the item should be made inlinable across crates: `{}`", tcx.def_path_str(def_id) }
+        cache_on_disk_if { def_id.is_local() }
+        separate_provide_extern
+    }
+
     query own_existential_vtable_entries(
         key: DefId
     ) -> &'tcx [DefId] {
@@ -1250,6 +1280,15 @@
         desc { |tcx| "finding all vtable entries for trait `{}`", tcx.def_path_str(key.def_id()) }
     }
 
+    /// Estimates the vtable layout of `dyn Trait` for the trait `key`, with the own
+    /// methods of each supertrait and where they are placed. This is computed without a
+    /// self type, so methods with possibly-impossible bounds are counted as well, and
+    /// supertraits that only differ in associated types are assumed to be different.
+    query vtable_layout_estimate(key: DefId) -> &'tcx ty::VtblLayoutEstimate<'tcx> {
+        arena_cache
+        desc { |tcx| "estimating the vtable layout of trait `{}`", tcx.def_path_str(key) }
+    }
+
     query vtable_trait_upcasting_coercion_new_vptr_slot(key: (Ty<'tcx>, Ty<'tcx>)) -> Option<usize> {
         desc { |tcx| "finding the slot within vtable for trait object `{}` vtable ptr during trait upcasting coercion from `{}` vtable",
             key.1, key.0 }
@@ -1470,6 +1509,15 @@
     query specializes(_: (DefId, DefId)) -> bool {
         desc { "computing whether impls specialize one another" }
     }
+    /// Whether the trait `key.1` is a (transitive) supertrait of the trait `key.0`.
+    /// Use `traits::is_supertrait_of` instead of calling this directly.
+    query is_supertrait_of(key: (DefId, DefId)) -> bool {
+        desc {
+            |tcx| "checking whether `{}` is a supertrait of `{}`",
+            tcx.def_path_str(key.1),
+            tcx.def_path_str(key.0),
+        }
+    }
     query in_scope_traits_map(_: hir::OwnerId)
         -> Option<&'tcx FxHashMap<ItemLocalId, Box<[TraitCandidate]>>> {
         desc { "getting traits in scope at a block" }
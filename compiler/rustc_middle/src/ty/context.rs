@@ -68,6 +68,7 @@
 use rustc_span::{Span, DUMMY_SP};
 use rustc_target::abi::{FieldIdx, Layout, LayoutS, TargetDataLayout, VariantIdx};
 use rustc_target::spec::abi;
+use rustc_type_ir::elaborate::ElaborationInterner;
 use rustc_type_ir::sty::TyKind::*;
 use rustc_type_ir::WithCachedTypeInfo;
 use rustc_type_ir::{CollectAndApply, DynKind, Interner, TypeFlags};
@@ -125,6 +126,48 @@ fn mutability_is_mut(mutbl: Self::Mutability) -> bool {
     }
 }
 
+impl<'tcx> ElaborationInterner for TyCtxt<'tcx> {
+    type PolyTraitRef = ty::PolyTraitRef<'tcx>;
+    type AssocName = Ident;
+
+    fn anonymize_trait_ref(self, trait_ref: ty::PolyTraitRef<'tcx>) -> ty::PolyTraitRef<'tcx> {
+        self.anonymize_bound_vars(trait_ref)
+    }
+
+    fn anonymize_predicate(self, predicate: Predicate<'tcx>) -> Predicate<'tcx> {
+        let new = self.anonymize_bound_vars(predicate.kind());
+        self.reuse_or_mk_predicate(predicate, new)
+    }
+
+    fn is_outlives_predicate(self, predicate: &Predicate<'tcx>) -> bool {
+        matches!(
+            predicate.kind().skip_binder(),
+            ty::PredicateKind::Clause(
+                ty::Clause::RegionOutlives(..) | ty::Clause::TypeOutlives(..)
+            )
+        )
+    }
+
+    fn supertraits_that_define_assoc_item(
+        self,
+        trait_ref: &ty::PolyTraitRef<'tcx>,
+        assoc_name: Ident,
+    ) -> Vec<ty::PolyTraitRef<'tcx>> {
+        let super_predicates =
+            self.super_predicates_that_define_assoc_item((trait_ref.def_id(), assoc_name));
+        super_predicates
+            .predicates
+            .iter()
+            .filter_map(|(super_predicate, _)| {
+                let subst_predicate = super_predicate.subst_supertrait(self, trait_ref);
+                subst_predicate
+                    .to_opt_poly_trait_pred()
+                    .map(|binder| binder.map_bound(|t| t.trait_ref))
+            })
+            .collect()
+    }
+}
+
 type InternedSet<'tcx, T> = ShardedHashMap<InternedInSet<'tcx, T>, ()>;
 
 pub struct CtxtInterners<'tcx> {
@@ -52,6 +52,12 @@ pub struct TraitDef {
     /// List of functions from `#[rustc_must_implement_one_of]` attribute one of which
     /// must be implemented.
     pub must_implement_one_of: Option<Box<[Ident]>>,
+
+    /// If `true`, then this trait has neither supertraits nor where clauses, so
+    /// `implied_predicates_of` and `super_predicates_of` are empty for it. This
+    /// is the case for most marker traits like `Send` or `Sync`, and lets the
+    /// elaborator skip these queries for them.
+    pub has_no_implied_predicates: bool,
 }
 
 /// Whether this trait is treated specially by the standard library
@@ -35,6 +35,31 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+/// An estimate of the vtable layout of `dyn Trait`, computed without knowing the
+/// self type, see the `vtable_layout_estimate` query.
+#[derive(Clone, Debug, HashStable)]
+pub struct VtblLayoutEstimate<'tcx> {
+    /// The own methods of `Trait` and each of its supertraits, in vtable order.
+    pub segments: Vec<VtblSegmentEstimate<'tcx>>,
+    /// Number of entries in the vtable, as-if we did not have trait upcasting.
+    pub entries_ignoring_upcasting: usize,
+    /// Number of entries in the vtable needed solely for upcasting.
+    pub entries_for_upcasting: usize,
+}
+
+/// The entries that one trait of a [`VtblLayoutEstimate`] contributes to the vtable.
+#[derive(Clone, Copy, Debug, HashStable)]
+pub struct VtblSegmentEstimate<'tcx> {
+    pub trait_ref: PolyTraitRef<'tcx>,
+    /// The index of the first own method of the trait in the vtable.
+    pub offset: usize,
+    /// Number of own methods of the trait in the vtable.
+    pub methods: usize,
+    /// Whether the segment is followed by a pointer to the vtable of the trait, which
+    /// is only needed for upcasting.
+    pub emit_vptr: bool,
+}
+
 // Needs to be associated with the `'tcx` lifetime
 impl<'tcx> TyCtxt<'tcx> {
     pub const COMMON_VTABLE_ENTRIES: &'tcx [VtblEntry<'tcx>] =
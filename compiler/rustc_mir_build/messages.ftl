@@ -65,6 +65,10 @@ mir_build_const_param_in_pattern = const parameters cannot be referenced in patt
 mir_build_const_pattern_depends_on_generic_parameter =
     constant pattern depends on a generic parameter
 
+mir_build_const_pattern_depends_on_mutable_memory =
+    constant pattern depends on mutable memory
+    .note = `{$path}` points to mutable memory, whose value may change at runtime
+
 mir_build_could_not_eval_const_pattern = could not evaluate constant pattern
 
 mir_build_deref_raw_pointer_requires_unsafe =
@@ -89,6 +93,10 @@ mir_build_extern_static_requires_unsafe_unsafe_op_in_unsafe_fn_allowed =
 
 mir_build_float_pattern = floating-point types cannot be used in patterns
 
+mir_build_fn_pointer_pattern =
+    function pointers in patterns behave unpredictably and should not be relied upon
+    .note = values of type `{$fn_ptr_ty}` are compared by address, but the same function can have different addresses, and different functions can have the same address
+
 mir_build_indirect_structural_match =
     to use a constant of type `{$non_sm_ty}` in a pattern, `{$non_sm_ty}` must be annotated with `#[derive(PartialEq, Eq)]`
 
@@ -476,6 +476,16 @@ pub struct ConstPatternDependsOnGenericParameter {
     pub span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(mir_build_const_pattern_depends_on_mutable_memory)]
+pub struct ConstPatternDependsOnMutableMemory {
+    #[primary_span]
+    pub span: Span,
+    #[note]
+    pub const_span: Span,
+    pub path: String,
+}
+
 #[derive(Diagnostic)]
 #[diag(mir_build_could_not_eval_const_pattern)]
 pub struct CouldNotEvalConstPattern {
@@ -681,6 +691,13 @@ pub struct UnsizedPattern<'tcx> {
 #[diag(mir_build_pointer_pattern)]
 pub struct PointerPattern;
 
+#[derive(LintDiagnostic)]
+#[diag(mir_build_fn_pointer_pattern)]
+#[note]
+pub struct FnPointerPattern<'tcx> {
+    pub fn_ptr_ty: Ty<'tcx>,
+}
+
 #[derive(LintDiagnostic)]
 #[diag(mir_build_indirect_structural_match)]
 #[note(mir_build_type_not_structural_tip)]
@@ -1,14 +1,16 @@
+use rustc_data_structures::fx::FxHashSet;
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
 use rustc_index::Idx;
 use rustc_infer::infer::{InferCtxt, TyCtxtInferExt};
 use rustc_infer::traits::Obligation;
-use rustc_middle::mir;
+use rustc_middle::mir::interpret::{alloc_range, AllocId, ConstValue, GlobalAlloc, Scalar};
+use rustc_middle::mir::{self, Mutability};
 use rustc_middle::thir::{FieldPat, Pat, PatKind};
 use rustc_middle::ty::{self, Ty, TyCtxt, ValTree};
 use rustc_session::lint;
 use rustc_span::Span;
-use rustc_target::abi::{FieldIdx, VariantIdx};
+use rustc_target::abi::{FieldIdx, VariantIdx, FIRST_VARIANT};
 use rustc_trait_selection::traits::query::evaluate_obligation::InferCtxtExt;
 use rustc_trait_selection::traits::{self, ObligationCause};
 
@@ -16,11 +18,94 @@
 
 use super::PatCtxt;
 use crate::errors::{
-    FloatPattern, IndirectStructuralMatch, InvalidPattern, NontrivialStructuralMatch,
-    PointerPattern, TypeNotStructural, UnionPattern, UnsizedPattern,
+    FloatPattern, FnPointerPattern, IndirectStructuralMatch, InvalidPattern,
+    NontrivialStructuralMatch, PointerPattern, TypeNotStructural, UnionPattern, UnsizedPattern,
 };
 
 impl<'a, 'tcx> PatCtxt<'a, 'tcx> {
+    /// Walks the allocations reachable from `cv` through references, and returns the path to the
+    /// first reference that points to mutable memory, i.e. to a `static mut`, to a `static` with
+    /// interior mutability, or to memory that was interned as mutable. `path` is the path to `cv`.
+    ///
+    /// Nothing is reported behind raw pointers and inside unions, as patterns never look through
+    /// them.
+    pub(super) fn find_mutable_memory(
+        &self,
+        cv: mir::ConstantKind<'tcx>,
+        path: String,
+        param_env: ty::ParamEnv<'tcx>,
+        visited: &mut FxHashSet<AllocId>,
+    ) -> Option<String> {
+        let tcx = self.tcx;
+        match *cv.ty().kind() {
+            ty::Ref(_, pointee, _) => {
+                let ptr = match cv {
+                    mir::ConstantKind::Val(ConstValue::Scalar(scalar), _) => scalar,
+                    mir::ConstantKind::Val(ConstValue::ByRef { alloc, offset }, _) => {
+                        // For wide references, this reads the data pointer.
+                        let range = alloc_range(offset, tcx.data_layout.pointer_size);
+                        alloc.inner().read_scalar(&tcx, range, true).ok()?
+                    }
+                    mir::ConstantKind::Val(ConstValue::Slice { data, .. }, _) => {
+                        return (data.inner().mutability == Mutability::Mut).then_some(path);
+                    }
+                    _ => return None,
+                };
+                let Scalar::Ptr(ptr, _) = ptr else { return None };
+                let (alloc_id, offset) = ptr.into_parts();
+                if !visited.insert(alloc_id) {
+                    return None;
+                }
+                let alloc = match tcx.global_alloc(alloc_id) {
+                    GlobalAlloc::Static(def_id) => {
+                        if tcx.is_mutable_static(def_id)
+                            || !tcx.type_of(def_id).subst_identity().is_freeze(tcx, param_env)
+                        {
+                            return Some(path);
+                        }
+                        tcx.eval_static_initializer(def_id).ok()?
+                    }
+                    GlobalAlloc::Memory(alloc) => {
+                        if alloc.inner().mutability == Mutability::Mut {
+                            return Some(path);
+                        }
+                        alloc
+                    }
+                    GlobalAlloc::Function(_) | GlobalAlloc::VTable(..) => return None,
+                };
+                // The pointee of an unsized reference cannot be represented as a `ConstantKind`.
+                if !pointee.is_sized(tcx, param_env) {
+                    return None;
+                }
+                let pointee = mir::ConstantKind::Val(ConstValue::ByRef { alloc, offset }, pointee);
+                self.find_mutable_memory(pointee, format!("*{path}"), param_env, visited)
+            }
+            ty::Adt(adt_def, _) if adt_def.is_union() => None,
+            ty::Adt(..) | ty::Tuple(_) | ty::Array(..) => {
+                let destructured = tcx.try_destructure_mir_constant(param_env.and(cv))?;
+                let variant = match *cv.ty().kind() {
+                    ty::Adt(adt_def, _) => {
+                        Some(adt_def.variant(destructured.variant.unwrap_or(FIRST_VARIANT)))
+                    }
+                    _ => None,
+                };
+                // Fields are accessed through the dereferenced value, if any.
+                let base = if path.starts_with('*') { format!("({path})") } else { path };
+                destructured.fields.iter().enumerate().find_map(|(i, &field)| {
+                    let path = match (cv.ty().kind(), variant) {
+                        (ty::Array(..), _) => format!("{base}[{i}]"),
+                        (_, Some(variant)) => {
+                            format!("{base}.{}", variant.fields[FieldIdx::new(i)].name)
+                        }
+                        _ => format!("{base}.{i}"),
+                    };
+                    self.find_mutable_memory(field, path, param_env, visited)
+                })
+            }
+            _ => None,
+        }
+    }
+
     /// Converts an evaluated constant to a pattern (if possible).
     /// This means aggregate values (like structs and enums) are converted
     /// to a pattern that matches the value (as if you'd compared via structural equality).
@@ -225,17 +310,22 @@ fn to_pat(
                     }
                 }
             } else if !self.saw_const_match_lint.get() {
-                match cv.ty().kind() {
-                    ty::RawPtr(pointee) if pointee.ty.is_sized(self.tcx(), self.param_env) => {}
-                    ty::FnPtr(..) | ty::RawPtr(..) => {
-                        self.tcx().emit_spanned_lint(
-                            lint::builtin::POINTER_STRUCTURAL_MATCH,
-                            self.id,
-                            self.span,
-                            PointerPattern,
-                        );
-                    }
-                    _ => {}
+                if let Some(fn_ptr_ty) = self.find_fn_ptr(cv.ty(), 0, &mut FxHashSet::default()) {
+                    self.tcx().emit_spanned_lint(
+                        lint::builtin::POINTER_STRUCTURAL_MATCH,
+                        self.id,
+                        self.span,
+                        FnPointerPattern { fn_ptr_ty },
+                    );
+                } else if let ty::RawPtr(pointee) = cv.ty().kind()
+                    && !pointee.ty.is_sized(self.tcx(), self.param_env)
+                {
+                    self.tcx().emit_spanned_lint(
+                        lint::builtin::POINTER_STRUCTURAL_MATCH,
+                        self.id,
+                        self.span,
+                        PointerPattern,
+                    );
                 }
             }
         }
@@ -243,6 +333,41 @@ fn to_pat(
         inlined_const_as_pat
     }
 
+    /// Returns the first function pointer type that a constant of type `ty` can contain, if any.
+    /// Such constants are compared by the address of the functions they point to.
+    ///
+    /// Function pointers behind raw pointers and inside unions are not compared, so they are not
+    /// returned. Types nested deeper than the recursion limit are not searched either, as
+    /// polymorphically recursive types like `struct S<T>(Option<Box<S<(T, T)>>>)` never repeat.
+    fn find_fn_ptr(
+        &self,
+        ty: Ty<'tcx>,
+        depth: usize,
+        visited: &mut FxHashSet<Ty<'tcx>>,
+    ) -> Option<Ty<'tcx>> {
+        if !self.tcx().recursion_limit().value_within_limit(depth) || !visited.insert(ty) {
+            return None;
+        }
+        let tcx = self.tcx();
+        match *ty.kind() {
+            ty::FnPtr(..) => Some(ty),
+            ty::Ref(_, elem_ty, _) | ty::Array(elem_ty, _) | ty::Slice(elem_ty) => {
+                self.find_fn_ptr(elem_ty, depth + 1, visited)
+            }
+            ty::Tuple(fields) => {
+                fields.iter().find_map(|field| self.find_fn_ptr(field, depth + 1, visited))
+            }
+            ty::Adt(adt_def, substs) if !adt_def.is_union() => {
+                adt_def.all_fields().find_map(|field| {
+                    let field_ty =
+                        tcx.normalize_erasing_regions(self.param_env, field.ty(tcx, substs));
+                    self.find_fn_ptr(field_ty, depth + 1, visited)
+                })
+            }
+            _ => None,
+        }
+    }
+
     #[instrument(level = "trace", skip(self), ret)]
     fn type_may_have_partial_eq_impl(&self, ty: Ty<'tcx>) -> bool {
         // double-check there even *is* a semantic `PartialEq` to dispatch to.
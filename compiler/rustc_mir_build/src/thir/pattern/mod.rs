@@ -11,6 +11,7 @@
 use crate::errors::*;
 use crate::thir::util::UserAnnotatedTyHelpers;
 
+use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::error_code;
 use rustc_hir as hir;
 use rustc_hir::def::{CtorOf, DefKind, Res};
@@ -23,6 +24,7 @@
 use rustc_middle::mir::{self, ConstantKind, UserTypeProjection};
 use rustc_middle::mir::{BorrowKind, Mutability};
 use rustc_middle::thir::{Ascription, BindingMode, FieldPat, LocalVarId, Pat, PatKind, PatRange};
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::subst::{GenericArg, SubstsRef};
 use rustc_middle::ty::CanonicalUserTypeAnnotation;
 use rustc_middle::ty::TypeVisitableExt;
@@ -536,6 +538,24 @@ fn lower_path(&mut self, qpath: &hir::QPath<'_>, id: hir::HirId, span: Span) ->
 
         match const_value {
             Ok(const_) => {
+                // Constants that could not be turned into a valtree are matched opaquely, and may
+                // still point to mutable memory through an immutable static.
+                if let ConstantKind::Val(..) = const_
+                    && let Some(path) = self.find_mutable_memory(
+                        const_,
+                        with_no_trimmed_paths!(self.tcx.def_path_str(instance.def_id())),
+                        param_env_reveal_all,
+                        &mut FxHashSet::default(),
+                    )
+                {
+                    self.tcx.sess.emit_err(ConstPatternDependsOnMutableMemory {
+                        span,
+                        const_span: self.tcx.def_span(instance.def_id()),
+                        path,
+                    });
+                    return pat_from_kind(PatKind::Wild);
+                }
+
                 let pattern = self.const_to_pat(const_, id, span, Some(instance.def_id()));
 
                 if !is_associated_const {
@@ -25,6 +25,11 @@ mir_transform_fn_item_ref = taking a reference to a function item does not give
 
 mir_transform_initializing_valid_range_label = initializing type with `rustc_layout_scalar_valid_range` attr
 mir_transform_initializing_valid_range_note = initializing a layout restricted type's field with a value outside the valid range is undefined behavior
+mir_transform_inline_always_cycle = `#[inline(always)]` function `{$callee}` was not fully inlined, as it is part of a call cycle
+    .label = this call cannot be fully inlined
+    .note = the call from `{$edge_caller}` to `{$edge_callee}` was left in place to break the cycle
+    .help = use `-Zinline-mir-cycle-depth` to unroll the cycle further
+
 mir_transform_must_not_suspend = {$pre}`{$def_path}`{$post} held across a suspend point, but should not be
     .label = the value is held across this suspend point
     .note = {$reason}
@@ -5,7 +5,7 @@
 
 use rustc_const_eval::const_eval::CheckAlignment;
 use rustc_const_eval::ReportErrorExt;
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashSet, FxIndexSet};
 use rustc_hir::def::DefKind;
 use rustc_index::bit_set::BitSet;
 use rustc_index::{IndexSlice, IndexVec};
@@ -13,17 +13,20 @@
     MutVisitor, MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor,
 };
 use rustc_middle::mir::*;
+use rustc_middle::ty::adjustment::PointerCast;
 use rustc_middle::ty::layout::{LayoutError, LayoutOf, LayoutOfHelpers, TyAndLayout};
 use rustc_middle::ty::InternalSubsts;
-use rustc_middle::ty::{self, ConstKind, Instance, ParamEnv, Ty, TyCtxt, TypeVisitableExt};
+use rustc_middle::ty::{
+    self, ConstKind, Instance, InstanceDef, ParamEnv, SubstsRef, Ty, TyCtxt, TypeVisitableExt,
+};
 use rustc_span::{def_id::DefId, Span, DUMMY_SP};
 use rustc_target::abi::{self, Align, HasDataLayout, Size, TargetDataLayout};
 use rustc_target::spec::abi::Abi as CallAbi;
 
 use crate::MirPass;
 use rustc_const_eval::interpret::{
-    self, compile_time_machine, AllocId, ConstAllocation, ConstValue, Frame, ImmTy, Immediate,
-    InterpCx, InterpResult, LocalValue, MemoryKind, OpTy, PlaceTy, Pointer, Scalar,
+    self, compile_time_machine, AllocId, ConstAllocation, ConstValue, FnVal, Frame, ImmTy,
+    Immediate, InterpCx, InterpResult, LocalValue, MemoryKind, OpTy, PlaceTy, Pointer, Scalar,
     StackPopCleanup,
 };
 
@@ -299,6 +302,9 @@ struct ConstPropagator<'mir, 'tcx> {
     tcx: TyCtxt<'tcx>,
     param_env: ParamEnv<'tcx>,
     local_decls: &'mir IndexSlice<Local, LocalDecl<'tcx>>,
+    /// The functions that calls through function pointers may be resolved to, see
+    /// [`reified_fns`].
+    reified_fns: FxIndexSet<(DefId, SubstsRef<'tcx>)>,
 }
 
 impl<'tcx> LayoutOfHelpers<'tcx> for ConstPropagator<'_, 'tcx> {
@@ -373,7 +379,16 @@ fn new(
         )
         .expect("failed to push initial stack frame");
 
-        ConstPropagator { ecx, tcx, param_env, local_decls: &dummy_body.local_decls }
+        let reified_fns =
+            if tcx.sess.mir_opt_level() >= 3 { reified_fns(tcx, body) } else { Default::default() };
+
+        ConstPropagator {
+            ecx,
+            tcx,
+            param_env,
+            local_decls: &dummy_body.local_decls,
+            reified_fns,
+        }
     }
 
     fn get_const(&self, place: Place<'tcx>) -> Option<OpTy<'tcx>> {
@@ -654,6 +669,26 @@ fn replace_with_const(&mut self, place: Place<'tcx>, rval: &mut Rvalue<'tcx>) {
         }
     }
 
+    /// If `func` is a function pointer with a known value, returns a direct reference to the
+    /// function it points to. Pointers to shims, e.g. of `#[track_caller]` functions or of
+    /// closures, are left alone, as the shims have to stay between the pointer and the function.
+    /// So are pointers to functions that this body doesn't reify itself, e.g. ones read from a
+    /// constant, as the inliner's call graph doesn't know about them.
+    fn resolve_fn_ptr(&mut self, func: &Operand<'tcx>, span: Span) -> Option<Operand<'tcx>> {
+        // Calls of function items are direct already.
+        if !func.ty(self.local_decls, self.tcx).is_fn_ptr() {
+            return None;
+        }
+        let value = self.eval_operand(func)?;
+        let ptr = self.ecx.read_pointer(&value).ok()?;
+        let FnVal::Instance(instance) = self.ecx.get_ptr_fn(ptr).ok()? else { return None };
+        let InstanceDef::Item(def_id) = instance.def else { return None };
+        if !self.reified_fns.contains(&(def_id, instance.substs)) {
+            return None;
+        }
+        Some(Operand::function_handle(self.tcx, def_id, instance.substs, span))
+    }
+
     /// Returns `true` if and only if this `op` should be const-propagated into.
     fn should_const_prop(&mut self, op: &OpTy<'tcx>) -> bool {
         if !self.tcx.consider_optimizing(|| format!("ConstantPropagation - OpTy: {:?}", op)) {
@@ -683,6 +718,29 @@ fn ensure_not_propagated(&self, local: Local) {
     }
 }
 
+/// Returns the function items that `body` turns into function pointers. These are the only
+/// functions that `ConstProp` resolves calls through function pointers to, so that the inliner can
+/// add them to its call graph, see `inline::cycle::mir_inliner_callees`. Inlining keeps the casts
+/// of the callee, so this includes the pointers passed into inlined functions.
+pub(crate) fn reified_fns<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+) -> FxIndexSet<(DefId, SubstsRef<'tcx>)> {
+    let mut fns = FxIndexSet::default();
+    for data in body.basic_blocks.iter() {
+        for stmt in &data.statements {
+            if let StatementKind::Assign(box (_, rvalue)) = &stmt.kind
+                && let Rvalue::Cast(CastKind::Pointer(PointerCast::ReifyFnPointer), operand, _) =
+                    rvalue
+                && let ty::FnDef(def_id, substs) = *operand.ty(body, tcx).kind()
+            {
+                fns.insert((def_id, substs));
+            }
+        }
+    }
+    fns
+}
+
 /// The mode that `ConstProp` is allowed to run in for a given `Local`.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ConstPropMode {
@@ -954,7 +1012,14 @@ fn visit_terminator(&mut self, terminator: &mut Terminator<'tcx>, location: Loca
             //
             // NOTE: because LLVM codegen gives slight performance regressions with it, so this is
             // gated on `mir_opt_level=3`.
-            TerminatorKind::Call { .. } => {}
+            TerminatorKind::Call { func, .. } => {
+                if self.tcx.sess.mir_opt_level() >= 3
+                    && let Some(callee) = self.resolve_fn_ptr(func, terminator.source_info.span)
+                {
+                    trace!("resolved call through {:?} to {:?}", func, callee);
+                    *func = callee;
+                }
+            }
         }
     }
 
@@ -0,0 +1,80 @@
+//! Decides which functions are made available for inlining in other crates.
+//!
+//! Functions marked `#[inline]` always get their MIR encoded and a CGU-internal copy in every
+//! crate that uses them. With `-Zcross-crate-inline-threshold`, small leaf functions without the
+//! attribute are treated the same way, so that downstream crates can inline trivial accessors and
+//! constructors that were never annotated.
+
+use rustc_attr::InlineAttr;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::LocalDefId;
+use rustc_middle::mir::{StatementKind, TerminatorKind};
+use rustc_middle::query::Providers;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::OptLevel;
+
+pub fn provide(providers: &mut Providers) {
+    providers.cross_crate_inlinable = cross_crate_inlinable;
+}
+
+fn cross_crate_inlinable(tcx: TyCtxt<'_>, def_id: LocalDefId) -> bool {
+    let codegen_fn_attrs = tcx.codegen_fn_attrs(def_id);
+    match codegen_fn_attrs.inline {
+        InlineAttr::Hint | InlineAttr::Always => return true,
+        InlineAttr::Never => return false,
+        InlineAttr::None => {}
+    }
+
+    let Some(threshold) = tcx.sess.opts.unstable_opts.cross_crate_inline_threshold else {
+        return false;
+    };
+
+    // Symbols that are exported by name must have a single definition.
+    if codegen_fn_attrs.contains_extern_indicator() {
+        return false;
+    }
+
+    // Generic functions are instantiated in the using crate anyway. The other checks mirror the
+    // ones for `deduced_param_attrs`: the analysis needs optimized MIR, and in incremental mode
+    // the extra CGU-internal copies cost more than they gain.
+    if !matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+        || tcx.generics_of(def_id).requires_monomorphization(tcx)
+        || tcx.sess.opts.optimize == OptLevel::No
+        || tcx.sess.opts.incremental.is_some()
+        || !tcx.sess.opts.output_types.should_codegen()
+        || !tcx.is_mir_available(def_id)
+    {
+        return false;
+    }
+
+    // Only consider leaf functions. Anything they call would have to be exported as well, and the
+    // inliner in the using crate would just reconsider the callees of the inlined body anyway.
+    let body = tcx.optimized_mir(def_id);
+    let mut statements = 0;
+    for block in body.basic_blocks.iter() {
+        if block.is_cleanup {
+            return false;
+        }
+        match block.terminator().kind {
+            TerminatorKind::Call { .. }
+            | TerminatorKind::Drop { .. }
+            | TerminatorKind::InlineAsm { .. }
+            | TerminatorKind::Resume
+            | TerminatorKind::Terminate => return false,
+            _ => {}
+        }
+        statements += block
+            .statements
+            .iter()
+            .filter(|statement| {
+                !matches!(
+                    statement.kind,
+                    StatementKind::StorageLive(_)
+                        | StatementKind::StorageDead(_)
+                        | StatementKind::Nop
+                )
+            })
+            .count();
+    }
+    statements <= threshold
+}
@@ -0,0 +1,213 @@
+//! Devirtualizes calls to trait object methods whose receiver has a known concrete type.
+//!
+//! When a reference is unsized into a `&dyn Trait` in the same body, as in
+//!   _2 = move _3 as &dyn Trait (Pointer(Unsize));
+//!   _4 = &(*_2);
+//!   _0 = <dyn Trait as Trait>::method(move _4)
+//! the type behind `_3` is the one whose impl the virtual call dispatches to. We replace the call
+//! with a direct call to `<T as Trait>::method(_3)`, which the inliner can then see through.
+//!
+//! We only consider `&self` methods, and require `_3` and every local between the coercion and
+//! the call to be SSA, so that the receiver still points to the coerced value.
+//!
+//! The concrete type is only ever taken from a coercion in the same body. Whether the trait has a
+//! single impl is not used: downstream crates can always add more impls, so that would not be
+//! enough to know which impl a trait object dispatches to.
+//!
+//! The inliner avoids query cycles using the calls it finds in `mir_promoted`, where these calls
+//! are still virtual. So [`devirtualizable_calls`] adds every call this pass may create to that
+//! call graph, see `inline::cycle::mir_inliner_callees`.
+
+use rustc_data_structures::fx::FxIndexSet;
+use rustc_hir::def_id::DefId;
+use rustc_index::bit_set::BitSet;
+use rustc_index::IndexVec;
+use rustc_middle::mir::visit::*;
+use rustc_middle::mir::*;
+use rustc_middle::ty::adjustment::PointerCast;
+use rustc_middle::ty::{self, Instance, InstanceDef, SubstsRef, Ty, TyCtxt};
+
+use crate::ssa::SsaLocals;
+use crate::MirPass;
+
+pub struct Devirtualize;
+
+impl<'tcx> MirPass<'tcx> for Devirtualize {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let ssa = SsaLocals::new(body);
+        let sources = compute_unsize_sources(&ssa, body);
+
+        let mut receivers = BitSet::new_empty(body.local_decls.len());
+        for data in body.basic_blocks.as_mut_preserves_cfg() {
+            let terminator = data.terminator_mut();
+            let span = terminator.source_info.span;
+            let TerminatorKind::Call { func, args, .. } = &mut terminator.kind else { continue };
+            let Some((def_id, substs)) = func.const_fn_def() else { continue };
+            if tcx.trait_of_item(def_id).is_none() || !substs.type_at(0).is_trait() {
+                continue;
+            }
+            let Some(receiver) =
+                args.first().and_then(|arg| arg.place()).and_then(|p| p.as_local())
+            else {
+                continue;
+            };
+            let Some(source) = sources[receiver] else { continue };
+            let ty::Ref(_, pointee, _) = *body.local_decls[receiver].ty.kind() else { continue };
+            if pointee != substs.type_at(0) {
+                continue;
+            }
+
+            let ty::Ref(_, concrete_ty, _) = *body.local_decls[source].ty.kind() else { bug!() };
+            let substs = devirtualized_substs(tcx, concrete_ty, substs);
+            // Only devirtualize calls that resolve to an actual impl, and not to another
+            // virtual call, e.g. when upcasting `&dyn Sub` to `&dyn Super`.
+            if !matches!(
+                Instance::resolve(tcx, param_env, def_id, substs),
+                Ok(Some(Instance { def: InstanceDef::Item(_), .. }))
+            ) {
+                continue;
+            }
+
+            debug!("devirtualizing call to {:?} on {:?}", def_id, concrete_ty);
+            *func = Operand::function_handle(tcx, def_id, substs, span);
+            args[0] = Operand::Copy(Place::from(source));
+            receivers.insert(source);
+        }
+
+        if !receivers.is_empty() {
+            Replacer { tcx, receivers }.visit_body_preserves_cfg(body);
+        }
+    }
+}
+
+/// Replaces the trait object type that a virtual call dispatches on with `concrete_ty`.
+fn devirtualized_substs<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    concrete_ty: Ty<'tcx>,
+    substs: SubstsRef<'tcx>,
+) -> SubstsRef<'tcx> {
+    tcx.mk_substs_from_iter(std::iter::once(concrete_ty.into()).chain(substs.iter().skip(1)))
+}
+
+/// Returns every direct call that `Devirtualize` may turn a virtual call of `body` into. This
+/// pairs each virtual call with each coercion of a shared reference to the same trait object type,
+/// without checking that the coerced reference actually reaches the call.
+pub(crate) fn devirtualizable_calls<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+) -> Vec<(DefId, SubstsRef<'tcx>)> {
+    let mut coercions = FxIndexSet::default();
+    let mut virtual_calls = Vec::new();
+    for data in body.basic_blocks.iter() {
+        for stmt in &data.statements {
+            if let StatementKind::Assign(box (_, rvalue)) = &stmt.kind
+                && let Rvalue::Cast(CastKind::Pointer(PointerCast::Unsize), operand, target_ty) =
+                    rvalue
+                && let ty::Ref(_, concrete_ty, Mutability::Not) = *operand.ty(body, tcx).kind()
+                && let ty::Ref(_, object_ty, Mutability::Not) = *target_ty.kind()
+                && object_ty.is_trait()
+            {
+                coercions.insert((object_ty, concrete_ty));
+            }
+        }
+        if let TerminatorKind::Call { func, .. } = &data.terminator().kind
+            && let Some((def_id, substs)) = func.const_fn_def()
+            && tcx.trait_of_item(def_id).is_some()
+            && substs.type_at(0).is_trait()
+        {
+            virtual_calls.push((def_id, substs));
+        }
+    }
+
+    let mut calls = Vec::new();
+    for (def_id, substs) in virtual_calls {
+        for &(object_ty, concrete_ty) in &coercions {
+            if object_ty == substs.type_at(0) {
+                calls.push((def_id, devirtualized_substs(tcx, concrete_ty, substs)));
+            }
+        }
+    }
+    calls
+}
+
+/// For each SSA local holding a `&dyn Trait`, finds the SSA local holding the reference it was
+/// unsized from, looking through copies and reborrows.
+fn compute_unsize_sources<'tcx>(
+    ssa: &SsaLocals,
+    body: &Body<'tcx>,
+) -> IndexVec<Local, Option<Local>> {
+    let mut sources = IndexVec::from_elem(None, &body.local_decls);
+
+    // Assignments are visited in dominance order, so the source of an operand is known by the
+    // time it is used.
+    for (local, rvalue, _) in ssa.assignments(body) {
+        sources[local] = match rvalue {
+            Rvalue::Cast(CastKind::Pointer(PointerCast::Unsize), operand, target_ty) => {
+                let Some(source) = operand.place().and_then(|place| place.as_local()) else {
+                    continue;
+                };
+                let is_shared_ref =
+                    |ty: Ty<'tcx>| matches!(ty.kind(), ty::Ref(_, _, Mutability::Not));
+                if !ssa.is_ssa(source)
+                    || !is_shared_ref(body.local_decls[source].ty)
+                    || !is_shared_ref(*target_ty)
+                {
+                    continue;
+                }
+                Some(source)
+            }
+            Rvalue::Use(Operand::Copy(place) | Operand::Move(place)) => {
+                place.as_local().and_then(|source| sources[source])
+            }
+            Rvalue::Ref(_, BorrowKind::Shared, place)
+                if let [PlaceElem::Deref] = place.projection[..] =>
+            {
+                sources[place.local]
+            }
+            _ => None,
+        };
+    }
+
+    sources
+}
+
+struct Replacer<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    receivers: BitSet<Local>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for Replacer<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_operand(&mut self, operand: &mut Operand<'tcx>, loc: Location) {
+        // The unsize coercion may have moved out of the receiver, which is now also used by the
+        // devirtualized call.
+        if let Operand::Move(place) = *operand
+            && let Some(local) = place.as_local()
+            && self.receivers.contains(local)
+        {
+            *operand = Operand::Copy(place);
+        }
+        self.super_operand(operand, loc);
+    }
+
+    fn visit_statement(&mut self, stmt: &mut Statement<'tcx>, loc: Location) {
+        // The storage of the receiver may have ended right after the coercion.
+        if let StatementKind::StorageLive(l) | StatementKind::StorageDead(l) = stmt.kind
+            && self.receivers.contains(l)
+        {
+            stmt.make_nop();
+            return;
+        }
+        self.super_statement(stmt, loc);
+    }
+}
@@ -237,6 +237,19 @@ pub(crate) struct FnItemRef {
     pub ident: String,
 }
 
+#[derive(LintDiagnostic)]
+#[diag(mir_transform_inline_always_cycle)]
+#[note]
+pub(crate) struct InlineAlwaysCycle {
+    #[label]
+    pub span: Span,
+    #[help]
+    pub unrollable: Option<()>,
+    pub callee: String,
+    pub edge_caller: String,
+    pub edge_callee: String,
+}
+
 #[derive(LintDiagnostic)]
 #[diag(mir_transform_must_not_suspend)]
 pub(crate) struct MustNotSupend<'a> {
@@ -0,0 +1,175 @@
+//! Folds `Vec::len` and `Vec::is_empty` calls on vectors that are known to be empty.
+//!
+//! A vector returned by `Vec::new()` or `Vec::with_capacity(_)` has length 0 until it is
+//! mutated. When a vector local is only ever shared-borrowed to call `len` or `is_empty`, and
+//! dropped, as in
+//!   _1 = Vec::<T>::new() -> bb1;
+//!   _2 = &_1;
+//!   _0 = Vec::<T>::len(move _2) -> bb2;
+//! nothing can have pushed to it, so we replace the call with `_0 = const 0_usize`.
+//!
+//! Any other use of the vector, like a mutable borrow or a move, makes us give up on it.
+
+use rustc_index::bit_set::BitSet;
+use rustc_index::IndexVec;
+use rustc_middle::mir::visit::*;
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::{sym, Symbol};
+
+use crate::MirPass;
+
+pub struct FoldEmptyVecLen;
+
+impl<'tcx> MirPass<'tcx> for FoldEmptyVecLen {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+        let receivers = compute_empty_vec_receivers(tcx, body);
+        if receivers.is_empty() {
+            return;
+        }
+
+        // Calls are replaced by a `Goto` to their return target, so the CFG is unchanged.
+        for data in body.basic_blocks.as_mut_preserves_cfg() {
+            let terminator = data.terminator();
+            let TerminatorKind::Call { func, args, destination, target: Some(target), .. } =
+                &terminator.kind
+            else {
+                continue;
+            };
+            let Some(receiver) =
+                args.first().and_then(|arg| arg.place()).and_then(|p| p.as_local())
+            else {
+                continue;
+            };
+            if !receivers.contains(receiver) {
+                continue;
+            }
+            let literal = match vec_method_name(tcx, func) {
+                Some(sym::len) => ConstantKind::from_usize(tcx, 0),
+                Some(sym::is_empty) => ConstantKind::from_bool(tcx, true),
+                _ => continue,
+            };
+            debug!(?receiver, ?literal, "folding call");
+
+            let source_info = terminator.source_info;
+            let constant = Constant { span: source_info.span, user_ty: None, literal };
+            let assign = Rvalue::Use(Operand::Constant(Box::new(constant)));
+            let kind = StatementKind::Assign(Box::new((*destination, assign)));
+            let target = *target;
+            data.statements.push(Statement { source_info, kind });
+            data.terminator_mut().kind = TerminatorKind::Goto { target };
+        }
+    }
+}
+
+/// Returns the name of the inherent `Vec` method that `func` calls, if any.
+fn vec_method_name<'tcx>(tcx: TyCtxt<'tcx>, func: &Operand<'tcx>) -> Option<Symbol> {
+    let (def_id, _) = func.const_fn_def()?;
+    let impl_def_id = tcx.impl_of_method(def_id)?;
+    if tcx.trait_id_of_impl(impl_def_id).is_some() {
+        return None;
+    }
+    let adt = tcx.type_of(impl_def_id).subst_identity().ty_adt_def()?;
+    tcx.is_diagnostic_item(sym::Vec, adt.did()).then(|| tcx.item_name(def_id))
+}
+
+/// Computes the locals that hold a shared borrow of a vector that is known to be empty, and
+/// that are only used as the receiver of `len` or `is_empty` calls.
+fn compute_empty_vec_receivers<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>) -> BitSet<Local> {
+    let mut finder = EmptyVecFinder {
+        tcx,
+        constructed: BitSet::new_empty(body.local_decls.len()),
+        borrowed_vec: IndexVec::from_elem(None, &body.local_decls),
+        borrow_assignments: IndexVec::from_elem(0, &body.local_decls),
+        escaped: BitSet::new_empty(body.local_decls.len()),
+    };
+    finder.visit_body(body);
+    debug!(?finder.constructed, ?finder.borrowed_vec, ?finder.escaped);
+
+    let mut receivers = BitSet::new_empty(body.local_decls.len());
+    for (receiver, &vec) in finder.borrowed_vec.iter_enumerated() {
+        let Some(vec) = vec else { continue };
+        if finder.borrow_assignments[receiver] == 1
+            && !finder.escaped.contains(receiver)
+            && finder.constructed.contains(vec)
+            && !finder.escaped.contains(vec)
+        {
+            receivers.insert(receiver);
+        }
+    }
+    receivers
+}
+
+struct EmptyVecFinder<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    /// Locals that are assigned the result of `Vec::new` or `Vec::with_capacity`.
+    constructed: BitSet<Local>,
+    /// For locals assigned `&_n`, the borrowed local `_n`.
+    borrowed_vec: IndexVec<Local, Option<Local>>,
+    /// Number of `_r = &_n` assignments to each local.
+    borrow_assignments: IndexVec<Local, u32>,
+    /// Locals that are used in any other way.
+    escaped: BitSet<Local>,
+}
+
+impl<'tcx> Visitor<'tcx> for EmptyVecFinder<'tcx> {
+    fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
+        if let StatementKind::Assign(box (place, Rvalue::Ref(_, BorrowKind::Shared, borrowed))) =
+            &statement.kind
+            && let Some(receiver) = place.as_local()
+            && let Some(vec) = borrowed.as_local()
+        {
+            self.borrowed_vec[receiver] = Some(vec);
+            self.borrow_assignments[receiver] += 1;
+            return;
+        }
+        self.super_statement(statement, location);
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        match &terminator.kind {
+            TerminatorKind::Call { func, args, destination, .. } => {
+                match vec_method_name(self.tcx, func) {
+                    Some(sym::new | sym::with_capacity)
+                        if let Some(vec) = destination.as_local() =>
+                    {
+                        self.constructed.insert(vec);
+                        for arg in args {
+                            self.visit_operand(arg, location);
+                        }
+                        return;
+                    }
+                    Some(sym::len | sym::is_empty)
+                        if let [arg] = &args[..]
+                            && let Some(receiver) = arg.place()
+                            && receiver.as_local().is_some() =>
+                    {
+                        self.visit_place(
+                            destination,
+                            PlaceContext::MutatingUse(MutatingUseContext::Call),
+                            location,
+                        );
+                        return;
+                    }
+                    _ => {}
+                }
+            }
+            // Dropping the vector does not observe its length.
+            TerminatorKind::Drop { place, .. } if place.as_local().is_some() => return,
+            _ => {}
+        }
+        self.super_terminator(terminator, location);
+    }
+
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if context.is_use() {
+            self.escaped.insert(local);
+        }
+    }
+}
@@ -1,21 +1,28 @@
 //! Inlining pass for MIR functions
 use crate::deref_separator::deref_finder;
-use rustc_attr::InlineAttr;
+use rustc_attr::{InlineAttr, OptimizeAttr};
 use rustc_hir::def_id::DefId;
 use rustc_index::bit_set::BitSet;
 use rustc_index::Idx;
 use rustc_middle::middle::codegen_fn_attrs::{CodegenFnAttrFlags, CodegenFnAttrs};
 use rustc_middle::mir::visit::*;
 use rustc_middle::mir::*;
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::TypeVisitableExt;
 use rustc_middle::ty::{self, Instance, InstanceDef, ParamEnv, Ty, TyCtxt};
+use rustc_session::code_stats::InlineDecisionInfo;
 use rustc_session::config::OptLevel;
+use rustc_session::lint::builtin::INLINE_ALWAYS_CYCLE;
 use rustc_target::abi::{FieldIdx, FIRST_VARIANT};
 use rustc_target::spec::abi::Abi;
+use rustc_target::spec::PanicStrategy;
 
+use crate::const_prop::ConstProp;
+use crate::errors;
 use crate::simplify::{remove_dead_blocks, CfgSimplifier};
 use crate::util;
 use crate::MirPass;
+use std::cell::Cell;
 use std::iter;
 use std::ops::{Range, RangeFrom};
 
@@ -59,15 +66,73 @@ fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         let span = trace_span!("inline", body = %tcx.def_path_str(body.source.def_id()));
         let _guard = span.enter();
-        if inline(tcx, body) {
-            debug!("running simplify cfg on {:?}", body.source);
-            CfgSimplifier::new(body).simplify();
-            remove_dead_blocks(tcx, body);
-            deref_finder(tcx, body);
+        inline_and_simplify(tcx, body);
+    }
+}
+
+/// Runs the inliner again after `SeparateConstSwitch`, which at mir-opt-level 3 threads switches
+/// over enum variants that the caller sets, e.g. in the bodies of inlined closures. This can make
+/// calls reachable that were skipped before.
+pub struct InlineAfterSeparateConstSwitch;
+
+impl<'tcx> MirPass<'tcx> for InlineAfterSeparateConstSwitch {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3 && Inline.is_enabled(sess)
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let span = trace_span!("inline", body = %tcx.def_path_str(body.source.def_id()));
+        let _guard = span.enter();
+        inline_and_simplify(tcx, body);
+    }
+}
+
+/// Runs the inliner again after `ConstProp`, which at mir-opt-level 3 turns calls through function
+/// pointers with a known value into direct calls, e.g. of a function item passed to an inlined
+/// higher-order function. While the inlined bodies call function pointers themselves, this runs
+/// `ConstProp` and the inliner again, at most [`CONST_PROP_INLINE_ROUNDS`] times.
+pub struct InlineAfterConstProp;
+
+const CONST_PROP_INLINE_ROUNDS: usize = 4;
+
+impl<'tcx> MirPass<'tcx> for InlineAfterConstProp {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3 && Inline.is_enabled(sess)
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let span = trace_span!("inline", body = %tcx.def_path_str(body.source.def_id()));
+        let _guard = span.enter();
+        for round in 1..=CONST_PROP_INLINE_ROUNDS {
+            if !inline_and_simplify(tcx, body) || round == CONST_PROP_INLINE_ROUNDS {
+                break;
+            }
+            let calls_fn_ptr = body.basic_blocks.iter().any(|data| {
+                matches!(&data.terminator().kind, TerminatorKind::Call { func, .. }
+                    if func.ty(&body.local_decls, tcx).is_fn_ptr())
+            });
+            if !calls_fn_ptr {
+                break;
+            }
+            debug!(?round, "resolving the calls through function pointers of the inlined bodies");
+            ConstProp.run_pass(tcx, body);
         }
     }
 }
 
+/// Inlines the calls in `body` and cleans up the CFG afterwards. Returns whether anything was
+/// inlined.
+pub(crate) fn inline_and_simplify<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
+    if !inline(tcx, body) {
+        return false;
+    }
+    debug!("running simplify cfg on {:?}", body.source);
+    CfgSimplifier::new(body).simplify();
+    remove_dead_blocks(tcx, body);
+    deref_finder(tcx, body);
+    true
+}
+
 fn inline<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
     let def_id = body.source.def_id().expect_local();
 
@@ -84,6 +149,10 @@ fn inline<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
     if body.generator.is_some() {
         return false;
     }
+    // `#[inline(never_callees)]` keeps the body of the caller as written.
+    if tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::NO_INLINE_CALLEES) {
+        return false;
+    }
 
     let param_env = tcx.param_env_reveal_all_normalized(def_id);
 
@@ -93,6 +162,9 @@ fn inline<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
         codegen_fn_attrs: tcx.codegen_fn_attrs(def_id),
         history: Vec::new(),
         changed: false,
+        inlined_cost: 0,
+        cost_estimate: Cell::new(None),
+        broken_cycle_edge: Cell::new(None),
     };
     let blocks = START_BLOCK..body.basic_blocks.next_index();
     this.process_blocks(body, blocks);
@@ -107,11 +179,21 @@ struct Inliner<'tcx> {
     /// Stack of inlined instances.
     /// We only check the `DefId` and not the substs because we want to
     /// avoid inlining cases of polymorphic recursion.
-    /// The number of `DefId`s is finite, so checking history is enough
-    /// to ensure that we do not loop endlessly while inlining.
+    /// The number of `DefId`s is finite, so bounding the number of times
+    /// each of them occurs in the history is enough to ensure that we do
+    /// not loop endlessly while inlining.
     history: Vec<DefId>,
     /// Indicates that the caller body has been modified.
     changed: bool,
+    /// Total cost of the callees inlined into the caller so far, which is limited by
+    /// `-Zinline-mir-total-threshold`.
+    inlined_cost: usize,
+    /// Cost and threshold computed for the callsite being considered, for `-Zinline-mir-report`.
+    cost_estimate: Cell<Option<(usize, usize)>>,
+    /// The first call to an `#[inline(always)]` function that was left in place to break a call
+    /// cycle while inlining the callsite of the caller being processed, as `(caller, callee)`,
+    /// and whether unrolling the cycle further would have inlined it.
+    broken_cycle_edge: Cell<Option<(DefId, DefId, bool)>>,
 }
 
 impl<'tcx> Inliner<'tcx> {
@@ -123,13 +205,20 @@ fn process_blocks(&mut self, caller_body: &mut Body<'tcx>, blocks: Range<BasicBl
             1..=TOP_DOWN_DEPTH_LIMIT => 1,
             _ => return,
         };
+        // Cycles are reported on the callsites of the caller itself, not on those that were
+        // inlined into it.
+        let top_level = self.history.is_empty();
         let mut inlined_count = 0;
         for bb in blocks {
-            let bb_data = &caller_body[bb];
-            if bb_data.is_cleanup {
+            if caller_body[bb].is_cleanup {
                 continue;
             }
 
+            if self.bypass_fn_def_shim(caller_body, bb) {
+                self.changed = true;
+            }
+
+            let bb_data = &caller_body[bb];
             let Some(callsite) = self.resolve_callsite(caller_body, bb, bb_data) else {
                 continue;
             };
@@ -137,18 +226,36 @@ fn process_blocks(&mut self, caller_body: &mut Body<'tcx>, blocks: Range<BasicBl
             let span = trace_span!("process_blocks", %callsite.callee, ?bb);
             let _guard = span.enter();
 
-            match self.try_inlining(caller_body, &callsite) {
+            self.cost_estimate.set(None);
+            if top_level {
+                self.broken_cycle_edge.set(None);
+            }
+            let result = self.try_inlining(caller_body, &callsite);
+            if self.tcx.sess.opts.unstable_opts.inline_mir_report.is_some() {
+                self.record_decision(caller_body, &callsite, result.as_ref().err().copied());
+            }
+
+            match result {
                 Err(reason) => {
                     debug!("not-inlined {} [{}]", callsite.callee, reason);
+                    if top_level {
+                        self.lint_broken_cycle(caller_body, &callsite);
+                    }
                     continue;
                 }
                 Ok(new_blocks) => {
                     debug!("inlined {}", callsite.callee);
                     self.changed = true;
+                    if let Some((cost, _)) = self.cost_estimate.get() {
+                        self.inlined_cost += cost;
+                    }
 
                     self.history.push(callsite.callee.def_id());
                     self.process_blocks(caller_body, new_blocks);
                     self.history.pop();
+                    if top_level {
+                        self.lint_broken_cycle(caller_body, &callsite);
+                    }
 
                     inlined_count += 1;
                     if inlined_count == inline_limit {
@@ -160,6 +267,57 @@ fn process_blocks(&mut self, caller_body: &mut Body<'tcx>, blocks: Range<BasicBl
         }
     }
 
+    fn record_decision(
+        &self,
+        caller_body: &Body<'tcx>,
+        callsite: &CallSite<'tcx>,
+        rejection: Option<&'static str>,
+    ) {
+        let (cost, threshold) = self.cost_estimate.get().unzip();
+        self.tcx.sess.code_stats.record_inline_decision(InlineDecisionInfo {
+            caller: with_no_trimmed_paths!(self.tcx.def_path_str(caller_body.source.def_id())),
+            callee: with_no_trimmed_paths!(callsite.callee.to_string()),
+            cost,
+            threshold,
+            caller_inlined_cost: self.inlined_cost,
+            rejection,
+        });
+    }
+
+    /// Records the call from the innermost inlined function to `callee` as the edge that breaks a
+    /// cycle, if `callee` is `#[inline(always)]` and no other edge was recorded yet.
+    fn break_cycle(&self, caller_body: &Body<'tcx>, callee: DefId, unrollable: bool) {
+        if self.broken_cycle_edge.get().is_some()
+            || self.tcx.codegen_fn_attrs(callee).inline != InlineAttr::Always
+        {
+            return;
+        }
+        let caller = self.history.last().copied().unwrap_or_else(|| caller_body.source.def_id());
+        self.broken_cycle_edge.set(Some((caller, callee, unrollable)));
+    }
+
+    /// Emits `inline_always_cycle` on a callsite of the caller if inlining it broke a cycle.
+    fn lint_broken_cycle(&self, caller_body: &Body<'tcx>, callsite: &CallSite<'tcx>) {
+        let Some((caller, callee, unrollable)) = self.broken_cycle_edge.take() else { return };
+        let ClearCrossCrate::Set(scope_data) =
+            &caller_body.source_scopes[callsite.source_info.scope].local_data
+        else {
+            return;
+        };
+        self.tcx.emit_spanned_lint(
+            INLINE_ALWAYS_CYCLE,
+            scope_data.lint_root,
+            callsite.source_info.span,
+            errors::InlineAlwaysCycle {
+                span: callsite.source_info.span,
+                unrollable: unrollable.then_some(()),
+                callee: with_no_trimmed_paths!(self.tcx.def_path_str(callsite.callee.def_id())),
+                edge_caller: with_no_trimmed_paths!(self.tcx.def_path_str(caller)),
+                edge_callee: with_no_trimmed_paths!(self.tcx.def_path_str(callee)),
+            },
+        );
+    }
+
     /// Attempts to inline a callsite into the caller body. When successful returns basic blocks
     /// containing the inlined body. Otherwise returns an error describing why inlining didn't take
     /// place.
@@ -255,6 +413,7 @@ fn check_mir_is_available(
         let caller_def_id = caller_body.source.def_id();
         let callee_def_id = callee.def_id();
         if callee_def_id == caller_def_id {
+            self.break_cycle(caller_body, callee_def_id, false);
             return Err("self-recursion");
         }
 
@@ -305,6 +464,7 @@ fn check_mir_is_available(
             // If we know for sure that the function we're calling will itself try to
             // call us, then we avoid inlining that function.
             if self.tcx.mir_callgraph_reachable((*callee, caller_def_id.expect_local())) {
+                self.break_cycle(caller_body, callee_def_id, false);
                 return Err("caller might be reachable from callee (query cycle avoidance)");
             }
 
@@ -339,8 +499,20 @@ fn resolve_callsite(
                     return None;
                 }
 
-                if self.history.contains(&callee.def_id()) {
-                    return None;
+                // `#[inline(always)]` functions may be inlined into themselves a few times, to
+                // unroll cycles of such functions. Everything else is inlined at most once.
+                let depth = self.history.iter().filter(|&&id| id == callee.def_id()).count();
+                if depth > 0 {
+                    let max_depth = match self.tcx.codegen_fn_attrs(callee.def_id()).inline {
+                        InlineAttr::Always => {
+                            self.tcx.sess.opts.unstable_opts.inline_mir_cycle_depth
+                        }
+                        _ => 1,
+                    };
+                    if depth >= max_depth {
+                        self.break_cycle(caller_body, callee.def_id(), true);
+                        return None;
+                    }
                 }
 
                 let fn_sig = self.tcx.fn_sig(def_id).subst(self.tcx, substs);
@@ -353,6 +525,76 @@ fn resolve_callsite(
         None
     }
 
+    /// Rewrites a call of an `Fn*` trait method on a function item into a direct call of that
+    /// function item. Returns `true` if the call was rewritten.
+    ///
+    /// Such calls show up once a generic function taking an `impl Fn` argument, as in
+    /// `fn f(g: impl Fn()) { g() }`, has been inlined into a caller passing a function item. They
+    /// resolve to a shim that merely untuples the arguments, which would otherwise have to be
+    /// inlined first, and would count against the inlining depth limit of the actual callee.
+    fn bypass_fn_def_shim(&self, caller_body: &mut Body<'tcx>, bb: BasicBlock) -> bool {
+        let tcx = self.tcx;
+        let terminator = caller_body[bb].terminator();
+        let TerminatorKind::Call { ref func, ref args, fn_span, .. } = terminator.kind else {
+            return false;
+        };
+        let ty::FnDef(def_id, substs) = *func.ty(caller_body, tcx).kind() else {
+            return false;
+        };
+        let Ok(substs) = tcx.try_normalize_erasing_regions(self.param_env, substs) else {
+            return false;
+        };
+        let Ok(Some(instance)) = Instance::resolve(tcx, self.param_env, def_id, substs) else {
+            return false;
+        };
+        let InstanceDef::FnPtrShim(_, shim_ty) = instance.def else {
+            return false;
+        };
+        let ty::FnDef(fn_def_id, fn_substs) = *shim_ty.kind() else {
+            return false;
+        };
+        if tcx.fn_sig(fn_def_id).skip_binder().abi() == Abi::RustCall {
+            return false;
+        }
+        // `#[track_caller]` functions see the location they were called from, which would
+        // change from the call in the shim to this call site.
+        if tcx.codegen_fn_attrs(fn_def_id).flags.contains(CodegenFnAttrFlags::TRACK_CALLER) {
+            return false;
+        }
+
+        // The shim takes the function item by reference and the arguments as a tuple.
+        let [_, arg_tuple] = &args[..] else {
+            return false;
+        };
+        let new_args = match *arg_tuple {
+            Operand::Move(tuple_place) | Operand::Copy(tuple_place) => {
+                let ty::Tuple(arg_tys) = *tuple_place.ty(caller_body, tcx).ty.kind() else {
+                    return false;
+                };
+                let operand: fn(Place<'tcx>) -> Operand<'tcx> =
+                    if let Operand::Move(_) = arg_tuple { Operand::Move } else { Operand::Copy };
+                arg_tys
+                    .iter()
+                    .enumerate()
+                    .map(|(i, ty)| operand(tcx.mk_place_field(tuple_place, FieldIdx::new(i), ty)))
+                    .collect()
+            }
+            // Calls without arguments pass the empty tuple as a constant.
+            Operand::Constant(ref constant) if constant.ty().is_unit() => Vec::new(),
+            Operand::Constant(_) => return false,
+        };
+        let new_func = Operand::function_handle(tcx, fn_def_id, fn_substs, fn_span);
+
+        debug!("bypassing {:?} in favor of a direct call of {:?}", instance, shim_ty);
+        let TerminatorKind::Call { func, args, .. } = &mut caller_body[bb].terminator_mut().kind
+        else {
+            bug!()
+        };
+        *func = new_func;
+        *args = new_args;
+        true
+    }
+
     /// Returns an error if inlining is not possible based on codegen attributes alone. A success
     /// indicates that inlining decision should be based on other criteria.
     fn check_codegen_attributes(
@@ -378,10 +620,22 @@ fn check_codegen_attributes(
             return Err("C variadic");
         }
 
-        if callee_attrs.flags.contains(CodegenFnAttrFlags::COLD) {
+        // Cold functions are only inlined if they ask for it, and then only if they are tiny, see
+        // `check_mir_body`.
+        if callee_attrs.flags.contains(CodegenFnAttrFlags::COLD)
+            && !callee_attrs.requests_inline()
+        {
             return Err("cold");
         }
 
+        // Callers optimized for size only inline the callees that ask for it.
+        if let OptimizeAttr::Size = self.codegen_fn_attrs.optimize
+            && !callee_attrs.requests_inline()
+            && callee_attrs.inline_budget.is_none()
+        {
+            return Err("caller optimized for size");
+        }
+
         if callee_attrs.no_sanitize != self.codegen_fn_attrs.no_sanitize {
             return Err("incompatible sanitizer set");
         }
@@ -415,18 +669,30 @@ fn check_mir_body(
     ) -> Result<(), &'static str> {
         let tcx = self.tcx;
 
-        let mut threshold = if callee_attrs.requests_inline() {
-            self.tcx.sess.opts.unstable_opts.inline_mir_hint_threshold.unwrap_or(100)
+        let threshold = if let Some(budget) = callee_attrs.inline_budget {
+            // An explicit `#[inline_budget]` is used as is, without the bonus below.
+            budget
         } else {
-            self.tcx.sess.opts.unstable_opts.inline_mir_threshold.unwrap_or(50)
-        };
+            let mut threshold = if callee_attrs.requests_inline() {
+                self.tcx.sess.opts.unstable_opts.inline_mir_hint_threshold.unwrap_or(100)
+            } else {
+                self.tcx.sess.opts.unstable_opts.inline_mir_threshold.unwrap_or(50)
+            };
 
-        // Give a bonus functions with a small number of blocks,
-        // We normally have two or three blocks for even
-        // very small functions.
-        if callee_body.basic_blocks.len() <= 3 {
-            threshold += threshold / 4;
-        }
+            // Give a bonus functions with a small number of blocks,
+            // We normally have two or three blocks for even
+            // very small functions.
+            if callee_body.basic_blocks.len() <= 3 {
+                threshold += threshold / 4;
+            }
+
+            // Calls to cold functions are not worth making the caller bigger, so they are only
+            // inlined if the callee is about as small as the call itself.
+            if callee_attrs.flags.contains(CodegenFnAttrFlags::COLD) {
+                threshold /= 10;
+            }
+            threshold
+        };
         debug!("    final inline threshold = {}", threshold);
 
         // FIXME: Give a bonus to functions with only a single caller
@@ -437,6 +703,7 @@ fn check_mir_body(
             instance: callsite.callee,
             callee_body,
             cost: 0,
+            unwinds: tcx.sess.panic_strategy() == PanicStrategy::Unwind,
             validation: Ok(()),
         };
 
@@ -449,6 +716,11 @@ fn check_mir_body(
             }
 
             let blk = &callee_body.basic_blocks[bb];
+            // The callee may have been built with `-Cpanic=unwind`, but its cleanup blocks are
+            // dead code once it's inlined into a function that can't unwind.
+            if blk.is_cleanup && !checker.unwinds {
+                continue;
+            }
             checker.visit_basic_block_data(bb, blk);
 
             let term = blk.terminator();
@@ -480,15 +752,30 @@ fn check_mir_body(
         checker.validation?;
 
         let cost = checker.cost;
-        if let InlineAttr::Always = callee_attrs.inline {
+        self.cost_estimate.set(Some((cost, threshold)));
+        // `#[cold]` wins over `#[inline(always)]`, so the reduced threshold applies to both.
+        if let InlineAttr::Always = callee_attrs.inline
+            && !callee_attrs.flags.contains(CodegenFnAttrFlags::COLD)
+        {
             debug!("INLINING {:?} because inline(always) [cost={}]", callsite, cost);
-            Ok(())
-        } else if cost <= threshold {
+            return Ok(());
+        }
+        if cost > threshold {
+            debug!("NOT inlining {:?} [cost={} > threshold={}]", callsite, cost, threshold);
+            return Err("cost above threshold");
+        }
+
+        if let Some(total_threshold) = tcx.sess.opts.unstable_opts.inline_mir_total_threshold
+            && self.inlined_cost + cost > total_threshold
+        {
+            debug!(
+                "NOT inlining {:?} [inlined cost={} + cost={} > total threshold={}]",
+                callsite, self.inlined_cost, cost, total_threshold
+            );
+            Err("total inlined cost above threshold")
+        } else {
             debug!("INLINING {:?} [cost={} <= threshold={}]", callsite, cost, threshold);
             Ok(())
-        } else {
-            debug!("NOT inlining {:?} [cost={} > threshold={}]", callsite, cost, threshold);
-            Err("cost above threshold")
         }
     }
 
@@ -767,11 +1054,23 @@ struct CostChecker<'b, 'tcx> {
     tcx: TyCtxt<'tcx>,
     param_env: ParamEnv<'tcx>,
     cost: usize,
+    /// Whether we're compiling with `-Cpanic=unwind`. Only then do landing pads and
+    /// cleanup blocks cost anything.
+    unwinds: bool,
     callee_body: &'b Body<'tcx>,
     instance: ty::Instance<'tcx>,
     validation: Result<(), &'static str>,
 }
 
+impl CostChecker<'_, '_> {
+    fn landing_pad_cost(&self, unwind: UnwindAction) -> usize {
+        match unwind {
+            UnwindAction::Cleanup(_) if self.unwinds => LANDINGPAD_PENALTY,
+            _ => 0,
+        }
+    }
+}
+
 impl<'tcx> Visitor<'tcx> for CostChecker<'_, 'tcx> {
     fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
         // Don't count StorageLive/StorageDead in the inlining cost.
@@ -795,10 +1094,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                     .instance
                     .subst_mir(tcx, ty::EarlyBinder::bind(&place.ty(self.callee_body, tcx).ty));
                 if ty.needs_drop(tcx, self.param_env) {
-                    self.cost += CALL_PENALTY;
-                    if let UnwindAction::Cleanup(_) = unwind {
-                        self.cost += LANDINGPAD_PENALTY;
-                    }
+                    self.cost += CALL_PENALTY + self.landing_pad_cost(unwind);
                 } else {
                     self.cost += INSTR_COST;
                 }
@@ -811,22 +1107,14 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                 } else {
                     CALL_PENALTY
                 };
-                if let UnwindAction::Cleanup(_) = unwind {
-                    self.cost += LANDINGPAD_PENALTY;
-                }
+                self.cost += self.landing_pad_cost(unwind);
             }
             TerminatorKind::Assert { unwind, .. } => {
-                self.cost += CALL_PENALTY;
-                if let UnwindAction::Cleanup(_) = unwind {
-                    self.cost += LANDINGPAD_PENALTY;
-                }
+                self.cost += CALL_PENALTY + self.landing_pad_cost(unwind);
             }
             TerminatorKind::Resume => self.cost += RESUME_PENALTY,
             TerminatorKind::InlineAsm { unwind, .. } => {
-                self.cost += INSTR_COST;
-                if let UnwindAction::Cleanup(_) = unwind {
-                    self.cost += LANDINGPAD_PENALTY;
-                }
+                self.cost += INSTR_COST + self.landing_pad_cost(unwind);
             }
             _ => self.cost += INSTR_COST,
         }
@@ -6,6 +6,10 @@
 use rustc_middle::ty::{self, subst::SubstsRef, InstanceDef, TyCtxt};
 use rustc_session::Limit;
 
+use crate::const_prop::reified_fns;
+use crate::devirtualize::{devirtualizable_calls, Devirtualize};
+use crate::MirPass;
+
 // FIXME: check whether it is cheaper to precompute the entire call graph instead of invoking
 // this query ridiculously often.
 #[instrument(level = "debug", skip(tcx, root, target))]
@@ -171,5 +175,14 @@ pub(crate) fn mir_inliner_callees<'tcx>(
             calls.insert(call);
         }
     }
+    // The virtual calls that `Devirtualize` turns into direct calls are callees as well. Otherwise
+    // two functions calling each other through trait objects could inline each other.
+    if Devirtualize.is_enabled(tcx.sess) {
+        calls.extend(devirtualizable_calls(tcx, body));
+    }
+    // So are the functions that `ConstProp` resolves calls through function pointers to.
+    if tcx.sess.mir_opt_level() >= 3 {
+        calls.extend(reified_fns(tcx, body));
+    }
     tcx.arena.alloc_from_iter(calls.iter().copied())
 }
@@ -37,6 +37,7 @@
 use rustc_middle::query::Providers;
 use rustc_middle::ty::{self, TyCtxt, TypeVisitableExt};
 use rustc_span::sym;
+use rustc_target::spec::abi::Abi;
 use rustc_trait_selection::traits;
 
 #[macro_use]
@@ -60,6 +61,7 @@
 mod const_prop_lint;
 mod copy_prop;
 mod coverage;
+mod cross_crate_inline;
 mod ctfe_limit;
 mod dataflow_const_prop;
 mod dead_store_elimination;
@@ -67,26 +69,31 @@
 mod deduplicate_blocks;
 mod deref_separator;
 mod dest_prop;
+mod devirtualize;
 pub mod dump_mir;
 mod early_otherwise_branch;
 mod elaborate_box_derefs;
 mod elaborate_drops;
 mod errors;
 mod ffi_unwind_calls;
+mod fold_empty_vec_len;
 mod function_item_references;
 mod generator;
 mod inline;
 mod instsimplify;
 mod large_enums;
+mod loop_invariant_code_motion;
 mod lower_intrinsics;
 mod lower_slice_len;
 mod match_branches;
+mod merge_panic_blocks;
 mod multiple_return_terminators;
 mod normalize_array_len;
 mod nrvo;
 mod prettify;
 mod ref_prop;
 mod remove_noop_landing_pads;
+mod remove_redundant_bounds_checks;
 mod remove_storage_markers;
 mod remove_uninit_drops;
 mod remove_unneeded_drops;
@@ -118,12 +125,14 @@
 pub fn provide(providers: &mut Providers) {
     check_unsafety::provide(providers);
     coverage::query::provide(providers);
+    cross_crate_inline::provide(providers);
     ffi_unwind_calls::provide(providers);
     shim::provide(providers);
     *providers = Providers {
         mir_keys,
         mir_const,
         mir_const_qualif,
+        is_const_fn_candidate,
         mir_promoted,
         mir_drops_elaborated_and_const_checked,
         mir_for_ctfe,
@@ -256,6 +265,39 @@ fn mir_const_qualif(tcx: TyCtxt<'_>, def: LocalDefId) -> ConstQualifs {
     validator.qualifs_in_return_place()
 }
 
+/// Const-checks the body of a non-const `fn` as if it was a `const fn`, without emitting any
+/// errors. Like `mir_const_qualif`, this has to be computed before `mir_promoted` steals the
+/// mir-const result.
+fn is_const_fn_candidate(tcx: TyCtxt<'_>, def: LocalDefId) -> bool {
+    if !matches!(tcx.def_kind(def), DefKind::Fn | DefKind::AssocFn)
+        || tcx.hir().body_const_context(def).is_some()
+        || tcx.asyncness(def).is_async()
+        || tcx.fn_sig(def).skip_binder().abi() != Abi::Rust
+        // Without const stability attributes, a `const fn` in a `staged_api` crate can't be
+        // checked the way it would be if it was declared as such.
+        || tcx.features().staged_api
+    {
+        return false;
+    }
+
+    let body = &tcx.mir_const(def).borrow();
+
+    if body.generator.is_some() || body.tainted_by_errors.is_some() {
+        return false;
+    }
+
+    let ccx = check_consts::ConstCx {
+        body,
+        tcx,
+        const_kind: Some(hir::ConstContext::ConstFn),
+        param_env: tcx.param_env(def),
+    };
+
+    let mut checker = check_consts::check::Checker::new_dry_run(&ccx);
+    checker.check_body();
+    !checker.found_illegal_op()
+}
+
 /// Make MIR ready for const evaluation. This is run on all MIR, not just on consts!
 /// FIXME(oli-obk): it's unclear whether we still need this phase (and its corresponding query).
 /// We used to have this for pre-miri MIR based const eval.
@@ -298,6 +340,11 @@ fn mir_promoted(
     // this point, before we steal the mir-const result.
     // Also this means promotion can rely on all const checks having been done.
     let const_qualifs = tcx.mir_const_qualif(def);
+    // Likewise for checking whether a non-const `fn` could be a `const fn`, but only if anyone
+    // is going to ask.
+    if tcx.const_fn_candidate_lints_enabled(()) {
+        tcx.ensure_with_value().is_const_fn_candidate(def);
+    }
     let mut body = tcx.mir_const(def).steal();
     if let Some(error_reported) = const_qualifs.tainted_by_errors {
         body.tainted_by_errors = Some(error_reported);
@@ -544,9 +591,11 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             &check_alignment::CheckAlignment,
             &reveal_all::RevealAll, // has to be done before inlining, since inlined code is in RevealAll mode.
             &lower_slice_len::LowerSliceLenCalls, // has to be done before inlining, otherwise actual call will be almost always inlined. Also simple, so can just do first
+            &fold_empty_vec_len::FoldEmptyVecLen, // has to be done before inlining, for the same reason as `LowerSliceLenCalls`
             &unreachable_prop::UnreachablePropagation,
             &uninhabited_enum_branching::UninhabitedEnumBranching,
             &o1(simplify::SimplifyCfg::AfterUninhabitedEnumBranching),
+            &devirtualize::Devirtualize, // has to be done before inlining, so that the direct calls can be inlined.
             &inline::Inline,
             &remove_storage_markers::RemoveStorageMarkers,
             &remove_zsts::RemoveZsts,
@@ -561,11 +610,19 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             &simplify::SimplifyLocals::BeforeConstProp,
             &copy_prop::CopyProp,
             &ref_prop::ReferencePropagation,
+            &remove_redundant_bounds_checks::RemoveRedundantBoundsChecks,
+            &loop_invariant_code_motion::LoopInvariantCodeMotion,
             // Perform `SeparateConstSwitch` after SSA-based analyses, as cloning blocks may
             // destroy the SSA property. It should still happen before const-propagation, so the
             // latter pass will leverage the created opportunities.
-            &separate_const_switch::SeparateConstSwitch,
+            &separate_const_switch::SeparateConstSwitch::Initial,
+            // Threading switches can make more calls reachable, and inlining them can create more
+            // switches to thread.
+            &inline::InlineAfterSeparateConstSwitch,
+            &separate_const_switch::SeparateConstSwitch::AfterInline,
             &const_prop::ConstProp,
+            // Inlines the calls that `ConstProp` resolved, so `DataflowConstProp` sees their bodies.
+            &inline::InlineAfterConstProp,
             &dataflow_const_prop::DataflowConstProp,
             //
             // Const-prop runs unconditionally, but doesn't mutate the MIR at mir-opt-level=0.
@@ -579,6 +636,8 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             &o1(remove_noop_landing_pads::RemoveNoopLandingPads),
             &o1(simplify::SimplifyCfg::Final),
             &nrvo::RenameReturnPlace,
+            // Before `SimplifyLocals::Final`, so that the temporaries of merged blocks are removed.
+            &merge_panic_blocks::MergePanicBlocks,
             &simplify::SimplifyLocals::Final,
             &multiple_return_terminators::MultipleReturnTerminators,
             &deduplicate_blocks::DeduplicateBlocks,
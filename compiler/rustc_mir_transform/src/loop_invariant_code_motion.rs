@@ -0,0 +1,254 @@
+//! Loop-invariant code motion.
+//!
+//! Hoists assignments whose value does not change between loop iterations into the block that
+//! precedes the loop. We consider patterns of the form
+//!   _a = rvalue
+//! in a loop body, where `_a` is only assigned once, `rvalue` cannot panic nor cause UB, and all
+//! the operands of `rvalue` are constants or SSA locals assigned outside of the loop. As SSA
+//! locals are never borrowed, the operands cannot be modified through a pointer, or be part of
+//! an `UnsafeCell`.
+//!
+//! Only loops whose header has a single predecessor outside of the loop, which unconditionally
+//! jumps to the header, are considered. Inner loops are processed first, so an assignment can be
+//! hoisted through several levels of nested loops.
+//!
+//! This pass runs at mir-opt-level 2 and above, and can be disabled with
+//! `-Zmir-loop-invariant-code-motion=no`.
+
+use rustc_index::bit_set::BitSet;
+use rustc_index::IndexVec;
+use rustc_middle::mir::visit::*;
+use rustc_middle::mir::*;
+use rustc_middle::ty::{ParamEnv, TyCtxt};
+
+use crate::ssa::SsaLocals;
+use crate::MirPass;
+
+pub struct LoopInvariantCodeMotion;
+
+impl<'tcx> MirPass<'tcx> for LoopInvariantCodeMotion {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.opts.unstable_opts.mir_loop_invariant_code_motion.unwrap_or(sess.mir_opt_level() >= 2)
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+        if !body.basic_blocks.is_cfg_cyclic() {
+            return;
+        }
+
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let ssa = SsaLocals::new(body);
+
+        let hoisted = find_hoisted_assignments(tcx, param_env, &ssa, body);
+        debug!(?hoisted);
+        if hoisted.is_empty() {
+            return;
+        }
+
+        let mut hoisted_locals = BitSet::new_empty(body.local_decls.len());
+        let basic_blocks = body.basic_blocks.as_mut_preserves_cfg();
+        for (local, location, preheader) in hoisted {
+            hoisted_locals.insert(local);
+            let statement = &mut basic_blocks[location.block].statements[location.statement_index];
+            let source_info = statement.source_info;
+            let kind = std::mem::replace(&mut statement.kind, StatementKind::Nop);
+            basic_blocks[preheader].statements.push(Statement { source_info, kind });
+        }
+
+        Replacer { tcx, hoisted_locals }.visit_body_preserves_cfg(body);
+    }
+}
+
+struct Loop {
+    header: BasicBlock,
+    blocks: BitSet<BasicBlock>,
+}
+
+/// Finds the natural loops in `body`, merging the loops that share a header, ordered from the
+/// innermost to the outermost loop.
+fn find_loops(body: &Body<'_>) -> Vec<Loop> {
+    let dominators = body.basic_blocks.dominators();
+    let predecessors = body.basic_blocks.predecessors();
+
+    let mut loops: Vec<Loop> = Vec::new();
+    for (bb, data) in body.basic_blocks.iter_enumerated() {
+        if data.is_cleanup || !dominators.is_reachable(bb) {
+            continue;
+        }
+        for header in data.terminator().successors() {
+            // A back edge is an edge to a block that dominates its source.
+            if !dominators.dominates(header, bb) {
+                continue;
+            }
+            let index = match loops.iter().position(|l| l.header == header) {
+                Some(index) => index,
+                None => {
+                    let mut blocks = BitSet::new_empty(body.basic_blocks.len());
+                    blocks.insert(header);
+                    loops.push(Loop { header, blocks });
+                    loops.len() - 1
+                }
+            };
+            let blocks = &mut loops[index].blocks;
+            let mut worklist = vec![bb];
+            while let Some(block) = worklist.pop() {
+                if dominators.is_reachable(block)
+                    && dominators.dominates(header, block)
+                    && blocks.insert(block)
+                {
+                    worklist.extend(predecessors[block].iter().copied());
+                }
+            }
+        }
+    }
+
+    // A loop nested in another one has fewer blocks.
+    loops.sort_by_key(|l| l.blocks.count());
+    loops
+}
+
+/// Returns the block that all executions of the loop come from.
+fn find_preheader(body: &Body<'_>, l: &Loop) -> Option<BasicBlock> {
+    if l.header == START_BLOCK {
+        return None;
+    }
+    let mut outside =
+        body.basic_blocks.predecessors()[l.header].iter().filter(|&&bb| !l.blocks.contains(bb));
+    let preheader = *outside.next()?;
+    if outside.any(|&bb| bb != preheader) {
+        return None;
+    }
+    // The hoisted statements are appended to the preheader, so it must unconditionally continue
+    // to the loop.
+    let data = &body.basic_blocks[preheader];
+    match data.terminator().kind {
+        TerminatorKind::Goto { target } if target == l.header && !data.is_cleanup => {
+            Some(preheader)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the operands of `rvalue`, if evaluating it is pure, and can neither panic nor cause UB
+/// when speculated, i.e. evaluated in an iteration that would not have reached it.
+fn pure_operands<'a, 'tcx>(rvalue: &'a Rvalue<'tcx>) -> Option<Vec<&'a Operand<'tcx>>> {
+    match rvalue {
+        Rvalue::Use(operand)
+        | Rvalue::UnaryOp(_, operand)
+        | Rvalue::Cast(
+            CastKind::IntToInt
+            | CastKind::IntToFloat
+            | CastKind::FloatToInt
+            | CastKind::FloatToFloat,
+            operand,
+            _,
+        ) => Some(vec![operand]),
+        Rvalue::BinaryOp(
+            BinOp::Add
+            | BinOp::Sub
+            | BinOp::Mul
+            | BinOp::BitXor
+            | BinOp::BitAnd
+            | BinOp::BitOr
+            | BinOp::Eq
+            | BinOp::Lt
+            | BinOp::Le
+            | BinOp::Ne
+            | BinOp::Ge
+            | BinOp::Gt,
+            box (lhs, rhs),
+        ) => Some(vec![lhs, rhs]),
+        _ => None,
+    }
+}
+
+/// Computes which assignments to hoist, and into which block, in the order in which they must be
+/// appended to their new block.
+fn find_hoisted_assignments<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    ssa: &SsaLocals,
+    body: &Body<'tcx>,
+) -> Vec<(Local, Location, BasicBlock)> {
+    // The block that currently holds the assignment of each SSA local. Arguments are assigned
+    // before the start block.
+    let mut assigned_in = IndexVec::from_elem(None, &body.local_decls);
+    let mut locations = IndexVec::from_elem(None, &body.local_decls);
+    for (local, _, location) in ssa.assignments(body) {
+        assigned_in[local] = Some(location.block);
+        locations[local] = Some(location);
+    }
+
+    let mut hoists = Vec::new();
+    for l in find_loops(body) {
+        let Some(preheader) = find_preheader(body, &l) else { continue };
+
+        let is_invariant = |assigned_in: &IndexVec<Local, Option<BasicBlock>>,
+                            operand: &Operand<'tcx>| match operand {
+            Operand::Constant(_) => true,
+            Operand::Copy(place) | Operand::Move(place) => place.as_local().is_some_and(|local| {
+                ssa.is_ssa(local) && assigned_in[local].map_or(true, |bb| !l.blocks.contains(bb))
+            }),
+        };
+
+        // Assignments are visited in dominance order, so the operands of an assignment are
+        // hoisted before the assignment itself.
+        for (local, rvalue, _) in ssa.assignments(body) {
+            if let Some(bb) = assigned_in[local]
+                && l.blocks.contains(bb)
+                && body.local_decls[local].ty.is_copy_modulo_regions(tcx, param_env)
+                && let Some(operands) = pure_operands(rvalue)
+                && operands.into_iter().all(|operand| is_invariant(&assigned_in, operand))
+            {
+                assigned_in[local] = Some(preheader);
+                hoists.push((local, preheader));
+            }
+        }
+    }
+
+    // An assignment hoisted out of several nested loops is only moved to the outermost preheader.
+    let mut seen = BitSet::new_empty(body.local_decls.len());
+    let mut hoisted: Vec<_> = hoists
+        .into_iter()
+        .rev()
+        .filter(|&(local, _)| seen.insert(local))
+        .map(|(local, preheader)| (local, locations[local].unwrap(), preheader))
+        .collect();
+    hoisted.reverse();
+    hoisted
+}
+
+struct Replacer<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    hoisted_locals: BitSet<Local>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for Replacer<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_operand(&mut self, operand: &mut Operand<'tcx>, loc: Location) {
+        // A hoisted local is only assigned once, but may still be used in every iteration.
+        if let Operand::Move(place) = *operand
+            && let Some(local) = place.as_local()
+            && self.hoisted_locals.contains(local)
+        {
+            *operand = Operand::Copy(place);
+        }
+        self.super_operand(operand, loc);
+    }
+
+    fn visit_statement(&mut self, stmt: &mut Statement<'tcx>, loc: Location) {
+        // The storage of a hoisted local may have been limited to a single iteration.
+        if let StatementKind::StorageLive(l) | StatementKind::StorageDead(l) = stmt.kind
+            && self.hoisted_locals.contains(l)
+        {
+            stmt.make_nop();
+            return;
+        }
+        self.super_statement(stmt, loc);
+    }
+}
@@ -0,0 +1,274 @@
+//! This pass merges structurally identical panic blocks, i.e. blocks that end in a call which
+//! never returns, and identical cleanup blocks. Macro expansion and inlining produce lots of
+//! them, for example one block calling the same panic function for each inlined copy of a
+//! function that contains an `assert!`.
+//!
+//! Blocks are considered identical if they only differ in the temporaries that are used in no
+//! other block and have no debuginfo, and if `#[track_caller]` callees would observe the same
+//! caller location in them. Cleanup blocks are only merged as long as they keep forming the tree
+//! that codegen needs for funclets.
+
+use std::collections::hash_map::Entry;
+
+use crate::simplify::remove_dead_blocks;
+use crate::MirPass;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_index::{IndexSlice, IndexVec};
+use rustc_middle::mir::visit::{MutVisitor, NonUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{Ty, TyCtxt};
+use rustc_span::Span;
+
+pub struct MergePanicBlocks;
+
+impl<'tcx> MirPass<'tcx> for MergePanicBlocks {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!("Running MergePanicBlocks on `{:?}`", body.source);
+        // Merging blocks can make their predecessors identical, e.g. two cleanup blocks that drop
+        // the same local and then continue to two separate `resume` blocks.
+        let mut merge_cleanup = true;
+        loop {
+            let duplicates = find_duplicates(tcx, body, merge_cleanup);
+            if duplicates.is_empty() {
+                break;
+            }
+            let unmerged = merge_cleanup.then(|| body.basic_blocks.clone());
+            for data in body.basic_blocks_mut().iter_mut() {
+                for target in data.terminator_mut().successors_mut() {
+                    if let Some(&replacement) = duplicates.get(target) {
+                        *target = replacement;
+                    }
+                }
+            }
+            remove_dead_blocks(tcx, body);
+            if let Some(unmerged) = unmerged && !cleanup_blocks_form_tree(body) {
+                debug!("Merging cleanup blocks of `{:?}` breaks their tree", body.source);
+                body.basic_blocks = unmerged;
+                merge_cleanup = false;
+            }
+        }
+    }
+}
+
+/// Maps each duplicated panic block, and cleanup block if `merge_cleanup` is set, to the first
+/// block that is identical to it.
+fn find_duplicates<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    merge_cleanup: bool,
+) -> FxHashMap<BasicBlock, BasicBlock> {
+    let local_uses = local_uses(body);
+    let mut first_blocks = FxHashMap::default();
+    let mut duplicates = FxHashMap::default();
+    for (bb, data) in body.basic_blocks.iter_enumerated() {
+        let diverges = matches!(data.terminator().kind, TerminatorKind::Call { target: None, .. });
+        let candidate = if data.is_cleanup { merge_cleanup } else { diverges };
+        if !candidate {
+            continue;
+        }
+        match first_blocks.entry(BlockKey::new(tcx, body, &local_uses, bb, data)) {
+            Entry::Occupied(occupied) => {
+                debug!("Merging {:?} into {:?}", bb, occupied.get());
+                duplicates.insert(bb, *occupied.get());
+            }
+            Entry::Vacant(vacant) => {
+                vacant.insert(bb);
+            }
+        }
+    }
+    duplicates
+}
+
+/// Whether the cleanup blocks of `body` still form the tree that codegen expects: the cleanup
+/// blocks dominated by the same landing pad may only continue to the blocks dominated by one
+/// other landing pad, without cycles. This is the check of the MIR validator. Merging cleanup
+/// blocks reached from different landing pads can break it, e.g. when one of them only continues
+/// to the merged block on some paths.
+fn cleanup_blocks_form_tree(body: &Body<'_>) -> bool {
+    let dominators = body.basic_blocks.dominators();
+    let landing_pad = |mut bb: BasicBlock| loop {
+        let parent = dominators.immediate_dominator(bb).unwrap();
+        if !body.basic_blocks[parent].is_cleanup {
+            return bb;
+        }
+        bb = parent;
+    };
+
+    let mut parent = IndexVec::from_elem(None, &body.basic_blocks);
+    for (bb, data) in body.basic_blocks.iter_enumerated() {
+        if !data.is_cleanup {
+            continue;
+        }
+        let pad = landing_pad(bb);
+        for successor in data.terminator().successors() {
+            let successor = landing_pad(successor);
+            if successor == pad {
+                continue;
+            }
+            match parent[pad] {
+                None => parent[pad] = Some(successor),
+                Some(previous) if previous == successor => {}
+                Some(_) => return false,
+            }
+        }
+    }
+    parent.indices().all(|pad| {
+        std::iter::successors(parent[pad], |&bb| parent[bb]).take(parent.len()).all(|bb| bb != pad)
+    })
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum LocalUse {
+    Unused,
+    /// The local is a temporary only used in this block, and has no debuginfo.
+    Block(BasicBlock),
+    Shared,
+}
+
+fn local_uses(body: &Body<'_>) -> IndexVec<Local, LocalUse> {
+    struct LocalUseVisitor {
+        uses: IndexVec<Local, LocalUse>,
+    }
+
+    impl<'tcx> Visitor<'tcx> for LocalUseVisitor {
+        fn visit_local(&mut self, local: Local, context: PlaceContext, location: Location) {
+            let local_use = &mut self.uses[local];
+            *local_use = match *local_use {
+                // Renaming a user variable in the merged block would leave its debuginfo
+                // pointing to a local that is never assigned.
+                _ if context == PlaceContext::NonUse(NonUseContext::VarDebugInfo) => {
+                    LocalUse::Shared
+                }
+                LocalUse::Unused => LocalUse::Block(location.block),
+                LocalUse::Block(bb) if bb == location.block => LocalUse::Block(bb),
+                _ => LocalUse::Shared,
+            };
+        }
+    }
+
+    let mut visitor = LocalUseVisitor {
+        uses: body
+            .local_decls
+            .indices()
+            .map(|local| match body.local_kind(local) {
+                LocalKind::Temp => LocalUse::Unused,
+                LocalKind::Arg | LocalKind::ReturnPointer => LocalUse::Shared,
+            })
+            .collect(),
+    };
+    for (bb, data) in body.basic_blocks.iter_enumerated() {
+        visitor.visit_basic_block_data(bb, data);
+    }
+    for var_debug_info in &body.var_debug_info {
+        visitor.visit_var_debug_info(var_debug_info);
+    }
+    visitor.uses
+}
+
+#[derive(PartialEq, Hash)]
+struct BlockKey<'tcx> {
+    is_cleanup: bool,
+    statements: Vec<StatementKind<'tcx>>,
+    terminator: TerminatorKind<'tcx>,
+    /// The types of the temporaries only used in this block, which are renamed in the order of
+    /// their first use.
+    temps: Vec<Ty<'tcx>>,
+    caller_location: Option<Span>,
+}
+
+// MIR statements and terminators don't implement `Eq`, but their `PartialEq` impls are
+// reflexive, which is all the map of first blocks relies on.
+impl Eq for BlockKey<'_> {}
+
+impl<'tcx> BlockKey<'tcx> {
+    fn new(
+        tcx: TyCtxt<'tcx>,
+        body: &Body<'tcx>,
+        local_uses: &IndexSlice<Local, LocalUse>,
+        bb: BasicBlock,
+        data: &BasicBlockData<'tcx>,
+    ) -> Self {
+        let mut data = data.clone();
+        let mut renamer = TempRenamer {
+            tcx,
+            body,
+            local_uses,
+            block: bb,
+            renamed: FxHashMap::default(),
+            temps: Vec::new(),
+        };
+        renamer.visit_basic_block_data(bb, &mut data);
+
+        let terminator = data.terminator.take().unwrap();
+        let caller_location = match terminator.kind {
+            TerminatorKind::Call { .. } | TerminatorKind::Assert { .. } => {
+                caller_location_span(tcx, body, terminator.source_info)
+            }
+            _ => None,
+        };
+        BlockKey {
+            is_cleanup: data.is_cleanup,
+            statements: data.statements.into_iter().map(|statement| statement.kind).collect(),
+            terminator: terminator.kind,
+            temps: renamer.temps,
+            caller_location,
+        }
+    }
+}
+
+/// Renames the temporaries only used in `block` to fresh locals past the end of the body, so
+/// that blocks that only differ in those temporaries compare equal.
+struct TempRenamer<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: &'a Body<'tcx>,
+    local_uses: &'a IndexSlice<Local, LocalUse>,
+    block: BasicBlock,
+    renamed: FxHashMap<Local, Local>,
+    temps: Vec<Ty<'tcx>>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for TempRenamer<'_, 'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_local(&mut self, local: &mut Local, _: PlaceContext, _: Location) {
+        if self.local_uses[*local] != LocalUse::Block(self.block) {
+            return;
+        }
+        let fresh = Local::from_usize(self.body.local_decls.len() + self.temps.len());
+        *local = *self.renamed.entry(*local).or_insert_with(|| {
+            self.temps.push(self.body.local_decls[*local].ty);
+            fresh
+        });
+    }
+}
+
+/// Returns the span that `Location::caller()` reports for a `#[track_caller]` call at
+/// `source_info`, or `None` if it reports the caller location passed to `body` itself. This walks
+/// up the scopes of inlined `#[track_caller]` functions the same way codegen does.
+fn caller_location_span<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    mut source_info: SourceInfo,
+) -> Option<Span> {
+    loop {
+        let scope_data = &body.source_scopes[source_info.scope];
+        if let Some((callee, callsite_span)) = scope_data.inlined {
+            if !callee.def.requires_caller_location(tcx) {
+                return Some(source_info.span);
+            }
+            source_info.span = callsite_span;
+        }
+        match scope_data.inlined_parent_scope {
+            Some(parent) => source_info.scope = parent,
+            None => break,
+        }
+    }
+    (!body.source.instance.requires_caller_location(tcx)).then_some(source_info.span)
+}
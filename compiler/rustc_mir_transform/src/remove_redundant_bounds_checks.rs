@@ -0,0 +1,117 @@
+//! Removes bounds checks that are dominated by an identical bounds check.
+//!
+//! Indexing the same slice with the same index more than once, as in `(a[i], a[i])` or
+//! `if a[i] > 0 { a[i] -= 1 }`, produces one `Len` + `Lt` + `assert` sequence per indexing
+//! operation. When neither the index nor the length can change between two such sequences, the
+//! second assert can never fail, and is replaced by a `goto`.
+//!
+//! The index must be an SSA local (up to copies). The length must be the length of an array
+//! local, which is part of its type, or the length of the slice behind an SSA pointer, which is
+//! part of the pointer's metadata.
+
+use crate::ssa::SsaLocals;
+use crate::MirPass;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_index::IndexVec;
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+
+pub struct RemoveRedundantBoundsChecks;
+
+impl<'tcx> MirPass<'tcx> for RemoveRedundantBoundsChecks {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    #[instrument(level = "trace", skip(self, _tcx, body))]
+    fn run_pass(&self, _tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+        let ssa = SsaLocals::new(body);
+
+        let bounds_checks = compute_bounds_checks(&ssa, body);
+        debug!(?bounds_checks);
+
+        // Group the asserts by the bounds check they perform.
+        let mut asserts: FxHashMap<BoundsCheck<'tcx>, Vec<BasicBlock>> = FxHashMap::default();
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            let terminator = data.terminator();
+            if let TerminatorKind::Assert { cond, expected: true, msg, .. } = &terminator.kind
+                && let AssertKind::BoundsCheck { .. } = **msg
+                && let Some(cond) = cond.place()
+                && let Some(cond) = cond.as_local()
+                && let Some(check) = bounds_checks[cond]
+            {
+                asserts.entry(check).or_default().push(bb);
+            }
+        }
+
+        let dominators = body.basic_blocks.dominators();
+        let mut redundant = Vec::new();
+        for blocks in asserts.values() {
+            for &bb in blocks {
+                if blocks.iter().any(|&other| other != bb && dominators.dominates(other, bb)) {
+                    redundant.push(bb);
+                }
+            }
+        }
+
+        if redundant.is_empty() {
+            return;
+        }
+
+        let basic_blocks = body.basic_blocks.as_mut();
+        for bb in redundant {
+            debug!("removing redundant bounds check in {:?}", bb);
+            let terminator = basic_blocks[bb].terminator_mut();
+            let TerminatorKind::Assert { target, .. } = terminator.kind else { bug!() };
+            terminator.kind = TerminatorKind::Goto { target };
+        }
+    }
+}
+
+/// The condition checked by a bounds check: whether `index` is less than the length of `place`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+struct BoundsCheck<'tcx> {
+    index: Local,
+    place: Place<'tcx>,
+}
+
+/// Finds the SSA locals that are assigned `Lt(index, len)`, where `len` is a stable length.
+fn compute_bounds_checks<'tcx>(
+    ssa: &SsaLocals,
+    body: &Body<'tcx>,
+) -> IndexVec<Local, Option<BoundsCheck<'tcx>>> {
+    let copy_classes = ssa.copy_classes();
+    let mut lengths = IndexVec::from_elem(None, &body.local_decls);
+    let mut bounds_checks = IndexVec::from_elem(None, &body.local_decls);
+
+    // Assignments are visited in dominance order, so `len` is known by the time it is compared.
+    for (local, rvalue, _) in ssa.assignments(body) {
+        match rvalue {
+            Rvalue::Len(place) => {
+                let place = match place.projection[..] {
+                    [] => *place,
+                    [PlaceElem::Deref] if ssa.is_ssa(place.local) => {
+                        Place { local: copy_classes[place.local], projection: place.projection }
+                    }
+                    _ => continue,
+                };
+                lengths[local] = Some(place);
+            }
+            Rvalue::BinaryOp(BinOp::Lt, box (index, len)) => {
+                if let Some(index) = index.place()
+                    && let Some(index) = index.as_local()
+                    && ssa.is_ssa(index)
+                    && let Some(len) = len.place()
+                    && let Some(len) = len.as_local()
+                    && let Some(place) = lengths[len]
+                {
+                    bounds_checks[local] = Some(BoundsCheck { index: copy_classes[index], place });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    bounds_checks
+}
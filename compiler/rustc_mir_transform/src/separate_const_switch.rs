@@ -32,36 +32,117 @@
 //!     x = y  ---- switch x ------> something else
 //! ```
 //!
+//! If the switch is over the discriminant of an enum, and
+//! the predecessor sets the variant of that enum, the copy
+//! of the switch is replaced by a jump to the right target
+//! right away, threading the jump through the switch.
+//! This is only done at mir-opt-level 3 and above, where
+//! the pass runs a second time after another round of
+//! inlining, so that switches in inlined bodies are
+//! threaded as well.
+//!
 //! This optimization is meant to cover simple cases
 //! like `?` desugaring. For now, it thus focuses on
 //! simplicity rather than completeness (it notably
 //! sometimes duplicates abusively).
 
 use crate::MirPass;
+use rustc_index::bit_set::BitSet;
 use rustc_middle::mir::*;
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_mir_dataflow::impls::borrowed_locals;
+use rustc_target::abi::VariantIdx;
 use smallvec::SmallVec;
 
-pub struct SeparateConstSwitch;
+pub enum SeparateConstSwitch {
+    Initial,
+    /// Runs again after the inliner, to thread switches in bodies that
+    /// were only inlined once the first run threaded their callers.
+    AfterInline,
+}
 
 impl<'tcx> MirPass<'tcx> for SeparateConstSwitch {
+    fn name(&self) -> &'static str {
+        match self {
+            SeparateConstSwitch::Initial => "SeparateConstSwitch",
+            SeparateConstSwitch::AfterInline => "SeparateConstSwitch-after-inline",
+        }
+    }
+
     fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
-        sess.mir_opt_level() >= 2
+        match self {
+            SeparateConstSwitch::Initial => sess.mir_opt_level() >= 2,
+            SeparateConstSwitch::AfterInline => sess.mir_opt_level() >= 3,
+        }
     }
 
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let thread_variants = tcx.sess.mir_opt_level() >= 3;
+
         // If execution did something, applying a simplification layer
         // helps later passes optimize the copy away.
-        if separate_const_switch(body) > 0 {
+        if separate_const_switch(tcx, body, thread_variants) > 0 {
             super::simplify::simplify_cfg(tcx, body);
         }
     }
 }
 
+/// How the value being switched over relates to the place being tracked.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Relation {
+    /// The value is a copy of the place.
+    Same,
+    /// The value is the discriminant of the place, which is an unborrowed local of enum type,
+    /// so its variant can only be changed by statements of the blocks we are looking at.
+    Discriminant,
+    /// The value is derived from the place in some other way.
+    Other,
+}
+
+impl Relation {
+    /// The relation after following `rvalue`, which the tracked place was assigned from, to
+    /// the place it reads.
+    fn through<'tcx>(
+        self,
+        rvalue: &Rvalue<'tcx>,
+        place: Place<'tcx>,
+        borrowed: &BitSet<Local>,
+    ) -> Self {
+        let unborrowed_local = place.as_local().is_some_and(|local| !borrowed.contains(local));
+        match (self, rvalue) {
+            (Relation::Discriminant, Rvalue::Use(_) | Rvalue::CopyForDeref(_))
+                if !unborrowed_local =>
+            {
+                Relation::Other
+            }
+            (_, Rvalue::Use(_) | Rvalue::CopyForDeref(_)) => self,
+            (Relation::Same, Rvalue::Discriminant(_)) if unborrowed_local => Relation::Discriminant,
+            _ => Relation::Other,
+        }
+    }
+}
+
+/// What a predecessor assigns to the place determining a switch.
+enum KnownValue<'tcx> {
+    /// The place is likely a constant, see [`is_likely_const`].
+    Const,
+    /// The switch is over the discriminant of the place, which is set to the given variant.
+    Variant(Place<'tcx>, VariantIdx),
+}
+
 /// Returns the amount of blocks that were duplicated
-pub fn separate_const_switch(body: &mut Body<'_>) -> usize {
-    let mut new_blocks: SmallVec<[(BasicBlock, BasicBlock); 6]> = SmallVec::new();
+///
+/// If `thread_variants` is set, switches over the discriminant
+/// of an enum whose variant a predecessor sets are threaded.
+pub fn separate_const_switch<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &mut Body<'tcx>,
+    thread_variants: bool,
+) -> usize {
+    // For each duplicated block, the value of its switch, if it is known.
+    let mut new_blocks: SmallVec<[(BasicBlock, BasicBlock, Option<u128>); 6]> = SmallVec::new();
     let predecessors = body.basic_blocks.predecessors();
+    let borrowed = borrowed_locals(body);
     'block_iter: for (block_id, block) in body.basic_blocks.iter_enumerated() {
         if let TerminatorKind::SwitchInt {
             discr: Operand::Copy(switch_place) | Operand::Move(switch_place),
@@ -84,7 +165,11 @@ pub fn separate_const_switch(body: &mut Body<'_>) -> usize {
 
             // First, let's find a non-const place
             // that determines the result of the switch
-            if let Some(switch_place) = find_determining_place(switch_place, block) {
+            if let Some((switch_place, relation)) =
+                find_determining_place(switch_place, block, &borrowed)
+            {
+                let relation = if thread_variants { relation } else { Relation::Other };
+
                 // We now have an input place for which it would
                 // be interesting if predecessors assigned it from a const
 
@@ -121,8 +206,21 @@ pub fn separate_const_switch(body: &mut Body<'_>) -> usize {
                         }
                     }
 
-                    if is_likely_const(switch_place, predecessor) {
-                        new_blocks.push((predecessor_id, block_id));
+                    if let Some(value) =
+                        is_likely_const(switch_place, relation, predecessor, &borrowed)
+                    {
+                        let discr = match value {
+                            KnownValue::Const => None,
+                            KnownValue::Variant(place, variant) => {
+                                match place.ty(&body.local_decls, tcx).ty.kind() {
+                                    ty::Adt(adt, _) if adt.is_enum() => {
+                                        Some(adt.discriminant_for_variant(tcx, variant).val)
+                                    }
+                                    _ => None,
+                                }
+                            }
+                        };
+                        new_blocks.push((predecessor_id, block_id, discr));
                         predecessors_left -= 1;
                         if predecessors_left < 2 {
                             // If the original block only has one predecessor left,
@@ -139,8 +237,15 @@ pub fn separate_const_switch(body: &mut Body<'_>) -> usize {
     let body_span = body.span;
     let copied_blocks = new_blocks.len();
     let blocks = body.basic_blocks_mut();
-    for (pred_id, target_id) in new_blocks {
-        let new_block = blocks[target_id].clone();
+    for (pred_id, target_id, discr) in new_blocks {
+        let mut new_block = blocks[target_id].clone();
+        if let Some(discr) = discr {
+            let TerminatorKind::SwitchInt { targets, .. } = &new_block.terminator().kind else {
+                bug!("duplicated block does not end in a switch");
+            };
+            let target = targets.target_for_value(discr);
+            new_block.terminator_mut().kind = TerminatorKind::Goto { target };
+        }
         let new_block_id = blocks.push(new_block);
         let terminator = blocks[pred_id].terminator_mut();
 
@@ -190,12 +295,28 @@ pub fn separate_const_switch(body: &mut Body<'_>) -> usize {
 /// whether a place is last set with a const within the block.
 /// Notably, it will be overly pessimistic in cases that are already
 /// not handled by `separate_const_switch`.
-fn is_likely_const<'tcx>(mut tracked_place: Place<'tcx>, block: &BasicBlockData<'tcx>) -> bool {
+///
+/// `relation` describes how the value being switched over relates
+/// to `tracked_place`, to find out whether the block sets the
+/// variant of an enum whose discriminant is being switched over.
+fn is_likely_const<'tcx>(
+    mut tracked_place: Place<'tcx>,
+    mut relation: Relation,
+    block: &BasicBlockData<'tcx>,
+    borrowed: &BitSet<Local>,
+) -> Option<KnownValue<'tcx>> {
     for statement in block.statements.iter().rev() {
         match &statement.kind {
             StatementKind::Assign(assign) => {
                 if assign.0 == tracked_place {
                     match assign.1 {
+                        // Setting the variant of the enum whose discriminant is switched over
+                        Rvalue::Aggregate(box AggregateKind::Adt(_, variant, ..), _)
+                            if relation == Relation::Discriminant =>
+                        {
+                            return Some(KnownValue::Variant(tracked_place, variant));
+                        }
+
                         // These rvalues are definitely constant
                         Rvalue::Use(Operand::Constant(_))
                         | Rvalue::Ref(_, _, _)
@@ -203,7 +324,9 @@ fn is_likely_const<'tcx>(mut tracked_place: Place<'tcx>, block: &BasicBlockData<
                         | Rvalue::Cast(_, Operand::Constant(_), _)
                         | Rvalue::NullaryOp(_, _)
                         | Rvalue::ShallowInitBox(_, _)
-                        | Rvalue::UnaryOp(_, Operand::Constant(_)) => return true,
+                        | Rvalue::UnaryOp(_, Operand::Constant(_)) => {
+                            return Some(KnownValue::Const);
+                        }
 
                         // These rvalues make things ambiguous
                         Rvalue::Repeat(_, _)
@@ -211,14 +334,17 @@ fn is_likely_const<'tcx>(mut tracked_place: Place<'tcx>, block: &BasicBlockData<
                         | Rvalue::Len(_)
                         | Rvalue::BinaryOp(_, _)
                         | Rvalue::CheckedBinaryOp(_, _)
-                        | Rvalue::Aggregate(_, _) => return false,
+                        | Rvalue::Aggregate(_, _) => return None,
 
                         // These rvalues move the place to track
                         Rvalue::Cast(_, Operand::Copy(place) | Operand::Move(place), _)
                         | Rvalue::Use(Operand::Copy(place) | Operand::Move(place))
                         | Rvalue::CopyForDeref(place)
                         | Rvalue::UnaryOp(_, Operand::Copy(place) | Operand::Move(place))
-                        | Rvalue::Discriminant(place) => tracked_place = place,
+                        | Rvalue::Discriminant(place) => {
+                            relation = relation.through(&assign.1, place, borrowed);
+                            tracked_place = place;
+                        }
                     }
                 }
             }
@@ -232,9 +358,14 @@ fn is_likely_const<'tcx>(mut tracked_place: Place<'tcx>, block: &BasicBlockData<
             // an enum directly, and if we were switching over
             // its content, we would have had to at least cast it to
             // some variant first)
-            StatementKind::SetDiscriminant { place, .. } => {
+            StatementKind::SetDiscriminant { place, variant_index } => {
                 if **place == tracked_place {
-                    return true;
+                    return Some(match relation {
+                        Relation::Discriminant => {
+                            KnownValue::Variant(tracked_place, *variant_index)
+                        }
+                        Relation::Same | Relation::Other => KnownValue::Const,
+                    });
                 }
             }
 
@@ -257,17 +388,22 @@ fn is_likely_const<'tcx>(mut tracked_place: Place<'tcx>, block: &BasicBlockData<
     // If no good reason for the place to be const is found,
     // give up. We could maybe go up predecessors, but in
     // most cases giving up now should be sufficient.
-    false
+    None
 }
 
 /// Finds a unique place that entirely determines the value
 /// of `switch_place`, if it exists. This is only a heuristic.
 /// Ideally we would like to track multiple determining places
 /// for some edge cases, but one is enough for a lot of situations.
+///
+/// Also returns how the value of `switch_place` relates to the
+/// determining place.
 fn find_determining_place<'tcx>(
     mut switch_place: Place<'tcx>,
     block: &BasicBlockData<'tcx>,
-) -> Option<Place<'tcx>> {
+    borrowed: &BitSet<Local>,
+) -> Option<(Place<'tcx>, Relation)> {
+    let mut relation = Relation::Same;
     for statement in block.statements.iter().rev() {
         match &statement.kind {
             StatementKind::Assign(op) => {
@@ -284,7 +420,10 @@ fn find_determining_place<'tcx>(
                     | Rvalue::Cast(_, Operand::Move(new) | Operand::Copy(new), _)
                     | Rvalue::Repeat(Operand::Move(new) | Operand::Copy(new), _)
                     | Rvalue::Discriminant(new)
-                    => switch_place = new,
+                    => {
+                        relation = relation.through(&op.1, new, borrowed);
+                        switch_place = new;
+                    }
 
                     // The following rvalues might still make the block
                     // be valid but for now we reject them
@@ -338,5 +477,5 @@ fn find_determining_place<'tcx>(
         }
     }
 
-    Some(switch_place)
+    Some((switch_place, relation))
 }
@@ -1,6 +1,8 @@
 use crate::MirPass;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::DefId;
 use rustc_index::bit_set::{BitSet, GrowableBitSet};
-use rustc_index::IndexVec;
+use rustc_index::{IndexSlice, IndexVec};
 use rustc_middle::mir::patch::MirPatch;
 use rustc_middle::mir::visit::*;
 use rustc_middle::mir::*;
@@ -24,8 +26,13 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             return;
         }
 
-        let mut excluded = excluded_locals(body);
         let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        // Replace the boxes that don't escape first, so that their contents are flattened too.
+        if tcx.sess.mir_opt_level() >= 3 {
+            replace_local_boxes(tcx, param_env, body);
+        }
+
+        let mut excluded = excluded_locals(body);
         loop {
             debug!(?excluded);
             let escaping = escaping_locals(tcx, param_env, &excluded, body);
@@ -466,3 +473,281 @@ fn visit_local(&mut self, local: &mut Local, _: PlaceContext, _: Location) {
         assert!(!self.all_dead_locals.contains(*local));
     }
 }
+
+/// Boxes of types larger than this many bytes are left alone by `replace_local_boxes`.
+const MAX_UNBOXED_SIZE: u64 = 64;
+
+/// Replaces the boxes of small types that are allocated in this body and never escape it with a
+/// local of the boxed type, which elides the allocation. Inlining `Box::new` results in
+///   _1 = alloc::alloc::exchange_malloc(move _2, move _3) -> bb1;
+///   _4 = ShallowInitBox(move _1, T);
+///   _5 = (((_4.0: Unique<T>).0: NonNull<T>).0: *const T);
+///   (*_5) = ...;
+///   _6 = move _4;
+///   drop(_6) -> bb2;
+/// of which only `_7 = ...; drop(_7) -> bb2` for a new local `_7: T` remains.
+///
+/// All the locals involved have to be assigned once, and a box is left alone if it or the
+/// pointer to its contents is used in any other way.
+fn replace_local_boxes<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    body: &mut Body<'tcx>,
+) {
+    let Some(exchange_malloc) = tcx.lang_items().exchange_malloc_fn() else { return };
+
+    let mut finder = LocalBoxFinder {
+        exchange_malloc,
+        local_decls: &body.local_decls,
+        assignments: IndexVec::from_elem(0, &body.local_decls),
+        allocations: BitSet::new_empty(body.local_decls.len()),
+        box_roots: FxHashMap::default(),
+        box_moves: Vec::new(),
+        pointers: Vec::new(),
+        escaping: BitSet::new_empty(body.local_decls.len()),
+    };
+    finder.visit_body(body);
+    let LocalBoxFinder {
+        assignments, allocations, box_roots, box_moves, pointers, escaping, ..
+    } = finder;
+
+    // Map every box local to the local that the allocation was initially put in.
+    let mut box_of = IndexVec::from_elem(None, &body.local_decls);
+    for &root in box_roots.keys() {
+        box_of[root] = Some(root);
+    }
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &(to, from) in &box_moves {
+            if box_of[to].is_none()
+                && let Some(root) = box_of[from]
+            {
+                box_of[to] = Some(root);
+                changed = true;
+            }
+        }
+    }
+
+    let is_unique = |local: Local| assignments[local] == 1 && !escaping.contains(local);
+    let mut invalid = BitSet::new_empty(body.local_decls.len());
+    for (&root, &(allocation, ty)) in &box_roots {
+        let is_small = tcx
+            .layout_of(param_env.and(ty))
+            .map_or(false, |layout| layout.size.bytes() <= MAX_UNBOXED_SIZE);
+        if !is_small || !allocations.contains(allocation) || !is_unique(allocation) {
+            invalid.insert(root);
+        }
+    }
+    for (local, root) in box_of.iter_enumerated() {
+        if let Some(root) = *root
+            && !is_unique(local)
+        {
+            invalid.insert(root);
+        }
+    }
+    for &(pointer, local) in &pointers {
+        if let Some(root) = box_of[local]
+            && !is_unique(pointer)
+        {
+            invalid.insert(root);
+        }
+    }
+
+    // The new local holding the contents of each box local and of each pointer to them.
+    let mut contents = IndexVec::from_elem(None, &body.local_decls);
+    let mut removed = GrowableBitSet::new_empty();
+    let mut needs_drop = GrowableBitSet::new_empty();
+    for (&root, &(allocation, ty)) in &box_roots {
+        if invalid.contains(root) {
+            continue;
+        }
+        debug!(?root, ?ty, "replacing box");
+        let span = body.local_decls[root].source_info.span;
+        let new_local = body.local_decls.push(LocalDecl::new(ty, span));
+        if ty.needs_drop(tcx, param_env) {
+            needs_drop.insert(new_local);
+        }
+        removed.insert(allocation);
+        for (local, &box_root) in box_of.iter_enumerated() {
+            if box_root == Some(root) {
+                contents[local] = Some(new_local);
+                removed.insert(local);
+            }
+        }
+        for &(pointer, local) in &pointers {
+            if box_of[local] == Some(root) {
+                contents[pointer] = Some(new_local);
+                removed.insert(pointer);
+            }
+        }
+    }
+    if removed.is_empty() {
+        return;
+    }
+    contents.resize(body.local_decls.len(), None);
+
+    for data in body.basic_blocks.as_mut() {
+        for statement in &mut data.statements {
+            match &statement.kind {
+                StatementKind::Assign(box (place, _))
+                    if place.as_local().map_or(false, |local| removed.contains(local)) =>
+                {
+                    statement.make_nop()
+                }
+                StatementKind::StorageLive(local) | StatementKind::StorageDead(local)
+                    if removed.contains(*local) =>
+                {
+                    statement.make_nop()
+                }
+                _ => {}
+            }
+        }
+
+        let terminator = data.terminator_mut();
+        match terminator.kind {
+            TerminatorKind::Call { destination, target: Some(target), .. }
+                if destination.as_local().map_or(false, |local| removed.contains(local)) =>
+            {
+                terminator.kind = TerminatorKind::Goto { target };
+            }
+            TerminatorKind::Drop { place, target, unwind, replace }
+                if let Some(new_local) = place.as_local().and_then(|local| contents[local]) =>
+            {
+                terminator.kind = if needs_drop.contains(new_local) {
+                    TerminatorKind::Drop { place: new_local.into(), target, unwind, replace }
+                } else {
+                    TerminatorKind::Goto { target }
+                };
+            }
+            _ => {}
+        }
+    }
+
+    BoxContentsReplacer { tcx, contents: &contents }.visit_body(body);
+
+    // Debuginfo that still refers to a removed local cannot be expressed in terms of the
+    // contents, e.g. a fragment of a composite that is a whole box.
+    body.var_debug_info.retain(|var_debug_info| match &var_debug_info.value {
+        VarDebugInfoContents::Place(place) => !removed.contains(place.local),
+        VarDebugInfoContents::Composite { fragments, .. } => {
+            fragments.iter().all(|fragment| !removed.contains(fragment.contents.local))
+        }
+        VarDebugInfoContents::Const(_) => true,
+    });
+}
+
+struct LocalBoxFinder<'tcx, 'a> {
+    exchange_malloc: DefId,
+    local_decls: &'a LocalDecls<'tcx>,
+    /// Number of assignments to each local.
+    assignments: IndexVec<Local, u32>,
+    /// Locals assigned the result of `exchange_malloc`.
+    allocations: BitSet<Local>,
+    /// Maps each local assigned `ShallowInitBox(move _a, T)` to `(_a, T)`.
+    box_roots: FxHashMap<Local, (Local, Ty<'tcx>)>,
+    /// `(_c, _b)` for each `_c = move _b` where `_b` is a box.
+    box_moves: Vec<(Local, Local)>,
+    /// `(_p, _b)` for each `_p = (((_b.0: Unique<T>).0: NonNull<T>).0: *const T)`.
+    pointers: Vec<(Local, Local)>,
+    /// Locals that are used in any other way.
+    escaping: BitSet<Local>,
+}
+
+impl<'tcx> Visitor<'tcx> for LocalBoxFinder<'tcx, '_> {
+    fn visit_assign(&mut self, place: &Place<'tcx>, rvalue: &Rvalue<'tcx>, location: Location) {
+        let Some(local) = place.as_local() else {
+            self.super_assign(place, rvalue, location);
+            return;
+        };
+        self.assignments[local] += 1;
+        match rvalue {
+            Rvalue::ShallowInitBox(Operand::Move(allocation), ty)
+                if let Some(allocation) = allocation.as_local() =>
+            {
+                self.box_roots.insert(local, (allocation, *ty));
+            }
+            Rvalue::Use(Operand::Copy(source) | Operand::Move(source))
+            | Rvalue::CopyForDeref(source)
+                if self.local_decls[source.local].ty.is_box() =>
+            {
+                match source.projection[..] {
+                    [] => self.box_moves.push((local, source.local)),
+                    [PlaceElem::Field(..), PlaceElem::Field(..), PlaceElem::Field(..)] => {
+                        self.pointers.push((local, source.local))
+                    }
+                    _ => self.visit_rvalue(rvalue, location),
+                }
+            }
+            _ => self.visit_rvalue(rvalue, location),
+        }
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        match &terminator.kind {
+            TerminatorKind::Call { func, args, destination, target: Some(_), .. }
+                if let Some((def_id, _)) = func.const_fn_def()
+                    && def_id == self.exchange_malloc
+                    && let Some(local) = destination.as_local() =>
+            {
+                self.assignments[local] += 1;
+                self.allocations.insert(local);
+                for arg in args {
+                    self.visit_operand(arg, location);
+                }
+            }
+            // Dropping a box drops its contents.
+            TerminatorKind::Drop { place, .. } if place.as_local().is_some() => {}
+            _ => self.super_terminator(terminator, location),
+        }
+    }
+
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        // Accessing the contents of a box through its pointer does not use the pointer itself.
+        if place.projection.first() == Some(&PlaceElem::Deref) {
+            self.visit_projection(place.as_ref(), context, location);
+        } else {
+            self.super_place(place, context, location);
+        }
+    }
+
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if context.is_use() {
+            self.escaping.insert(local);
+        }
+    }
+}
+
+/// Replaces `(*_p)` with the local that holds the contents of the box that `_p` points into.
+struct BoxContentsReplacer<'tcx, 'a> {
+    tcx: TyCtxt<'tcx>,
+    contents: &'a IndexSlice<Local, Option<Local>>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for BoxContentsReplacer<'tcx, '_> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_var_debug_info(&mut self, var_debug_info: &mut VarDebugInfo<'tcx>) {
+        // A box, or a pointer into it, is now described as a reference to its contents.
+        if let VarDebugInfoContents::Place(ref mut place) = var_debug_info.value
+            && let Some(local) = place.as_local()
+            && let Some(new_local) = self.contents[local]
+            && let Some(references) = var_debug_info.references.checked_add(1)
+        {
+            var_debug_info.references = references;
+            *place = new_local.into();
+        }
+        self.super_var_debug_info(var_debug_info);
+    }
+
+    fn visit_place(&mut self, place: &mut Place<'tcx>, context: PlaceContext, location: Location) {
+        if place.projection.first() == Some(&PlaceElem::Deref)
+            && let Some(new_local) = self.contents[place.local]
+        {
+            *place = Place::from(new_local).project_deeper(&place.projection[1..], self.tcx);
+        }
+        self.super_place(place, context, location);
+    }
+}
@@ -638,6 +638,10 @@ passes_should_be_applied_to_trait =
     attribute should be applied to a trait
     .label = not a trait
 
+passes_should_be_applied_to_type_param =
+    attribute should be applied to a type parameter
+    .label = not a type parameter
+
 passes_size =
     size: {$size}
 
@@ -105,8 +105,10 @@ fn check_attributes(
         let attrs = self.tcx.hir().attrs(hir_id);
         for attr in attrs {
             match attr.name_or_empty() {
+                sym::do_not_elaborate => self.check_do_not_elaborate(hir_id, attr, span),
                 sym::do_not_recommend => self.check_do_not_recommend(attr.span, target),
                 sym::inline => self.check_inline(hir_id, attr, span, target),
+                sym::inline_budget => self.check_inline_budget(hir_id, attr, span, target),
                 sym::no_coverage => self.check_no_coverage(hir_id, attr, span, target),
                 sym::non_exhaustive => self.check_non_exhaustive(hir_id, attr, span, target),
                 sym::marker => self.check_marker(hir_id, attr, span, target),
@@ -274,6 +276,23 @@ fn inline_attr_str_error_without_macro_def(&self, hir_id: HirId, attr: &Attribut
         );
     }
 
+    /// Checks if a `#[do_not_elaborate]` is applied to a type parameter.
+    fn check_do_not_elaborate(&self, hir_id: HirId, attr: &Attribute, span: Span) -> bool {
+        if let hir::Node::GenericParam(hir::GenericParam {
+            kind: hir::GenericParamKind::Type { .. },
+            ..
+        }) = self.tcx.hir().get(hir_id)
+        {
+            true
+        } else {
+            self.tcx.sess.emit_err(errors::AttrShouldBeAppliedToTypeParam {
+                attr_span: attr.span,
+                defn_span: span,
+            });
+            false
+        }
+    }
+
     /// Checks if `#[do_not_recommend]` is applied on a trait impl.
     fn check_do_not_recommend(&self, attr_span: Span, target: Target) -> bool {
         if let Target::Impl = target {
@@ -327,6 +346,29 @@ fn check_inline(&self, hir_id: HirId, attr: &Attribute, span: Span, target: Targ
         }
     }
 
+    /// Checks if an `#[inline_budget]` is applied to a function with a body.
+    fn check_inline_budget(
+        &self,
+        hir_id: HirId,
+        attr: &Attribute,
+        span: Span,
+        target: Target,
+    ) -> bool {
+        match target {
+            Target::Fn
+            | Target::Closure
+            | Target::Method(MethodKind::Trait { body: true } | MethodKind::Inherent) => true,
+            _ => {
+                self.tcx.sess.emit_err(errors::AttrShouldBeAppliedToFn {
+                    attr_span: attr.span,
+                    defn_span: span,
+                    on_crate: hir_id == CRATE_HIR_ID,
+                });
+                false
+            }
+        }
+    }
+
     /// Checks if a `#[no_coverage]` is applied directly to a function
     fn check_no_coverage(
         &self,
@@ -2416,6 +2458,15 @@ fn check_duplicates(
     if matches!(duplicates, WarnFollowingWordOnly) && !attr.is_word() {
         return;
     }
+    // `#[inline(never_callees)]` is about inlining into the function rather than inlining of it,
+    // so it can be combined with another `#[inline]` attribute.
+    if attr.has_name(sym::inline)
+        && attr
+            .meta_item_list()
+            .map_or(false, |items| items.iter().any(|item| item.has_name(sym::never_callees)))
+    {
+        return;
+    }
     match duplicates {
         DuplicatesOk => {}
         WarnFollowing | FutureWarnFollowing | WarnFollowingWordOnly | FutureWarnPreceding => {
@@ -129,6 +129,15 @@ pub struct AttrShouldBeAppliedToTrait {
     pub defn_span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(passes_should_be_applied_to_type_param)]
+pub struct AttrShouldBeAppliedToTypeParam {
+    #[primary_span]
+    pub attr_span: Span,
+    #[label]
+    pub defn_span: Span,
+}
+
 #[derive(LintDiagnostic)]
 #[diag(passes_target_feature_on_statement)]
 pub struct TargetFeatureOnStatement;
@@ -19,8 +19,8 @@
 use rustc_target::spec::abi::Abi;
 
 // Returns true if the given item must be inlined because it may be
-// monomorphized or it was marked with `#[inline]`. This will only return
-// true for functions.
+// monomorphized, it was marked with `#[inline]` or it is small enough to be
+// made inlinable across crates. This will only return true for functions.
 fn item_might_be_inlined(tcx: TyCtxt<'_>, item: &hir::Item<'_>, attrs: &CodegenFnAttrs) -> bool {
     if attrs.requests_inline() {
         return true;
@@ -28,6 +28,7 @@ fn item_might_be_inlined(tcx: TyCtxt<'_>, item: &hir::Item<'_>, attrs: &CodegenF
 
     match item.kind {
         hir::ItemKind::Fn(ref sig, ..) if sig.header.is_const() => true,
+        hir::ItemKind::Fn(..) if tcx.cross_crate_inlinable(item.owner_id.to_def_id()) => true,
         hir::ItemKind::Impl { .. } | hir::ItemKind::Fn(..) => {
             let generics = tcx.generics_of(item.owner_id);
             generics.requires_monomorphization(tcx)
@@ -47,7 +48,8 @@ fn method_might_be_inlined(
         return true;
     }
     if let hir::ImplItemKind::Fn(method_sig, _) = &impl_item.kind {
-        if method_sig.header.is_const() {
+        if method_sig.header.is_const() || tcx.cross_crate_inlinable(impl_item.owner_id.to_def_id())
+        {
             return true;
         }
     }
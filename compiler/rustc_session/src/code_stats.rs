@@ -83,12 +83,58 @@ pub struct VTableSizeInfo {
     /// Cost of having upcasting in % relative to the number of entries without
     /// upcasting (i.e. `entries_for_upcasting / entries_ignoring_upcasting * 100%`).
     pub upcasting_cost_percent: f64,
+
+    /// The entries contributed by the trait and each of its supertraits, in vtable order.
+    pub segments: Vec<VTableSegmentInfo>,
+}
+
+pub struct VTableSegmentInfo {
+    pub trait_name: String,
+
+    /// Index of the first own method of the trait in the vtable.
+    pub offset: usize,
+
+    /// Number of own methods of the trait in the vtable.
+    pub methods: usize,
+
+    /// Whether the methods are followed by a vtable pointer, which is only
+    /// needed for upcasting.
+    pub vptr: bool,
+}
+
+/// A decision of the MIR inliner, recorded for `-Zinline-mir-report`.
+pub struct InlineDecisionInfo {
+    pub caller: String,
+    pub callee: String,
+    /// The estimated cost of the callee, if the inliner got as far as computing it.
+    pub cost: Option<usize>,
+    pub threshold: Option<usize>,
+    /// The total cost of the callees inlined into the caller before this decision.
+    pub caller_inlined_cost: usize,
+    /// Why the callee was not inlined, or `None` if it was.
+    pub rejection: Option<&'static str>,
+}
+
+/// How many predicates a root predicate implied while being elaborated, recorded
+/// for `-Zdump-elaboration`.
+#[derive(Default)]
+pub struct ElaborationStatsInfo {
+    /// The number of elaborations of the root predicate.
+    pub elaborations: usize,
+    /// All implied predicates, including the deduplicated and filtered ones.
+    pub generated: usize,
+    /// Implied predicates that were dropped because they were already visited.
+    pub deduped: usize,
+    /// Implied predicates that were elaborated, but not yielded.
+    pub filtered: usize,
 }
 
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
     vtable_sizes: Lock<FxHashMap<DefId, VTableSizeInfo>>,
+    inline_decisions: Lock<Vec<InlineDecisionInfo>>,
+    elaboration_stats: Lock<FxHashMap<String, ElaborationStatsInfo>>,
 }
 
 impl CodeStats {
@@ -130,6 +176,33 @@ pub fn record_vtable_size(&self, trait_did: DefId, trait_name: &str, info: VTabl
         );
     }
 
+    pub fn record_inline_decision(&self, info: InlineDecisionInfo) {
+        self.inline_decisions.lock().push(info);
+    }
+
+    /// Returns the recorded inlining decisions, grouped by caller. MIR may be optimized in
+    /// parallel, so the decisions for different callers are recorded in no particular order.
+    pub fn take_inline_decisions(&self) -> Vec<InlineDecisionInfo> {
+        let mut decisions = std::mem::take(&mut *self.inline_decisions.lock());
+        decisions.sort_by(|a, b| a.caller.cmp(&b.caller));
+        decisions
+    }
+
+    pub fn record_elaboration(
+        &self,
+        predicate: String,
+        generated: usize,
+        deduped: usize,
+        filtered: usize,
+    ) {
+        let mut elaboration_stats = self.elaboration_stats.lock();
+        let info = elaboration_stats.entry(predicate).or_default();
+        info.elaborations += 1;
+        info.generated += generated;
+        info.deduped += deduped;
+        info.filtered += filtered;
+    }
+
     pub fn print_type_sizes(&self) {
         let type_sizes = self.type_sizes.borrow();
         let mut sorted: Vec<_> = type_sizes.iter().collect();
@@ -247,11 +320,35 @@ pub fn print_vtable_sizes(&self, crate_name: &str) {
             entries_ignoring_upcasting,
             entries_for_upcasting,
             upcasting_cost_percent,
+            segments,
         } in infos
         {
             println!(
                 r#"print-vtable-sizes {{ "crate_name": "{crate_name}", "trait_name": "{trait_name}", "entries": "{entries}", "entries_ignoring_upcasting": "{entries_ignoring_upcasting}", "entries_for_upcasting": "{entries_for_upcasting}", "upcasting_cost_percent": "{upcasting_cost_percent}" }}"#
             );
+            for VTableSegmentInfo { trait_name: segment, offset, methods, vptr } in segments {
+                println!(
+                    r#"print-vtable-sizes-segment {{ "crate_name": "{crate_name}", "trait_name": "{trait_name}", "segment": "{segment}", "offset": "{offset}", "methods": "{methods}", "vptr": "{vptr}" }}"#
+                );
+            }
+        }
+    }
+
+    pub fn print_elaboration_stats(&self) {
+        let mut stats: Vec<_> =
+            std::mem::take(&mut *self.elaboration_stats.lock()).into_iter().collect();
+
+        // Primary sort: most generated predicates first.
+        // Secondary sort: predicate (dictionary order)
+        stats.sort_by(|(a_pred, a), (b_pred, b)| {
+            b.generated.cmp(&a.generated).then_with(|| a_pred.cmp(b_pred))
+        });
+
+        for (predicate, info) in stats {
+            let ElaborationStatsInfo { elaborations, generated, deduped, filtered } = info;
+            println!(
+                "dump-elaboration `{predicate}`: {elaborations} elaborations, {generated} generated, {deduped} deduplicated, {filtered} filtered"
+            );
         }
     }
 }
@@ -1377,8 +1377,14 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "the backend to use"),
     combine_cgu: bool = (false, parse_bool, [TRACKED],
         "combine CGUs into a single one"),
+    const_eval_memory_limit: Option<u64> = (None, parse_opt_number, [TRACKED],
+        "limit the memory (in bytes) that a constant evaluation may have allocated at once \
+        (default: no limit)"),
     crate_attr: Vec<String> = (Vec::new(), parse_string_push, [TRACKED],
         "inject the given attribute in the crate"),
+    cross_crate_inline_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "make small non-generic functions without `#[inline]` available for inlining in other \
+        crates if their optimized MIR has at most this many statements (default: no)"),
     debug_info_for_profiling: bool = (false, parse_bool, [TRACKED],
         "emit discriminators and other data necessary for AutoFDO"),
     debug_macros: bool = (false, parse_bool, [TRACKED],
@@ -1407,6 +1413,9 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         (default: no)"),
     dump_drop_tracking_cfg: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "dump drop-tracking control-flow graph as a `.dot` file (default: no)"),
+    dump_elaboration: bool = (false, parse_bool, [UNTRACKED],
+        "print how many predicates were generated, deduplicated and filtered out while \
+        elaborating each root predicate (default: no)"),
     dump_mir: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "dump MIR state to file.
         `val` is used to select which passes and functions to dump. For example:
@@ -1436,6 +1445,9 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "output statistics about monomorphization collection"),
     dump_mono_stats_format: DumpMonoStatsFormat = (DumpMonoStatsFormat::Markdown, parse_dump_mono_stats, [UNTRACKED],
         "the format to use for -Z dump-mono-stats (`markdown` (default) or `json`)"),
+    dump_supertrait_graph: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "print the transitive supertrait graph of each trait whose path contains the given \
+        string, in the Graphviz DOT format (default: no)"),
     dwarf_version: Option<u32> = (None, parse_opt_number, [TRACKED],
         "version of DWARF debug information to emit (default: 2 or 4, depending on platform)"),
     dylib_lto: bool = (false, parse_bool, [UNTRACKED],
@@ -1448,6 +1460,9 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "export symbols from executables, as if they were dynamic libraries"),
     extra_const_ub_checks: bool = (false, parse_bool, [TRACKED],
         "turns on more checks to detect const UB, which can be slow (default: no)"),
+    extra_const_ub_info: bool = (false, parse_bool, [TRACKED],
+        "print the raw bytes of constants that refer to statics or mutable memory in const-eval \
+        validation errors (default: no)"),
     #[rustc_lint_opt_deny_field_access("use `Session::fewer_names` instead of this field")]
     fewer_names: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "reduce memory use by retaining fewer names within compilation artifacts (LLVM-IR) \
@@ -1491,10 +1506,19 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "enable LLVM inlining (default: yes)"),
     inline_mir: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "enable MIR inlining (default: no)"),
+    inline_mir_cycle_depth: usize = (1, parse_number, [TRACKED],
+        "how many nested copies of an `#[inline(always)]` function the MIR inliner may create \
+        when unrolling a call cycle (default: 1)"),
     inline_mir_hint_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
         "inlining threshold for functions with inline hint (default: 100)"),
+    inline_mir_report: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write a JSON report of every MIR inlining decision to the given file \
+        (incomplete with `-C incremental`)"),
     inline_mir_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
         "a default MIR inlining threshold (default: 50)"),
+    inline_mir_total_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "the maximum total cost of the callees the MIR inliner inlines into one caller \
+        (default: no limit)"),
     input_stats: bool = (false, parse_bool, [UNTRACKED],
         "gather statistics about the input (default: no)"),
     #[rustc_lint_opt_deny_field_access("use `Session::instrument_coverage` instead of this field")]
@@ -1563,6 +1587,9 @@ pub(crate) fn parse_proc_macro_execution_strategy(
     mir_keep_place_mention: bool = (false, parse_bool, [TRACKED],
         "keep place mention MIR statements, interpreted e.g., by miri; implies -Zmir-opt-level=0 \
         (default: no)"),
+    mir_loop_invariant_code_motion: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "hoist loop-invariant computations out of loops in MIR \
+        (default: yes with mir-opt-level >= 2)"),
     #[rustc_lint_opt_deny_field_access("use `Session::mir_opt_level` instead of this field")]
     mir_opt_level: Option<usize> = (None, parse_opt_number, [TRACKED],
         "MIR optimization level (0-4; default: 1 in non optimized builds and 2 in optimized builds)"),
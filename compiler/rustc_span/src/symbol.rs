@@ -543,6 +543,7 @@
         const_raw_ptr_deref,
         const_raw_ptr_to_usize_cast,
         const_refs_to_cell,
+        const_refs_to_static,
         const_trait,
         const_trait_bound_opt_out,
         const_trait_impl,
@@ -627,6 +628,7 @@
         dispatch_from_dyn,
         div,
         div_assign,
+        do_not_elaborate,
         do_not_recommend,
         doc,
         doc_alias,
@@ -837,8 +839,10 @@
         inherit,
         inlateout,
         inline,
+        inline_budget,
         inline_const,
         inline_const_pat,
+        inline_never_callees,
         inout,
         instruction_set,
         integer_: "integer",
@@ -848,6 +852,7 @@
         intra_doc_pointers,
         intrinsics,
         irrefutable_let_patterns,
+        is_empty,
         isa_attribute,
         isize,
         issue,
@@ -997,6 +1002,7 @@
         negative_impls,
         neon,
         never,
+        never_callees,
         never_type,
         never_type_fallback,
         new,
@@ -1482,6 +1488,7 @@
         suggestion,
         sym,
         sync,
+        sync_unsafe_cell,
         t32,
         target,
         target_abi,
@@ -1651,6 +1658,7 @@
         width,
         windows,
         windows_subsystem,
+        with_capacity,
         with_negative_coherence,
         wrapping_add,
         wrapping_mul,
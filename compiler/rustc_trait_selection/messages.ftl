@@ -21,4 +21,7 @@ trait_selection_no_value_in_rustc_on_unimplemented = this attribute must have a
     .label = expected value here
     .note = eg `#[rustc_on_unimplemented(message="foo")]`
 
+trait_selection_non_const_trait_method_call = calling non-const `{$func}` on `{$self_ty}` in a const context
+    .help = the implementation of `{$trait_path}` for `{$self_ty}` is not `const`
+
 trait_selection_unable_to_construct_constant_value = unable to construct a constant value for the unevaluated constant {$unevaluated}
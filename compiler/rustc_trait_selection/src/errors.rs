@@ -46,6 +46,18 @@ pub struct NoValueInOnUnimplemented {
     pub span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(trait_selection_non_const_trait_method_call, code = "E0015")]
+pub struct NonConstTraitMethodCall<'tcx> {
+    #[primary_span]
+    pub span: Span,
+    pub func: String,
+    pub self_ty: Ty<'tcx>,
+    pub trait_path: String,
+    #[help]
+    pub impl_span: Span,
+}
+
 pub struct NegativePositiveConflict<'tcx> {
     pub impl_span: Span,
     pub trait_desc: ty::TraitRef<'tcx>,
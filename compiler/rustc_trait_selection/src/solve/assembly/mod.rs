@@ -6,7 +6,7 @@
 use rustc_data_structures::fx::FxIndexSet;
 use rustc_hir::def_id::DefId;
 use rustc_infer::traits::query::NoSolution;
-use rustc_infer::traits::util::elaborate;
+use rustc_infer::traits::util::{elaborate, PredicateSet};
 use rustc_infer::traits::Reveal;
 use rustc_middle::traits::solve::{CanonicalResponse, Certainty, Goal, MaybeCause, QueryResult};
 use rustc_middle::ty::fast_reject::TreatProjections;
@@ -666,10 +666,8 @@ fn assemble_object_bound_candidates<G: GoalKind<'tcx>>(
         let tcx = self.tcx();
         let own_bounds: FxIndexSet<_> =
             bounds.iter().map(|bound| bound.with_self_ty(tcx, self_ty)).collect();
-        for assumption in elaborate(tcx, own_bounds.iter().copied())
-            // we only care about bounds that match the `Self` type
-            .filter_only_self()
-        {
+        // we only care about bounds that match the `Self` type
+        for assumption in self.elaborate_only_self(own_bounds.iter().copied()) {
             // FIXME: Predicates are fully elaborated in the object type's existential bounds
             // list. We want to only consider these pre-elaborated projections, and not other
             // projection predicates that we reach by elaborating the principal trait ref,
@@ -691,6 +689,46 @@ fn assemble_object_bound_candidates<G: GoalKind<'tcx>>(
         }
     }
 
+    /// Elaborates `bounds` like `elaborate(tcx, bounds).filter_only_self()` would.
+    ///
+    /// The supertraits of a trait bound only depend on its trait, so they are taken from the
+    /// `elaborated_implied_predicates` of each trait, and then substituted with the trait ref
+    /// of the bound. This avoids walking the same supertraits again for every goal.
+    fn elaborate_only_self(
+        &self,
+        bounds: impl IntoIterator<Item = ty::Predicate<'tcx>>,
+    ) -> Vec<ty::Predicate<'tcx>> {
+        let tcx = self.tcx();
+        let mut visited = PredicateSet::new(tcx);
+        let mut elaborated = vec![];
+        for bound in bounds {
+            let bound_predicate = bound.kind();
+            if let ty::PredicateKind::Clause(ty::Clause::Trait(data)) =
+                bound_predicate.skip_binder()
+                && data.constness == ty::BoundConstness::NotConst
+                && data.polarity == ty::ImplPolarity::Positive
+            {
+                let trait_ref = bound_predicate.rebind(data.trait_ref);
+                for pred in tcx.elaborated_implied_predicates(data.def_id()) {
+                    // The bounds on the associated types of the trait are implied as well,
+                    // but only the predicates on `Self` are elaborated here.
+                    if !is_predicate_on(pred, tcx.types.self_param) {
+                        continue;
+                    }
+                    let pred = pred.subst_supertrait(tcx, &trait_ref);
+                    if visited.insert(pred) {
+                        elaborated.push(pred);
+                    }
+                }
+            } else {
+                elaborated.extend(
+                    elaborate(tcx, [bound]).filter_only_self().filter(|&pred| visited.insert(pred)),
+                );
+            }
+        }
+        elaborated
+    }
+
     #[instrument(level = "debug", skip_all)]
     fn assemble_coherence_unknowable_candidates<G: GoalKind<'tcx>>(
         &mut self,
@@ -761,3 +799,16 @@ pub(super) fn merge_candidates(
         self.flounder(&responses)
     }
 }
+
+/// Whether `pred` is a predicate on the type `self_ty`, like the ones that elaborating with
+/// `filter_only_self` results in.
+fn is_predicate_on<'tcx>(pred: ty::Predicate<'tcx>, self_ty: Ty<'tcx>) -> bool {
+    match pred.kind().skip_binder() {
+        ty::PredicateKind::Clause(ty::Clause::Trait(data)) => data.self_ty() == self_ty,
+        ty::PredicateKind::Clause(ty::Clause::Projection(data)) => data.self_ty() == self_ty,
+        ty::PredicateKind::Clause(ty::Clause::TypeOutlives(ty::OutlivesPredicate(ty, _))) => {
+            ty == self_ty
+        }
+        _ => false,
+    }
+}
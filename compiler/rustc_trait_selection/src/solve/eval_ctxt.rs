@@ -119,6 +119,20 @@ fn evaluate_root_goal(
         &self,
         goal: Goal<'tcx, ty::Predicate<'tcx>>,
     ) -> Result<(bool, Certainty, Vec<Goal<'tcx, ty::Predicate<'tcx>>>), NoSolution>;
+
+    /// Evaluates `goal` again, this time recording the first inductive cycle
+    /// hit while doing so. This is used to explain overflow errors.
+    ///
+    /// The returned goals start with the head of the cycle, followed by
+    /// every goal on the stack which requires proving the next one, and
+    /// end with the head of the cycle again. The evaluation happens inside
+    /// of a probe, so this does not constrain any inference variables.
+    /// It stops at the first cycle and gives up after a fixed number of
+    /// goals, returning `None` if no cycle was found by then.
+    fn find_inductive_cycle(
+        &self,
+        goal: Goal<'tcx, ty::Predicate<'tcx>>,
+    ) -> Option<Vec<CanonicalInput<'tcx>>>;
 }
 
 impl<'tcx> InferCtxtEvalExt<'tcx> for InferCtxt<'tcx> {
@@ -129,33 +143,50 @@ fn evaluate_root_goal(
     ) -> Result<(bool, Certainty, Vec<Goal<'tcx, ty::Predicate<'tcx>>>), NoSolution> {
         let mode = if self.intercrate { SolverMode::Coherence } else { SolverMode::Normal };
         let mut search_graph = search_graph::SearchGraph::new(self.tcx, mode);
+        evaluate_root_goal_in(self, &mut search_graph, goal)
+    }
 
-        let mut ecx = EvalCtxt {
-            search_graph: &mut search_graph,
-            infcx: self,
-            // Only relevant when canonicalizing the response,
-            // which we don't do within this evaluation context.
-            predefined_opaques_in_body: self
-                .tcx
-                .mk_predefined_opaques_in_body(PredefinedOpaquesData::default()),
-            // Only relevant when canonicalizing the response.
-            max_input_universe: ty::UniverseIndex::ROOT,
-            var_values: CanonicalVarValues::dummy(),
-            nested_goals: NestedGoals::new(),
-            tainted: Ok(()),
-        };
-        let result = ecx.evaluate_goal(IsNormalizesToHack::No, goal);
-
-        assert!(
-            ecx.nested_goals.is_empty(),
-            "root `EvalCtxt` should not have any goals added to it"
-        );
-
-        assert!(search_graph.is_empty());
-        result
+    #[instrument(level = "debug", skip(self), ret)]
+    fn find_inductive_cycle(
+        &self,
+        goal: Goal<'tcx, ty::Predicate<'tcx>>,
+    ) -> Option<Vec<CanonicalInput<'tcx>>> {
+        let mode = if self.intercrate { SolverMode::Coherence } else { SolverMode::Normal };
+        let mut search_graph = search_graph::SearchGraph::new(self.tcx, mode).record_cycles();
+        self.probe(|_| {
+            let _ = evaluate_root_goal_in(self, &mut search_graph, goal);
+        });
+        search_graph.take_inductive_cycle()
     }
 }
 
+fn evaluate_root_goal_in<'tcx>(
+    infcx: &InferCtxt<'tcx>,
+    search_graph: &mut SearchGraph<'tcx>,
+    goal: Goal<'tcx, ty::Predicate<'tcx>>,
+) -> Result<(bool, Certainty, Vec<Goal<'tcx, ty::Predicate<'tcx>>>), NoSolution> {
+    let mut ecx = EvalCtxt {
+        search_graph,
+        infcx,
+        // Only relevant when canonicalizing the response,
+        // which we don't do within this evaluation context.
+        predefined_opaques_in_body: infcx
+            .tcx
+            .mk_predefined_opaques_in_body(PredefinedOpaquesData::default()),
+        // Only relevant when canonicalizing the response.
+        max_input_universe: ty::UniverseIndex::ROOT,
+        var_values: CanonicalVarValues::dummy(),
+        nested_goals: NestedGoals::new(),
+        tainted: Ok(()),
+    };
+    let result = ecx.evaluate_goal(IsNormalizesToHack::No, goal);
+
+    assert!(ecx.nested_goals.is_empty(), "root `EvalCtxt` should not have any goals added to it");
+
+    assert!(ecx.search_graph.is_empty());
+    result
+}
+
 impl<'a, 'tcx> EvalCtxt<'a, 'tcx> {
     pub(super) fn solver_mode(&self) -> SolverMode {
         self.search_graph.solver_mode()
@@ -18,6 +18,10 @@
     pub struct StackDepth {}
 }
 
+/// How many goals a search graph that records cycles evaluates before giving up, see
+/// [`SearchGraph::record_cycles`].
+const CYCLE_SEARCH_GOAL_LIMIT: usize = 1024;
+
 struct StackElem<'tcx> {
     input: CanonicalInput<'tcx>,
     has_been_used: bool,
@@ -31,6 +35,12 @@ pub(super) struct SearchGraph<'tcx> {
     stack: IndexVec<StackDepth, StackElem<'tcx>>,
     overflow_data: OverflowData,
     provisional_cache: ProvisionalCache<'tcx>,
+    /// Whether to record the first inductive cycle we encounter, see
+    /// [`super::InferCtxtEvalExt::find_inductive_cycle`].
+    record_cycles: bool,
+    inductive_cycle: Option<Vec<CanonicalInput<'tcx>>>,
+    /// How many more goals to evaluate while looking for an inductive cycle.
+    remaining_cycle_search_goals: usize,
 }
 
 impl<'tcx> SearchGraph<'tcx> {
@@ -40,9 +50,27 @@ pub(super) fn new(tcx: TyCtxt<'tcx>, mode: SolverMode) -> SearchGraph<'tcx> {
             stack: Default::default(),
             overflow_data: OverflowData::new(tcx),
             provisional_cache: ProvisionalCache::empty(),
+            record_cycles: false,
+            inductive_cycle: None,
+            remaining_cycle_search_goals: CYCLE_SEARCH_GOAL_LIMIT,
         }
     }
 
+    /// Records the first inductive cycle encountered while using this search
+    /// graph. This disables the global cache, as cache hits would hide cycles.
+    ///
+    /// Without the cache, goals with exponentially many nested goals take
+    /// exponentially long to evaluate. So once the cycle is found, or after
+    /// [`CYCLE_SEARCH_GOAL_LIMIT`] goals, all further goals are treated as
+    /// overflowing without evaluating them.
+    pub(super) fn record_cycles(self) -> SearchGraph<'tcx> {
+        SearchGraph { record_cycles: true, ..self }
+    }
+
+    pub(super) fn take_inductive_cycle(&mut self) -> Option<Vec<CanonicalInput<'tcx>>> {
+        self.inductive_cycle.take()
+    }
+
     pub(super) fn solver_mode(&self) -> SolverMode {
         self.mode
     }
@@ -57,6 +85,10 @@ pub(super) fn solver_mode(&self) -> SolverMode {
     /// We could add another global cache for coherence instead,
     /// but that's effort so let's only do it if necessary.
     pub(super) fn should_use_global_cache(&self) -> bool {
+        if self.record_cycles {
+            return false;
+        }
+
         match self.mode {
             SolverMode::Normal => true,
             SolverMode::Coherence => false,
@@ -139,6 +171,10 @@ fn try_push_stack(
                 {
                     Err(cache.provisional_result(entry_index))
                 } else {
+                    if self.record_cycles && self.inductive_cycle.is_none() {
+                        let cycle = self.stack.raw[stack_depth.index()..].iter().map(|g| g.input);
+                        self.inductive_cycle = Some(cycle.chain([input]).collect());
+                    }
                     Err(super::response_no_constraints(
                         tcx,
                         input,
@@ -207,6 +243,17 @@ pub(super) fn with_new_goal(
         canonical_input: CanonicalInput<'tcx>,
         mut loop_body: impl FnMut(&mut Self) -> QueryResult<'tcx>,
     ) -> QueryResult<'tcx> {
+        if self.record_cycles {
+            if self.inductive_cycle.is_some() || self.remaining_cycle_search_goals == 0 {
+                return super::response_no_constraints(
+                    tcx,
+                    canonical_input,
+                    Certainty::Maybe(MaybeCause::Overflow),
+                );
+            }
+            self.remaining_cycle_search_goals -= 1;
+        }
+
         if self.should_use_global_cache() {
             if let Some(result) = tcx.new_solver_evaluation_cache.get(&canonical_input, tcx) {
                 debug!(?canonical_input, ?result, "cache hit");
@@ -1,3 +1,5 @@
+// ignore-tidy-filelength
+
 mod ambiguity;
 pub mod on_unimplemented;
 pub mod suggestions;
@@ -7,9 +9,11 @@
     ObligationCauseCode, ObligationCtxt, OutputTypeParameterMismatch, Overflow,
     PredicateObligation, SelectionContext, SelectionError, TraitNotObjectSafe,
 };
+use crate::errors::NonConstTraitMethodCall;
 use crate::infer::error_reporting::{TyCategory, TypeAnnotationNeeded as ErrorCode};
 use crate::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
 use crate::infer::{self, InferCtxt};
+use crate::solve::InferCtxtEvalExt as _;
 use crate::traits::query::evaluate_obligation::InferCtxtExt as _;
 use crate::traits::query::normalize::QueryNormalizeExt as _;
 use crate::traits::specialize::to_pretty_impl_header;
@@ -27,12 +31,14 @@
 use rustc_hir::{GenericParam, Item, Node};
 use rustc_infer::infer::error_reporting::TypeErrCtxt;
 use rustc_infer::infer::{InferOk, TypeTrace};
-use rustc_middle::traits::select::OverflowError;
+use rustc_middle::traits::select::{OverflowError, SelectionCandidate};
 use rustc_middle::traits::SelectionOutputTypeParameterMismatch;
 use rustc_middle::ty::abstract_const::NotConstEvaluatable;
 use rustc_middle::ty::error::{ExpectedFound, TypeError};
 use rustc_middle::ty::fold::{TypeFolder, TypeSuperFoldable};
-use rustc_middle::ty::print::{with_forced_trimmed_paths, FmtPrinter, Print};
+use rustc_middle::ty::print::{
+    with_forced_trimmed_paths, with_no_trimmed_paths, FmtPrinter, Print,
+};
 use rustc_middle::ty::{
     self, SubtypePredicate, ToPolyTraitRef, ToPredicate, TraitRef, Ty, TyCtxt, TypeFoldable,
     TypeVisitable, TypeVisitableExt,
@@ -618,6 +624,25 @@ fn report_overflow_obligation_cycle(&self, cycle: &[PredicateObligation<'tcx>])
     fn report_overflow_no_abort(&self, obligation: PredicateObligation<'tcx>) -> ErrorGuaranteed {
         let obligation = self.resolve_vars_if_possible(obligation);
         let mut err = self.build_overflow_error(&obligation.predicate, obligation.cause.span, true);
+        // Only the new solver reports overflow without aborting. If the overflow is caused by
+        // an inductive cycle, list all the goals involved in it.
+        if let Some(cycle) = self.find_inductive_cycle(obligation.clone().into()) {
+            self.probe(|_| {
+                let mut goals = cycle.iter().map(|input| {
+                    let (input, _) = self.instantiate_canonical_with_fresh_inference_vars(
+                        obligation.cause.span,
+                        input,
+                    );
+                    self.resolve_vars_if_possible(input.goal.predicate)
+                });
+                let head = goals.next().unwrap();
+                err.note(format!("cycle when proving `{head}`"));
+                for goal in goals.take(cycle.len() - 2) {
+                    err.note(format!("...which requires proving `{goal}`..."));
+                }
+                err.note(format!("...which again requires proving `{head}`, completing the cycle"));
+            });
+        }
         self.note_obligation_cause(&mut err, &obligation);
         self.point_at_returns_when_relevant(&mut err, &obligation);
         err.emit()
@@ -693,6 +718,14 @@ fn report_selection_error(
                         }
                         let trait_ref = trait_predicate.to_poly_trait_ref();
 
+                        if predicate_is_const
+                            && let Some(mut err) =
+                                self.report_non_const_trait_method_call(&obligation, trait_ref)
+                        {
+                            err.emit();
+                            return;
+                        }
+
                         let (post_message, pre_message, type_def) = self
                             .get_parent_trait_ref(obligation.cause.code())
                             .map(|(t, s)| {
@@ -896,6 +929,7 @@ fn report_selection_error(
                         self.note_version_mismatch(&mut err, &trait_ref);
                         self.suggest_remove_await(&obligation, &mut err);
                         self.suggest_derive(&obligation, &mut err, trait_predicate);
+                        self.note_unelaborated_bound(&obligation, &mut err, trait_predicate);
 
                         if Some(trait_ref.def_id()) == tcx.lang_items().try_trait() {
                             self.suggest_await_before_try(
@@ -1451,6 +1485,12 @@ fn maybe_add_note_for_unsatisfied_const(
         span: Span,
     ) -> UnsatisfiedConst;
 
+    fn report_non_const_trait_method_call(
+        &self,
+        obligation: &PredicateObligation<'tcx>,
+        trait_ref: ty::PolyTraitRef<'tcx>,
+    ) -> Option<DiagnosticBuilder<'tcx, ErrorGuaranteed>>;
+
     fn report_closure_error(
         &self,
         obligation: &PredicateObligation<'tcx>,
@@ -3114,6 +3154,68 @@ fn maybe_add_note_for_unsatisfied_const(
         unsatisfied_const
     }
 
+    /// Reports calling a method of a `#[const_trait]` through a non-const impl of that trait
+    /// in a const context, pointing at the impl instead of at the unsatisfied `~const` bound.
+    fn report_non_const_trait_method_call(
+        &self,
+        obligation: &PredicateObligation<'tcx>,
+        trait_ref: ty::PolyTraitRef<'tcx>,
+    ) -> Option<DiagnosticBuilder<'tcx, ErrorGuaranteed>> {
+        let mut span = obligation.cause.span;
+        // Bounds on the receiver of a method call are blamed on the receiver argument, but the
+        // error is about the method, so point at its name instead.
+        let code = if let ObligationCauseCode::FunctionArgumentObligation {
+            call_hir_id,
+            parent_code,
+            ..
+        } = obligation.cause.code()
+        {
+            if let Some(Node::Expr(hir::Expr {
+                kind: hir::ExprKind::MethodCall(segment, ..),
+                ..
+            })) = self.tcx.hir().find(*call_hir_id)
+            {
+                span = segment.ident.span;
+            }
+            &parent_code
+        } else {
+            obligation.cause.code()
+        };
+        let (ObligationCauseCode::ExprItemObligation(callee_def_id, ..)
+        | ObligationCauseCode::ExprBindingObligation(callee_def_id, ..)) = *code
+        else {
+            return None;
+        };
+        if !obligation.param_env.is_const()
+            || self.tcx.trait_of_item(callee_def_id) != Some(trait_ref.def_id())
+        {
+            return None;
+        }
+
+        let non_const_obligation = Obligation {
+            cause: obligation.cause.clone(),
+            param_env: obligation.param_env.without_const(),
+            predicate: trait_ref.without_const(),
+            recursion_depth: obligation.recursion_depth,
+        };
+        // Only point at user-written impls, the other candidates (e.g. where-clauses or
+        // closures) are better explained by the generic error.
+        let impl_def_id = self.probe(|_| {
+            match SelectionContext::new(self).select_from_obligation(&non_const_obligation) {
+                Ok(Some(SelectionCandidate::ImplCandidate(impl_def_id))) => Some(impl_def_id),
+                _ => None,
+            }
+        })?;
+
+        Some(self.tcx.sess.create_err(NonConstTraitMethodCall {
+            span,
+            func: with_no_trimmed_paths!(self.tcx.def_path_str(callee_def_id)),
+            self_ty: trait_ref.skip_binder().self_ty(),
+            trait_path: trait_ref.print_only_trait_path().to_string(),
+            impl_span: self.tcx.def_span(impl_def_id),
+        }))
+    }
+
     fn report_closure_error(
         &self,
         obligation: &PredicateObligation<'tcx>,
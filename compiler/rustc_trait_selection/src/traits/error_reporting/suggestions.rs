@@ -6,7 +6,7 @@
 };
 
 use crate::infer::InferCtxt;
-use crate::traits::{NormalizeExt, ObligationCtxt};
+use crate::traits::{explain_derived_obligation, NormalizeExt, ObligationCtxt};
 
 use hir::def::CtorOf;
 use rustc_data_structures::fx::FxHashSet;
@@ -356,6 +356,13 @@ fn suggest_derive(
         trait_pred: ty::PolyTraitPredicate<'tcx>,
     );
 
+    fn note_unelaborated_bound(
+        &self,
+        obligation: &PredicateObligation<'tcx>,
+        err: &mut Diagnostic,
+        trait_pred: ty::PolyTraitPredicate<'tcx>,
+    );
+
     fn suggest_dereferencing_index(
         &self,
         obligation: &PredicateObligation<'tcx>,
@@ -3053,6 +3060,41 @@ fn note_obligation_cause_code<T>(
                     });
                 }
             }
+            ObligationCauseCode::ImplDerivedObligation(_)
+                if let Some(tree) = explain_derived_obligation(tcx, cause_code) =>
+            {
+                // Point at each supertrait bound that was followed, starting at the one that is
+                // the unsatisfied predicate.
+                for step in tree.steps.iter().rev() {
+                    let parent = self.resolve_vars_if_possible(step.parent);
+                    let predicate = self.resolve_vars_if_possible(step.predicate);
+                    let bound = match predicate.to_opt_poly_trait_pred() {
+                        Some(trait_pred) => trait_pred.print_modifiers_and_trait_path().to_string(),
+                        None => predicate.to_string(),
+                    };
+                    err.span_note(
+                        step.span,
+                        format!(
+                            "required because of the supertrait `{bound}` of `{}`",
+                            parent.print_modifiers_and_trait_path()
+                        ),
+                    );
+                    seen_requirements.insert(parent.def_id());
+                }
+                let root = self.resolve_vars_if_possible(tree.root);
+                // #74711: avoid a stack overflow
+                ensure_sufficient_stack(|| {
+                    self.note_obligation_cause_code(
+                        body_id,
+                        err,
+                        root,
+                        param_env,
+                        tree.root_code,
+                        obligated_types,
+                        seen_requirements,
+                    )
+                });
+            }
             ObligationCauseCode::ImplDerivedObligation(ref data) => {
                 let mut parent_trait_pred =
                     self.resolve_vars_if_possible(data.derived.parent_trait_pred);
@@ -3352,6 +3394,49 @@ fn suggest_floating_point_literal(
         }
     }
 
+    /// Points out the `#[do_not_elaborate]` attribute when `trait_pred` does not hold for a
+    /// type parameter only because the bound that would imply it was not elaborated.
+    fn note_unelaborated_bound(
+        &self,
+        obligation: &PredicateObligation<'tcx>,
+        err: &mut Diagnostic,
+        trait_pred: ty::PolyTraitPredicate<'tcx>,
+    ) {
+        let tcx = self.tcx;
+        let ty::Param(param) = *trait_pred.skip_binder().self_ty().kind() else {
+            return;
+        };
+        let body_def_id = obligation.cause.body_id.to_def_id();
+        if !crate::traits::unelaborated_params(tcx, body_def_id).contains(&param.index) {
+            return;
+        }
+
+        let trait_ref = trait_pred.skip_binder().trait_ref;
+        let Some(bound) = tcx.param_env(body_def_id).caller_bounds().iter().find(|&bound| {
+            bound
+                .to_opt_poly_trait_pred()
+                .is_some_and(|bound| bound.skip_binder().self_ty() == trait_ref.self_ty())
+                && crate::traits::elaborate(tcx, [bound]).skip(1).any(|implied| {
+                    implied
+                        .to_opt_poly_trait_pred()
+                        .is_some_and(|implied| implied.skip_binder().trait_ref == trait_ref)
+                })
+        }) else {
+            return;
+        };
+        let param_def_id = tcx.generics_of(body_def_id).type_param(&param, tcx).def_id;
+        let Some(attr) = tcx.get_attr(param_def_id, sym::do_not_elaborate) else {
+            return;
+        };
+        err.span_note(
+            attr.span,
+            format!(
+                "`{trait_pred}` is implied by the bound `{bound}`, but the bounds on `{param}` \
+                are not elaborated because of `#[do_not_elaborate]`"
+            ),
+        );
+    }
+
     fn suggest_derive(
         &self,
         obligation: &PredicateObligation<'tcx>,
@@ -66,10 +66,12 @@
 pub use self::structural_normalize::StructurallyNormalizeExt;
 pub use self::util::elaborate;
 pub use self::util::{expand_trait_aliases, TraitAliasExpander};
+pub use self::util::{explain_derived_obligation, DerivationStep, DerivationTree};
 pub use self::util::{get_vtable_index_of_object_method, impl_item_is_final, upcast_choices};
 pub use self::util::{
-    supertrait_def_ids, supertraits, transitive_bounds, transitive_bounds_that_define_assoc_item,
-    SupertraitDefIds,
+    is_supertrait_of, supertrait_def_ids, supertrait_def_ids_with_paths, supertrait_graph_to_dot,
+    supertraits, transitive_bounds, transitive_bounds_that_define_assoc_item, unelaborated_params,
+    SupertraitDefIds, SupertraitDefIdsWithPaths,
 };
 
 pub use self::chalk_fulfill::FulfillmentContext as ChalkFulfillmentContext;
@@ -254,6 +256,7 @@ fn do_normalize_predicates<'tcx>(
 pub fn normalize_param_env_or_error<'tcx>(
     tcx: TyCtxt<'tcx>,
     unnormalized_env: ty::ParamEnv<'tcx>,
+    unelaborated_params: Vec<u32>,
     cause: ObligationCause<'tcx>,
 ) -> ty::ParamEnv<'tcx> {
     // I'm not wild about reporting errors here; I'd prefer to
@@ -325,6 +328,7 @@ fn fold_const(&mut self, c: ty::Const<'tcx>) -> ty::Const<'tcx> {
             predicate.fold_with(&mut ConstNormalizer(tcx))
         }),
     )
+    .without_elaborating_params(unelaborated_params)
     .collect();
 
     debug!("normalize_param_env_or_error: elaborated-predicates={:?}", predicates);
@@ -562,6 +566,8 @@ pub fn provide(providers: &mut Providers) {
     *providers = Providers {
         specialization_graph_of: specialize::specialization_graph_provider,
         specializes: specialize::specializes,
+        is_supertrait_of: util::is_supertrait_of_provider,
+        elaborated_implied_predicates: util::elaborated_implied_predicates_provider,
         subst_and_check_impossible_predicates,
         check_tys_might_be_eq: misc::check_tys_might_be_eq,
         is_impossible_method,
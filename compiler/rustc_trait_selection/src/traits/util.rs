@@ -1,13 +1,16 @@
 use super::NormalizeExt;
-use super::{ObligationCause, PredicateObligation, SelectionContext};
-use rustc_data_structures::fx::FxHashSet;
+use super::{ObligationCause, ObligationCauseCode, PredicateObligation, SelectionContext};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_errors::Diagnostic;
+use rustc_hir::def::DefKind;
 use rustc_hir::def_id::DefId;
 use rustc_infer::infer::InferOk;
 use rustc_middle::ty::SubstsRef;
 use rustc_middle::ty::{self, ImplSubject, ToPredicate, Ty, TyCtxt, TypeVisitableExt};
 use rustc_span::Span;
 use smallvec::SmallVec;
+use std::collections::VecDeque;
+use std::fmt::Write;
 
 pub use rustc_infer::traits::{self, util::*};
 
@@ -190,6 +193,194 @@ fn next(&mut self) -> Option<DefId> {
     }
 }
 
+/// Like [`SupertraitDefIds`], but also yields the chain of traits through which
+/// each supertrait was reached, starting at the original trait. For example,
+/// starting from `Ord`, this yields `(Ord, [])`, `(Eq, [Ord])`, `(PartialOrd, [Ord])`
+/// and `(PartialEq, [Ord, Eq])`. Supertraits are visited breadth-first, so each one
+/// is yielded with one of the shortest chains through which it is reached. This lets
+/// diagnostics explain *why* some supertrait bound is implied.
+pub struct SupertraitDefIdsWithPaths<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    queue: VecDeque<(DefId, SmallVec<[DefId; 4]>)>,
+    visited: FxHashSet<DefId>,
+}
+
+pub fn supertrait_def_ids_with_paths(
+    tcx: TyCtxt<'_>,
+    trait_def_id: DefId,
+) -> SupertraitDefIdsWithPaths<'_> {
+    SupertraitDefIdsWithPaths {
+        tcx,
+        queue: VecDeque::from([(trait_def_id, SmallVec::new())]),
+        visited: Some(trait_def_id).into_iter().collect(),
+    }
+}
+
+impl Iterator for SupertraitDefIdsWithPaths<'_> {
+    type Item = (DefId, SmallVec<[DefId; 4]>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (def_id, path) = self.queue.pop_front()?;
+        let predicates = self.tcx.super_predicates_of(def_id);
+        let visited = &mut self.visited;
+        self.queue.extend(
+            predicates
+                .predicates
+                .iter()
+                .filter_map(|(pred, _)| pred.to_opt_poly_trait_pred())
+                .map(|trait_ref| trait_ref.def_id())
+                .filter(|&super_def_id| visited.insert(super_def_id))
+                .map(|super_def_id| {
+                    let mut super_path = path.clone();
+                    super_path.push(def_id);
+                    (super_def_id, super_path)
+                }),
+        );
+        Some((def_id, path))
+    }
+}
+
+/// Whether `super_def_id` is a (transitive) supertrait of `sub_def_id`. Every trait
+/// is considered to be a supertrait of itself.
+///
+/// This is preferable to searching through [`supertrait_def_ids`] manually, since
+/// the result is cached, and the search stops as soon as `super_def_id` is found.
+pub fn is_supertrait_of(tcx: TyCtxt<'_>, sub_def_id: DefId, super_def_id: DefId) -> bool {
+    // Shortcut the most common case.
+    sub_def_id == super_def_id || tcx.is_supertrait_of((sub_def_id, super_def_id))
+}
+
+pub(super) fn is_supertrait_of_provider(
+    tcx: TyCtxt<'_>,
+    (sub_def_id, super_def_id): (DefId, DefId),
+) -> bool {
+    supertrait_def_ids(tcx, sub_def_id).any(|def_id| def_id == super_def_id)
+}
+
+pub(super) fn elaborated_implied_predicates_provider<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    trait_def_id: DefId,
+) -> &'tcx ty::List<ty::Predicate<'tcx>> {
+    let identity: ty::Predicate<'tcx> =
+        ty::TraitRef::identity(tcx, trait_def_id).without_const().to_predicate(tcx);
+    tcx.mk_predicates_from_iter(
+        elaborate(tcx, [identity]).map(|pred| anonymize_predicate(tcx, pred)),
+    )
+}
+
+/// Renders the graph of the transitive supertraits of `trait_def_id`, as visited by
+/// [`supertrait_def_ids`], in the Graphviz DOT format. This is used by
+/// `-Zdump-supertrait-graph`.
+///
+/// Every trait has an edge to each of its direct supertraits, labeled with the supertrait
+/// bound, and a dashed edge to each region that its supertrait bounds require a type to
+/// outlive, e.g. to `'static` for `trait Foo: 'static {}`.
+pub fn supertrait_graph_to_dot(tcx: TyCtxt<'_>, trait_def_id: DefId) -> String {
+    let name = |def_id| ty::print::with_no_trimmed_paths!(tcx.def_path_str(def_id));
+    let def_ids: Vec<_> = supertrait_def_ids(tcx, trait_def_id).collect();
+    let nodes: FxHashMap<_, _> =
+        def_ids.iter().enumerate().map(|(i, &def_id)| (def_id, i)).collect();
+
+    let mut out = String::new();
+    let title = format!("supertraits of {}", name(trait_def_id));
+    writeln!(out, "digraph {title:?} {{").unwrap();
+    writeln!(out, "    node [shape=box];").unwrap();
+    for (i, &def_id) in def_ids.iter().enumerate() {
+        writeln!(out, "    t{i} [label={:?}];", name(def_id)).unwrap();
+    }
+
+    // Regions get a node for every outlives bound, as the same name may refer to different
+    // parameters in different traits.
+    let mut regions = 0;
+    for (i, &def_id) in def_ids.iter().enumerate() {
+        for &(predicate, _) in tcx.super_predicates_of(def_id).predicates {
+            match predicate.kind().skip_binder() {
+                ty::PredicateKind::Clause(ty::Clause::Trait(trait_pred)) => {
+                    let j = nodes[&trait_pred.def_id()];
+                    let bound = predicate.kind().rebind(trait_pred.trait_ref);
+                    let bound = bound.print_only_trait_path();
+                    let label = ty::print::with_no_trimmed_paths!(bound.to_string());
+                    writeln!(out, "    t{i} -> t{j} [label={label:?}];").unwrap();
+                }
+                ty::PredicateKind::Clause(ty::Clause::TypeOutlives(ty::OutlivesPredicate(
+                    ty,
+                    region,
+                ))) => {
+                    let label = format!("{ty}: {region}");
+                    let region = region.to_string();
+                    writeln!(out, "    r{regions} [label={region:?}, shape=ellipse];").unwrap();
+                    writeln!(out, "    t{i} -> r{regions} [label={label:?}, style=dashed];")
+                        .unwrap();
+                    regions += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// The result of [`explain_derived_obligation`]: the chain of supertrait bounds through which
+/// an elaborated predicate follows from a trait bound.
+#[derive(Debug, Clone)]
+pub struct DerivationTree<'a, 'tcx> {
+    /// The trait bound that the predicate was elaborated from.
+    pub root: ty::PolyTraitPredicate<'tcx>,
+    /// The cause of the obligation for `root`.
+    pub root_code: &'a ObligationCauseCode<'tcx>,
+    /// The supertrait bounds that were followed, starting with a supertrait bound of the trait
+    /// of `root`, and ending with the bound that is the explained predicate.
+    pub steps: Vec<DerivationStep<'tcx>>,
+}
+
+/// A supertrait bound in a [`DerivationTree`].
+#[derive(Debug, Clone)]
+pub struct DerivationStep<'tcx> {
+    /// The trait predicate whose trait has the supertrait bound.
+    pub parent: ty::PolyTraitPredicate<'tcx>,
+    /// The index of the bound in the `implied_predicates_of` the trait of `parent`.
+    pub index: usize,
+    /// The span of the bound.
+    pub span: Span,
+    /// The bound, substituted with the generic arguments of `parent`.
+    pub predicate: ty::Predicate<'tcx>,
+}
+
+/// Explains how an obligation whose cause is `cause_code` was elaborated from the supertrait
+/// bounds of another trait bound, by following the causes that the elaborator derived for it,
+/// and reconstructing each supertrait bound from its index. Returns `None` if the obligation
+/// was not elaborated from a supertrait bound.
+pub fn explain_derived_obligation<'a, 'tcx>(
+    tcx: TyCtxt<'tcx>,
+    cause_code: &'a ObligationCauseCode<'tcx>,
+) -> Option<DerivationTree<'a, 'tcx>> {
+    let mut steps = vec![];
+    let mut code = cause_code;
+    while let ObligationCauseCode::ImplDerivedObligation(data) = code
+        && let Some(index) = data.impl_def_predicate_index
+        && tcx.def_kind(data.impl_or_alias_def_id) == DefKind::Trait
+        && !tcx.trait_is_auto(data.impl_or_alias_def_id)
+    {
+        let parent = data.derived.parent_trait_pred;
+        let Some(&(predicate, span)) =
+            tcx.implied_predicates_of(parent.def_id()).predicates.get(index)
+        else {
+            break;
+        };
+        // Only the super predicates are elaborated in some modes, and their indices may differ.
+        if span != data.span {
+            break;
+        }
+        let predicate = predicate.subst_supertrait(tcx, &parent.map_bound(|pred| pred.trait_ref));
+        steps.push(DerivationStep { parent, index, span, predicate });
+        code = &data.derived.parent_code;
+    }
+    steps.reverse();
+    let root = steps.first()?.parent;
+    Some(DerivationTree { root, root_code: code, steps })
+}
+
 ///////////////////////////////////////////////////////////////////////////
 // Other
 ///////////////////////////////////////////////////////////////////////////
@@ -7,7 +7,10 @@
 use rustc_middle::query::Providers;
 use rustc_middle::ty::visit::TypeVisitableExt;
 use rustc_middle::ty::InternalSubsts;
-use rustc_middle::ty::{self, GenericParamDefKind, ToPredicate, Ty, TyCtxt, VtblEntry};
+use rustc_middle::ty::{
+    self, GenericParamDefKind, ToPredicate, Ty, TyCtxt, VtblEntry, VtblLayoutEstimate,
+    VtblSegmentEstimate,
+};
 use rustc_span::{sym, Span};
 use smallvec::SmallVec;
 
@@ -380,10 +383,76 @@ pub(crate) fn count_own_vtable_entries<'tcx>(
     tcx.own_existential_vtable_entries(trait_ref.def_id()).len()
 }
 
+/// Estimates the vtable layout of `dyn Trait` for the trait `trait_def_id`.
+///
+/// This is a slightly edited version of [`vtable_entries`] that works without a self
+/// type and just counts the number of entries of each trait. Note that this is
+/// technically wrong for traits which have associated types in supertraits:
+///
+///   trait A: AsRef<Self::T> + AsRef<()> { type T; }
+///
+/// Without self type we can't normalize `Self::T`, so we can't know if `AsRef<Self::T>`
+/// and `AsRef<()>` are the same trait, thus we assume that those are different, and
+/// potentially over-estimate how many vtable entries there are.
+///
+/// Similarly this is wrong for traits that have methods with possibly-impossible bounds.
+/// For example:
+///
+///   trait B<T> { fn f(&self) where T: Copy; }
+///
+/// Here `dyn B<u8>` will have 4 entries, while `dyn B<String>` will only have 3.
+/// However, since we don't know `T`, we can't know if `T: Copy` holds or not,
+/// thus we lean on the bigger side and say it has 4 entries.
+fn vtable_layout_estimate(tcx: TyCtxt<'_>, trait_def_id: DefId) -> VtblLayoutEstimate<'_> {
+    let mut estimate = VtblLayoutEstimate {
+        segments: Vec::new(),
+        entries_ignoring_upcasting: 0,
+        entries_for_upcasting: 0,
+    };
+    let mut first_dsa = true;
+
+    let trait_ref = ty::Binder::dummy(ty::TraitRef::identity(tcx, trait_def_id));
+    prepare_vtable_segments(tcx, trait_ref, |segment| {
+        match segment {
+            VtblSegment::MetadataDSA => {
+                // If this is the first dsa, it would be included either way,
+                // otherwise it's needed for upcasting
+                if std::mem::take(&mut first_dsa) {
+                    estimate.entries_ignoring_upcasting += TyCtxt::COMMON_VTABLE_ENTRIES.len();
+                } else {
+                    estimate.entries_for_upcasting += TyCtxt::COMMON_VTABLE_ENTRIES.len();
+                }
+            }
+            VtblSegment::TraitOwnEntries { trait_ref, emit_vptr } => {
+                // `vtable_entries` ignores the methods whose predicates are impossible.
+                // We can't really do that as, for example, all not trivial bounds on
+                // generic parameters are impossible (since we don't know the parameters...),
+                // see the comment above.
+                let methods = tcx.own_existential_vtable_entries(trait_ref.def_id()).len();
+                estimate.segments.push(VtblSegmentEstimate {
+                    trait_ref,
+                    offset: estimate.entries_ignoring_upcasting + estimate.entries_for_upcasting,
+                    methods,
+                    emit_vptr,
+                });
+                estimate.entries_ignoring_upcasting += methods;
+                if emit_vptr {
+                    estimate.entries_for_upcasting += 1;
+                }
+            }
+        }
+
+        ControlFlow::Continue::<std::convert::Infallible>(())
+    });
+
+    estimate
+}
+
 pub(super) fn provide(providers: &mut Providers) {
     *providers = Providers {
         own_existential_vtable_entries,
         vtable_entries,
+        vtable_layout_estimate,
         vtable_trait_upcasting_coercion_new_vptr_slot,
         ..*providers
     };
@@ -3,11 +3,14 @@
 use rustc_hir as hir;
 use rustc_hir::lang_items::LangItem;
 use rustc_middle::ty::subst::{GenericArg, GenericArgKind, SubstsRef};
-use rustc_middle::ty::{self, Ty, TyCtxt, TypeVisitableExt};
+use rustc_middle::ty::{
+    self, ToPredicate, Ty, TyCtxt, TypeSuperVisitable, TypeVisitable, TypeVisitableExt, TypeVisitor,
+};
 use rustc_span::def_id::{DefId, LocalDefId, CRATE_DEF_ID};
 use rustc_span::{Span, DUMMY_SP};
 
 use std::iter;
+use std::ops::ControlFlow;
 /// Returns the set of obligations needed to make `arg` well-formed.
 /// If `arg` contains unresolved inference variables, this may include
 /// further WF obligations. However, if `arg` IS an unresolved
@@ -905,6 +908,61 @@ fn from_object_ty(
     }
 }
 
+/// Returns the where clauses of the principal traits of the `dyn Trait` types in `arg`, and of
+/// their supertraits, that don't mention the erased self type. For example, given
+/// `trait Trait<T>: Super<T>` and `trait Super<T: Copy + 'static>`, this returns `T: Copy` and
+/// `T: 'static` for `dyn Trait<T>`.
+///
+/// Unlike the bounds on the erased self type, which `from_object_ty` requires, these are not
+/// part of the well-formedness of `arg` yet (see #27579), so wfcheck only lints if they don't
+/// hold.
+pub fn dyn_principal_implied_predicates<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    arg: GenericArg<'tcx>,
+) -> Vec<ty::Predicate<'tcx>> {
+    let mut predicates = Vec::new();
+    for ty in arg.walk().filter_map(|arg| arg.as_type()) {
+        let ty::Dynamic(data, _, _) = *ty.kind() else { continue };
+        // Higher-ranked principals and objects below binders would need the bound variables
+        // to be instantiated first.
+        let Some(principal) = data.principal().and_then(|principal| principal.no_bound_vars())
+        else {
+            continue;
+        };
+        if ty.has_escaping_bound_vars() {
+            continue;
+        }
+
+        let trait_pred: ty::Predicate<'tcx> = principal.with_self_ty(tcx, ty).to_predicate(tcx);
+        for pred in traits::elaborate(tcx, [trait_pred]) {
+            let Some(trait_pred) = pred.to_opt_poly_trait_pred().and_then(|p| p.no_bound_vars())
+            else {
+                continue;
+            };
+            let where_clauses = tcx
+                .predicates_of(trait_pred.def_id())
+                .instantiate(tcx, trait_pred.trait_ref.substs);
+            predicates.extend(
+                where_clauses
+                    .predicates
+                    .into_iter()
+                    .filter(|pred| !pred.visit_with(&mut MentionsTy(ty)).is_break()),
+            );
+        }
+    }
+    predicates
+}
+
+struct MentionsTy<'tcx>(Ty<'tcx>);
+
+impl<'tcx> TypeVisitor<TyCtxt<'tcx>> for MentionsTy<'tcx> {
+    type BreakTy = ();
+
+    fn visit_ty(&mut self, ty: Ty<'tcx>) -> ControlFlow<Self::BreakTy> {
+        if ty == self.0 { ControlFlow::Break(()) } else { ty.super_visit_with(self) }
+    }
+}
+
 /// Given an object type like `SomeTrait + Send`, computes the lifetime
 /// bounds that must hold on the elided self type. These are derived
 /// from the declarations of `SomeTrait`, `Send`, and friends -- if
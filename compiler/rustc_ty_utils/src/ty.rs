@@ -244,12 +244,23 @@ fn param_env(tcx: TyCtxt<'_>, def_id: DefId) -> ty::ParamEnv<'_> {
         None => hir::Constness::NotConst,
     };
 
+    // An associated function may declare `~const` bounds of its own even when its parent trait
+    // or impl is not const. Such bounds can only ever be used in a non-const context, where they
+    // mean the same as plain bounds, so we lower them as such. Otherwise `T: ~const Trait` and
+    // `T: Trait` would end up as two distinct, competing where-clauses in the environment.
+    if constness == hir::Constness::NotConst && tcx.def_kind(def_id) == DefKind::AssocFn {
+        for predicate in &mut predicates {
+            *predicate = predicate.without_const(tcx);
+        }
+    }
+
     let unnormalized_env =
         ty::ParamEnv::new(tcx.mk_predicates(&predicates), traits::Reveal::UserFacing, constness);
 
     let body_id = local_did.unwrap_or(CRATE_DEF_ID);
     let cause = traits::ObligationCause::misc(tcx.def_span(def_id), body_id);
-    traits::normalize_param_env_or_error(tcx, unnormalized_env, cause)
+    let unelaborated_params = traits::unelaborated_params(tcx, def_id);
+    traits::normalize_param_env_or_error(tcx, unnormalized_env, unelaborated_params, cause)
 }
 
 /// Walk through a function type, gathering all RPITITs and installing a
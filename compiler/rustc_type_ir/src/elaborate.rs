@@ -0,0 +1,187 @@
+//! Parts of elaboration that do not depend on a specific [`Interner`], so that
+//! they can be shared between the trait solvers.
+
+use rustc_data_structures::fx::FxHashSet;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::Interner;
+
+#[cfg(test)]
+mod tests;
+
+/// The operations on an [`Interner`] that are needed to elaborate supertraits.
+pub trait ElaborationInterner: Interner + Copy {
+    /// A trait reference under a binder, e.g. `for<'a> T: Trait<'a>`.
+    type PolyTraitRef: Clone + Debug + Hash + Eq;
+    /// The name of an associated item.
+    type AssocName: Copy + Debug;
+
+    /// Replaces the bound vars of `trait_ref` by anonymous ones, so that trait
+    /// references which only differ in the names of their bound vars are equal.
+    fn anonymize_trait_ref(self, trait_ref: Self::PolyTraitRef) -> Self::PolyTraitRef;
+
+    /// Replaces the bound vars of `predicate` by anonymous ones, see
+    /// [`ElaborationInterner::anonymize_trait_ref`].
+    fn anonymize_predicate(self, predicate: Self::Predicate) -> Self::Predicate;
+
+    /// Whether `predicate` is a region or type outlives predicate.
+    fn is_outlives_predicate(self, predicate: &Self::Predicate) -> bool;
+
+    /// Returns the direct supertraits of `trait_ref` that may define an associated
+    /// item named `assoc_name`, instantiated for `trait_ref`.
+    fn supertraits_that_define_assoc_item(
+        self,
+        trait_ref: &Self::PolyTraitRef,
+        assoc_name: Self::AssocName,
+    ) -> Vec<Self::PolyTraitRef>;
+}
+
+/// The operations on an [`Interner`] that are needed to elaborate predicates with [`elaborate`].
+pub trait ImpliedPredicatesInterner: ElaborationInterner {
+    /// Returns the predicates that `predicate` directly implies. For a trait predicate, these are
+    /// the bounds of its trait instantiated for it, or only the supertrait bounds, i.e. the ones
+    /// with `Self` as their self type, for [`Filter::OnlySelf`]. For a type outlives predicate,
+    /// these are the outlives predicates of the components of its type.
+    fn implied_predicates(
+        self,
+        predicate: &Self::Predicate,
+        filter: Filter,
+    ) -> Vec<Self::Predicate>;
+}
+
+/// Which of the implied predicates an elaborator should yield.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Filter {
+    /// Yield all implied predicates.
+    All,
+    /// Only elaborate the supertraits of trait predicates, i.e. the
+    /// predicates that have `Self` as their self type.
+    OnlySelf,
+    /// Elaborate all implied predicates, but only yield the region and
+    /// type outlives predicates among them.
+    OnlyOutlives,
+}
+
+impl Filter {
+    /// Whether an elaborator using this filter yields `predicate`. Predicates that are
+    /// not yielded are still elaborated.
+    pub fn yields<I: ElaborationInterner>(self, interner: I, predicate: &I::Predicate) -> bool {
+        match self {
+            Filter::All | Filter::OnlySelf => true,
+            Filter::OnlyOutlives => interner.is_outlives_predicate(predicate),
+        }
+    }
+}
+
+/// The predicates visited by an elaborator, which are used to avoid yielding or elaborating
+/// the same predicate twice.
+pub struct PredicateSet<I: ElaborationInterner> {
+    interner: I,
+    set: FxHashSet<I::Predicate>,
+}
+
+impl<I: ElaborationInterner> PredicateSet<I>
+where
+    I::Predicate: Hash + Eq,
+{
+    pub fn new(interner: I) -> Self {
+        Self { interner, set: Default::default() }
+    }
+
+    pub fn interner(&self) -> I {
+        self.interner
+    }
+
+    pub fn insert(&mut self, pred: I::Predicate) -> bool {
+        // We have to be careful here because we want
+        //
+        //    for<'a> Foo<&'a i32>
+        //
+        // and
+        //
+        //    for<'b> Foo<&'b i32>
+        //
+        // to be considered equivalent. So normalize all late-bound
+        // regions before we throw things into the underlying set.
+        self.set.insert(self.interner.anonymize_predicate(pred))
+    }
+}
+
+impl<I: ElaborationInterner> Extend<I::Predicate> for PredicateSet<I>
+where
+    I::Predicate: Hash + Eq,
+{
+    fn extend<T: IntoIterator<Item = I::Predicate>>(&mut self, iter: T) {
+        for pred in iter {
+            self.insert(pred);
+        }
+    }
+
+    fn extend_one(&mut self, pred: I::Predicate) {
+        self.insert(pred);
+    }
+
+    fn extend_reserve(&mut self, additional: usize) {
+        Extend::<I::Predicate>::extend_reserve(&mut self.set, additional);
+    }
+}
+
+/// Elaborates `predicates` depth-first, yielding them and all the predicates they imply that
+/// `filter` lets through. Each predicate is yielded and elaborated at most once, even if it is
+/// implied several times or only differs in the names of its bound vars.
+///
+/// This follows the order in which `rustc_infer`'s `Elaborator` visits predicates, without its
+/// obligations, depth limit and statistics.
+pub fn elaborate<I: ImpliedPredicatesInterner>(
+    interner: I,
+    predicates: impl IntoIterator<Item = I::Predicate>,
+    filter: Filter,
+) -> impl Iterator<Item = I::Predicate>
+where
+    I::Predicate: Clone + Hash + Eq,
+{
+    let mut visited = PredicateSet::new(interner);
+    let mut stack: Vec<_> =
+        predicates.into_iter().filter(|predicate| visited.insert(predicate.clone())).collect();
+
+    std::iter::from_fn(move || {
+        while let Some(predicate) = stack.pop() {
+            stack.extend(
+                interner
+                    .implied_predicates(&predicate, filter)
+                    .into_iter()
+                    .filter(|implied| visited.insert(implied.clone())),
+            );
+            if filter.yields(interner, &predicate) {
+                return Some(predicate);
+            }
+        }
+
+        None
+    })
+}
+
+/// A specialized variant of elaboration that only elaborates trait references that may
+/// define the given associated item with the name `assoc_name`, and never walks the
+/// supertraits that cannot lead to it. This is used when resolving types like `Self::Item`
+/// or `T::Item` and helps to avoid cycle errors (see e.g. #35237).
+pub fn transitive_bounds_that_define_assoc_item<I: ElaborationInterner>(
+    interner: I,
+    bounds: impl IntoIterator<Item = I::PolyTraitRef>,
+    assoc_name: I::AssocName,
+) -> impl Iterator<Item = I::PolyTraitRef> {
+    let mut stack: Vec<_> = bounds.into_iter().collect();
+    let mut visited = FxHashSet::default();
+
+    std::iter::from_fn(move || {
+        while let Some(trait_ref) = stack.pop() {
+            if visited.insert(interner.anonymize_trait_ref(trait_ref.clone())) {
+                stack.extend(interner.supertraits_that_define_assoc_item(&trait_ref, assoc_name));
+                return Some(trait_ref);
+            }
+        }
+
+        None
+    })
+}
@@ -0,0 +1,165 @@
+use super::*;
+use crate::mock::{MockDb, MockPredicate, MockPredicateKind, MockTrait, MockTraitRef};
+
+fn trait_(assoc_items: &[&'static str], supertraits: &[MockTraitRef]) -> MockTrait {
+    MockTrait {
+        assoc_items: assoc_items.to_vec(),
+        supertraits: supertraits.to_vec(),
+        ..Default::default()
+    }
+}
+
+fn trait_ref(trait_id: usize) -> MockTraitRef {
+    MockTraitRef::new(trait_id, &[])
+}
+
+/// Elaborates `bounds` for `assoc_name`, returning the ids of the traits in visiting order.
+fn transitive_bounds(db: &MockDb, bounds: &[MockTraitRef], assoc_name: &'static str) -> Vec<usize> {
+    transitive_bounds_that_define_assoc_item(db.interner(), bounds.to_vec(), assoc_name)
+        .map(|trait_ref| trait_ref.trait_id)
+        .collect()
+}
+
+#[test]
+fn transitive_bounds_filter_by_assoc_name() {
+    // 0: 1 + 2, 1: 3, 2: 3, where 1 defines `A`, 2 defines `B` and 3 defines `C`.
+    let db = MockDb::new(vec![
+        trait_(&[], &[trait_ref(1), trait_ref(2)]),
+        trait_(&["A"], &[trait_ref(3)]),
+        trait_(&["B"], &[trait_ref(3)]),
+        trait_(&["C"], &[]),
+    ]);
+
+    let cases: &[(&[MockTraitRef], &str, &[usize])] = &[
+        // The bounds themselves are always returned, even if they cannot define the item.
+        (&[trait_ref(0)], "D", &[0]),
+        (&[trait_ref(3)], "A", &[3]),
+        // Only the supertraits that lead to the item are walked.
+        (&[trait_ref(0)], "A", &[0, 1]),
+        (&[trait_ref(0)], "B", &[0, 2]),
+        (&[trait_ref(1)], "B", &[1]),
+        // A supertrait reachable through several paths is only returned once.
+        (&[trait_ref(0)], "C", &[0, 2, 3, 1]),
+        (&[trait_ref(1), trait_ref(2)], "C", &[2, 3, 1]),
+        (&[trait_ref(3), trait_ref(3)], "C", &[3]),
+    ];
+    for &(bounds, assoc_name, expected) in cases {
+        assert_eq!(
+            transitive_bounds(&db, bounds, assoc_name),
+            expected,
+            "elaborating {bounds:?} for `{assoc_name}`",
+        );
+    }
+}
+
+#[test]
+fn transitive_bounds_dedup_modulo_bound_var_names() {
+    // 0: for<'a> 1<'a> + for<'b> 1<'b>
+    let db = MockDb::new(vec![
+        trait_(&[], &[MockTraitRef::new(1, &["'a"]), MockTraitRef::new(1, &["'b"])]),
+        trait_(&["A"], &[]),
+    ]);
+    assert_eq!(transitive_bounds(&db, &[trait_ref(0)], "A"), [0, 1]);
+
+    let bounds = [MockTraitRef::new(1, &["'a"]), MockTraitRef::new(1, &["'b", "'c"])];
+    assert_eq!(transitive_bounds(&db, &bounds, "A"), [1, 1]);
+}
+
+#[test]
+fn transitive_bounds_terminate_on_cycles() {
+    // Supertrait cycles are an error, but elaboration still has to terminate on them.
+    let db = MockDb::new(vec![trait_(&["A"], &[trait_ref(1)]), trait_(&["A"], &[trait_ref(0)])]);
+    assert_eq!(transitive_bounds(&db, &[trait_ref(0)], "A"), [0, 1]);
+}
+
+fn trait_pred(trait_id: usize, bound_vars: &[&'static str]) -> MockPredicate {
+    MockPredicate::new(MockPredicateKind::Trait("T".to_string(), trait_id), bound_vars)
+}
+
+fn outlives_pred(region: &'static str, bound_vars: &[&'static str]) -> MockPredicate {
+    MockPredicate::new(MockPredicateKind::Outlives("T".to_string(), region), bound_vars)
+}
+
+#[test]
+fn elaborate_filter_modes() {
+    // 0: 1 + 'a where Self::A: 2, 1: 'static, 2
+    let db = MockDb {
+        traits: vec![
+            MockTrait {
+                supertraits: vec![trait_ref(1)],
+                outlives: vec!["'a"],
+                assoc_bounds: vec![("A", 2)],
+                ..Default::default()
+            },
+            MockTrait { outlives: vec!["'static"], ..Default::default() },
+            MockTrait::default(),
+        ],
+        components: vec![("Vec<U>", vec!["U"])],
+    };
+    let vec_outlives =
+        || MockPredicate::new(MockPredicateKind::Outlives("Vec<U>".into(), "'a"), &[]);
+
+    let cases: &[(&[MockPredicate], Filter, &[&str])] = &[
+        (&[trait_pred(0, &[])], Filter::All, &["T: 0", "T::A: 2", "T: 'a", "T: 1", "T: 'static"]),
+        // Bounds on associated types are not supertraits.
+        (&[trait_pred(0, &[])], Filter::OnlySelf, &["T: 0", "T: 'a", "T: 1", "T: 'static"]),
+        // Predicates that aren't yielded are still elaborated.
+        (&[trait_pred(0, &[])], Filter::OnlyOutlives, &["T: 'a", "T: 'static"]),
+        (&[trait_pred(2, &[])], Filter::OnlyOutlives, &[]),
+        // Outlives predicates are elaborated into their components, in every mode.
+        (&[vec_outlives()], Filter::All, &["Vec<U>: 'a", "U: 'a"]),
+        (&[vec_outlives()], Filter::OnlySelf, &["Vec<U>: 'a", "U: 'a"]),
+        (&[vec_outlives()], Filter::OnlyOutlives, &["Vec<U>: 'a", "U: 'a"]),
+        // A predicate implied by another one is only yielded once, where it is reached first.
+        (
+            &[trait_pred(0, &[]), trait_pred(1, &[])],
+            Filter::All,
+            &["T: 1", "T: 'static", "T: 0", "T::A: 2", "T: 'a"],
+        ),
+        (&[trait_pred(1, &[]), trait_pred(1, &[])], Filter::All, &["T: 1", "T: 'static"]),
+        (
+            &[outlives_pred("'a", &[]), trait_pred(0, &[])],
+            Filter::OnlyOutlives,
+            &["T: 'static", "T: 'a"],
+        ),
+        // Also if the predicates only differ in the names of their bound vars.
+        (&[trait_pred(2, &["'x"]), trait_pred(2, &["'y"])], Filter::All, &["for<'x> T: 2"]),
+    ];
+    for (predicates, filter, expected) in cases {
+        let elaborated: Vec<_> = elaborate(db.interner(), predicates.iter().cloned(), *filter)
+            .map(|predicate| predicate.to_string())
+            .collect();
+        assert_eq!(elaborated, *expected, "elaborating {predicates:?} with {filter:?}");
+    }
+}
+
+#[test]
+fn predicate_set_dedup() {
+    let db = MockDb::new(vec![]);
+    let mut set = PredicateSet::new(db.interner());
+
+    assert!(set.insert(trait_pred(0, &[])));
+    assert!(!set.insert(trait_pred(0, &[])));
+    // Predicates of different kinds, or about different traits, are different predicates.
+    assert!(set.insert(trait_pred(1, &[])));
+    assert!(set.insert(outlives_pred("'static", &[])));
+    assert!(set.insert(outlives_pred("'a", &[])));
+    assert!(!set.insert(outlives_pred("'static", &[])));
+}
+
+#[test]
+fn predicate_set_dedup_modulo_bound_var_names() {
+    let db = MockDb::new(vec![]);
+    let mut set = PredicateSet::new(db.interner());
+
+    // `for<'a> T: Trait<'a>` and `for<'b> T: Trait<'b>` are the same predicate.
+    assert!(set.insert(trait_pred(0, &["'a"])));
+    assert!(!set.insert(trait_pred(0, &["'b"])));
+    // But they are different from predicates with more bound vars, or none at all.
+    assert!(set.insert(trait_pred(0, &["'a", "'b"])));
+    assert!(set.insert(trait_pred(0, &[])));
+
+    // Extending the set deduplicates in the same way.
+    set.extend([outlives_pred("'a", &["'b"]), outlives_pred("'a", &["'c"])]);
+    assert!(!set.insert(outlives_pred("'a", &["'d"])));
+}
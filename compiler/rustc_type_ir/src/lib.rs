@@ -1,4 +1,5 @@
 #![feature(associated_type_defaults)]
+#![feature(extend_one)]
 #![feature(fmt_helpers_for_derive)]
 #![feature(min_specialization)]
 #![feature(never_type)]
@@ -21,6 +22,7 @@
 use std::mem::discriminant;
 
 pub mod codec;
+pub mod elaborate;
 pub mod fold;
 pub mod sty;
 pub mod ty_info;
@@ -28,6 +30,8 @@
 
 #[macro_use]
 mod macros;
+#[cfg(test)]
+mod mock;
 mod structural_impls;
 
 pub use codec::*;
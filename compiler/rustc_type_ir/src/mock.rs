@@ -0,0 +1,227 @@
+//! A minimal [`Interner`] for unit tests of the interner-independent parts of the type
+//! system, like elaboration, which would otherwise need a whole `TyCtxt` to run.
+//!
+//! Traits are identified by their index in [`MockDb::traits`] and types by their names, and only
+//! the parts of the type system that these parts need are modelled. All other types of the
+//! interner are `()`.
+
+use std::fmt;
+
+use crate::elaborate::{ElaborationInterner, Filter, ImpliedPredicatesInterner};
+use crate::Interner;
+
+/// A trait declared in a [`MockDb`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MockTrait {
+    /// The associated items that the trait itself defines.
+    pub assoc_items: Vec<&'static str>,
+    /// The supertraits of the trait, with the bound vars of their binders.
+    pub supertraits: Vec<MockTraitRef>,
+    /// The regions that `Self` has to outlive, e.g. `'a` for `trait Trait: 'a`.
+    pub outlives: Vec<&'static str>,
+    /// The trait bounds of associated types of `Self`, which are implied but aren't supertraits,
+    /// e.g. `("A", 1)` for `trait Trait where Self::A: Trait1`.
+    pub assoc_bounds: Vec<(&'static str, usize)>,
+}
+
+/// A trait reference under a binder, e.g. `for<'a> T: Trait<'a>` is the trait reference to
+/// `Trait` with the bound var `'a`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct MockTraitRef {
+    pub trait_id: usize,
+    pub bound_vars: Vec<&'static str>,
+}
+
+impl MockTraitRef {
+    pub(crate) fn new(trait_id: usize, bound_vars: &[&'static str]) -> Self {
+        MockTraitRef { trait_id, bound_vars: bound_vars.to_vec() }
+    }
+}
+
+/// A predicate under a binder, with the bound vars of the binder.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct MockPredicate {
+    pub kind: MockPredicateKind,
+    pub bound_vars: Vec<&'static str>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(crate) enum MockPredicateKind {
+    /// `T: Trait`, for the type with the given name and the trait with the given index.
+    Trait(String, usize),
+    /// `T: 'a`, for the type and the region with the given names.
+    Outlives(String, &'static str),
+}
+
+impl MockPredicate {
+    pub(crate) fn new(kind: MockPredicateKind, bound_vars: &[&'static str]) -> Self {
+        MockPredicate { kind, bound_vars: bound_vars.to_vec() }
+    }
+}
+
+/// Prints the predicate the way it would be written in Rust, with the trait as its index, e.g.
+/// `for<'a> T::A: 1` or `Vec<T>: 'static`.
+impl fmt::Display for MockPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.bound_vars.is_empty() {
+            write!(f, "for<{}> ", self.bound_vars.join(", "))?;
+        }
+        match &self.kind {
+            MockPredicateKind::Trait(self_ty, trait_id) => write!(f, "{self_ty}: {trait_id}"),
+            MockPredicateKind::Outlives(ty, region) => write!(f, "{ty}: {region}"),
+        }
+    }
+}
+
+/// The declarations that a [`MockInterner`] looks up.
+#[derive(Default)]
+pub(crate) struct MockDb {
+    pub traits: Vec<MockTrait>,
+    /// The types that outlive a region whenever a type made of them does, e.g. `("Vec<U>", ["U"])`.
+    /// Types that aren't listed have no such components.
+    pub components: Vec<(&'static str, Vec<&'static str>)>,
+}
+
+impl MockDb {
+    pub(crate) fn new(traits: Vec<MockTrait>) -> Self {
+        MockDb { traits, components: Vec::new() }
+    }
+
+    fn components(&self, ty: &str) -> &[&'static str] {
+        self.components
+            .iter()
+            .find(|(composite, _)| *composite == ty)
+            .map(|(_, components)| &components[..])
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn interner(&self) -> MockInterner<'_> {
+        MockInterner { db: self }
+    }
+
+    /// Whether `trait_id` or one of its transitive supertraits defines `assoc_name`.
+    fn may_define_assoc_item(&self, trait_id: usize, assoc_name: &str) -> bool {
+        let mut stack = vec![trait_id];
+        let mut visited = vec![false; self.traits.len()];
+        while let Some(trait_id) = stack.pop() {
+            if std::mem::replace(&mut visited[trait_id], true) {
+                continue;
+            }
+            let trait_ = &self.traits[trait_id];
+            if trait_.assoc_items.contains(&assoc_name) {
+                return true;
+            }
+            stack.extend(trait_.supertraits.iter().map(|supertrait| supertrait.trait_id));
+        }
+        false
+    }
+}
+
+#[derive(Copy, Clone)]
+pub(crate) struct MockInterner<'db> {
+    db: &'db MockDb,
+}
+
+impl Interner for MockInterner<'_> {
+    type AdtDef = ();
+    type SubstsRef = ();
+    type DefId = usize;
+    type Binder<T> = T;
+    type Ty = ();
+    type Const = ();
+    type Region = ();
+    type Predicate = MockPredicate;
+    type TypeAndMut = ();
+    type Mutability = ();
+    type Movability = ();
+    type PolyFnSig = ();
+    type ListBinderExistentialPredicate = ();
+    type BinderListTy = ();
+    type ListTy = Vec<()>;
+    type AliasTy = ();
+    type ParamTy = ();
+    type BoundTy = ();
+    type PlaceholderType = ();
+    type InferTy = ();
+    type ErrorGuaranteed = ();
+    type PredicateKind = ();
+    type AllocId = ();
+
+    type EarlyBoundRegion = ();
+    type BoundRegion = ();
+    type FreeRegion = ();
+    type RegionVid = ();
+    type PlaceholderRegion = ();
+
+    fn ty_and_mut_to_parts(_: ()) -> ((), ()) {
+        ((), ())
+    }
+
+    fn mutability_is_mut(_: ()) -> bool {
+        false
+    }
+}
+
+impl ElaborationInterner for MockInterner<'_> {
+    type PolyTraitRef = MockTraitRef;
+    type AssocName = &'static str;
+
+    fn anonymize_trait_ref(self, trait_ref: MockTraitRef) -> MockTraitRef {
+        MockTraitRef { bound_vars: vec!["'_"; trait_ref.bound_vars.len()], ..trait_ref }
+    }
+
+    fn anonymize_predicate(self, predicate: MockPredicate) -> MockPredicate {
+        MockPredicate { bound_vars: vec!["'_"; predicate.bound_vars.len()], ..predicate }
+    }
+
+    fn is_outlives_predicate(self, predicate: &MockPredicate) -> bool {
+        matches!(predicate.kind, MockPredicateKind::Outlives(..))
+    }
+
+    fn supertraits_that_define_assoc_item(
+        self,
+        trait_ref: &MockTraitRef,
+        assoc_name: &'static str,
+    ) -> Vec<MockTraitRef> {
+        self.db.traits[trait_ref.trait_id]
+            .supertraits
+            .iter()
+            .filter(|supertrait| self.db.may_define_assoc_item(supertrait.trait_id, assoc_name))
+            .cloned()
+            .collect()
+    }
+}
+
+impl ImpliedPredicatesInterner for MockInterner<'_> {
+    fn implied_predicates(self, predicate: &MockPredicate, filter: Filter) -> Vec<MockPredicate> {
+        match &predicate.kind {
+            MockPredicateKind::Trait(self_ty, trait_id) => {
+                let trait_ = &self.db.traits[*trait_id];
+                let supertraits = trait_.supertraits.iter().map(|supertrait| {
+                    let kind = MockPredicateKind::Trait(self_ty.clone(), supertrait.trait_id);
+                    MockPredicate::new(kind, &supertrait.bound_vars)
+                });
+                let outlives = trait_.outlives.iter().map(|&region| {
+                    MockPredicate::new(MockPredicateKind::Outlives(self_ty.clone(), region), &[])
+                });
+                let assoc_bounds =
+                    trait_.assoc_bounds.iter().filter(|_| filter != Filter::OnlySelf).map(
+                        |&(assoc_name, trait_id)| {
+                            let assoc_ty = format!("{self_ty}::{assoc_name}");
+                            MockPredicate::new(MockPredicateKind::Trait(assoc_ty, trait_id), &[])
+                        },
+                    );
+                supertraits.chain(outlives).chain(assoc_bounds).collect()
+            }
+            MockPredicateKind::Outlives(ty, region) => self
+                .db
+                .components(ty)
+                .iter()
+                .map(|&component| {
+                    let kind = MockPredicateKind::Outlives(component.to_string(), region);
+                    MockPredicate::new(kind, &predicate.bound_vars)
+                })
+                .collect(),
+        }
+    }
+}
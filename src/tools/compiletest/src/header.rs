@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 use std::env;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::prelude::*;
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
@@ -71,6 +71,8 @@ pub struct TestProps {
     pub regex_error_patterns: Vec<String>,
     // Extra flags to pass to the compiler
     pub compile_flags: Vec<String>,
+    // Extra flags to pass to the compiler when building auxiliary crates
+    pub aux_compile_flags: Vec<String>,
     // Extra flags to pass when the compiled code is run (such as --bench)
     pub run_flags: Option<String>,
     // If present, the name of a file that this test should match when
@@ -152,6 +154,10 @@ pub struct TestProps {
     pub known_bug: bool,
     // How far should the test proceed while still passing.
     pass_mode: Option<PassMode>,
+    // If `true`, the test is run like a `run-pass` test, and then checked again with
+    // `--cfg run_pass_in_const`, so that its `#[cfg(run_pass_in_const)] const _: () = ...`
+    // harness is evaluated at compile time as well.
+    pub check_run_pass_in_const: bool,
     // Ignore `--pass` overrides from the command line for this test.
     ignore_pass: bool,
     // How far this test should proceed to start failing.
@@ -179,12 +185,19 @@ pub struct TestProps {
     // Whether to tell `rustc` to remap the "src base" directory to a fake
     // directory.
     pub remap_src_base: bool,
+    // If `true`, this UI test is compiled again with `-Ztrait-solver=next`, and fails if the new
+    // trait solver emits different diagnostics than the old one.
+    pub compare_solvers: bool,
+    // Diagnostics whose message contains one of these patterns are expected to differ between
+    // the old and the new trait solver, and are ignored by `compare-solvers`.
+    pub compare_solvers_allow: Vec<String>,
 }
 
 mod directives {
     pub const ERROR_PATTERN: &'static str = "error-pattern";
     pub const REGEX_ERROR_PATTERN: &'static str = "regex-error-pattern";
     pub const COMPILE_FLAGS: &'static str = "compile-flags";
+    pub const AUX_COMPILE_FLAGS: &'static str = "aux-compile-flags";
     pub const RUN_FLAGS: &'static str = "run-flags";
     pub const SHOULD_ICE: &'static str = "should-ice";
     pub const BUILD_AUX_DOCS: &'static str = "build-aux-docs";
@@ -217,6 +230,9 @@ mod directives {
     pub const MIR_UNIT_TEST: &'static str = "unit-test";
     pub const REMAP_SRC_BASE: &'static str = "remap-src-base";
     pub const COMPARE_OUTPUT_LINES_BY_SUBSET: &'static str = "compare-output-lines-by-subset";
+    pub const NORMALIZE_USE: &'static str = "normalize-use";
+    pub const COMPARE_SOLVERS: &'static str = "compare-solvers";
+    pub const COMPARE_SOLVERS_ALLOW: &'static str = "compare-solvers-allow";
     // This isn't a real directive, just one that is probably mistyped often
     pub const INCORRECT_COMPILER_FLAGS: &'static str = "compiler-flags";
 }
@@ -227,6 +243,7 @@ pub fn new() -> Self {
             error_patterns: vec![],
             regex_error_patterns: vec![],
             compile_flags: vec![],
+            aux_compile_flags: vec![],
             run_flags: None,
             pp_exact: None,
             aux_builds: vec![],
@@ -252,6 +269,7 @@ pub fn new() -> Self {
             incremental: false,
             known_bug: false,
             pass_mode: None,
+            check_run_pass_in_const: false,
             fail_mode: None,
             ignore_pass: false,
             check_test_line_numbers_match: false,
@@ -266,6 +284,8 @@ pub fn new() -> Self {
             stderr_per_bitwidth: false,
             mir_unit_test: None,
             remap_src_base: false,
+            compare_solvers: false,
+            compare_solvers_allow: vec![],
         }
     }
 
@@ -276,6 +296,7 @@ pub fn from_aux_file(&self, testfile: &Path, cfg: Option<&str>, config: &Config)
         props.incremental_dir = self.incremental_dir.clone();
         props.ignore_pass = true;
         props.load_from(testfile, cfg, config);
+        props.compile_flags.extend(self.aux_compile_flags.iter().cloned());
 
         props
     }
@@ -332,6 +353,9 @@ fn load_from(&mut self, testfile: &Path, cfg: Option<&str>, config: &Config) {
                 if let Some(flags) = config.parse_name_value_directive(ln, COMPILE_FLAGS) {
                     self.compile_flags.extend(flags.split_whitespace().map(|s| s.to_owned()));
                 }
+                if let Some(flags) = config.parse_name_value_directive(ln, AUX_COMPILE_FLAGS) {
+                    self.aux_compile_flags.extend(flags.split_whitespace().map(|s| s.to_owned()));
+                }
                 if config.parse_name_value_directive(ln, INCORRECT_COMPILER_FLAGS).is_some() {
                     panic!("`compiler-flags` directive should be spelled `compile-flags`");
                 }
@@ -423,6 +447,11 @@ fn load_from(&mut self, testfile: &Path, cfg: Option<&str>, config: &Config) {
                 if let Some(rule) = config.parse_custom_normalization(ln, "normalize-stderr") {
                     self.normalize_stderr.push(rule);
                 }
+                if let Some(name) = config.parse_name_value_directive(ln, NORMALIZE_USE) {
+                    let rules = config.named_normalizations(name.trim());
+                    self.normalize_stdout.extend(rules.iter().cloned());
+                    self.normalize_stderr.extend(rules);
+                }
 
                 if let Some(code) = config
                     .parse_name_value_directive(ln, FAILURE_STATUS)
@@ -488,6 +517,13 @@ fn load_from(&mut self, testfile: &Path, cfg: Option<&str>, config: &Config) {
                     COMPARE_OUTPUT_LINES_BY_SUBSET,
                     &mut self.compare_output_lines_by_subset,
                 );
+                config.set_name_directive(ln, COMPARE_SOLVERS, &mut self.compare_solvers);
+                config.push_name_value_directive(
+                    ln,
+                    COMPARE_SOLVERS_ALLOW,
+                    &mut self.compare_solvers_allow,
+                    |r| r.trim().to_string(),
+                );
             });
         }
 
@@ -510,6 +546,10 @@ fn load_from(&mut self, testfile: &Path, cfg: Option<&str>, config: &Config) {
             }
         }
 
+        if self.compare_solvers && config.mode != Mode::Ui {
+            panic!("`compare-solvers` header is only supported in UI tests");
+        }
+
         if let (Some(edition), false) = (&config.edition, has_edition) {
             self.compile_flags.push(format!("--edition={}", edition));
         }
@@ -566,6 +606,12 @@ fn update_pass_mode(&mut self, ln: &str, revision: Option<&str>, config: &Config
                 panic!("`run-pass` header is only supported in UI tests")
             }
             Some(PassMode::Run)
+        } else if config.parse_name_directive(ln, "check-run-pass-in-const") {
+            if config.mode != Mode::Ui {
+                panic!("`check-run-pass-in-const` header is only supported in UI tests")
+            }
+            self.check_run_pass_in_const = true;
+            Some(PassMode::Run)
         } else {
             None
         };
@@ -699,6 +745,19 @@ fn parse_custom_normalization(&self, mut line: &str, prefix: &str) -> Option<(St
         }
     }
 
+    /// Looks up the rules of a `normalize-use: NAME` directive in the `normalizations.txt` file
+    /// next to the test suites.
+    fn named_normalizations(&self, name: &str) -> Vec<(String, String)> {
+        let path = self.src_base.parent().unwrap().join(NAMED_NORMALIZATIONS_FILE);
+        let contents = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+        match parse_named_normalizations(&contents, name) {
+            Some(rules) if !rules.is_empty() => rules,
+            Some(_) => panic!("unknown normalization `{name}`, see {}", path.display()),
+            None => panic!("malformed normalization `{name}` in {}", path.display()),
+        }
+    }
+
     fn parse_name_directive(&self, line: &str, directive: &str) -> bool {
         // Ensure the directive is a whole word. Do not match "ignore-x86" when
         // the line says "ignore-x86_64".
@@ -799,6 +858,28 @@ fn expand_variables(mut value: String, config: &Config) -> String {
     value
 }
 
+/// The file defining the named normalizations used by `normalize-use` directives, relative to
+/// the root of the test suites.
+const NAMED_NORMALIZATIONS_FILE: &str = "normalizations.txt";
+
+/// Collects the rules named `name` from the contents of [`NAMED_NORMALIZATIONS_FILE`], in order.
+///
+/// Each rule is a line of the form `NAME: "from" -> "to"`, using the same syntax as the
+/// `normalize-stderr` directives. Empty lines and lines starting with `#` are ignored. Returns
+/// `None` if one of the rules is malformed.
+fn parse_named_normalizations(contents: &str, name: &str) -> Option<Vec<(String, String)>> {
+    contents
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+        .map(|mut rule| {
+            let from = parse_normalization_string(&mut rule)?;
+            let to = parse_normalization_string(&mut rule)?;
+            Some((from, to))
+        })
+        .collect()
+}
+
 /// Finds the next quoted string `"..."` in `line`, and extract the content from it. Move the `line`
 /// variable after the end of the quoted string.
 ///
@@ -2,7 +2,9 @@
 use std::path::Path;
 
 use crate::common::{Config, Debugger};
-use crate::header::{parse_normalization_string, EarlyProps, HeadersCache};
+use crate::header::{
+    parse_named_normalizations, parse_normalization_string, EarlyProps, HeadersCache,
+};
 
 fn make_test_description<R: Read>(
     config: &Config,
@@ -53,6 +55,26 @@ fn test_parse_normalization_string() {
     assert_eq!(s, r#"normalize-stderr-16bit: something (16 bits) -> something ($WORD bits)."#);
 }
 
+#[test]
+fn test_parse_named_normalizations() {
+    let contents = r#"
+# Comments are ignored.
+ALLOC_ID: "alloc[0-9]+" -> "ALLOC_ID"
+ALLOC: "alloc" -> "ALLOC"
+ALLOC_ID: "ALLOC_ID\+0x[0-9a-f]+" -> "ALLOC_ID+OFFSET"
+"#;
+    assert_eq!(
+        parse_named_normalizations(contents, "ALLOC_ID"),
+        Some(vec![
+            ("alloc[0-9]+".to_owned(), "ALLOC_ID".to_owned()),
+            ("ALLOC_ID\\+0x[0-9a-f]+".to_owned(), "ALLOC_ID+OFFSET".to_owned()),
+        ])
+    );
+    assert_eq!(parse_named_normalizations(contents, "ALLOC").map(|rules| rules.len()), Some(1));
+    assert_eq!(parse_named_normalizations(contents, "UNKNOWN"), Some(vec![]));
+    assert_eq!(parse_named_normalizations("MALFORMED: \"from\"", "MALFORMED"), None);
+}
+
 fn config() -> Config {
     let args = &[
         "compiletest",
@@ -139,6 +139,40 @@ pub fn extract_rendered(output: &str) -> String {
         .collect()
 }
 
+/// Like `extract_rendered`, but leaves out the diagnostics whose message contains one of
+/// `patterns`, and the summaries counting the errors and warnings, which change along with them.
+pub fn extract_rendered_except(output: &str, patterns: &[String]) -> String {
+    let kept: String = output
+        .lines()
+        .filter(|line| {
+            !line.starts_with('{')
+                || serde_json::from_str::<Diagnostic>(line)
+                    .map_or(true, |diagnostic| !is_excepted(&diagnostic.message, patterns))
+        })
+        .map(|line| format!("{}\n", line))
+        .collect();
+    extract_rendered(&kept)
+}
+
+/// Like `extract_rendered_except`, for output that the compiler already rendered in a
+/// human-readable format, in which diagnostics are separated by empty lines.
+pub fn filter_rendered_except(output: &str, patterns: &[String]) -> String {
+    output
+        .split_inclusive("\n\n")
+        .filter(|diagnostic| {
+            let message = diagnostic.lines().next().unwrap_or_default();
+            !is_excepted(message, patterns)
+        })
+        .collect()
+}
+
+fn is_excepted(message: &str, patterns: &[String]) -> bool {
+    let is_summary = message.contains("aborting due to")
+        || message.ends_with("warning emitted")
+        || message.ends_with("warnings emitted");
+    is_summary || patterns.iter().any(|pattern| message.contains(pattern.as_str()))
+}
+
 pub fn parse_output(file_name: &str, output: &str, proc_res: &ProcRes) -> Vec<Error> {
     output.lines().flat_map(|line| parse_line(file_name, line, output, proc_res)).collect()
 }
@@ -3487,6 +3487,15 @@ fn run_ui_test(&self) {
             }
         }
 
+        if self.props.check_run_pass_in_const {
+            self.check_const_harness();
+        }
+
+        // Comparing against the new solver is pointless if this run already used it.
+        if self.props.compare_solvers && self.config.compare_mode.is_none() {
+            self.compare_solvers(&proc_res, emit_metadata, explicit);
+        }
+
         debug!(
             "run_ui_test: explicit={:?} config.compare_mode={:?} expected_errors={:?} \
                proc_res.status={:?} props.error_patterns={:?}",
@@ -3539,6 +3548,67 @@ fn run_ui_test(&self) {
         }
     }
 
+    /// Checks the test again with `--cfg run_pass_in_const`, which enables its
+    /// `const _: () = ...` harness, so that the same assertions that were just
+    /// run are also evaluated at compile time.
+    fn check_const_harness(&self) {
+        let mut rustc = self.make_compile_args(
+            &self.testpaths.file,
+            TargetLocation::ThisDirectory(self.output_base_dir()),
+            Emit::Metadata,
+            AllowUnused::Yes,
+            LinkToAux::Yes,
+            Vec::new(),
+        );
+        rustc.arg("--cfg=run_pass_in_const");
+        let res = self.compose_and_run_compiler(rustc, None);
+        if !res.status.success() {
+            self.fatal_proc_rec("failed to evaluate the const harness", &res);
+        }
+    }
+
+    /// Checks the test again with `-Ztrait-solver=next`, and fails if the new trait solver
+    /// doesn't emit the same diagnostics as the old one did in `old_res`. Diagnostics matching
+    /// one of the `compare-solvers-allow` patterns are left out of the comparison, and so are the
+    /// error and warning counts.
+    fn compare_solvers(&self, old_res: &ProcRes, emit: Emit, explicit_format: bool) {
+        let allow_unused = if self.props.local_pass_mode() == Some(PassMode::Run) {
+            AllowUnused::No
+        } else {
+            AllowUnused::Yes
+        };
+        let mut rustc = self.make_compile_args(
+            &self.testpaths.file,
+            TargetLocation::ThisDirectory(self.output_base_dir()),
+            emit,
+            allow_unused,
+            LinkToAux::Yes,
+            Vec::new(),
+        );
+        rustc.arg("-Ztrait-solver=next");
+        let new_res = self.compose_and_run_compiler(rustc, None);
+
+        let diagnostics = |res: &ProcRes| {
+            let allow = &self.props.compare_solvers_allow;
+            let stderr = if explicit_format {
+                json::filter_rendered_except(&res.stderr, allow)
+            } else {
+                json::extract_rendered_except(&res.stderr, allow)
+            };
+            self.normalize_output(&stderr, &self.props.normalize_stderr)
+        };
+        let old_diagnostics = diagnostics(old_res);
+        let new_diagnostics = diagnostics(&new_res);
+        if old_diagnostics != new_diagnostics {
+            println!("diff of the diagnostics of the old and the new trait solver:\n");
+            print!("{}", write_diff(&old_diagnostics, &new_diagnostics, 3));
+            self.fatal_proc_rec("the new trait solver emitted different diagnostics", &new_res);
+        }
+        if old_res.status.success() != new_res.status.success() {
+            self.fatal_proc_rec("the new trait solver changed the outcome of the test", &new_res);
+        }
+    }
+
     fn run_mir_opt_test(&self) {
         let pm = self.pass_mode();
         let should_run = self.should_run(pm);
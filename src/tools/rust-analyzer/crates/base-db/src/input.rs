@@ -315,6 +315,20 @@ pub struct CrateData {
     // FIXME: These things should not be per crate! These are more per workspace crate graph level things
     pub target_layout: TargetLayoutLoadResult,
     pub channel: Option<ReleaseChannel>,
+    /// Files that the crate's package deliberately leaves out of all of its targets, for example
+    /// `src/bin/*.rs` files of a Cargo package with `autobins = false`.
+    pub excluded_files: Vec<FileId>,
+    /// Directories outside of the crate's module tree whose files belong to a module of the crate.
+    pub module_roots: Vec<ModuleRoot>,
+}
+
+/// A directory whose files are submodules of a module of a crate, even though the directory is not
+/// where that module's submodules would usually live, e.g. a directory of generated code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleRoot {
+    pub dir: VfsPath,
+    /// The names of the modules from the crate root to the parent module of the files in `dir`.
+    pub module_path: Vec<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -383,6 +397,8 @@ pub fn add_crate_root(
             target_layout,
             is_proc_macro,
             channel,
+            excluded_files: Vec::new(),
+            module_roots: Vec::new(),
         };
         self.arena.alloc(data)
     }
@@ -535,6 +551,16 @@ pub fn crate_id_for_crate_root(&self, file_id: FileId) -> Option<CrateId> {
         Some(crate_id)
     }
 
+    /// Records the files that the package of `krate` deliberately leaves out of its targets.
+    pub fn set_excluded_files(&mut self, krate: CrateId, files: Vec<FileId>) {
+        self.arena[krate].excluded_files = files;
+    }
+
+    /// Records the directories outside of the module tree of `krate` that contain its modules.
+    pub fn set_module_roots(&mut self, krate: CrateId, roots: Vec<ModuleRoot>) {
+        self.arena[krate].module_roots = roots;
+    }
+
     pub fn sort_deps(&mut self) {
         self.arena
             .iter_mut()
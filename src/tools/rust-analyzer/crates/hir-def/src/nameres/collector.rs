@@ -1860,6 +1860,22 @@ fn collect_module(&mut self, module_id: FileItemTreeId<Mod>, attrs: &Attrs) {
                 match self.mod_dir.resolve_declaration(db, self.file_id(), &module.name, path_attr)
                 {
                     Ok((file_id, is_mod_rs, mod_dir)) => {
+                        if let Some(chain) = self.module_file_cycle(file_id) {
+                            // Collecting the file again would recurse until `MOD_DEPTH_LIMIT`.
+                            cov_mark::hit!(circular_mods);
+                            self.push_child_module(
+                                module.name.clone(),
+                                ast_id,
+                                None,
+                                &self.item_tree[module.visibility],
+                                module_id,
+                            );
+                            self.def_collector
+                                .def_map
+                                .diagnostics
+                                .push(DefDiagnostic::cyclic_module(self.module_id, ast_id, chain));
+                            return;
+                        }
                         let item_tree = db.file_item_tree(file_id.into());
                         let krate = self.def_collector.def_map.krate;
                         let is_enabled = item_tree
@@ -1910,6 +1926,25 @@ fn collect_module(&mut self, module_id: FileItemTreeId<Mod>, attrs: &Attrs) {
         }
     }
 
+    /// If `file_id` is the file of the current module or one of its ancestors, returns the files
+    /// of the modules from that ancestor down to the current module, followed by `file_id` again.
+    fn module_file_cycle(&self, file_id: FileId) -> Option<Box<[FileId]>> {
+        let modules = &self.def_collector.def_map.modules;
+        let mut chain = vec![file_id];
+        let mut module = Some(self.module_id);
+        while let Some(id) = module {
+            if let Some(ancestor_file) = modules[id].origin.file_id() {
+                chain.push(ancestor_file);
+                if ancestor_file == file_id {
+                    chain.reverse();
+                    return Some(chain.into_boxed_slice());
+                }
+            }
+            module = modules[id].parent;
+        }
+        None
+    }
+
     fn push_child_module(
         &mut self,
         name: Name,
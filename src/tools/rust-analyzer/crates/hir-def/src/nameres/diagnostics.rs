@@ -1,6 +1,6 @@
 //! Diagnostics emitted during DefMap construction.
 
-use base_db::CrateId;
+use base_db::{CrateId, FileId};
 use cfg::{CfgExpr, CfgOptions};
 use hir_expand::{attrs::AttrId, MacroCallKind};
 use la_arena::Idx;
@@ -20,6 +20,8 @@
 pub enum DefDiagnosticKind {
     UnresolvedModule { ast: AstId<ast::Module>, candidates: Box<[String]> },
 
+    CyclicModule { ast: AstId<ast::Module>, chain: Box<[FileId]> },
+
     UnresolvedExternCrate { ast: AstId<ast::ExternCrate> },
 
     UnresolvedImport { id: ItemTreeId<item_tree::Import>, index: Idx<ast::UseTree> },
@@ -61,6 +63,19 @@ pub(super) fn unresolved_module(
         }
     }
 
+    /// A `mod` declaration that resolves to the file of one of its ancestor modules. `chain` lists
+    /// the files from that ancestor down to the declaring file, followed by the ancestor again.
+    pub(super) fn cyclic_module(
+        container: LocalModuleId,
+        declaration: AstId<ast::Module>,
+        chain: Box<[FileId]>,
+    ) -> Self {
+        Self {
+            in_module: container,
+            kind: DefDiagnosticKind::CyclicModule { ast: declaration, chain },
+        }
+    }
+
     pub(super) fn unresolved_extern_crate(
         container: LocalModuleId,
         declaration: AstId<ast::ExternCrate>,
@@ -5,8 +5,10 @@
 use std::fmt;
 
 use either::Either;
-use hir_def::lang_item::LangItem;
-use hir_def::{resolver::HasResolver, AdtId, AssocItemId, DefWithBodyId, HasModule};
+use hir_def::hir::Statement;
+use hir_def::lang_item::{lang_attr, LangItem};
+use hir_def::resolver::{resolver_for_expr, HasResolver, ValueNs};
+use hir_def::{AdtId, AssocItemId, ConstId, DefWithBodyId, EnumVariantId, HasModule, StaticId};
 use hir_def::{ItemContainerId, Lookup};
 use hir_expand::name;
 use itertools::Itertools;
@@ -22,7 +24,7 @@
         usefulness::{compute_match_usefulness, MatchCheckCtx},
     },
     display::HirDisplay,
-    InferenceResult, Ty, TyExt,
+    InferenceResult, Interner, Substitution, Ty, TyExt, TyKind,
 };
 
 pub(crate) use hir_def::{
@@ -44,6 +46,11 @@ pub enum BodyValidationDiagnostic {
         match_expr: ExprId,
         uncovered_patterns: String,
     },
+    ConstPatternRefersToStatic {
+        pat: PatId,
+        const_: ConstId,
+        static_: StaticId,
+    },
 }
 
 impl BodyValidationDiagnostic {
@@ -84,8 +91,21 @@ fn validate_body(&mut self, db: &dyn HirDatabase) {
 
             match expr {
                 Expr::Match { expr, arms } => {
+                    for arm in arms.iter() {
+                        self.validate_const_patterns(id, arm.pat, db);
+                    }
                     self.validate_match(id, *expr, arms, db);
                 }
+                Expr::Let { pat, .. } => self.validate_const_patterns(id, *pat, db),
+                Expr::Block { statements, .. }
+                | Expr::Unsafe { statements, .. }
+                | Expr::Async { statements, .. } => {
+                    for statement in statements.iter() {
+                        if let Statement::Let { pat, .. } = statement {
+                            self.validate_const_patterns(id, *pat, db);
+                        }
+                    }
+                }
                 Expr::Call { .. } | Expr::MethodCall { .. } => {
                     self.validate_call(db, id, expr, &mut filter_map_next_checker);
                 }
@@ -145,6 +165,37 @@ fn validate_call(
         };
     }
 
+    /// Flags the constants in `pat` whose value refers to a static that rustc cannot match on.
+    /// `scope` is the expression containing the pattern, in whose scope paths are resolved.
+    fn validate_const_patterns(&mut self, scope: ExprId, pat: PatId, db: &dyn HirDatabase) {
+        let body = db.body(self.owner);
+        let resolver = resolver_for_expr(db.upcast(), self.owner, scope);
+        let mut pats = vec![pat];
+        while let Some(pat) = pats.pop() {
+            body[pat].walk_child_pats(|it| pats.push(it));
+
+            let const_ = match self.infer.assoc_resolutions_for_pat(pat) {
+                Some((AssocItemId::ConstId(it), _)) => it,
+                _ => match &body[pat] {
+                    Pat::Path(path) => {
+                        match resolver.resolve_path_in_value_ns_fully(db.upcast(), path) {
+                            Some(ValueNs::ConstId(it)) => it,
+                            _ => continue,
+                        }
+                    }
+                    _ => continue,
+                },
+            };
+            if let Some(static_) = const_refers_to_static(db, const_) {
+                self.diagnostics.push(BodyValidationDiagnostic::ConstPatternRefersToStatic {
+                    pat,
+                    const_,
+                    static_,
+                });
+            }
+        }
+    }
+
     fn validate_match(
         &mut self,
         match_expr: ExprId,
@@ -237,6 +288,96 @@ fn lower_pattern<'p>(
     }
 }
 
+/// Returns a static that the body of `const_` refers to, either directly or through the body of
+/// another constant, and that prevents it from being used as a pattern. Statics that are only read
+/// by value don't end up in the value of the constant, so only statics that are borrowed count.
+/// With `const_refs_to_static`, constants may refer to immutable statics without interior
+/// mutability, so only `static mut`s and statics with interior mutability count.
+fn const_refers_to_static(db: &dyn HirDatabase, const_: ConstId) -> Option<StaticId> {
+    let mut visited = FxHashSet::default();
+    let mut worklist = vec![const_];
+    while let Some(const_) = worklist.pop() {
+        if !visited.insert(const_) {
+            continue;
+        }
+        let owner = DefWithBodyId::ConstId(const_);
+        let body = db.body(owner);
+
+        // The base expressions of all borrowed places, e.g. `S` in `&S.0`.
+        let mut borrowed = FxHashSet::default();
+        for (_, expr) in body.exprs.iter() {
+            let &Expr::Ref { mut expr, .. } = expr else { continue };
+            while let Expr::Field { expr: base, .. } | Expr::Index { base, .. } = body[expr] {
+                expr = base;
+            }
+            borrowed.insert(expr);
+        }
+
+        for (id, expr) in body.exprs.iter() {
+            let Expr::Path(path) = expr else { continue };
+            match resolver_for_expr(db.upcast(), owner, id)
+                .resolve_path_in_value_ns_fully(db.upcast(), path)
+            {
+                Some(ValueNs::StaticId(it))
+                    if borrowed.contains(&id) && static_may_be_mutated(db, it) =>
+                {
+                    return Some(it)
+                }
+                Some(ValueNs::ConstId(it)) => worklist.push(it),
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+/// Whether `static_` is a `static mut`, or has a type with interior mutability.
+fn static_may_be_mutated(db: &dyn HirDatabase, static_: StaticId) -> bool {
+    if db.static_data(static_).mutable {
+        return true;
+    }
+    let ty = db.value_ty(static_.into()).substitute(Interner, &Substitution::empty(Interner));
+    contains_unsafe_cell(db, &ty, &mut FxHashSet::default())
+}
+
+/// Whether `ty` contains an `UnsafeCell` that isn't behind a pointer, i.e. whether `ty` isn't
+/// `Freeze`. Types that are visited a second time are assumed not to, as they are already being
+/// checked further up.
+fn contains_unsafe_cell(db: &dyn HirDatabase, ty: &Ty, visited: &mut FxHashSet<Ty>) -> bool {
+    if !visited.insert(ty.clone()) {
+        return false;
+    }
+    match ty.kind(Interner) {
+        TyKind::Adt(chalk_ir::AdtId(adt), subst) => {
+            if lang_attr(db.upcast(), *adt) == Some(LangItem::UnsafeCell) {
+                return true;
+            }
+            let variants: Vec<VariantId> = match *adt {
+                AdtId::StructId(it) => vec![it.into()],
+                AdtId::UnionId(it) => vec![it.into()],
+                AdtId::EnumId(it) => db
+                    .enum_data(it)
+                    .variants
+                    .iter()
+                    .map(|(local_id, _)| EnumVariantId { parent: it, local_id }.into())
+                    .collect(),
+            };
+            variants.into_iter().any(|variant| {
+                db.field_types(variant).iter().any(|(_, field_ty)| {
+                    let field_ty = field_ty.clone().substitute(Interner, subst);
+                    contains_unsafe_cell(db, &field_ty, visited)
+                })
+            })
+        }
+        TyKind::Tuple(_, subst) => subst
+            .iter(Interner)
+            .filter_map(|it| it.ty(Interner))
+            .any(|ty| contains_unsafe_cell(db, ty, visited)),
+        TyKind::Array(ty, _) | TyKind::Slice(ty) => contains_unsafe_cell(db, ty, visited),
+        _ => false,
+    }
+}
+
 struct FilterMapNextChecker {
     filter_map_function_id: Option<hir_def::FunctionId>,
     next_function_id: Option<hir_def::FunctionId>,
@@ -79,7 +79,9 @@ macro_rules! eprintln {
     to_placeholder_idx,
 };
 pub use traits::TraitEnvironment;
-pub use utils::{all_super_traits, is_fn_unsafe_to_call};
+pub use utils::{
+    all_super_traits, elaborate_trait_refs, is_fn_unsafe_to_call, trait_requires_static,
+};
 
 pub use chalk_ir::{
     cast::Cast, AdtId, BoundVar, DebruijnIndex, Mutability, Safety, Scalar, TyVariableKind,
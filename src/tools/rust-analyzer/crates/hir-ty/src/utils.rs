@@ -18,11 +18,12 @@
     },
     lang_item::LangItem,
     resolver::{HasResolver, TypeNs},
-    type_ref::{TraitBoundModifier, TypeRef},
+    type_ref::{TraitBoundModifier, TypeBound, TypeRef},
     ConstParamId, EnumId, EnumVariantId, FunctionId, GenericDefId, ItemContainerId,
-    LocalEnumVariantId, Lookup, TraitId, TypeAliasId, TypeOrConstParamId, TypeParamId,
+    LocalEnumVariantId, LocalTypeOrConstParamId, Lookup, TraitId, TypeAliasId, TypeOrConstParamId,
+    TypeParamId,
 };
-use hir_expand::name::Name;
+use hir_expand::name::{name, Name};
 use intern::Interned;
 use rustc_hash::FxHashSet;
 use smallvec::{smallvec, SmallVec};
@@ -82,6 +83,34 @@ pub(super) fn all_super_trait_refs<T>(
     SuperTraits { db, seen, stack: vec![trait_ref] }.find_map(cb)
 }
 
+/// Returns the trait refs of all the supertraits of `trait_refs`, except for the ones in
+/// `trait_refs` themselves. For example, for `[T: Ord]` this returns `T: PartialOrd`,
+/// `T: Eq` and `T: PartialEq`. A trait is returned once for each of its distinct substitutions,
+/// so for `trait Tr: Sup<u8> + Sup<u16>` both `Sup`s are returned.
+pub fn elaborate_trait_refs(db: &dyn HirDatabase, trait_refs: &[TraitRef]) -> Vec<TraitRef> {
+    // Supertraits can't be cyclic, but in erroneous code like `trait A<T>: A<Vec<T>>` they can
+    // have ever larger substitutions, so we stop at some point.
+    const MAX_SUPER_TRAIT_REFS: usize = 256;
+
+    let mut seen: FxHashSet<TraitRef> = trait_refs.iter().cloned().collect();
+    let mut result = trait_refs.to_vec();
+    let mut i = 0;
+    while i < result.len() && result.len() < trait_refs.len() + MAX_SUPER_TRAIT_REFS {
+        let trait_ref = result[i].clone();
+        let self_ty = trait_ref.self_type_parameter(Interner);
+        direct_super_trait_refs(db, &trait_ref, |trait_ref| {
+            // The predicates of a trait also include the implicit `Sized` bounds of its other
+            // parameters, which are not about `Self`.
+            if trait_ref.self_type_parameter(Interner) == self_ty && seen.insert(trait_ref.clone())
+            {
+                result.push(trait_ref);
+            }
+        });
+        i += 1;
+    }
+    result.split_off(trait_refs.len())
+}
+
 struct SuperTraits<'a> {
     db: &'a dyn HirDatabase,
     stack: Vec<TraitRef>,
@@ -121,16 +150,7 @@ fn direct_super_traits(db: &dyn DefDatabase, trait_: TraitId, cb: impl FnMut(Tra
         .filter_map(|pred| match pred {
             WherePredicate::ForLifetime { target, bound, .. }
             | WherePredicate::TypeBound { target, bound } => {
-                let is_trait = match target {
-                    WherePredicateTypeTarget::TypeRef(type_ref) => match &**type_ref {
-                        TypeRef::Path(p) => p.is_self_type(),
-                        _ => false,
-                    },
-                    WherePredicateTypeTarget::TypeOrConstParam(local_id) => {
-                        Some(*local_id) == trait_self
-                    }
-                };
-                match is_trait {
+                match is_trait_self(target, trait_self) {
                     true => bound.as_path(),
                     false => None,
                 }
@@ -145,6 +165,33 @@ fn direct_super_traits(db: &dyn DefDatabase, trait_: TraitId, cb: impl FnMut(Tra
         .for_each(cb);
 }
 
+/// Whether `trait_` requires its `Self` type to outlive `'static`, as in `trait Foo: 'static`.
+pub fn trait_requires_static(db: &dyn DefDatabase, trait_: TraitId) -> bool {
+    let generic_params = db.generic_params(trait_.into());
+    let trait_self = generic_params.find_trait_self_param();
+    generic_params.where_predicates.iter().any(|pred| match pred {
+        WherePredicate::ForLifetime { target, bound, .. }
+        | WherePredicate::TypeBound { target, bound } => {
+            is_trait_self(target, trait_self)
+                && matches!(&**bound, TypeBound::Lifetime(it) if it.name == name!('static))
+        }
+        WherePredicate::Lifetime { .. } => false,
+    })
+}
+
+fn is_trait_self(
+    target: &WherePredicateTypeTarget,
+    trait_self: Option<LocalTypeOrConstParamId>,
+) -> bool {
+    match target {
+        WherePredicateTypeTarget::TypeRef(type_ref) => match &**type_ref {
+            TypeRef::Path(p) => p.is_self_type(),
+            _ => false,
+        },
+        WherePredicateTypeTarget::TypeOrConstParam(local_id) => Some(*local_id) == trait_self,
+    }
+}
+
 fn direct_super_trait_refs(db: &dyn HirDatabase, trait_ref: &TraitRef, cb: impl FnMut(TraitRef)) {
     let generic_params = db.generic_params(trait_ref.hir_trait_id().into());
     let trait_self = match generic_params.find_trait_self_param() {
@@ -5,14 +5,14 @@
 //! be expressed in terms of hir types themselves.
 pub use hir_ty::diagnostics::{IncoherentImpl, IncorrectCase};
 
-use base_db::CrateId;
+use base_db::{CrateId, FileId};
 use cfg::{CfgExpr, CfgOptions};
 use either::Either;
 use hir_def::path::ModPath;
 use hir_expand::{name::Name, HirFileId, InFile};
 use syntax::{ast, AstPtr, SyntaxError, SyntaxNodePtr, TextRange};
 
-use crate::{AssocItem, Field, Local, MacroKind, Type};
+use crate::{AssocItem, Const, Field, Local, MacroKind, Static, Type};
 
 macro_rules! diagnostics {
     ($($diag:ident,)*) => {
@@ -33,6 +33,8 @@ fn from(d: $diag) -> AnyDiagnostic {
 
 diagnostics![
     BreakOutsideOfLoop,
+    ConstPatternRefersToStatic,
+    CyclicModule,
     ExpectedFunction,
     InactiveCode,
     IncorrectCase,
@@ -86,6 +88,13 @@ pub struct UnresolvedModule {
     pub candidates: Box<[String]>,
 }
 
+#[derive(Debug)]
+pub struct CyclicModule {
+    pub decl: InFile<AstPtr<ast::Module>>,
+    /// The module files that form the cycle, starting and ending with the same file.
+    pub chain: Box<[FileId]>,
+}
+
 #[derive(Debug)]
 pub struct UnresolvedExternCrate {
     pub decl: InFile<AstPtr<ast::ExternCrate>>,
@@ -237,6 +246,13 @@ pub struct MismatchedArgCount {
     pub found: usize,
 }
 
+#[derive(Debug)]
+pub struct ConstPatternRefersToStatic {
+    pub pat: InFile<AstPtr<ast::Pat>>,
+    pub const_: Const,
+    pub static_: Static,
+}
+
 #[derive(Debug)]
 pub struct MissingMatchArms {
     pub scrutinee_expr: InFile<AstPtr<ast::Expr>>,
@@ -88,14 +88,14 @@
 pub use crate::{
     attrs::{HasAttrs, Namespace},
     diagnostics::{
-        AnyDiagnostic, BreakOutsideOfLoop, ExpectedFunction, InactiveCode, IncoherentImpl,
-        IncorrectCase, InvalidDeriveTarget, MacroDefError, MacroError, MacroExpansionParseError,
-        MalformedDerive, MismatchedArgCount, MissingFields, MissingMatchArms, MissingUnsafe,
-        MovedOutOfRef, NeedMut, NoSuchField, PrivateAssocItem, PrivateField,
-        ReplaceFilterMapNextWithFindMap, TypeMismatch, TypedHole, UndeclaredLabel,
-        UnimplementedBuiltinMacro, UnreachableLabel, UnresolvedExternCrate, UnresolvedField,
-        UnresolvedImport, UnresolvedMacroCall, UnresolvedMethodCall, UnresolvedModule,
-        UnresolvedProcMacro, UnusedMut,
+        AnyDiagnostic, BreakOutsideOfLoop, ConstPatternRefersToStatic, CyclicModule,
+        ExpectedFunction, InactiveCode, IncoherentImpl, IncorrectCase, InvalidDeriveTarget,
+        MacroDefError, MacroError, MacroExpansionParseError, MalformedDerive, MismatchedArgCount,
+        MissingFields, MissingMatchArms, MissingUnsafe, MovedOutOfRef, NeedMut, NoSuchField,
+        PrivateAssocItem, PrivateField, ReplaceFilterMapNextWithFindMap, TypeMismatch, TypedHole,
+        UndeclaredLabel, UnimplementedBuiltinMacro, UnreachableLabel, UnresolvedExternCrate,
+        UnresolvedField, UnresolvedImport, UnresolvedMacroCall, UnresolvedMethodCall,
+        UnresolvedModule, UnresolvedProcMacro, UnusedMut,
     },
     has_source::HasSource,
     semantics::{PathResolution, Semantics, SemanticsScope, TypeInfo, VisibleTraits},
@@ -730,6 +730,16 @@ fn emit_def_diagnostic_(
                 .into(),
             )
         }
+        DefDiagnosticKind::CyclicModule { ast: declaration, chain } => {
+            let decl = declaration.to_node(db.upcast());
+            acc.push(
+                CyclicModule {
+                    decl: InFile::new(declaration.file_id, AstPtr::new(&decl)),
+                    chain: chain.clone(),
+                }
+                .into(),
+            )
+        }
         DefDiagnosticKind::UnresolvedExternCrate { ast } => {
             let item = ast.to_node(db.upcast());
             acc.push(
@@ -1765,6 +1775,20 @@ pub fn diagnostics(self, db: &dyn HirDatabase, acc: &mut Vec<AnyDiagnostic>) {
                         );
                     }
                 }
+                BodyValidationDiagnostic::ConstPatternRefersToStatic { pat, const_, static_ } => {
+                    if let Ok(source_ptr) = source_map.pat_syntax(pat) {
+                        if let Some(ptr) = source_ptr.value.left() {
+                            acc.push(
+                                ConstPatternRefersToStatic {
+                                    pat: InFile::new(source_ptr.file_id, ptr),
+                                    const_: const_.into(),
+                                    static_: static_.into(),
+                                }
+                                .into(),
+                            );
+                        }
+                    }
+                }
                 BodyValidationDiagnostic::MissingMatchArms { match_expr, uncovered_patterns } => {
                     match source_map.expr_syntax(match_expr) {
                         Ok(source_ptr) => {
@@ -2177,6 +2201,11 @@ pub fn items(self, db: &dyn HirDatabase) -> Vec<AssocItem> {
         db.trait_data(self.id).items.iter().map(|(_name, it)| (*it).into()).collect()
     }
 
+    /// Returns this trait and all of its transitive supertraits.
+    pub fn all_supertraits(self, db: &dyn HirDatabase) -> Vec<Trait> {
+        all_super_traits(db.upcast(), self.into()).into_iter().map(Trait::from).collect()
+    }
+
     pub fn items_with_supertraits(self, db: &dyn HirDatabase) -> Vec<AssocItem> {
         let traits = all_super_traits(db.upcast(), self.into());
         traits.iter().flat_map(|tr| Trait::from(*tr).items(db)).collect()
@@ -3009,6 +3038,42 @@ pub fn trait_bounds(self, db: &dyn HirDatabase) -> Vec<Trait> {
             .collect()
     }
 
+    /// Returns the bounds that the trait bounds on this type parameter imply, without the trait
+    /// bounds themselves: the supertraits of the bounds, and `'static` if any of these traits
+    /// requires it.
+    ///
+    /// FIXME: like [`TypeParam::trait_bounds`], this only looks at the bounds from the item
+    /// defining the type parameter. Lifetime bounds other than `'static` are not tracked either.
+    pub fn implied_bounds(self, db: &dyn HirDatabase) -> Vec<ImpliedBound> {
+        let parent = self.id.parent();
+        let subst = TyBuilder::placeholder_subst(db, parent);
+        let trait_refs: Vec<_> = db
+            .generic_predicates_for_param(parent, self.id.into(), None)
+            .iter()
+            .filter_map(|pred| match pred.clone().substitute(Interner, &subst).skip_binders() {
+                hir_ty::WhereClause::Implemented(trait_ref) => Some(trait_ref.clone()),
+                _ => None,
+            })
+            .collect();
+        let super_trait_refs = hir_ty::elaborate_trait_refs(db, &trait_refs);
+        let requires_static = trait_refs
+            .iter()
+            .chain(&super_trait_refs)
+            .any(|trait_ref| hir_ty::trait_requires_static(db.upcast(), trait_ref.hir_trait_id()));
+
+        let resolver = parent.resolver(db.upcast());
+        let mut bounds: Vec<_> = super_trait_refs
+            .into_iter()
+            .map(|trait_ref| {
+                ImpliedBound::Trait(TraitRef::new_with_resolver(db, &resolver, trait_ref))
+            })
+            .collect();
+        if requires_static {
+            bounds.push(ImpliedBound::Static);
+        }
+        bounds
+    }
+
     pub fn default(self, db: &dyn HirDatabase) -> Option<Type> {
         let params = db.generic_defaults(self.id.parent());
         let local_idx = hir_ty::param_idx(db, self.id.into())?;
@@ -3023,6 +3088,15 @@ pub fn default(self, db: &dyn HirDatabase) -> Option<Type> {
     }
 }
 
+/// A bound implied by the trait bounds on a type parameter, see [`TypeParam::implied_bounds`].
+#[derive(Clone, Debug)]
+pub enum ImpliedBound {
+    /// The type parameter implements a supertrait of one of its trait bounds.
+    Trait(TraitRef),
+    /// The type parameter outlives `'static`.
+    Static,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct LifetimeParam {
     pub(crate) id: LifetimeParamId,
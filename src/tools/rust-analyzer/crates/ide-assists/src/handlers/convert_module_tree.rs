@@ -0,0 +1,287 @@
+use hir::Module;
+use ide_db::{
+    assists::{AssistId, AssistKind},
+    base_db::{AnchoredPathBuf, FileId},
+    RootDatabase,
+};
+use syntax::{
+    ast::{self, HasAttrs},
+    AstNode,
+};
+
+use crate::assist_context::{AssistContext, Assists};
+
+// Assist: convert_module_tree_from_mod_rs
+//
+// Moves all `xxx/mod.rs` files of a module tree to `xxx.rs`.
+//
+// ```
+// //- /main.rs
+// mod $0a;
+// //- /a/mod.rs
+// mod b;
+// //- /a/b/mod.rs
+// fn t() {}
+// ```
+// ->
+// ```
+// mod a;
+// ```
+pub(crate) fn convert_module_tree_from_mod_rs(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    convert_module_tree(acc, ctx, true)
+}
+
+// Assist: convert_module_tree_to_mod_rs
+//
+// Moves all `xxx.rs` files of a module tree to `xxx/mod.rs`.
+//
+// ```
+// //- /main.rs
+// mod $0a;
+// //- /a.rs
+// mod b;
+// //- /a/b.rs
+// fn t() {}
+// ```
+// ->
+// ```
+// mod a;
+// ```
+pub(crate) fn convert_module_tree_to_mod_rs(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    convert_module_tree(acc, ctx, false)
+}
+
+fn convert_module_tree(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+    from_mod_rs: bool,
+) -> Option<()> {
+    let decl = ctx.find_node_at_offset::<ast::Module>()?;
+    if decl.item_list().is_some() {
+        return None;
+    }
+    let module = ctx.sema.to_def(&decl)?;
+
+    let mut moves = Vec::new();
+    if collect_moves(ctx.db(), module, from_mod_rs, &mut moves).is_none() {
+        cov_mark::hit!(module_tree_relative_path_attr);
+        return None;
+    }
+    if moves.is_empty() {
+        cov_mark::hit!(module_tree_already_converted);
+        return None;
+    }
+
+    let module_name = module.name(ctx.db())?.display(ctx.db()).to_string();
+    let (id, label) = if from_mod_rs {
+        (
+            "convert_module_tree_from_mod_rs",
+            format!("Convert `{module_name}` tree from mod.rs files"),
+        )
+    } else {
+        ("convert_module_tree_to_mod_rs", format!("Convert `{module_name}` tree to mod.rs files"))
+    };
+    acc.add(AssistId(id, AssistKind::Refactor), label, decl.syntax().text_range(), |builder| {
+        for (file_id, dst) in moves {
+            builder.move_file(file_id, dst);
+        }
+    })
+}
+
+/// Collects the file moves for `module` and all its descendants. Moving a module file between
+/// `xxx.rs` and `xxx/mod.rs` doesn't change where its children are looked up, so every file can
+/// be moved independently.
+///
+/// The exception are children with a relative `#[path]` attribute, which is resolved against the
+/// directory of the file declaring them. Returns `None` if a file that would be moved declares
+/// such a child.
+fn collect_moves(
+    db: &RootDatabase,
+    module: Module,
+    from_mod_rs: bool,
+    acc: &mut Vec<(FileId, AnchoredPathBuf)>,
+) -> Option<()> {
+    // Modules with a `#[path]` attribute don't follow either layout, leave their subtree alone.
+    if path_attr(db, module).is_some() {
+        return Some(());
+    }
+
+    if let (false, Some(name)) = (module.is_inline(db), module.name(db)) {
+        let file_id = module.definition_source(db).file_id.original_file(db);
+        let name = name.display(db);
+        let dst = match (from_mod_rs, module.is_mod_rs(db)) {
+            (true, true) => Some(format!("../{name}.rs")),
+            (false, false) => Some(format!("./{name}/mod.rs")),
+            _ => None,
+        };
+        if let Some(dst) = dst {
+            let has_relative_path_attr = module.children(db).any(|child| {
+                !child.is_inline(db)
+                    && path_attr(db, child).map_or(false, |it| !it.starts_with('/'))
+            });
+            if has_relative_path_attr {
+                return None;
+            }
+            acc.push((file_id, AnchoredPathBuf { anchor: file_id, path: dst }));
+        }
+    }
+
+    for child in module.children(db) {
+        collect_moves(db, child, from_mod_rs, acc)?;
+    }
+    Some(())
+}
+
+/// Returns the value of the `#[path]` attribute of the declaration of `module`, if it has one.
+fn path_attr(db: &RootDatabase, module: Module) -> Option<String> {
+    let decl = module.declaration_source(db)?;
+    let attr = decl.value.attrs().find(|attr| attr.simple_name().as_deref() == Some("path"))?;
+    let path = match attr.expr() {
+        Some(ast::Expr::Literal(literal)) => match literal.kind() {
+            ast::LiteralKind::String(string) => string.value().map(|it| it.into_owned()),
+            _ => None,
+        },
+        _ => None,
+    };
+    Some(path.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn from_mod_rs() {
+        check_assist(
+            convert_module_tree_from_mod_rs,
+            r#"
+//- /main.rs
+mod $0a;
+//- /a/mod.rs
+mod b;
+mod c;
+//- /a/b/mod.rs
+fn b() {}
+//- /a/c.rs
+fn c() {}
+"#,
+            r#"
+//- /a.rs
+mod b;
+mod c;
+//- /a/b.rs
+fn b() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn to_mod_rs() {
+        check_assist(
+            convert_module_tree_to_mod_rs,
+            r#"
+//- /main.rs
+mod $0a;
+//- /a.rs
+mod b {
+    mod c;
+}
+//- /a/b/c.rs
+fn c() {}
+"#,
+            r#"
+//- /a/mod.rs
+mod b {
+    mod c;
+}
+//- /a/b/c/mod.rs
+fn c() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn already_converted() {
+        cov_mark::check!(module_tree_already_converted);
+        check_assist_not_applicable(
+            convert_module_tree_to_mod_rs,
+            r#"
+//- /main.rs
+mod $0a;
+//- /a/mod.rs
+mod b;
+//- /a/b/mod.rs
+"#,
+        );
+    }
+
+    #[test]
+    fn skips_path_attr() {
+        check_assist_not_applicable(
+            convert_module_tree_from_mod_rs,
+            r#"
+//- /main.rs
+#[path = "a/mod.rs"]
+mod $0a;
+//- /a/mod.rs
+mod b;
+//- /a/b/mod.rs
+"#,
+        );
+    }
+
+    #[test]
+    fn keeps_absolute_path_attrs() {
+        check_assist(
+            convert_module_tree_from_mod_rs,
+            r#"
+//- /main.rs
+mod $0a;
+//- /a/mod.rs
+#[path = "/b_impl.rs"]
+mod b;
+//- /b_impl.rs
+"#,
+            r#"
+//- /a.rs
+#[path = "/b_impl.rs"]
+mod b;
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_relative_path_attrs() {
+        cov_mark::check!(module_tree_relative_path_attr);
+        check_assist_not_applicable(
+            convert_module_tree_to_mod_rs,
+            r#"
+//- /main.rs
+mod $0a;
+//- /a.rs
+#[path = "b_impl.rs"]
+mod b;
+//- /b_impl.rs
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_inline_modules() {
+        check_assist_not_applicable(
+            convert_module_tree_from_mod_rs,
+            r#"
+//- /main.rs
+mod $0a {}
+"#,
+        );
+    }
+}
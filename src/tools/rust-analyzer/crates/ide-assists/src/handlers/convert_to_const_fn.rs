@@ -0,0 +1,534 @@
+use hir::{AsAssocItem, HasAttrs, ModuleDef, PathResolution, Semantics, Trait, Type};
+use ide_db::{
+    assists::{AssistId, AssistKind},
+    syntax_helpers::node_ext::preorder_expr,
+    FxHashSet, RootDatabase,
+};
+use syntax::{
+    ast::{self, HasGenericParams},
+    AstNode, WalkEvent,
+};
+
+use crate::{AssistContext, Assists};
+
+// Assist: convert_to_const_fn
+//
+// Makes a function a `const fn` if everything its body calls can be called in a const context.
+// Bounds on `#[const_trait]` traits whose methods the body calls are turned into `~const` bounds.
+//
+// ```
+// #[const_trait]
+// trait Double {
+//     fn double(&self) -> Self;
+// }
+//
+// f$0n double_of<T: Double>(x: &T) -> T {
+//     x.double()
+// }
+// ```
+// ->
+// ```
+// #[const_trait]
+// trait Double {
+//     fn double(&self) -> Self;
+// }
+//
+// const fn double_of<T: ~const Double>(x: &T) -> T {
+//     x.double()
+// }
+// ```
+pub(crate) fn convert_to_const_fn(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let function: ast::Fn = ctx.find_node_at_offset()?;
+    let body = function.body()?;
+
+    // Only offer the assist on the prototype, not in the middle of the function body.
+    if ctx.offset() >= body.syntax().text_range().start() {
+        return None;
+    }
+    if function.const_token().is_some() || function.async_token().is_some() {
+        return None;
+    }
+    // The methods of a trait impl can only be const if the whole impl is, and default bodies of
+    // trait methods are const if the trait is.
+    let container = function.syntax().ancestors().nth(2);
+    if container.clone().and_then(ast::Trait::cast).is_some()
+        || container.and_then(ast::Impl::cast).map_or(false, |impl_| impl_.trait_().is_some())
+    {
+        return None;
+    }
+
+    let func = ctx.sema.to_def(&function)?;
+    let params = func.assoc_fn_params(ctx.db());
+    if !params.iter().all(|param| can_drop_in_const_fn(ctx.db(), param.ty()))
+        || drops_non_const_values(&ctx.sema, &body)
+    {
+        cov_mark::hit!(convert_to_const_fn_drops_values);
+        return None;
+    }
+
+    let called_traits = const_traits_called_in(&ctx.sema, &ast::Expr::BlockExpr(body))?;
+
+    // The bounds on the function itself that the called trait methods can go through. Bounds
+    // from an enclosing impl can't be made `~const` from here.
+    let bounds: Vec<_> = [
+        function.generic_param_list().map(|it| it.syntax().clone()),
+        function.param_list().map(|it| it.syntax().clone()),
+        function.where_clause().map(|it| it.syntax().clone()),
+    ]
+    .into_iter()
+    .flatten()
+    .flat_map(|node| node.descendants().filter_map(ast::TypeBound::cast))
+    .filter_map(|bound| {
+        let ast::Type::PathType(path_type) = bound.ty()? else { return None };
+        let trait_ = ctx.sema.resolve_trait(&path_type.path()?)?;
+        called_traits.contains(&trait_).then_some((bound, trait_))
+    })
+    .collect();
+    if !called_traits.iter().all(|trait_| bounds.iter().any(|(_, it)| it == trait_)) {
+        cov_mark::hit!(convert_to_const_fn_bound_not_on_fn);
+        return None;
+    }
+
+    // `const` goes after the visibility and `default`, but before all other qualifiers.
+    let const_offset = function
+        .unsafe_token()
+        .or_else(|| function.abi()?.syntax().first_token())
+        .or_else(|| function.fn_token())?
+        .text_range()
+        .start();
+
+    acc.add(
+        AssistId("convert_to_const_fn", AssistKind::RefactorRewrite),
+        "Convert to `const fn`",
+        function.fn_token()?.text_range(),
+        |builder| {
+            builder.insert(const_offset, "const ");
+            for (bound, _) in bounds {
+                if bound.tilde_token().is_none() {
+                    builder.insert(bound.syntax().text_range().start(), "~const ");
+                }
+            }
+        },
+    )
+}
+
+/// Returns the `#[const_trait]` traits whose methods `body` calls through generic bounds, or
+/// `None` if `body` does something that can't be done in a `const fn`.
+fn const_traits_called_in(
+    sema: &Semantics<'_, RootDatabase>,
+    body: &ast::Expr,
+) -> Option<FxHashSet<Trait>> {
+    let mut traits = FxHashSet::default();
+    let mut is_const = true;
+    preorder_expr(body, &mut |ev| {
+        let expr = match ev {
+            WalkEvent::Enter(_) if !is_const => return true,
+            WalkEvent::Enter(expr) => expr,
+            WalkEvent::Leave(_) => return false,
+        };
+        let callee = match expr {
+            ast::Expr::CallExpr(call) => match call.expr() {
+                Some(ast::Expr::PathExpr(path_expr)) => {
+                    match path_expr.path().and_then(|path| sema.resolve_path(&path)) {
+                        Some(PathResolution::Def(ModuleDef::Function(func))) => Some(func),
+                        // Tuple struct and tuple variant constructors.
+                        Some(PathResolution::Def(ModuleDef::Adt(_) | ModuleDef::Variant(_))) => {
+                            None
+                        }
+                        _ => {
+                            is_const = false;
+                            None
+                        }
+                    }
+                }
+                _ => {
+                    is_const = false;
+                    None
+                }
+            },
+            ast::Expr::MethodCallExpr(call) => sema.resolve_method_call(&call),
+            ast::Expr::BinExpr(bin_expr) if !has_builtin_ops(sema, bin_expr.lhs()) => {
+                sema.resolve_bin_expr(&bin_expr)
+            }
+            ast::Expr::IndexExpr(index_expr) if !has_builtin_indexing(sema, index_expr.base()) => {
+                sema.resolve_index_expr(&index_expr)
+            }
+            ast::Expr::PrefixExpr(prefix_expr) if !has_builtin_unary_op(sema, &prefix_expr) => {
+                sema.resolve_prefix_expr(&prefix_expr)
+            }
+            ast::Expr::BoxExpr(_)
+            | ast::Expr::ForExpr(_)
+            | ast::Expr::TryExpr(_)
+            | ast::Expr::YieldExpr(_)
+            | ast::Expr::AwaitExpr(_)
+            | ast::Expr::MacroExpr(_) => {
+                is_const = false;
+                None
+            }
+            _ => None,
+        };
+        if let Some(func) = callee {
+            if !func.is_const(sema.db) {
+                match func.as_assoc_item(sema.db).and_then(|it| it.containing_trait(sema.db)) {
+                    Some(trait_) if trait_.attrs(sema.db).by_key("const_trait").exists() => {
+                        traits.insert(trait_);
+                    }
+                    _ => is_const = false,
+                }
+            }
+        }
+        !is_const
+    });
+    is_const.then_some(traits)
+}
+
+/// Returns whether a `const fn` can have a binding of type `ty`, which it may drop at the end of
+/// its scope. `&mut` references can't be used in a `const fn` at all, and we can't tell which
+/// types don't need to be dropped, so conservatively only allow `Copy` types.
+fn can_drop_in_const_fn(db: &RootDatabase, ty: &Type) -> bool {
+    if ty.is_mutable_reference() {
+        return false;
+    }
+    ty.is_reference()
+        || ty.is_raw_ptr()
+        || ty.is_int_or_uint()
+        || ty.is_float()
+        || ty.is_bool()
+        || ty.is_char()
+        || ty.is_unit()
+        || ty.is_never()
+        || ty.is_copy(db)
+}
+
+/// Returns whether `body` binds or discards a value that a `const fn` may not be able to drop.
+fn drops_non_const_values(sema: &Semantics<'_, RootDatabase>, body: &ast::BlockExpr) -> bool {
+    body.syntax().descendants().any(|node| {
+        let ty = if let Some(pat) = ast::IdentPat::cast(node.clone()) {
+            sema.type_of_binding_in_pat(&pat)
+        } else if let Some(stmt) = ast::ExprStmt::cast(node.clone()) {
+            stmt.expr().and_then(|expr| sema.type_of_expr(&expr)).map(|it| it.original)
+        } else if let Some(stmt) = ast::LetStmt::cast(node) {
+            if !matches!(stmt.pat(), Some(ast::Pat::WildcardPat(_))) {
+                return false;
+            }
+            stmt.initializer().and_then(|expr| sema.type_of_expr(&expr)).map(|it| it.original)
+        } else {
+            return false;
+        };
+        ty.map_or(true, |ty| !can_drop_in_const_fn(sema.db, &ty))
+    })
+}
+
+fn has_builtin_unary_op(sema: &Semantics<'_, RootDatabase>, prefix_expr: &ast::PrefixExpr) -> bool {
+    match prefix_expr.op_kind() {
+        Some(ast::UnaryOp::Deref) => {
+            let Some(ty) = prefix_expr.expr().and_then(|it| sema.type_of_expr(&it)) else {
+                return true;
+            };
+            let ty = ty.original;
+            ty.is_reference() || ty.is_raw_ptr() || ty.is_unknown()
+        }
+        _ => has_builtin_ops(sema, prefix_expr.expr()),
+    }
+}
+
+fn has_builtin_ops(sema: &Semantics<'_, RootDatabase>, operand: Option<ast::Expr>) -> bool {
+    let Some(ty) = operand.and_then(|it| sema.type_of_expr(&it)) else { return true };
+    let ty = ty.original;
+    ty.is_int_or_uint()
+        || ty.is_float()
+        || ty.is_bool()
+        || ty.is_char()
+        || ty.is_raw_ptr()
+        || ty.is_unknown()
+}
+
+fn has_builtin_indexing(sema: &Semantics<'_, RootDatabase>, base: Option<ast::Expr>) -> bool {
+    let Some(ty) = base.and_then(|it| sema.type_of_expr(&it)) else { return true };
+    let ty = ty.original.strip_references();
+    ty.is_array() || ty.is_slice() || ty.is_unknown()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn converts_fn_with_builtin_ops() {
+        check_assist(
+            convert_to_const_fn,
+            r#"
+//- minicore: copy
+fn add$0(a: u32, b: u32) -> u32 {
+    let sum = [a, b];
+    sum[0] + sum[1]
+}
+"#,
+            r#"
+const fn add(a: u32, b: u32) -> u32 {
+    let sum = [a, b];
+    sum[0] + sum[1]
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn inserts_const_after_visibility() {
+        check_assist(
+            convert_to_const_fn,
+            r#"
+pub(crate) unsafe f$0n foo() {}
+"#,
+            r#"
+pub(crate) const unsafe fn foo() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn converts_fn_calling_const_fns() {
+        check_assist(
+            convert_to_const_fn,
+            r#"
+struct S(u32);
+impl S {
+    const fn get(&self) -> u32 { self.0 }
+}
+const fn wrap(x: u32) -> S { S(x) }
+fn f$0oo(x: u32) -> u32 {
+    wrap(x).get()
+}
+"#,
+            r#"
+struct S(u32);
+impl S {
+    const fn get(&self) -> u32 { self.0 }
+}
+const fn wrap(x: u32) -> S { S(x) }
+const fn foo(x: u32) -> u32 {
+    wrap(x).get()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn constifies_all_bounds_of_called_traits() {
+        check_assist(
+            convert_to_const_fn,
+            r#"
+#[const_trait]
+trait Double {
+    fn double(&self) -> u32;
+}
+trait Other {}
+fn f$0oo<T: Double + Other, U>(x: &T, y: &impl Double, z: &U) -> u32
+where
+    U: ~const Double,
+{
+    let y = y.double();
+    z.double();
+    y + Double::double(x)
+}
+"#,
+            r#"
+#[const_trait]
+trait Double {
+    fn double(&self) -> u32;
+}
+trait Other {}
+const fn foo<T: ~const Double + Other, U>(x: &T, y: &impl ~const Double, z: &U) -> u32
+where
+    U: ~const Double,
+{
+    let y = y.double();
+    z.double();
+    y + Double::double(x)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_calling_non_const_fn() {
+        check_assist_not_applicable(
+            convert_to_const_fn,
+            r#"
+fn bar() {}
+fn f$0oo() {
+    bar();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_calling_non_const_trait_method() {
+        check_assist_not_applicable(
+            convert_to_const_fn,
+            r#"
+trait Double {
+    fn double(&self) -> Self;
+}
+fn f$0oo<T: Double>(x: &T) -> T {
+    x.double()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_bound_is_not_on_fn() {
+        cov_mark::check!(convert_to_const_fn_bound_not_on_fn);
+        check_assist_not_applicable(
+            convert_to_const_fn,
+            r#"
+#[const_trait]
+trait Double {
+    fn double(&self) -> Self;
+}
+struct S<T>(T);
+impl<T: Double> S<T> {
+    fn f$0oo(&self) -> T {
+        self.0.double()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_non_const_expressions() {
+        check_assist_not_applicable(
+            convert_to_const_fn,
+            r#"
+//- minicore: iterator
+fn f$0oo() {
+    for _ in [1, 2] {}
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_to_const_fn,
+            r#"
+fn f$0oo(f: fn()) {
+    f()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_mut_self_methods() {
+        cov_mark::check!(convert_to_const_fn_drops_values);
+        check_assist_not_applicable(
+            convert_to_const_fn,
+            r#"
+struct S(u32);
+impl S {
+    fn s$0et(&mut self, x: u32) {
+        self.0 = x;
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_dropping_values() {
+        check_assist_not_applicable(
+            convert_to_const_fn,
+            r#"
+//- minicore: copy
+struct Owned(u32);
+fn f$0oo(s: Owned) {}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_to_const_fn,
+            r#"
+//- minicore: copy
+struct Owned(u32);
+fn f$0oo(x: u32) -> u32 {
+    let s = Owned(x);
+    s.0
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_to_const_fn,
+            r#"
+//- minicore: copy
+struct Owned(u32);
+const fn make() -> Owned { Owned(0) }
+fn f$0oo() {
+    make();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_overloaded_unary_ops() {
+        check_assist_not_applicable(
+            convert_to_const_fn,
+            r#"
+#[lang = "neg"]
+pub trait Neg {
+    type Output;
+    fn neg(self) -> Self::Output;
+}
+struct V(i32);
+impl Neg for &V {
+    type Output = i32;
+    fn neg(self) -> i32 { -self.0 }
+}
+fn f$0oo(v: &V) -> i32 {
+    -v
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_const_or_async_fn() {
+        check_assist_not_applicable(convert_to_const_fn, r#"const f$0n foo() {}"#);
+        check_assist_not_applicable(convert_to_const_fn, r#"async f$0n foo() {}"#);
+    }
+
+    #[test]
+    fn not_applicable_in_body() {
+        check_assist_not_applicable(convert_to_const_fn, r#"fn foo() { $0 }"#);
+    }
+
+    #[test]
+    fn not_applicable_to_trait_methods() {
+        check_assist_not_applicable(
+            convert_to_const_fn,
+            r#"
+trait Tr {
+    fn f$0oo() {}
+}
+struct S;
+impl Tr for S {
+    fn foo() {}
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_to_const_fn,
+            r#"
+trait Tr {
+    fn foo();
+}
+struct S;
+impl Tr for S {
+    fn f$0oo() {}
+}
+"#,
+        );
+    }
+}
@@ -6,9 +6,10 @@
 use hir::{HasSource, ModuleSource};
 use ide_db::{
     assists::{AssistId, AssistKind},
-    base_db::FileId,
+    base_db::{AnchoredPathBuf, FileId},
     defs::{Definition, NameClass, NameRefClass},
     search::{FileReference, SearchScope},
+    source_change::SourceChangeBuilder,
 };
 use itertools::Itertools;
 use smallvec::SmallVec;
@@ -162,26 +163,12 @@ pub(crate) fn extract_module(acc: &mut Assists, ctx: &AssistContext<'_>) -> Opti
             let module_name = module.name;
             format_to!(module_def, "mod {module_name} {{\n{body}\n{old_item_indent}}}");
 
-            let mut usages_to_be_updated_for_curr_file = vec![];
-            for usages_to_be_updated_for_file in usages_to_be_processed {
-                if usages_to_be_updated_for_file.0 == ctx.file_id() {
-                    usages_to_be_updated_for_curr_file = usages_to_be_updated_for_file.1;
-                    continue;
-                }
-                builder.edit_file(usages_to_be_updated_for_file.0);
-                for usage_to_be_processed in usages_to_be_updated_for_file.1 {
-                    builder.replace(usage_to_be_processed.0, usage_to_be_processed.1)
-                }
-            }
-
-            builder.edit_file(ctx.file_id());
-            for usage_to_be_processed in usages_to_be_updated_for_curr_file {
-                builder.replace(usage_to_be_processed.0, usage_to_be_processed.1)
-            }
-
-            for import_path_text_range in import_paths_to_be_removed {
-                builder.delete(import_path_text_range);
-            }
+            update_usages_and_imports(
+                builder,
+                ctx,
+                usages_to_be_processed,
+                import_paths_to_be_removed,
+            );
 
             if let Some(impl_) = impl_parent {
                 // Remove complete impl block if it has only one child (as such it will be empty
@@ -207,6 +194,121 @@ pub(crate) fn extract_module(acc: &mut Assists, ctx: &AssistContext<'_>) -> Opti
     )
 }
 
+// Assist: extract_module_to_file
+//
+// Extracts a selected region into a new module in a separate file, and declares the module
+// in place of the selection. All the references, visibility and imports are resolved.
+//
+// ```
+// $0fn foo(name: i32) -> i32 {
+//     name + 1
+// }$0
+//
+// fn bar(name: i32) -> i32 {
+//     name + 2
+// }
+// ```
+// ->
+// ```
+// mod modname;
+//
+// fn bar(name: i32) -> i32 {
+//     name + 2
+// }
+// ```
+pub(crate) fn extract_module_to_file(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    if ctx.has_empty_selection() {
+        return None;
+    }
+
+    let node = match ctx.covering_element() {
+        syntax::NodeOrToken::Node(n) => n,
+        syntax::NodeOrToken::Token(t) => t.parent()?,
+    };
+
+    // Only items at the top level of a file can be moved to a file of their own, items of
+    // inline modules and impl blocks would need their parents to be recreated as well.
+    let parent = match node.parent() {
+        Some(parent) => parent,
+        None => node.clone(),
+    };
+    if !ast::SourceFile::can_cast(parent.kind()) {
+        return None;
+    }
+
+    let mut module = extract_target(&node, ctx.selection_trimmed())?;
+    if module.body_items.is_empty() {
+        return None;
+    }
+
+    let parent_module = ctx.sema.to_module_def(ctx.file_id())?;
+
+    acc.add(
+        AssistId("extract_module_to_file", AssistKind::RefactorExtract),
+        "Extract Module to File",
+        module.text_range,
+        |builder| {
+            let (usages_to_be_processed, record_fields) = module.get_usages_and_record_fields(ctx);
+
+            let import_paths_to_be_removed = module.resolve_imports(None, ctx);
+            module.change_visibility(record_fields);
+
+            let items = module.use_items.iter().chain(&module.body_items).join("\n\n");
+            let contents = format!("{items}\n");
+
+            update_usages_and_imports(
+                builder,
+                ctx,
+                usages_to_be_processed,
+                import_paths_to_be_removed,
+            );
+
+            let module_name = module.name;
+            builder.replace(module.text_range, format!("mod {module_name};"));
+
+            // The new file is only part of the module tree because of the `mod` item above, so
+            // it has to end up where that item looks for it.
+            let path = match parent_module.name(ctx.db()) {
+                Some(name) if !parent_module.is_mod_rs(ctx.db()) => {
+                    format!("./{}/{module_name}.rs", name.display(ctx.db()))
+                }
+                _ => format!("./{module_name}.rs"),
+            };
+            builder.create_file(AnchoredPathBuf { anchor: ctx.file_id(), path }, contents);
+        },
+    )
+}
+
+/// Replaces the usages of the extracted items, and removes the imports that are moved into the
+/// new module.
+fn update_usages_and_imports(
+    builder: &mut SourceChangeBuilder,
+    ctx: &AssistContext<'_>,
+    usages_to_be_processed: HashMap<FileId, Vec<(TextRange, String)>>,
+    import_paths_to_be_removed: Vec<TextRange>,
+) {
+    let mut usages_to_be_updated_for_curr_file = vec![];
+    for usages_to_be_updated_for_file in usages_to_be_processed {
+        if usages_to_be_updated_for_file.0 == ctx.file_id() {
+            usages_to_be_updated_for_curr_file = usages_to_be_updated_for_file.1;
+            continue;
+        }
+        builder.edit_file(usages_to_be_updated_for_file.0);
+        for usage_to_be_processed in usages_to_be_updated_for_file.1 {
+            builder.replace(usage_to_be_processed.0, usage_to_be_processed.1)
+        }
+    }
+
+    builder.edit_file(ctx.file_id());
+    for usage_to_be_processed in usages_to_be_updated_for_curr_file {
+        builder.replace(usage_to_be_processed.0, usage_to_be_processed.1)
+    }
+
+    for import_path_text_range in import_paths_to_be_removed {
+        builder.delete(import_path_text_range);
+    }
+}
+
 #[derive(Debug)]
 struct Module {
     text_range: TextRange,
@@ -1806,6 +1908,74 @@ pub(crate) struct S {
         pub(crate) condvar: B,
     }
 }
+"#,
+        );
+    }
+
+    #[test]
+    fn test_extract_module_to_file() {
+        check_assist(
+            extract_module_to_file,
+            r#"
+//- /main.rs
+$0fn foo(name: i32) -> i32 {
+    name + 1
+}$0
+
+fn bar(name: i32) -> i32 {
+    foo(name) + 2
+}
+"#,
+            r#"
+//- /main.rs
+mod modname;
+
+fn bar(name: i32) -> i32 {
+    modname::foo(name) + 2
+}
+//- /modname.rs
+pub(crate) fn foo(name: i32) -> i32 {
+    name + 1
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_extract_module_to_file_from_submodule() {
+        check_assist(
+            extract_module_to_file,
+            r#"
+//- /main.rs
+mod submod;
+//- /submod.rs
+$0fn foo() {}
+
+fn bar() {}$0
+
+fn baz() {}
+"#,
+            r#"
+//- /submod.rs
+mod modname;
+
+fn baz() {}
+//- /submod/modname.rs
+pub(crate) fn foo() {}
+
+pub(crate) fn bar() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_extract_module_to_file_not_applicable_in_inline_module() {
+        check_assist_not_applicable(
+            extract_module_to_file,
+            r#"
+mod inline {
+    $0fn foo() {}$0
+}
 "#,
         );
     }
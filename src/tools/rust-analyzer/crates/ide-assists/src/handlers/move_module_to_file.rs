@@ -5,8 +5,8 @@
 use itertools::Itertools;
 use stdx::format_to;
 use syntax::{
-    ast::{self, edit::AstNodeEdit, HasName},
-    AstNode, SmolStr, TextRange,
+    ast::{self, edit::AstNodeEdit, make, HasAttrs, HasModuleItem, HasName},
+    ted, AstNode, SmolStr, TextRange,
 };
 
 use crate::{AssistContext, AssistId, AssistKind, Assists};
@@ -73,6 +73,12 @@ pub(crate) fn move_module_to_file(acc: &mut Assists, ctx: &AssistContext<'_>) ->
                 buf
             };
             let contents = {
+                let module_items = module_items.clone_for_update();
+                // `mod r#mod` is moved to a `mod.rs` file whose directory is the one the paths
+                // were relative to already.
+                if module_name.text() != "r#mod" {
+                    rebase_path_attrs(&module_items, module_name.text().trim_start_matches("r#"));
+                }
                 let items = module_items.dedent(IndentLevel(1)).to_string();
                 let mut items =
                     items.trim_start_matches('{').trim_end_matches('}').trim().to_string();
@@ -101,6 +107,32 @@ pub(crate) fn move_module_to_file(acc: &mut Assists, ctx: &AssistContext<'_>) ->
     )
 }
 
+/// Rewrites the relative `#[path]` attributes of the file modules declared directly in
+/// `module_items`. Inside the inline module they are relative to the `module_name` directory, in
+/// the new file they are relative to the directory containing it.
+fn rebase_path_attrs(module_items: &ast::ItemList, module_name: &str) {
+    let path_attrs = module_items
+        .items()
+        .filter_map(|item| match item {
+            ast::Item::Module(module) if module.item_list().is_none() => Some(module),
+            _ => None,
+        })
+        .flat_map(|module| module.attrs())
+        .filter(|attr| attr.simple_name().as_deref() == Some("path"))
+        .collect::<Vec<_>>();
+    for attr in path_attrs {
+        let Some(ast::Expr::Literal(literal)) = attr.expr() else { continue };
+        let ast::LiteralKind::String(string) = literal.kind() else { continue };
+        let Some(path) = string.value() else { continue };
+        if path.starts_with('/') {
+            continue;
+        }
+        let new_path = format!("{module_name}/{path}");
+        let new_literal = make::expr_literal(&format!("{new_path:?}")).clone_for_update();
+        ted::replace(literal.syntax(), new_literal.syntax());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tests::{check_assist, check_assist_not_applicable};
@@ -331,6 +363,60 @@ mod foo {
     }
 }
 //- /mod/foo/mod/mod.rs
+"#,
+        )
+    }
+
+    #[test]
+    fn extract_with_path_attrs() {
+        check_assist(
+            move_module_to_file,
+            r#"
+//- /main.rs
+mod $0foo {
+    #[path = "bar_impl.rs"]
+    mod bar;
+    #[path = "/abs/baz.rs"]
+    mod baz;
+    mod inner {
+        #[path = "qux_impl.rs"]
+        mod qux;
+    }
+}
+"#,
+            r#"
+//- /main.rs
+mod foo;
+//- /foo.rs
+#[path = "foo/bar_impl.rs"]
+mod bar;
+#[path = "/abs/baz.rs"]
+mod baz;
+mod inner {
+    #[path = "qux_impl.rs"]
+    mod qux;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn keep_path_attrs_of_r_mod() {
+        check_assist(
+            move_module_to_file,
+            r#"
+//- /main.rs
+mod $0r#mod {
+    #[path = "bar_impl.rs"]
+    mod bar;
+}
+"#,
+            r#"
+//- /main.rs
+mod r#mod;
+//- /mod/mod.rs
+#[path = "bar_impl.rs"]
+mod bar;
 "#,
         )
     }
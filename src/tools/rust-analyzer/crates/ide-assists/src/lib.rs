@@ -123,6 +123,7 @@ mod handlers {
     mod convert_let_else_to_match;
     mod convert_match_to_let_else;
     mod convert_nested_function_to_closure;
+    mod convert_to_const_fn;
     mod convert_tuple_struct_to_named_struct;
     mod convert_named_struct_to_tuple_struct;
     mod convert_to_guarded_return;
@@ -174,6 +175,7 @@ mod handlers {
     mod move_module_to_file;
     mod move_to_mod_rs;
     mod move_from_mod_rs;
+    mod convert_module_tree;
     mod number_representation;
     mod promote_local_to_const;
     mod pull_assignment_up;
@@ -233,6 +235,7 @@ pub(crate) fn all() -> &'static [Handler] {
             convert_match_to_let_else::convert_match_to_let_else,
             convert_named_struct_to_tuple_struct::convert_named_struct_to_tuple_struct,
             convert_nested_function_to_closure::convert_nested_function_to_closure,
+            convert_to_const_fn::convert_to_const_fn,
             convert_to_guarded_return::convert_to_guarded_return,
             convert_tuple_struct_to_named_struct::convert_tuple_struct_to_named_struct,
             convert_two_arm_bool_match_to_matches_macro::convert_two_arm_bool_match_to_matches_macro,
@@ -280,6 +283,8 @@ pub(crate) fn all() -> &'static [Handler] {
             move_module_to_file::move_module_to_file,
             move_to_mod_rs::move_to_mod_rs,
             move_from_mod_rs::move_from_mod_rs,
+            convert_module_tree::convert_module_tree_from_mod_rs,
+            convert_module_tree::convert_module_tree_to_mod_rs,
             number_representation::reformat_number_literal,
             pull_assignment_up::pull_assignment_up,
             promote_local_to_const::promote_local_to_const,
@@ -331,6 +336,7 @@ pub(crate) fn all() -> &'static [Handler] {
             extract_variable::extract_variable,
             extract_function::extract_function,
             extract_module::extract_module,
+            extract_module::extract_module_to_file,
             //
             generate_getter::generate_getter,
             generate_getter::generate_getter_mut,
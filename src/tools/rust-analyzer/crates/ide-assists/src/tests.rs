@@ -213,6 +213,9 @@ fn check_with_config(
                         // temporary placeholder for MoveDir since we are not using MoveDir in ide assists yet.
                         (dst, format!("{src_id:?}\n{src:?}"))
                     }
+                    FileSystemEdit::AppendToFile { dst, contents } => (dst, contents),
+                    // Assists don't delete files.
+                    FileSystemEdit::DeleteFile { .. } => continue,
                 };
                 let sr = db.file_source_root(dst.anchor);
                 let sr = db.source_root(sr);
@@ -453,6 +453,42 @@ fn foo(opt: Option<()>) {
     )
 }
 
+#[test]
+fn doctest_convert_module_tree_from_mod_rs() {
+    check_doc_test(
+        "convert_module_tree_from_mod_rs",
+        r#####"
+//- /main.rs
+mod $0a;
+//- /a/mod.rs
+mod b;
+//- /a/b/mod.rs
+fn t() {}
+"#####,
+        r#####"
+mod a;
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_module_tree_to_mod_rs() {
+    check_doc_test(
+        "convert_module_tree_to_mod_rs",
+        r#####"
+//- /main.rs
+mod $0a;
+//- /a.rs
+mod b;
+//- /a/b.rs
+fn t() {}
+"#####,
+        r#####"
+mod a;
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_named_struct_to_tuple_struct() {
     check_doc_test(
@@ -519,6 +555,33 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_to_const_fn() {
+    check_doc_test(
+        "convert_to_const_fn",
+        r#####"
+#[const_trait]
+trait Double {
+    fn double(&self) -> Self;
+}
+
+f$0n double_of<T: Double>(x: &T) -> T {
+    x.double()
+}
+"#####,
+        r#####"
+#[const_trait]
+trait Double {
+    fn double(&self) -> Self;
+}
+
+const fn double_of<T: ~const Double>(x: &T) -> T {
+    x.double()
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_to_guarded_return() {
     check_doc_test(
@@ -776,6 +839,29 @@ fn bar(name: i32) -> i32 {
     )
 }
 
+#[test]
+fn doctest_extract_module_to_file() {
+    check_doc_test(
+        "extract_module_to_file",
+        r#####"
+$0fn foo(name: i32) -> i32 {
+    name + 1
+}$0
+
+fn bar(name: i32) -> i32 {
+    name + 2
+}
+"#####,
+        r#####"
+mod modname;
+
+fn bar(name: i32) -> i32 {
+    name + 2
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_extract_struct_from_enum_variant() {
     check_doc_test(
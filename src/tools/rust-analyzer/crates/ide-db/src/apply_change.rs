@@ -172,6 +172,9 @@ macro_rules! purge_each_query {
             // LineIndexDatabase
             crate::LineIndexQuery
 
+            // IncludedFilesDatabase
+            crate::IncludedFilesQuery
+
             // InternDatabase
             hir::db::InternFunctionQuery
             hir::db::InternStructQuery
@@ -49,6 +49,7 @@ pub mod syntax_helpers {
     AnchoredPath, CrateId, FileId, FileLoader, FileLoaderDelegate, SourceDatabase, Upcast,
 };
 use hir::db::{DefDatabase, ExpandDatabase, HirDatabase};
+use syntax::{ast, AstNode, AstToken};
 use triomphe::Arc;
 
 use crate::{line_index::LineIndex, symbol_index::SymbolsDatabase};
@@ -71,6 +72,7 @@ pub mod syntax_helpers {
     hir::db::HirDatabaseStorage,
     hir::db::InternDatabaseStorage,
     LineIndexDatabaseStorage,
+    IncludedFilesDatabaseStorage,
     symbol_index::SymbolsDatabaseStorage
 )]
 pub struct RootDatabase {
@@ -298,6 +300,9 @@ macro_rules! update_lru_capacity_per_query {
             // LineIndexDatabase
             crate::LineIndexQuery
 
+            // IncludedFilesDatabase
+            crate::IncludedFilesQuery
+
             // InternDatabase
             // hir_db::InternFunctionQuery
             // hir_db::InternStructQuery
@@ -334,6 +339,46 @@ fn line_index(db: &dyn LineIndexDatabase, file_id: FileId) -> Arc<LineIndex> {
     Arc::new(LineIndex::new(&text))
 }
 
+#[salsa::query_group(IncludedFilesDatabaseStorage)]
+pub trait IncludedFilesDatabase: DefDatabase {
+    /// The files that the modules of `krate` include with `include!("...")` and a string
+    /// literal. Such files are part of the crate, just not as modules of their own.
+    fn included_files(&self, krate: CrateId) -> Arc<FxHashSet<FileId>>;
+}
+
+fn included_files(db: &dyn IncludedFilesDatabase, krate: CrateId) -> Arc<FxHashSet<FileId>> {
+    let _p = profile::span("included_files");
+    let crate_def_map = db.crate_def_map(krate);
+    let files = crate_def_map
+        .modules()
+        .filter_map(|(_, module)| module.origin.file_id())
+        .flat_map(|including| {
+            let source_file = db.parse(including).tree();
+            let calls = source_file.syntax().descendants().filter_map(ast::MacroCall::cast);
+            calls.filter_map(move |call| included_file(db, including, &call)).collect::<Vec<_>>()
+        })
+        .collect();
+    Arc::new(files)
+}
+
+/// Resolves the file that `call` includes if it is an `include!` call with a string literal.
+fn included_file(
+    db: &dyn IncludedFilesDatabase,
+    anchor: FileId,
+    call: &ast::MacroCall,
+) -> Option<FileId> {
+    if call.path()?.segment()?.name_ref()?.text() != "include" {
+        return None;
+    }
+    let literal = call
+        .token_tree()?
+        .syntax()
+        .children_with_tokens()
+        .filter_map(|it| it.into_token())
+        .find_map(ast::String::cast)?;
+    db.resolve_path(AnchoredPath { anchor, path: &literal.value()? })
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum SymbolKind {
     Attribute,
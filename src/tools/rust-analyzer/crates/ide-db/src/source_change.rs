@@ -269,6 +269,8 @@ pub enum FileSystemEdit {
     CreateFile { dst: AnchoredPathBuf, initial_contents: String },
     MoveFile { src: FileId, dst: AnchoredPathBuf },
     MoveDir { src: AnchoredPathBuf, src_id: FileId, dst: AnchoredPathBuf },
+    AppendToFile { dst: AnchoredPathBuf, contents: String },
+    DeleteFile { src: FileId },
 }
 
 impl From<FileSystemEdit> for SourceChange {
@@ -0,0 +1,306 @@
+use hir::db::ExpandDatabase;
+use ide_db::source_change::SourceChange;
+use syntax::{ast, AstNode};
+use text_edit::TextEdit;
+
+use crate::{fix, Assist, Diagnostic, DiagnosticsContext};
+
+// Diagnostic: const-pattern-refers-to-static
+//
+// This diagnostic is triggered when a pattern uses a constant whose value refers to a `static mut`
+// or to a static with interior mutability. rustc cannot match on such constants.
+pub(crate) fn const_pattern_refers_to_static(
+    ctx: &DiagnosticsContext<'_>,
+    d: &hir::ConstPatternRefersToStatic,
+) -> Diagnostic {
+    Diagnostic::new(
+        "const-pattern-refers-to-static",
+        format!(
+            "constant pattern refers to static `{}`",
+            d.static_.name(ctx.sema.db).display(ctx.sema.db)
+        ),
+        ctx.sema.diagnostics_display_range(d.pat.clone().map(Into::into)).range,
+    )
+    .with_fixes(fixes(ctx, d))
+}
+
+fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::ConstPatternRefersToStatic) -> Option<Vec<Assist>> {
+    let root = ctx.sema.db.parse_or_expand(d.pat.file_id);
+    let pat = d.pat.value.to_node(&root);
+    if !matches!(pat, ast::Pat::IdentPat(_) | ast::Pat::PathPat(_)) {
+        return None;
+    }
+    // Only a constant that is the whole pattern of an arm can be moved into its guard.
+    let arm = ast::MatchArm::cast(pat.syntax().parent()?)?;
+    if arm.guard().is_some() {
+        return None;
+    }
+
+    // Don't shadow any variable the arm might use.
+    let mut names_in_scope = vec![];
+    ctx.sema.scope(arm.expr()?.syntax())?.process_all_names(&mut |name, _| {
+        names_in_scope.push(name.display(ctx.sema.db).to_string())
+    });
+    let mut name = "value".to_string();
+    let mut counter = 0;
+    while names_in_scope.contains(&name) {
+        counter += 1;
+        name = format!("value{counter}");
+    }
+
+    let range = pat.syntax().text_range();
+    let edit = TextEdit::replace(range, format!("{name} if {name} == {pat}"));
+    let source_change =
+        SourceChange::from_text_edit(d.pat.file_id.original_file(ctx.sema.db), edit);
+    Some(vec![fix(
+        "compare_in_match_guard",
+        "Compare with the constant in a match guard",
+        source_change,
+        range,
+    )])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_diagnostics, check_fix};
+
+    #[test]
+    fn const_referring_to_static_mut() {
+        check_diagnostics(
+            r#"
+//- minicore: option
+static mut S: i32 = 0;
+const C: Option<&i32> = Some(unsafe { &S });
+
+fn f(x: Option<&i32>) {
+    match x {
+        C => {}
+      //^ 💡 error: constant pattern refers to static `S`
+        _ => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn const_referring_to_interior_mutable_static() {
+        check_diagnostics(
+            r#"
+//- minicore: cell, option
+use core::cell::Cell;
+
+struct Counter {
+    count: (u8, [Cell<i32>; 1]),
+}
+static S: Counter = Counter { count: (0, [Cell::new(0)]) };
+const C: Option<&Counter> = Some(&S);
+
+fn f(x: Option<&Counter>) {
+    match x {
+        C => {}
+      //^ 💡 error: constant pattern refers to static `S`
+        _ => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn const_referring_to_immutable_static() {
+        check_diagnostics(
+            r#"
+//- minicore: cell, option
+use core::cell::Cell;
+
+static S: i32 = 0;
+static T: &Cell<i32> = &Cell::new(0);
+const C: Option<&i32> = Some(&S);
+const D: Option<&&Cell<i32>> = Some(&T);
+
+fn f(x: Option<&i32>, y: Option<&&Cell<i32>>) {
+    match x {
+        C => {}
+        _ => {}
+    }
+    match y {
+        D => {}
+        _ => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn const_referring_to_static_through_const() {
+        check_diagnostics(
+            r#"
+//- minicore: option
+static mut S: i32 = 0;
+const C: &i32 = unsafe { &S };
+const D: Option<&i32> = Some(C);
+
+fn f(x: (u8, Option<&i32>)) {
+    match x {
+        (0, D) => {}
+          //^ error: constant pattern refers to static `S`
+        _ => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn assoc_const_referring_to_static() {
+        check_diagnostics(
+            r#"
+//- minicore: option
+static mut S: i32 = 0;
+struct Foo;
+impl Foo {
+    const C: Option<&'static i32> = Some(unsafe { &S });
+}
+
+fn f(x: Option<&i32>) {
+    match x {
+        Foo::C => {}
+      //^^^^^^ 💡 error: constant pattern refers to static `S`
+        _ => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn const_reading_static_by_value() {
+        check_diagnostics(
+            r#"
+//- minicore: copy
+static mut S: i32 = 0;
+const C: i32 = unsafe { S };
+
+fn f(x: i32) {
+    match x {
+        C => {}
+        _ => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn const_referring_to_static_field() {
+        check_diagnostics(
+            r#"
+//- minicore: option
+static mut S: (i32, i32) = (0, 1);
+const C: Option<&i32> = Some(unsafe { &S.1 });
+
+fn f(x: Option<&i32>) {
+    match x {
+        C => {}
+      //^ 💡 error: constant pattern refers to static `S`
+        _ => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn const_not_referring_to_static() {
+        check_diagnostics(
+            r#"
+//- minicore: option
+const ZERO: i32 = 0;
+const C: Option<&i32> = Some(&ZERO);
+
+fn f(x: Option<&i32>) {
+    match x {
+        C => {}
+        _ => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn const_referring_to_static_in_let() {
+        check_diagnostics(
+            r#"
+//- minicore: option
+static mut S: i32 = 0;
+const C: Option<&i32> = Some(unsafe { &S });
+
+fn f(x: Option<Option<&i32>>) {
+    if let Some(C) = x {}
+              //^ error: constant pattern refers to static `S`
+    let Some(C) = x else { return };
+           //^ error: constant pattern refers to static `S`
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn compare_in_match_guard() {
+        check_fix(
+            r#"
+static mut S: i32 = 0;
+const C: &i32 = unsafe { &S };
+
+fn f(x: &i32) {
+    match x {
+        C$0 => {}
+        _ => {}
+    }
+}
+"#,
+            r#"
+static mut S: i32 = 0;
+const C: &i32 = unsafe { &S };
+
+fn f(x: &i32) {
+    match x {
+        value if value == C => {}
+        _ => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn compare_in_match_guard_fresh_name() {
+        check_fix(
+            r#"
+static mut S: i32 = 0;
+const C: &i32 = unsafe { &S };
+
+fn f(x: &i32, value: i32) {
+    match x {
+        C$0 => { value; }
+        _ => {}
+    }
+}
+"#,
+            r#"
+static mut S: i32 = 0;
+const C: &i32 = unsafe { &S };
+
+fn f(x: &i32, value: i32) {
+    match x {
+        value1 if value1 == C => { value; }
+        _ => {}
+    }
+}
+"#,
+        );
+    }
+}
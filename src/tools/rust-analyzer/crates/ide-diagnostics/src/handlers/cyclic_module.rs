@@ -0,0 +1,136 @@
+use hir::db::ExpandDatabase;
+use ide_db::{
+    assists::Assist,
+    base_db::{FileId, SourceDatabaseExt},
+    source_change::SourceChange,
+};
+use itertools::Itertools;
+use syntax::{
+    ast::{self, HasAttrs},
+    AstNode, SyntaxKind, TextRange,
+};
+use text_edit::TextEdit;
+
+use crate::{fix, Diagnostic, DiagnosticsContext};
+
+// Diagnostic: cyclic-module
+//
+// This diagnostic is triggered if a `mod` declaration, usually one with a `#[path]` attribute,
+// resolves to the file of the module it is declared in or of one of its ancestors. rustc either
+// errors out on such a module tree or recurses until it overflows. The message lists the files
+// that form the cycle, and a fix removing the `#[path]` attribute is offered.
+pub(crate) fn cyclic_module(ctx: &DiagnosticsContext<'_>, d: &hir::CyclicModule) -> Diagnostic {
+    let chain = d.chain.iter().map(|&file_id| file_path(ctx, file_id)).format(" -> ");
+    Diagnostic::new(
+        "cyclic-module",
+        format!("module file is its own ancestor: {chain}"),
+        ctx.sema.diagnostics_display_range(d.decl.clone().map(|it| it.into())).range,
+    )
+    .with_fixes(fixes(ctx, d))
+}
+
+fn file_path(ctx: &DiagnosticsContext<'_>, file_id: FileId) -> String {
+    let db = ctx.sema.db;
+    let source_root = db.source_root(db.file_source_root(file_id));
+    source_root
+        .path_for_file(&file_id)
+        .map_or_else(|| format!("{file_id:?}"), |path| path.to_string())
+}
+
+fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::CyclicModule) -> Option<Vec<Assist>> {
+    let file_id = d.decl.file_id.file_id()?;
+    let root = ctx.sema.db.parse_or_expand(d.decl.file_id);
+    let decl = d.decl.value.to_node(&root);
+    let path_attr = decl.attrs().find(|attr| attr.simple_name().as_deref() == Some("path"))?;
+    Some(vec![fix(
+        "remove_path_attr",
+        "Remove `#[path]` attribute",
+        SourceChange::from_text_edit(file_id, TextEdit::delete(attr_delete_range(&path_attr))),
+        decl.syntax().text_range(),
+    )])
+}
+
+/// The range to delete to remove `attr`, including the whitespace following it so that the
+/// `mod` declaration stays where the attribute started.
+fn attr_delete_range(attr: &ast::Attr) -> TextRange {
+    let range = attr.syntax().text_range();
+    match attr.syntax().next_sibling_or_token() {
+        Some(ws) if ws.kind() == SyntaxKind::WHITESPACE => {
+            TextRange::new(range.start(), ws.text_range().end())
+        }
+        _ => range,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_diagnostics, check_fix};
+
+    #[test]
+    fn self_include() {
+        check_diagnostics(
+            r#"
+//- /lib.rs
+mod foo;
+//- /foo.rs
+  #[path = "foo.rs"] mod foo;
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 error: module file is its own ancestor: /foo.rs -> /foo.rs
+"#,
+        );
+    }
+
+    #[test]
+    fn cycle_through_other_file() {
+        check_diagnostics(
+            r#"
+//- /lib.rs
+mod foo;
+//- /foo.rs
+#[path = "bar.rs"] mod bar;
+//- /bar.rs
+  #[path = "foo.rs"] mod foo;
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 error: module file is its own ancestor: /foo.rs -> /bar.rs -> /foo.rs
+"#,
+        );
+    }
+
+    #[test]
+    fn include_crate_root() {
+        check_diagnostics(
+            r#"
+//- /lib.rs
+  #[path = "lib.rs"] mod again;
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 error: module file is its own ancestor: /lib.rs -> /lib.rs
+"#,
+        );
+    }
+
+    #[test]
+    fn no_cycle_for_sibling() {
+        check_diagnostics(
+            r#"
+//- /lib.rs
+mod foo;
+#[path = "foo/bar.rs"] mod bar;
+//- /foo.rs
+//- /foo/bar.rs
+"#,
+        );
+    }
+
+    #[test]
+    fn remove_path_attr() {
+        check_fix(
+            r#"
+//- /lib.rs
+mod foo;
+//- /foo.rs
+#[path = "foo.rs"]
+mod $0foo;
+"#,
+            r#"
+mod foo;
+"#,
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use hir::db::DefDatabase;
+use ide_db::{
+    base_db::{FileId, FileLoader},
+    source_change::SourceChange,
+    FxHashSet,
+};
+use syntax::{ast, AstNode, SyntaxKind, TextRange};
+use text_edit::TextEdit;
+
+use crate::{fix, Assist, Diagnostic, DiagnosticsContext, Severity};
+
+// Diagnostic: duplicate-mod-decl
+//
+// This diagnostic is triggered if a `mod` declaration resolves to a file that is already part of
+// the module tree through another declaration (possibly by way of a `#[path]` attribute), which
+// silently duplicates all items of that file.
+pub(crate) fn duplicate_mod_decl(
+    ctx: &DiagnosticsContext<'_>,
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+) {
+    let mut reported = FxHashSet::default();
+    for &krate in ctx.sema.db.relevant_crates(file_id).iter() {
+        let def_map = ctx.sema.db.crate_def_map(krate);
+        // Modules are collected in declaration order, so the first one to claim a file is the
+        // one we consider to be the "real" declaration.
+        let mut seen_files = FxHashSet::default();
+        for (_, module) in def_map.modules() {
+            let Some(definition) = module.origin.file_id() else { continue };
+            if seen_files.insert(definition) {
+                continue;
+            }
+            let Some(declaration) = module.origin.declaration() else { continue };
+            if declaration.file_id.file_id() != Some(file_id) {
+                continue;
+            }
+            let decl = declaration.to_node(ctx.sema.db);
+            let range = decl.syntax().text_range();
+            if !reported.insert(range) {
+                continue;
+            }
+            acc.push(
+                Diagnostic::new(
+                    "duplicate-mod-decl",
+                    "file is already included in the module tree by another `mod` declaration",
+                    range,
+                )
+                .severity(Severity::Error)
+                .with_fixes(Some(vec![remove_decl_fix(file_id, &decl)])),
+            );
+        }
+    }
+}
+
+fn remove_decl_fix(file_id: FileId, decl: &ast::Module) -> Assist {
+    fix(
+        "remove_duplicate_mod_decl",
+        "Remove duplicate `mod` declaration",
+        SourceChange::from_text_edit(file_id, TextEdit::delete(mod_decl_delete_range(decl))),
+        decl.syntax().text_range(),
+    )
+}
+
+/// The range to delete to remove `decl`, including the whitespace following it so that we don't
+/// leave an empty line.
+pub(crate) fn mod_decl_delete_range(decl: &ast::Module) -> TextRange {
+    let range = decl.syntax().text_range();
+    match decl.syntax().next_sibling_or_token() {
+        Some(ws) if ws.kind() == SyntaxKind::WHITESPACE => {
+            TextRange::new(range.start(), ws.text_range().end())
+        }
+        _ => range,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_diagnostics, check_fix};
+
+    #[test]
+    fn mod_decls_for_same_file() {
+        check_diagnostics(
+            r#"
+//- /main.rs
+  mod foo;
+  #[path = "foo.rs"] mod bar;
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 error: file is already included in the module tree by another `mod` declaration
+//- /foo.rs
+"#,
+        );
+    }
+
+    #[test]
+    fn mod_decls_for_same_file_in_different_files() {
+        check_diagnostics(
+            r#"
+//- /main.rs
+mod foo;
+mod bar;
+//- /bar/mod.rs
+  #[path = "../foo.rs"] mod baz;
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 error: file is already included in the module tree by another `mod` declaration
+//- /foo.rs
+"#,
+        );
+    }
+
+    #[test]
+    fn no_diagnostic_for_distinct_files() {
+        check_diagnostics(
+            r#"
+//- /main.rs
+mod foo;
+#[path = "bar.rs"] mod baz;
+//- /foo.rs
+//- /bar.rs
+"#,
+        );
+    }
+
+    #[test]
+    fn remove_duplicate_mod_decl() {
+        check_fix(
+            r#"
+//- /main.rs
+mod foo;
+#[path = "foo.rs"]
+mod bar$0;
+fn f() {}
+//- /foo.rs
+"#,
+            r#"
+mod foo;
+fn f() {}
+"#,
+        );
+    }
+}
@@ -21,10 +21,7 @@ pub(crate) fn macro_def_error(ctx: &DiagnosticsContext<'_>, d: &hir::MacroDefErr
 
 #[cfg(test)]
 mod tests {
-    use crate::{
-        tests::{check_diagnostics, check_diagnostics_with_config},
-        DiagnosticsConfig,
-    };
+    use crate::tests::check_diagnostics;
 
     #[test]
     fn builtin_macro_fails_expansion() {
@@ -86,14 +83,7 @@ macro_rules! concat { () => {} }
 
     #[test]
     fn include_macro_should_allow_empty_content() {
-        let mut config = DiagnosticsConfig::test_sample();
-
-        // FIXME: This is a false-positive, the file is actually linked in via
-        // `include!` macro
-        config.disabled.insert("unlinked-file".to_string());
-
-        check_diagnostics_with_config(
-            config,
+        check_diagnostics(
             r#"
 //- /lib.rs
 #[rustc_builtin_macro]
@@ -4,17 +4,25 @@
 
 use hir::{db::DefDatabase, DefMap, InFile, ModuleSource};
 use ide_db::{
-    base_db::{FileId, FileLoader, SourceDatabase, SourceDatabaseExt},
-    source_change::SourceChange,
-    RootDatabase,
+    base_db::{
+        AnchoredPathBuf, FileId, FileLoader, SourceDatabase, SourceDatabaseExt, SourceRoot, VfsPath,
+    },
+    label::Label,
+    source_change::{FileSystemEdit, SourceChange},
+    symbol_index::SymbolsDatabase,
+    IncludedFilesDatabase, RootDatabase,
 };
+use itertools::Itertools;
 use syntax::{
-    ast::{self, edit::IndentLevel, HasModuleItem, HasName},
+    algo::find_node_at_range,
+    ast::{self, edit::IndentLevel, HasAttrs, HasModuleItem, HasName},
     AstNode, TextRange,
 };
 use text_edit::TextEdit;
 
-use crate::{fix, Assist, Diagnostic, DiagnosticsContext, Severity};
+use crate::{
+    fix, unresolved_fix, Assist, Diagnostic, DiagnosticsContext, InsertModDeclsMode, Severity,
+};
 
 // Diagnostic: unlinked-file
 //
@@ -25,9 +33,21 @@ pub(crate) fn unlinked_file(
     acc: &mut Vec<Diagnostic>,
     file_id: FileId,
 ) {
+    if is_included(ctx, file_id) {
+        cov_mark::hit!(unlinked_file_included);
+        return;
+    }
+    if is_excluded_from_targets(ctx, file_id) {
+        cov_mark::hit!(unlinked_file_excluded_from_targets);
+        return;
+    }
+
     // Limit diagnostic to the first few characters in the file. This matches how VS Code
     // renders it with the full span, but on other editors, and is less invasive.
-    let fixes = fixes(ctx, file_id);
+    let mut fixes = fixes(ctx, file_id);
+    if let Some(fixes) = &mut fixes {
+        fixes.extend(link_all_unlinked_files_fix(ctx, file_id));
+    }
     // FIXME: This is a hack for the vscode extension to notice whether there is an autofix or not before having to resolve diagnostics.
     // This is to prevent project linking popups from appearing when there is an autofix. https://github.com/rust-lang/rust-analyzer/issues/14523
     let message = if fixes.is_none() {
@@ -52,23 +72,227 @@ pub(crate) fn unlinked_file(
     );
 }
 
+/// A file that is not part of any crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnlinkedFile {
+    pub file_id: FileId,
+    /// The files that the fixes of the `unlinked-file` diagnostic would declare this file in,
+    /// sorted.
+    pub suggested_parents: Vec<FileId>,
+}
+
+/// Finds the unlinked files in all local source roots, sorted by file id.
+pub(crate) fn unlinked_files(ctx: &DiagnosticsContext<'_>) -> Vec<UnlinkedFile> {
+    let db = ctx.sema.db;
+    let mut res = Vec::new();
+    for &root in db.local_roots().iter() {
+        let source_root = db.source_root(root);
+        for file_id in source_root.iter() {
+            let Some(path) = source_root.path_for_file(&file_id) else { continue };
+            let is_rust_file =
+                path.name_and_extension().map_or(false, |(_, ext)| ext == Some("rs"));
+            if !is_rust_file
+                || ctx.sema.to_module_def(file_id).is_some()
+                || is_included(ctx, file_id)
+                || is_excluded_from_targets(ctx, file_id)
+            {
+                continue;
+            }
+            let suggested_parents = fixes(ctx, file_id)
+                .into_iter()
+                .flatten()
+                .filter_map(|fix| fix.source_change)
+                .flat_map(|source_change| source_change.source_file_edits.into_keys())
+                .filter(|&parent| parent != file_id)
+                .sorted()
+                .dedup()
+                .collect();
+            res.push(UnlinkedFile { file_id, suggested_parents });
+        }
+    }
+    res.sort_by_key(|it| it.file_id);
+    res
+}
+
+/// Collects the `mod` declaration fixes of all unlinked files in the source root of `file_id`
+/// into a single source change, which is handy after copying a whole directory of modules into a
+/// crate.
+///
+/// Finding out which files fit into the module tree needs the fixes of every unlinked file, so
+/// this is only done when the fix is resolved. Until then, it is offered whenever the cheap checks
+/// find at least one other unlinked file in the source root.
+fn link_all_unlinked_files_fix(ctx: &DiagnosticsContext<'_>, file_id: FileId) -> Option<Assist> {
+    let source_root = ctx.sema.db.source_root(ctx.sema.db.file_source_root(file_id));
+    let unlinked_files: Vec<_> = source_root
+        .iter()
+        .sorted()
+        .filter_map(|unlinked| {
+            let path = source_root.path_for_file(&unlinked)?;
+            let is_rust_file =
+                path.name_and_extension().map_or(false, |(_, ext)| ext == Some("rs"));
+            let is_unlinked = is_rust_file
+                && ctx.sema.to_module_def(unlinked).is_none()
+                && !is_included(ctx, unlinked)
+                && !is_excluded_from_targets(ctx, unlinked);
+            is_unlinked.then_some((unlinked, path))
+        })
+        .collect();
+    if unlinked_files.len() < 2 {
+        return None;
+    }
+
+    let trigger_range = ctx.sema.db.parse(file_id).tree().syntax().text_range();
+    let mut res = unresolved_fix(
+        "add_mod_declarations_for_unlinked_files",
+        "Insert `mod` declarations for all unlinked files",
+        trigger_range,
+    );
+    if ctx.resolve.should_resolve(&res.id) {
+        let mut source_change = SourceChange::default();
+        for (unlinked, path) in unlinked_files {
+            // Only files that fit into the existing module tree are linked, files that would need
+            // a `#[path]` attribute or a Cargo target of their own are left for the user to decide.
+            let Some(mod_decl_change) = module_tree_fixes(ctx, &source_root, path, unlinked)
+                .and_then(|fixes| fixes.into_iter().find(|fix| fix.id.0 == "add_mod_declaration"))
+                .and_then(|fix| fix.source_change)
+            else {
+                continue;
+            };
+            source_change = source_change.merge(mod_decl_change);
+        }
+        res.source_change = Some(source_change);
+    }
+    Some(res)
+}
+
+/// Whether `file_id` is included with `include!("...")` in a file of one of its crates. Such a
+/// file is part of the crate, just not as a module of its own, so it is not unlinked.
+fn is_included(ctx: &DiagnosticsContext<'_>, file_id: FileId) -> bool {
+    let db = ctx.sema.db;
+    db.relevant_crates(file_id).iter().any(|&krate| db.included_files(krate).contains(&file_id))
+}
+
+/// Whether the project model knows that `file_id` is deliberately left out of all targets of its
+/// package, e.g. a `src/bin/*.rs` file of a Cargo package with `autobins = false`.
+fn is_excluded_from_targets(ctx: &DiagnosticsContext<'_>, file_id: FileId) -> bool {
+    let crate_graph = ctx.sema.db.crate_graph();
+    let excluded =
+        crate_graph.iter().any(|krate| crate_graph[krate].excluded_files.contains(&file_id));
+    excluded
+}
+
 fn fixes(ctx: &DiagnosticsContext<'_>, file_id: FileId) -> Option<Vec<Assist>> {
     // If there's an existing module that could add `mod` or `pub mod` items to include the unlinked file,
     // suggest that as a fix.
 
     let source_root = ctx.sema.db.source_root(ctx.sema.db.file_source_root(file_id));
     let our_path = source_root.path_for_file(&file_id)?;
-    let parent = our_path.parent()?;
-    let (module_name, _) = our_path.name_and_extension()?;
-    let (parent, module_name) = match module_name {
+
+    // Files in `src/bin/` and `examples/` are Cargo targets of their own, adding them to the
+    // module tree of some other target is almost certainly not what the user wants.
+    if let Some(fix) = cargo_target_fix(ctx.sema.db, our_path, file_id) {
+        return Some(vec![fix]);
+    }
+
+    if let Some(fixes) = module_root_fixes(ctx, our_path, file_id) {
+        return Some(fixes);
+    }
+
+    if let Some(fixes) = module_tree_fixes(ctx, &source_root, our_path, file_id) {
+        return Some(fixes);
+    }
+
+    // Otherwise, the file doesn't fit into the module tree anywhere, so offer to include it from
+    // a crate root with an explicit `#[path]` attribute instead.
+    let (_, module_name) = parent_and_module_name(our_path)?;
+    if let Some(fixes) = integration_test_fixes(ctx, &source_root, our_path, &module_name, file_id)
+    {
+        return Some(fixes);
+    }
+    path_attr_fixes(ctx, &source_root, our_path, &module_name, file_id)
+}
+
+fn parent_and_module_name(path: &VfsPath) -> Option<(VfsPath, String)> {
+    let parent = path.parent()?;
+    let (module_name, _) = path.name_and_extension()?;
+    match module_name {
         // for mod.rs we need to actually look up one higher
         // and take the parent as our to be module name
         "mod" => {
             let (name, _) = parent.name_and_extension()?;
-            (parent.parent()?, name.to_owned())
+            Some((parent.parent()?, name.to_owned()))
         }
-        _ => (parent, module_name.to_owned()),
-    };
+        _ => Some((parent, module_name.to_owned())),
+    }
+}
+
+/// Files directly in a module root that the project model declares for a crate, e.g. a directory
+/// of generated code, are submodules of the module the root belongs to. Offers to declare them
+/// there, with a `#[path]` attribute relative to the file of that module.
+fn module_root_fixes(
+    ctx: &DiagnosticsContext<'_>,
+    our_path: &VfsPath,
+    file_id: FileId,
+) -> Option<Vec<Assist>> {
+    let db = ctx.sema.db;
+    let (parent, module_name) = parent_and_module_name(our_path)?;
+    let crate_graph = db.crate_graph();
+    let module_roots = crate_graph
+        .iter()
+        .flat_map(|krate| crate_graph[krate].module_roots.iter().map(move |root| (krate, root)));
+    'roots: for (krate, root) in module_roots {
+        if root.dir != parent {
+            continue;
+        }
+        let crate_def_map = db.crate_def_map(krate);
+        let mut current = &crate_def_map[DefMap::ROOT];
+        for segment in &root.module_path {
+            match current.children.iter().find(|(name, _)| name.to_smol_str() == segment) {
+                Some((_, &child)) => current = &crate_def_map[child],
+                None => continue 'roots,
+            }
+        }
+        // `#[path]` attributes in inline modules are relative to the directories of the modules
+        // they are nested in, so only modules with files of their own are supported.
+        if current.origin.is_inline() {
+            continue;
+        }
+        let Some(definition) = current.origin.file_id() else { continue };
+        let source_root = db.source_root(db.file_source_root(definition));
+        let Some(definition_dir) = source_root.path_for_file(&definition).and_then(VfsPath::parent)
+        else {
+            continue;
+        };
+        // `#[path]` attributes of outline modules are relative to the directory of their file.
+        let Some((ups, rel)) = iter::successors(Some(definition_dir), VfsPath::parent)
+            .enumerate()
+            .find_map(|(ups, dir)| Some((ups, relative_path(our_path, &dir)?)))
+        else {
+            continue;
+        };
+        let path = format!("{}{rel}", "../".repeat(ups));
+
+        cov_mark::hit!(unlinked_file_in_module_root);
+        return make_fixes(
+            ctx,
+            definition,
+            current.definition_source(db).value,
+            &module_name,
+            file_id,
+            Some(&path),
+        );
+    }
+
+    None
+}
+
+fn module_tree_fixes(
+    ctx: &DiagnosticsContext<'_>,
+    source_root: &SourceRoot,
+    our_path: &VfsPath,
+    file_id: FileId,
+) -> Option<Vec<Assist>> {
+    let (parent, module_name) = parent_and_module_name(our_path)?;
 
     // check crate roots, i.e. main.rs, lib.rs, ...
     'crates: for &krate in &*ctx.sema.db.relevant_crates(file_id) {
@@ -100,23 +324,116 @@ fn fixes(ctx: &DiagnosticsContext<'_>, file_id: FileId) -> Option<Vec<Assist>> {
         let InFile { file_id: parent_file_id, value: source } =
             current.definition_source(ctx.sema.db);
         let parent_file_id = parent_file_id.file_id()?;
-        return make_fixes(ctx.sema.db, parent_file_id, source, &module_name, file_id);
+        let mut fixes = make_fixes(ctx, parent_file_id, source, &module_name, file_id, None)?;
+        if !current.origin.is_inline() {
+            let path = relative_path(our_path, &parent)?;
+            fixes.extend(fn_body_fixes(ctx, parent_file_id, &module_name, &path, file_id));
+        }
+        return Some(fixes);
     }
 
     // if we aren't adding to a crate root, walk backwards such that we support `#[path = ...]` overrides if possible
+    if let Some(fixes) =
+        fixes_from_ancestor_files(ctx, source_root, parent.clone(), &module_name, file_id)
+    {
+        return Some(fixes);
+    }
+
+    // Modules declared with a `#[path]` attribute resolve their own `mod` items relative to the
+    // directory their file is in, so they may be a parent even if their file name doesn't match.
+    for &krate in ctx.sema.db.relevant_crates(file_id).iter() {
+        let crate_def_map = ctx.sema.db.crate_def_map(krate);
+        for (_, module) in crate_def_map.modules() {
+            let Some(definition) = module.origin.file_id() else { continue };
+            if module.origin.is_inline()
+                || source_root.path_for_file(&definition).and_then(VfsPath::parent).as_ref()
+                    != Some(&parent)
+            {
+                continue;
+            }
+            let Some(declaration) = module.declaration_source(ctx.sema.db) else { continue };
+            if declaration.value.attrs().any(|attr| attr.simple_name().as_deref() == Some("path")) {
+                cov_mark::hit!(unlinked_file_parent_with_path_attr);
+                return make_fixes(
+                    ctx,
+                    definition,
+                    module.definition_source(ctx.sema.db).value,
+                    &module_name,
+                    file_id,
+                    None,
+                );
+            }
+        }
+    }
+
+    None
+}
 
+fn fixes_from_ancestor_files(
+    ctx: &DiagnosticsContext<'_>,
+    source_root: &SourceRoot,
+    parent: VfsPath,
+    module_name: &str,
+    file_id: FileId,
+) -> Option<Vec<Assist>> {
     // build all parent paths of the form `../module_name/mod.rs` and `../module_name.rs`
     let paths = iter::successors(Some(parent), |prev| prev.parent()).filter_map(|path| {
         let parent = path.parent()?;
         let (name, _) = path.name_and_extension()?;
-        Some(([parent.join(&format!("{name}.rs"))?, path.join("mod.rs")?], name.to_owned()))
+        let candidates = [
+            (parent.join(&format!("{name}.rs"))?, format!("{name}.rs")),
+            (path.join("mod.rs")?, format!("{name}/mod.rs")),
+        ];
+        Some((candidates, name.to_owned()))
     });
     let mut stack = vec![];
-    let &parent_id =
-        paths.inspect(|(_, name)| stack.push(name.clone())).find_map(|(paths, _)| {
-            paths.into_iter().find_map(|path| source_root.file_for_path(&path))
+    // Both `foo.rs` and `foo/mod.rs` can exist at the same time, e.g. when they come from
+    // branches using different layouts, so keep all the candidates of the nearest ancestor.
+    let candidates: Vec<_> =
+        paths.inspect(|(_, name)| stack.push(name.clone())).find_map(|(candidates, _)| {
+            let found: Vec<_> = candidates
+                .into_iter()
+                .filter_map(|(path, name)| Some((*source_root.file_for_path(&path)?, name)))
+                .collect();
+            (!found.is_empty()).then_some(found)
         })?;
     stack.pop();
+
+    let mut candidate_fixes: Vec<_> = candidates
+        .into_iter()
+        .filter_map(|(parent_id, name)| {
+            let fixes = fixes_from_ancestor_file(ctx, parent_id, &stack, module_name, file_id)?;
+            Some((fixes, name))
+        })
+        .collect();
+    if candidate_fixes.len() < 2 {
+        return candidate_fixes.pop().map(|(fixes, _)| fixes);
+    }
+
+    // Offer the fixes for the layout the crates use for their other modules first, and tell the
+    // fixes apart by the file they add the `mod` item to.
+    cov_mark::hit!(unlinked_file_ranked_parent_candidates);
+    let prefers_mod_rs = prefers_mod_rs(ctx, source_root, file_id);
+    candidate_fixes.sort_by_key(|(_, name)| name.ends_with("/mod.rs") != prefers_mod_rs);
+    let fixes = candidate_fixes
+        .into_iter()
+        .flat_map(|(fixes, name)| {
+            fixes.into_iter().map(move |mut fix| {
+                fix.label = Label::new(format!("{} in `{name}`", fix.label));
+                fix
+            })
+        })
+        .collect();
+    Some(fixes)
+}
+
+fn fixes_from_ancestor_file(
+    ctx: &DiagnosticsContext<'_>,
+    parent_id: FileId,
+    stack: &[String],
+    module_name: &str,
+    file_id: FileId,
+) -> Option<Vec<Assist>> {
     'crates: for &krate in ctx.sema.db.relevant_crates(parent_id).iter() {
         let crate_def_map = ctx.sema.db.crate_def_map(krate);
         let Some((_, module)) =
@@ -126,11 +443,12 @@ fn fixes(ctx: &DiagnosticsContext<'_>, file_id: FileId) -> Option<Vec<Assist>> {
 
         if stack.is_empty() {
             return make_fixes(
-                ctx.sema.db,
+                ctx,
                 parent_id,
                 module.definition_source(ctx.sema.db).value,
-                &module_name,
+                module_name,
                 file_id,
+                None,
             );
         } else {
             // direct parent file is missing,
@@ -150,67 +468,354 @@ fn fixes(ctx: &DiagnosticsContext<'_>, file_id: FileId) -> Option<Vec<Assist>> {
             let InFile { file_id: parent_file_id, value: source } =
                 current.definition_source(ctx.sema.db);
             let parent_file_id = parent_file_id.file_id()?;
-            return make_fixes(ctx.sema.db, parent_file_id, source, &module_name, file_id);
+            return make_fixes(ctx, parent_file_id, source, module_name, file_id, None);
         }
     }
 
     None
 }
 
+/// Whether the modules in the crates of `file_id` that have submodules in files of their own
+/// mostly live in `foo/mod.rs` files, rather than in `foo.rs` files next to a `foo/` directory.
+fn prefers_mod_rs(ctx: &DiagnosticsContext<'_>, source_root: &SourceRoot, file_id: FileId) -> bool {
+    let (mut mod_rs, mut non_mod_rs) = (0, 0);
+    for &krate in ctx.sema.db.relevant_crates(file_id).iter() {
+        let crate_def_map = ctx.sema.db.crate_def_map(krate);
+        for (_, module) in crate_def_map.modules() {
+            // Crate roots are `mod.rs`-like with either layout, and only modules with
+            // submodules in other files tell the layouts apart.
+            let Some(definition) = module.origin.file_id() else { continue };
+            let has_file_children =
+                module.children.values().any(|&child| !crate_def_map[child].origin.is_inline());
+            if module.parent.is_none() || !has_file_children {
+                continue;
+            }
+            match source_root.path_for_file(&definition).and_then(|it| it.name_and_extension()) {
+                Some(("mod", _)) => mod_rs += 1,
+                Some(_) => non_mod_rs += 1,
+                None => {}
+            }
+        }
+    }
+    mod_rs > non_mod_rs
+}
+
+fn path_attr_fixes(
+    ctx: &DiagnosticsContext<'_>,
+    source_root: &SourceRoot,
+    our_path: &VfsPath,
+    module_name: &str,
+    file_id: FileId,
+) -> Option<Vec<Assist>> {
+    for &krate in ctx.sema.db.relevant_crates(file_id).iter() {
+        let crate_def_map = ctx.sema.db.crate_def_map(krate);
+        let root_module = &crate_def_map[DefMap::ROOT];
+        let Some(root_file_id) = root_module.origin.file_id() else { continue };
+        let Some(crate_root_dir) =
+            source_root.path_for_file(&root_file_id).and_then(VfsPath::parent)
+        else {
+            continue;
+        };
+
+        // `#[path]` attributes in crate roots are relative to the directory of the crate root.
+        let Some((ups, rel)) = iter::successors(Some(crate_root_dir), VfsPath::parent)
+            .enumerate()
+            .find_map(|(ups, dir)| Some((ups, relative_path(our_path, &dir)?)))
+        else {
+            continue;
+        };
+        let path = format!("{}{rel}", "../".repeat(ups));
+
+        cov_mark::hit!(unlinked_file_insert_path_attr);
+        return make_fixes(
+            ctx,
+            root_file_id,
+            root_module.definition_source(ctx.sema.db).value,
+            module_name,
+            file_id,
+            Some(&path),
+        );
+    }
+
+    None
+}
+
+/// Files in subdirectories of `tests/` are usually helpers shared between integration tests, which
+/// are crates of their own rooted at `tests/*.rs` and `tests/*/main.rs`. Offers to include the file
+/// from the nearest such root with a `#[path]` attribute, or to turn its directory into an
+/// integration test of its own.
+fn integration_test_fixes(
+    ctx: &DiagnosticsContext<'_>,
+    source_root: &SourceRoot,
+    our_path: &VfsPath,
+    module_name: &str,
+    file_id: FileId,
+) -> Option<Vec<Assist>> {
+    let tests_dir = iter::successors(our_path.parent(), VfsPath::parent)
+        .find(|dir| dir.name_and_extension() == Some(("tests", None)))?;
+    // `src/tests/` is an ordinary module directory.
+    if tests_dir.parent()?.name_and_extension() == Some(("src", None)) {
+        return None;
+    }
+    // Files directly in `tests/` are integration tests themselves, and files with a `main`
+    // function are meant to be the root of one.
+    let rel = relative_path(our_path, &tests_dir)?;
+    let (test_name, rest) = rel.split_once('/')?;
+    let has_main = ctx.sema.parse(file_id).items().any(|item| match item {
+        ast::Item::Fn(func) => func.name().map_or(false, |name| name.text() == "main"),
+        _ => false,
+    });
+    if has_main {
+        return None;
+    }
+
+    let nearest_root = ctx
+        .sema
+        .db
+        .relevant_crates(file_id)
+        .iter()
+        .filter_map(|&krate| {
+            let crate_def_map = ctx.sema.db.crate_def_map(krate);
+            let root_module = &crate_def_map[DefMap::ROOT];
+            let root_file_id = root_module.origin.file_id()?;
+            let root_path = source_root.path_for_file(&root_file_id)?;
+            let is_test_root = match relative_path(root_path, &tests_dir)?.split_once('/') {
+                None => true,
+                Some((_, rest)) => rest == "main.rs",
+            };
+            if !is_test_root {
+                return None;
+            }
+            // `#[path]` attributes in crate roots are relative to the directory of the crate root.
+            let (ups, rel) = iter::successors(root_path.parent(), VfsPath::parent)
+                .enumerate()
+                .find_map(|(ups, dir)| Some((ups, relative_path(our_path, &dir)?)))?;
+            let source = root_module.definition_source(ctx.sema.db).value;
+            Some((ups, root_path.to_string(), root_file_id, source, rel))
+        })
+        .min_by(|(ups, root_path, ..), (other_ups, other_root_path, ..)| {
+            (ups, root_path).cmp(&(other_ups, other_root_path))
+        });
+
+    let mut fixes = Vec::new();
+    if let Some((ups, _, root_file_id, source, rel)) = nearest_root {
+        cov_mark::hit!(unlinked_file_integration_test_path_attr);
+        let path = format!("{}{rel}", "../".repeat(ups));
+        fixes.extend(
+            make_fixes(ctx, root_file_id, source, module_name, file_id, Some(&path))
+                .into_iter()
+                .flatten(),
+        );
+    }
+
+    let main_path = tests_dir.join(test_name)?.join("main.rs")?;
+    let is_direct_child = !rest.contains('/') && rest != "mod.rs";
+    if is_direct_child && source_root.file_for_path(&main_path).is_none() {
+        let trigger_range = ctx.sema.db.parse(file_id).tree().syntax().text_range();
+        fixes.push(fix(
+            "create_integration_test_main",
+            &format!("Create `tests/{test_name}/main.rs` declaring `mod {module_name};`"),
+            FileSystemEdit::CreateFile {
+                dst: AnchoredPathBuf { anchor: file_id, path: "main.rs".to_owned() },
+                initial_contents: format!("mod {module_name};\n"),
+            }
+            .into(),
+            trigger_range,
+        ));
+    }
+
+    if fixes.is_empty() {
+        None
+    } else {
+        Some(fixes)
+    }
+}
+
+/// Offers to declare the module inside the bodies of functions that already contain `mod`
+/// items, such as test functions with their own module trees. Outline modules in blocks need
+/// a `#[path]` attribute, relative to the directory of the file containing the block.
+fn fn_body_fixes(
+    ctx: &DiagnosticsContext<'_>,
+    parent_file_id: FileId,
+    module_name: &str,
+    path: &str,
+    added_file_id: FileId,
+) -> Vec<Assist> {
+    let source_file = ctx.sema.parse(parent_file_id);
+    source_file
+        .syntax()
+        .descendants()
+        .filter_map(ast::Fn::cast)
+        .filter_map(|func| {
+            let name = func.name()?;
+            let body = func.body()?;
+            let has_mods = body
+                .stmt_list()?
+                .statements()
+                .any(|stmt| matches!(stmt, ast::Stmt::Item(ast::Item::Module(_))));
+            if !has_mods {
+                return None;
+            }
+            cov_mark::hit!(unlinked_file_insert_in_fn_body);
+            let source = ModuleSource::BlockExpr(body);
+            let mod_decl_fix =
+                make_fixes(ctx, parent_file_id, source, module_name, added_file_id, Some(path))?
+                    .into_iter()
+                    .next()?;
+            Some(fix(
+                "add_mod_declaration_in_fn",
+                &format!("Insert `mod {module_name};` in `fn {name}`"),
+                mod_decl_fix.source_change?,
+                mod_decl_fix.target,
+            ))
+        })
+        .collect()
+}
+
+fn relative_path(path: &VfsPath, dir: &VfsPath) -> Option<String> {
+    let rel = path.strip_prefix(dir)?;
+    let mut components = rel.as_ref().components().filter_map(|component| match component {
+        std::path::Component::Normal(seg) => seg.to_str(),
+        _ => None,
+    });
+    Some(components.join("/"))
+}
+
+fn cargo_target_fix(db: &RootDatabase, our_path: &VfsPath, file_id: FileId) -> Option<Assist> {
+    let (name, extension) = our_path.name_and_extension()?;
+    if extension != Some("rs") {
+        return None;
+    }
+
+    // Both `src/bin/foo.rs` and `src/bin/foo/main.rs` define a target named `foo`.
+    let parent = our_path.parent()?;
+    let (target_name, target_dir) = match name {
+        "main" => (parent.name_and_extension()?.0, parent.parent()?),
+        _ => (name, parent),
+    };
+    let (kind, target_dir_path) = match target_dir.name_and_extension()? {
+        ("examples", None) => ("example", "examples"),
+        ("bin", None) if target_dir.parent()?.name_and_extension()? == ("src", None) => {
+            ("bin", "src/bin")
+        }
+        _ => return None,
+    };
+    let target_path = match name {
+        "main" => format!("{target_dir_path}/{target_name}/main.rs"),
+        _ => format!("{target_dir_path}/{target_name}.rs"),
+    };
+
+    // `Cargo.toml` lives in the package root, which `target_path` is relative to.
+    let manifest_path = format!("{}Cargo.toml", "../".repeat(target_path.matches('/').count()));
+    let target = format!("\n[[{kind}]]\nname = \"{target_name}\"\npath = \"{target_path}\"\n");
+
+    let trigger_range = db.parse(file_id).tree().syntax().text_range();
+    Some(fix(
+        "add_cargo_target",
+        &format!("Add `{target_name}` as a `[[{kind}]]` target to `Cargo.toml`"),
+        FileSystemEdit::AppendToFile {
+            dst: AnchoredPathBuf { anchor: file_id, path: manifest_path },
+            contents: target,
+        }
+        .into(),
+        trigger_range,
+    ))
+}
+
 fn make_fixes(
-    db: &RootDatabase,
+    ctx: &DiagnosticsContext<'_>,
     parent_file_id: FileId,
     source: ModuleSource,
     new_mod_name: &str,
     added_file_id: FileId,
+    path_attr: Option<&str>,
 ) -> Option<Vec<Assist>> {
     fn is_outline_mod(item: &ast::Item) -> bool {
         matches!(item, ast::Item::Module(m) if m.item_list().is_none())
     }
 
-    let mod_decl = format!("mod {new_mod_name};");
-    let pub_mod_decl = format!("pub mod {new_mod_name};");
-
-    let mut mod_decl_builder = TextEdit::builder();
-    let mut pub_mod_decl_builder = TextEdit::builder();
-
-    let mut items = match &source {
-        ModuleSource::SourceFile(it) => it.items(),
-        ModuleSource::Module(it) => it.item_list()?.items(),
-        ModuleSource::BlockExpr(_) => return None,
+    let items: Vec<ast::Item> = match &source {
+        ModuleSource::SourceFile(it) => it.items().collect(),
+        ModuleSource::Module(it) => it.item_list()?.items().collect(),
+        ModuleSource::BlockExpr(it) => it
+            .stmt_list()?
+            .statements()
+            .filter_map(|stmt| match stmt {
+                ast::Stmt::Item(item) => Some(item),
+                _ => None,
+            })
+            .collect(),
     };
+    let mut items = items.into_iter();
 
-    // If there's an existing `mod m;` statement matching the new one, don't emit a fix (it's
-    // probably `#[cfg]`d out).
+    // If there's an existing `mod m;` statement matching the new one, don't add another one. It's
+    // probably `#[cfg]`d out, so offer to enable it for rust-analyzer instead.
     for item in items.clone() {
         if let ast::Item::Module(m) = item {
             if let Some(name) = m.name() {
                 if m.item_list().is_none() && name.to_string() == new_mod_name {
-                    cov_mark::hit!(unlinked_file_skip_fix_when_mod_already_exists);
-                    return None;
+                    return cfg_off_mod_fixes(ctx, parent_file_id, &m, added_file_id);
                 }
             }
         }
     }
 
-    // If there are existing `mod m;` items, append after them (after the first group of them, rather).
-    match items.clone().skip_while(|item| !is_outline_mod(item)).take_while(is_outline_mod).last() {
-        Some(last) => {
+    let existing_mod = match ctx.config.insert_mod_decls {
+        // If there are existing `mod m;` items, append after (the first group of) them.
+        InsertModDeclsMode::AfterExisting => items
+            .clone()
+            .skip_while(|item| !is_outline_mod(item))
+            .take_while(is_outline_mod)
+            .last()
+            .map(|last| (last, false)),
+        // Keep existing `mod m;` items sorted by inserting before the first one that sorts after
+        // the new module, or after the last one if there is none.
+        InsertModDeclsMode::Alphabetical => {
+            let mods = items.clone().filter(is_outline_mod);
+            let sorts_after = |item: &ast::Item| match item {
+                ast::Item::Module(m) => m.name().map_or(false, |name| *name.text() > *new_mod_name),
+                _ => false,
+            };
+            match mods.clone().find(sorts_after) {
+                Some(next) => Some((next, true)),
+                None => mods.last().map(|last| (last, false)),
+            }
+        }
+        InsertModDeclsMode::Top => None,
+    };
+    let (offset, before, after) = match existing_mod {
+        Some((next, true)) => {
+            cov_mark::hit!(unlinked_file_insert_sorted);
+            let offset = next.syntax().text_range().start();
+            let indent = IndentLevel::from_node(next.syntax());
+            (offset, String::new(), format!("\n{indent}"))
+        }
+        Some((last, false)) => {
             cov_mark::hit!(unlinked_file_append_to_existing_mods);
             let offset = last.syntax().text_range().end();
             let indent = IndentLevel::from_node(last.syntax());
-            mod_decl_builder.insert(offset, format!("\n{indent}{mod_decl}"));
-            pub_mod_decl_builder.insert(offset, format!("\n{indent}{pub_mod_decl}"));
+            (offset, format!("\n{indent}"), String::new())
         }
         None => {
             // Prepend before the first item in the file.
-            match items.next() {
+            let first = items.next().map(|item| item.syntax().clone()).or_else(|| match &source {
+                // Items in blocks have to come before the tail expression, so prepend before the
+                // first statement there.
+                ModuleSource::BlockExpr(it) => {
+                    let stmt_list = it.stmt_list()?;
+                    match stmt_list.statements().next() {
+                        Some(stmt) => Some(stmt.syntax().clone()),
+                        None => Some(stmt_list.tail_expr()?.syntax().clone()),
+                    }
+                }
+                _ => None,
+            });
+            match first {
                 Some(first) => {
                     cov_mark::hit!(unlinked_file_prepend_before_first_item);
-                    let offset = first.syntax().text_range().start();
-                    let indent = IndentLevel::from_node(first.syntax());
-                    mod_decl_builder.insert(offset, format!("{mod_decl}\n\n{indent}"));
-                    pub_mod_decl_builder.insert(offset, format!("{pub_mod_decl}\n\n{indent}"));
+                    let offset = first.text_range().start();
+                    let indent = IndentLevel::from_node(&first);
+                    (offset, String::new(), format!("\n\n{indent}"))
                 }
                 None => {
                     // No items in the file, so just append at the end.
@@ -223,36 +828,287 @@ fn is_outline_mod(item: &ast::Item) -> bool {
                             it.item_list()?.r_curly_token()?.text_range().start()
                         }
                         ModuleSource::BlockExpr(it) => {
+                            indent = IndentLevel::from_node(it.syntax()) + 1;
                             it.stmt_list()?.r_curly_token()?.text_range().start()
                         }
                     };
-                    mod_decl_builder.insert(offset, format!("{indent}{mod_decl}\n"));
-                    pub_mod_decl_builder.insert(offset, format!("{indent}{pub_mod_decl}\n"));
+                    (offset, indent.to_string(), "\n".to_owned())
+                }
+            }
+        }
+    };
+
+    // Offer the visibility that paths elsewhere already expect first, so that it gets picked by
+    // default.
+    let parent_module = match &source {
+        ModuleSource::SourceFile(_) => ctx.sema.to_module_def(parent_file_id),
+        ModuleSource::Module(it) => {
+            let file = ctx.sema.parse(parent_file_id);
+            let module = find_node_at_range::<ast::Module>(file.syntax(), it.syntax().text_range());
+            ctx.sema.to_def(&module?)
+        }
+        ModuleSource::BlockExpr(_) => None,
+    };
+    let required_visibility = parent_module.map_or(RequiredVisibility::Private, |parent| {
+        required_visibility(ctx, parent, new_mod_name)
+    });
+    let visibilities: &[(&str, &str)] = match required_visibility {
+        RequiredVisibility::Private => {
+            &[("add_mod_declaration", ""), ("add_pub_mod_declaration", "pub ")]
+        }
+        RequiredVisibility::Crate => {
+            cov_mark::hit!(unlinked_file_prefer_pub_crate_mod);
+            &[
+                ("add_pub_crate_mod_declaration", "pub(crate) "),
+                ("add_mod_declaration", ""),
+                ("add_pub_mod_declaration", "pub "),
+            ]
+        }
+        RequiredVisibility::Public => {
+            cov_mark::hit!(unlinked_file_prefer_pub_mod);
+            &[("add_pub_mod_declaration", "pub "), ("add_mod_declaration", "")]
+        }
+    };
+
+    let path_attr = path_attr.map(|path| format!("#[path = \"{path}\"] ")).unwrap_or_default();
+    let trigger_range = ctx.sema.db.parse(added_file_id).tree().syntax().text_range();
+    let fixes = visibilities
+        .iter()
+        .map(|&(id, visibility)| {
+            let mod_decl = format!("{path_attr}{visibility}mod {new_mod_name};");
+            fix(
+                id,
+                &format!("Insert `{mod_decl}`"),
+                SourceChange::from_text_edit(
+                    parent_file_id,
+                    TextEdit::insert(offset, format!("{before}{mod_decl}{after}")),
+                ),
+                trigger_range,
+            )
+        })
+        .collect();
+    Some(fixes)
+}
+
+/// The visibility that a new `mod` item needs for the existing paths through it to resolve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum RequiredVisibility {
+    Private,
+    Crate,
+    Public,
+}
+
+/// Looks for paths that go through the module `new_mod_name` once it is declared in `parent`, and
+/// returns the visibility the declaration needs for all of them.
+///
+/// Like a usage search, this only looks at the local crates that can see the new module, and only
+/// parses the files that mention its name.
+fn required_visibility(
+    ctx: &DiagnosticsContext<'_>,
+    parent: hir::Module,
+    new_mod_name: &str,
+) -> RequiredVisibility {
+    let db = ctx.sema.db;
+    let krate = parent.krate();
+    let crate_name = krate.display_name(db).map(|name| name.crate_name().to_string());
+    let mut new_mod_path = module_path(db, parent);
+    new_mod_path.push(new_mod_name.to_owned());
+
+    let mut source_roots: Vec<_> = krate
+        .transitive_reverse_dependencies(db)
+        .map(|krate| db.file_source_root(krate.root_file(db)))
+        .collect();
+    source_roots.sort_unstable();
+    source_roots.dedup();
+
+    let mut res = RequiredVisibility::Private;
+    for root in source_roots {
+        let source_root = db.source_root(root);
+        if source_root.is_library {
+            continue;
+        }
+        for file_id in source_root.iter() {
+            if !FileLoader::file_text(db, file_id).contains(new_mod_name) {
+                continue;
+            }
+            let source_file = ctx.sema.parse(file_id);
+            for path in source_file.syntax().descendants().filter_map(ast::Path::cast) {
+                if path.parent_path().is_some() {
+                    continue;
+                }
+                let Some(segments) = path_segments(&path) else { continue };
+                if !segments.iter().any(|segment| segment == new_mod_name) {
+                    continue;
                 }
+                let Some(module) = ctx.sema.scope(path.syntax()).map(|scope| scope.module()) else {
+                    continue;
+                };
+                let same_crate = module.krate() == krate;
+                let module_path = module_path(db, module);
+                let Some(absolute) =
+                    absolute_path(segments, &module_path, same_crate, crate_name.as_deref())
+                else {
+                    continue;
+                };
+                if !absolute.starts_with(&new_mod_path) {
+                    continue;
+                }
+                let required = if !same_crate {
+                    RequiredVisibility::Public
+                } else if module_path.starts_with(&new_mod_path[..new_mod_path.len() - 1]) {
+                    RequiredVisibility::Private
+                } else {
+                    RequiredVisibility::Crate
+                };
+                res = res.max(required);
             }
         }
     }
+    res
+}
 
-    let trigger_range = db.parse(added_file_id).tree().syntax().text_range();
-    Some(vec![
-        fix(
-            "add_mod_declaration",
-            &format!("Insert `{mod_decl}`"),
-            SourceChange::from_text_edit(parent_file_id, mod_decl_builder.finish()),
-            trigger_range,
+/// The names of the modules from the crate root to `module`.
+fn module_path(db: &RootDatabase, module: hir::Module) -> Vec<String> {
+    let mut path: Vec<_> = module
+        .path_to_root(db)
+        .into_iter()
+        .filter_map(|module| Some(module.name(db)?.to_smol_str().to_string()))
+        .collect();
+    path.reverse();
+    path
+}
+
+/// The segments of `path`, including the prefixes of the use trees it is nested in.
+fn path_segments(path: &ast::Path) -> Option<Vec<String>> {
+    let mut paths = vec![path.clone()];
+    paths.extend(
+        path.syntax()
+            .ancestors()
+            .filter_map(ast::UseTree::cast)
+            .skip(1)
+            .filter_map(|use_tree| use_tree.path()),
+    );
+    let mut segments = Vec::new();
+    for path in paths.iter().rev() {
+        for segment in path.segments() {
+            segments.push(match segment.kind()? {
+                ast::PathSegmentKind::Name(name) => name.text().to_string(),
+                ast::PathSegmentKind::SelfKw => "self".to_owned(),
+                ast::PathSegmentKind::SuperKw => "super".to_owned(),
+                ast::PathSegmentKind::CrateKw => "crate".to_owned(),
+                ast::PathSegmentKind::Type { .. } | ast::PathSegmentKind::SelfTypeKw => {
+                    return None
+                }
+            });
+        }
+    }
+    Some(segments)
+}
+
+/// Resolves the module part of `segments`, used in the module at `module_path`, to the
+/// corresponding path from the root of the crate with the new module.
+fn absolute_path(
+    segments: Vec<String>,
+    module_path: &[String],
+    same_crate: bool,
+    crate_name: Option<&str>,
+) -> Option<Vec<String>> {
+    let mut segments = segments.into_iter().peekable();
+    let first = segments.next()?;
+    let mut res = match &*first {
+        "crate" if same_crate => Vec::new(),
+        "self" if same_crate => module_path.to_vec(),
+        "super" if same_crate => {
+            let mut res = module_path.to_vec();
+            res.pop()?;
+            while segments.next_if(|segment| segment == "super").is_some() {
+                res.pop()?;
+            }
+            res
+        }
+        _ if !same_crate => {
+            if Some(&*first) != crate_name {
+                return None;
+            }
+            Vec::new()
+        }
+        // Relative to the module the path is used in.
+        _ => {
+            let mut res = module_path.to_vec();
+            res.push(first);
+            res
+        }
+    };
+    res.extend(segments);
+    Some(res)
+}
+
+/// Offers to apply the `#[cfg]` attributes of `module` only when the `rust_analyzer` cfg is not
+/// set, so that the module is still disabled for the compiler but can be enabled for
+/// rust-analyzer by adding `rust_analyzer` to the `rust-analyzer.cargo.cfgs` setting.
+///
+/// rust-analyzer doesn't set that cfg on its own and assists can't change the settings, so the
+/// label tells the user that the setting is needed as well.
+fn cfg_off_mod_fixes(
+    ctx: &DiagnosticsContext<'_>,
+    parent_file_id: FileId,
+    module: &ast::Module,
+    added_file_id: FileId,
+) -> Option<Vec<Assist>> {
+    let cfgs: Vec<_> =
+        module.attrs().filter(|attr| attr.simple_name().as_deref() == Some("cfg")).collect();
+    let [first, ..] = &*cfgs else {
+        cov_mark::hit!(unlinked_file_skip_fix_when_mod_already_exists);
+        return None;
+    };
+
+    let mut builder = TextEdit::builder();
+    for attr in &cfgs {
+        let meta = attr.meta()?;
+        builder.replace(
+            attr.syntax().text_range(),
+            format!("#[cfg_attr(not(rust_analyzer), {})]", meta.syntax().text()),
+        );
+    }
+
+    let name = module.name()?;
+    let setting = "also requires `rust_analyzer` in the `rust-analyzer.cargo.cfgs` setting";
+    let label = match &*cfgs {
+        [_] => format!(
+            "Apply `{first}` only outside of rust-analyzer to enable `mod {name};` ({setting})"
         ),
-        fix(
-            "add_pub_mod_declaration",
-            &format!("Insert `{pub_mod_decl}`"),
-            SourceChange::from_text_edit(parent_file_id, pub_mod_decl_builder.finish()),
-            trigger_range,
+        _ => format!(
+            "Apply the `#[cfg]` attributes only outside of rust-analyzer to enable `mod {name};` \
+             ({setting})"
         ),
-    ])
+    };
+    let trigger_range = ctx.sema.db.parse(added_file_id).tree().syntax().text_range();
+    Some(vec![fix(
+        "enable_cfg_off_mod_declaration",
+        &label,
+        SourceChange::from_text_edit(parent_file_id, builder.finish()),
+        trigger_range,
+    )])
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::{check_diagnostics, check_fix, check_fixes, check_no_fix};
+    use expect_test::expect;
+    use itertools::Itertools;
+
+    use crate::tests::{
+        check_diagnostics, check_file_system_edit_fix, check_fix, check_fix_with_config,
+        check_fixes, check_no_fix, check_nth_fix,
+    };
+    use crate::{DiagnosticsConfig, InsertModDeclsMode, UnlinkedFile};
+    use ide_db::{
+        assists::AssistResolveStrategy,
+        base_db::{
+            fixture::{ChangeFixture, WithFixture},
+            ModuleRoot, SourceDatabaseExt, VfsPath,
+        },
+        RootDatabase,
+    };
 
     #[test]
     fn unlinked_file_prepend_first_item() {
@@ -391,42 +1247,189 @@ fn unlinked_file_new_style_mod() {
     }
 
     #[test]
-    fn unlinked_file_with_cfg_off() {
-        cov_mark::check!(unlinked_file_skip_fix_when_mod_already_exists);
-        check_no_fix(
+    fn unlinked_file_both_styles_prefers_modrs() {
+        cov_mark::check!(unlinked_file_ranked_parent_candidates);
+        check_fixes(
             r#"
 //- /main.rs
-#[cfg(never)]
-mod foo;
-
-//- /foo.rs
+mod other;
+mod submod;
+#[path = "submod/mod.rs"]
+mod submod_modrs;
+//- /other/mod.rs
+mod nested;
+//- /other/nested.rs
+//- /submod.rs
+// in submod.rs
+//- /submod/mod.rs
+// in mod.rs
+//- /submod/foo.rs
 $0
 "#,
-        );
-    }
-
-    #[test]
-    fn unlinked_file_with_cfg_on() {
-        check_diagnostics(
-            r#"
-//- /main.rs
-#[cfg(not(never))]
+            vec![
+                r#"
+// in mod.rs
 mod foo;
-
-//- /foo.rs
 "#,
+                r#"
+// in mod.rs
+pub mod foo;
+"#,
+                r#"
+// in submod.rs
+mod foo;
+"#,
+                r#"
+// in submod.rs
+pub mod foo;
+"#,
+            ],
         );
     }
 
     #[test]
-    fn unlinked_file_insert_into_inline_simple() {
-        check_fix(
+    fn unlinked_file_both_styles_prefers_new_style() {
+        check_fixes(
             r#"
 //- /main.rs
-mod bar;
-//- /bar.rs
-mod foo {
-}
+mod other;
+mod submod;
+#[path = "submod/mod.rs"]
+mod submod_modrs;
+//- /other.rs
+mod nested;
+//- /other/nested.rs
+//- /submod.rs
+// in submod.rs
+//- /submod/mod.rs
+// in mod.rs
+//- /submod/foo.rs
+$0
+"#,
+            vec![
+                r#"
+// in submod.rs
+mod foo;
+"#,
+                r#"
+// in submod.rs
+pub mod foo;
+"#,
+                r#"
+// in mod.rs
+mod foo;
+"#,
+                r#"
+// in mod.rs
+pub mod foo;
+"#,
+            ],
+        );
+    }
+
+    #[test]
+    fn unlinked_file_with_cfg_off() {
+        check_fix(
+            r#"
+//- /main.rs
+#[cfg(never)]
+mod foo;
+
+//- /foo.rs
+$0
+"#,
+            r#"
+#[cfg_attr(not(rust_analyzer), cfg(never))]
+mod foo;
+
+"#,
+        );
+    }
+
+    #[test]
+    fn unlinked_file_with_multiple_cfgs_off() {
+        check_fix(
+            r#"
+//- /main.rs
+mod inline {
+    #[cfg(never)]
+    #[cfg(feature = "foo")]
+    /// Docs.
+    mod foo;
+}
+
+//- /inline/foo.rs
+$0
+"#,
+            r#"
+mod inline {
+    #[cfg_attr(not(rust_analyzer), cfg(never))]
+    #[cfg_attr(not(rust_analyzer), cfg(feature = "foo"))]
+    /// Docs.
+    mod foo;
+}
+
+"#,
+        );
+    }
+
+    #[test]
+    fn unlinked_file_with_existing_mod_without_cfg() {
+        cov_mark::check!(unlinked_file_skip_fix_when_mod_already_exists);
+        check_no_fix(
+            r#"
+//- /main.rs
+#[path = "bar.rs"]
+mod foo;
+
+//- /bar.rs
+//- /foo.rs
+$0
+"#,
+        );
+    }
+
+    #[test]
+    fn unlinked_file_with_cfg_on() {
+        check_diagnostics(
+            r#"
+//- /main.rs
+#[cfg(not(never))]
+mod foo;
+
+//- /foo.rs
+"#,
+        );
+    }
+
+    #[test]
+    fn included_file_is_not_unlinked() {
+        cov_mark::check!(unlinked_file_included);
+        check_diagnostics(
+            r#"
+//- /main.rs
+#[rustc_builtin_macro]
+macro_rules! include { () => {} }
+
+mod foo;
+fn main() {}
+//- /foo.rs
+include!("generated/items.rs");
+//- /generated/items.rs
+fn generated() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn unlinked_file_insert_into_inline_simple() {
+        check_fix(
+            r#"
+//- /main.rs
+mod bar;
+//- /bar.rs
+mod foo {
+}
 //- /bar/foo/baz.rs
 $0
 "#,
@@ -475,4 +1478,560 @@ mod bar {
 "#,
         );
     }
+
+    #[test]
+    fn unlinked_file_in_src_bin() {
+        check_file_system_edit_fix(
+            r#"
+//- /src/main.rs crate:main
+fn main() {}
+//- /src/bin/foo.rs
+$0fn main() {}
+"#,
+            expect![[r#"
+                [
+                    AppendToFile {
+                        dst: AnchoredPathBuf {
+                            anchor: FileId(
+                                1,
+                            ),
+                            path: "../../Cargo.toml",
+                        },
+                        contents: "\n[[bin]]\nname = \"foo\"\npath = \"src/bin/foo.rs\"\n",
+                    },
+                ]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn unlinked_file_in_examples_dir() {
+        check_file_system_edit_fix(
+            r#"
+//- /src/lib.rs crate:main
+//- /examples/foo/main.rs
+$0fn main() {}
+"#,
+            expect![[r#"
+                [
+                    AppendToFile {
+                        dst: AnchoredPathBuf {
+                            anchor: FileId(
+                                1,
+                            ),
+                            path: "../../Cargo.toml",
+                        },
+                        contents: "\n[[example]]\nname = \"foo\"\npath = \"examples/foo/main.rs\"\n",
+                    },
+                ]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn unlinked_file_parent_with_path_attr() {
+        cov_mark::check!(unlinked_file_parent_with_path_attr);
+        check_fix(
+            r#"
+//- /main.rs
+#[path = "other/m.rs"]
+mod m;
+//- /other/m.rs
+fn f() {}
+//- /other/foo.rs
+$0
+"#,
+            r#"
+mod foo;
+
+fn f() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn unlinked_file_insert_path_attr() {
+        cov_mark::check!(unlinked_file_insert_path_attr);
+        check_fix(
+            r#"
+//- /src/main.rs crate:main
+fn main() {}
+//- /src/foo/bar.rs
+$0
+"#,
+            r#"
+#[path = "foo/bar.rs"] mod bar;
+
+fn main() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn unlinked_file_in_integration_test_dir() {
+        cov_mark::check!(unlinked_file_integration_test_path_attr);
+        check_fix(
+            r#"
+//- /src/lib.rs crate:foo
+//- /tests/a/main.rs crate:a deps:foo
+#[test]
+fn a() {}
+//- /tests/z.rs crate:z deps:foo
+#[test]
+fn z() {}
+//- /tests/support/util.rs
+$0
+"#,
+            r#"
+#[path = "support/util.rs"] mod util;
+
+#[test]
+fn z() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn unlinked_file_in_integration_test_dir_create_main() {
+        check_file_system_edit_fix(
+            r#"
+//- /src/lib.rs crate:main
+//- /tests/smoke/util.rs
+$0
+"#,
+            expect![[r#"
+                [
+                    CreateFile {
+                        dst: AnchoredPathBuf {
+                            anchor: FileId(
+                                1,
+                            ),
+                            path: "main.rs",
+                        },
+                        initial_contents: "mod util;\n",
+                    },
+                ]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn link_all_unlinked_files() {
+        check_nth_fix(
+            2,
+            r#"
+//- /main.rs
+mod existing;
+//- /existing.rs
+//- /foo.rs
+$0
+//- /bar/mod.rs
+//- /bar/baz.rs
+"#,
+            r#"
+mod existing;
+mod foo;
+mod bar;
+"#,
+        );
+    }
+
+    #[test]
+    fn link_all_unlinked_files_into_several_parents() {
+        let (db, file_position) = RootDatabase::with_position(
+            r#"
+//- /main.rs
+mod bar;
+include!("included.rs");
+//- /bar.rs
+//- /foo.rs
+$0
+//- /bar/baz.rs
+//- /bar/qux.rs
+//- /included.rs
+"#,
+        );
+        let config = DiagnosticsConfig::test_sample();
+        let link_all_fix = |resolve| {
+            crate::diagnostics(&db, &config, resolve, file_position.file_id)
+                .into_iter()
+                .find(|it| it.code.as_str() == "unlinked-file")
+                .and_then(|it| it.fixes)
+                .and_then(|fixes| {
+                    fixes
+                        .into_iter()
+                        .find(|fix| fix.id.0 == "add_mod_declarations_for_unlinked_files")
+                })
+                .expect("no fix linking all unlinked files")
+        };
+
+        // The edits are only computed once the fix is resolved.
+        assert!(link_all_fix(&AssistResolveStrategy::None).source_change.is_none());
+
+        // `included.rs` is part of the crate through `include!`, so it isn't linked.
+        let source_change = link_all_fix(&AssistResolveStrategy::All).source_change.unwrap();
+        let edited_files: Vec<_> = source_change
+            .source_file_edits
+            .iter()
+            .map(|(&file_id, edit)| {
+                let mut text = db.file_text(file_id).to_string();
+                edit.apply(&mut text);
+                (file_id, text)
+            })
+            .sorted()
+            .collect();
+        expect![[r#"
+            [
+                (
+                    FileId(
+                        0,
+                    ),
+                    "mod bar;\nmod foo;\ninclude!(\"included.rs\");\n",
+                ),
+                (
+                    FileId(
+                        1,
+                    ),
+                    "mod baz;\nmod qux;\n",
+                ),
+            ]
+        "#]]
+        .assert_debug_eq(&edited_files);
+    }
+
+    #[test]
+    fn no_link_all_fix_for_single_unlinked_file() {
+        check_fixes(
+            r#"
+//- /main.rs
+//- /foo.rs
+$0
+"#,
+            vec![
+                r#"
+mod foo;
+"#,
+                r#"
+pub mod foo;
+"#,
+            ],
+        );
+    }
+
+    #[test]
+    fn no_link_all_fix_without_other_unlinked_files() {
+        // `included.rs` is part of the crate, so `foo.rs` is the only unlinked file.
+        let (db, file_position) = RootDatabase::with_position(
+            r#"
+//- /main.rs
+include!("included.rs");
+//- /foo.rs
+$0
+//- /included.rs
+"#,
+        );
+        let fix_ids: Vec<_> = crate::diagnostics(
+            &db,
+            &DiagnosticsConfig::test_sample(),
+            &AssistResolveStrategy::All,
+            file_position.file_id,
+        )
+        .into_iter()
+        .filter(|it| it.code.as_str() == "unlinked-file")
+        .flat_map(|it| it.fixes.into_iter().flatten())
+        .map(|fix| fix.id.0)
+        .collect();
+        assert!(fix_ids.contains(&"add_mod_declaration"));
+        assert!(!fix_ids.contains(&"add_mod_declarations_for_unlinked_files"));
+    }
+
+    #[test]
+    fn unlinked_file_insert_alphabetically() {
+        cov_mark::check!(unlinked_file_insert_sorted);
+        let mut config = DiagnosticsConfig::test_sample();
+        config.insert_mod_decls = InsertModDeclsMode::Alphabetical;
+        check_fix_with_config(
+            config,
+            r#"
+//- /main.rs
+mod a;
+mod c;
+//- /a.rs
+//- /c.rs
+//- /b.rs
+$0
+"#,
+            r#"
+mod a;
+mod b;
+mod c;
+"#,
+        );
+    }
+
+    #[test]
+    fn unlinked_file_insert_alphabetically_last() {
+        cov_mark::check!(unlinked_file_append_to_existing_mods);
+        let mut config = DiagnosticsConfig::test_sample();
+        config.insert_mod_decls = InsertModDeclsMode::Alphabetical;
+        check_fix_with_config(
+            config,
+            r#"
+//- /main.rs
+mod a;
+fn f() {}
+mod b;
+//- /a.rs
+//- /b.rs
+//- /c.rs
+$0
+"#,
+            r#"
+mod a;
+fn f() {}
+mod b;
+mod c;
+"#,
+        );
+    }
+
+    #[test]
+    fn unlinked_file_insert_top() {
+        cov_mark::check!(unlinked_file_prepend_before_first_item);
+        let mut config = DiagnosticsConfig::test_sample();
+        config.insert_mod_decls = InsertModDeclsMode::Top;
+        check_fix_with_config(
+            config,
+            r#"
+//- /main.rs
+use std::fmt;
+
+mod a;
+//- /a.rs
+//- /b.rs
+$0
+"#,
+            r#"
+mod b;
+
+use std::fmt;
+
+mod a;
+"#,
+        );
+    }
+
+    #[test]
+    fn unlinked_file_insert_in_fn_body() {
+        cov_mark::check!(unlinked_file_insert_in_fn_body);
+        check_nth_fix(
+            2,
+            r#"
+//- /main.rs
+fn main() {}
+
+#[test]
+fn test() {
+    #[path = "helpers.rs"]
+    mod helpers;
+    helpers::run();
+}
+//- /helpers.rs
+pub fn run() {}
+//- /foo.rs
+$0
+"#,
+            r#"
+fn main() {}
+
+#[test]
+fn test() {
+    #[path = "helpers.rs"]
+    mod helpers;
+    #[path = "foo.rs"] mod foo;
+    helpers::run();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn unlinked_files_lists_suggested_parents() {
+        let fixture = ChangeFixture::parse(
+            r#"
+//- /main.rs
+mod bar;
+//- /bar.rs
+//- /foo.rs
+//- /bar/baz.rs
+//- /bar/qux.rs
+"#,
+        );
+        let mut db = RootDatabase::default();
+        db.apply_change(fixture.change);
+        let [main, bar, foo, baz, qux] = fixture.files[..] else { panic!() };
+
+        let unlinked = crate::unlinked_files(&db, &DiagnosticsConfig::test_sample());
+        assert_eq!(
+            unlinked,
+            vec![
+                UnlinkedFile { file_id: foo, suggested_parents: vec![main] },
+                UnlinkedFile { file_id: baz, suggested_parents: vec![bar] },
+                UnlinkedFile { file_id: qux, suggested_parents: vec![bar] },
+            ]
+        );
+    }
+
+    #[test]
+    fn unlinked_file_excluded_from_targets() {
+        cov_mark::check!(unlinked_file_excluded_from_targets);
+        let mut fixture = ChangeFixture::parse(
+            r#"
+//- /src/main.rs crate:main
+fn main() {}
+//- /src/bin/foo.rs
+fn main() {}
+//- /src/bin/bar.rs
+fn main() {}
+"#,
+        );
+        let [_, foo, bar] = fixture.files[..] else { panic!() };
+        let crate_graph = fixture.change.crate_graph.as_mut().unwrap();
+        let krate = crate_graph.iter().next().unwrap();
+        crate_graph.set_excluded_files(krate, vec![foo]);
+        let mut db = RootDatabase::default();
+        db.apply_change(fixture.change);
+
+        let config = DiagnosticsConfig::test_sample();
+        let diagnostics = crate::diagnostics(&db, &config, &AssistResolveStrategy::All, foo);
+        assert!(diagnostics.iter().all(|it| it.code.as_str() != "unlinked-file"));
+
+        let unlinked = crate::unlinked_files(&db, &config);
+        assert_eq!(unlinked, vec![UnlinkedFile { file_id: bar, suggested_parents: vec![] }]);
+    }
+
+    #[test]
+    fn unlinked_file_used_in_parent_stays_private() {
+        check_fix(
+            r#"
+//- /main.rs
+mod bar;
+//- /bar.rs
+use foo::Foo;
+//- /bar/foo.rs
+$0pub struct Foo;
+"#,
+            r#"
+mod foo;
+
+use foo::Foo;
+"#,
+        );
+    }
+
+    #[test]
+    fn unlinked_file_used_elsewhere_in_crate() {
+        cov_mark::check!(unlinked_file_prefer_pub_crate_mod);
+        check_fixes(
+            r#"
+//- /main.rs
+mod bar;
+mod baz;
+//- /bar.rs
+//- /bar/foo.rs
+$0pub struct Foo;
+//- /baz.rs
+use crate::bar::{foo::Foo, other};
+"#,
+            vec![
+                r#"
+pub(crate) mod foo;
+"#,
+                r#"
+mod foo;
+"#,
+                r#"
+pub mod foo;
+"#,
+            ],
+        );
+    }
+
+    #[test]
+    fn unlinked_file_used_via_super() {
+        check_fix(
+            r#"
+//- /main.rs
+mod bar;
+mod baz;
+//- /bar.rs
+//- /bar/foo.rs
+$0pub struct Foo;
+//- /baz.rs
+fn f() {
+    let _ = super::bar::foo::Foo;
+}
+"#,
+            r#"
+pub(crate) mod foo;
+"#,
+        );
+    }
+
+    #[test]
+    fn unlinked_file_used_from_other_crate() {
+        cov_mark::check!(unlinked_file_prefer_pub_mod);
+        check_fixes(
+            r#"
+//- /main.rs crate:main deps:lib
+use lib::bar::foo::Foo;
+//- /lib.rs crate:lib
+pub mod bar;
+//- /bar.rs
+//- /bar/foo.rs
+$0pub struct Foo;
+"#,
+            vec![
+                r#"
+pub mod foo;
+"#,
+                r#"
+mod foo;
+"#,
+            ],
+        );
+    }
+
+    #[test]
+    fn unlinked_file_in_module_root() {
+        cov_mark::check!(unlinked_file_in_module_root);
+        let mut fixture = ChangeFixture::parse(
+            r#"
+//- /src/lib.rs crate:main
+mod generated;
+//- /src/generated.rs
+pub struct Marker;
+//- /out/gen/foo.rs
+pub struct Foo;
+"#,
+        );
+        let [_, generated, foo] = fixture.files[..] else { panic!() };
+        let crate_graph = fixture.change.crate_graph.as_mut().unwrap();
+        let krate = crate_graph.iter().next().unwrap();
+        let root = ModuleRoot {
+            dir: VfsPath::new_virtual_path("/out/gen".to_owned()),
+            module_path: vec!["generated".to_owned()],
+        };
+        crate_graph.set_module_roots(krate, vec![root]);
+        let mut db = RootDatabase::default();
+        db.apply_change(fixture.change);
+
+        let config = DiagnosticsConfig::test_sample();
+        let diagnostics = crate::diagnostics(&db, &config, &AssistResolveStrategy::All, foo);
+        let diagnostic = diagnostics.iter().find(|it| it.code.as_str() == "unlinked-file").unwrap();
+        let fix = &diagnostic.fixes.as_ref().unwrap()[0];
+        let mut actual = db.file_text(generated).to_string();
+        fix.source_change.as_ref().unwrap().source_file_edits[&generated].apply(&mut actual);
+        assert_eq!(actual, "#[path = \"../out/gen/foo.rs\"] mod foo;\n\npub struct Marker;\n");
+    }
 }
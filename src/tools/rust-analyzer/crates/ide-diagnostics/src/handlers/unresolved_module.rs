@@ -1,13 +1,20 @@
 use hir::db::ExpandDatabase;
 use ide_db::{assists::Assist, base_db::AnchoredPathBuf, source_change::FileSystemEdit};
 use itertools::Itertools;
-use syntax::AstNode;
+use syntax::{
+    ast::{self, HasAttrs, HasName},
+    AstNode,
+};
 
 use crate::{fix, Diagnostic, DiagnosticsContext};
 
 // Diagnostic: unresolved-module
 //
 // This diagnostic is triggered if rust-analyzer is unable to discover referred module.
+// Fixes to create the missing module file at each candidate path are offered, this is the
+// counterpart of `unlinked-file` for files that are missing from the module tree. For modules
+// declared with a `#[path]` attribute, the only candidate is the file the attribute points at,
+// which is created with a stub comment.
 pub(crate) fn unresolved_module(
     ctx: &DiagnosticsContext<'_>,
     d: &hir::UnresolvedModule,
@@ -33,6 +40,13 @@ pub(crate) fn unresolved_module(
 fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::UnresolvedModule) -> Option<Vec<Assist>> {
     let root = ctx.sema.db.parse_or_expand(d.decl.file_id);
     let unresolved_module = d.decl.value.to_node(&root);
+    let initial_contents = match path_attr_value(&unresolved_module) {
+        Some(attr_path) => {
+            let name = unresolved_module.name().map_or_else(String::new, |it| it.to_string());
+            format!("// The `{name}` module, declared with `#[path = \"{attr_path}\"]`.\n")
+        }
+        None => String::new(),
+    };
     Some(
         d.candidates
             .iter()
@@ -45,7 +59,7 @@ fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::UnresolvedModule) -> Option<Vec<
                             anchor: d.decl.file_id.original_file(ctx.sema.db),
                             path: candidate.clone(),
                         },
-                        initial_contents: "".to_string(),
+                        initial_contents: initial_contents.clone(),
                     }
                     .into(),
                     unresolved_module.syntax().text_range(),
@@ -55,11 +69,24 @@ fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::UnresolvedModule) -> Option<Vec<
     )
 }
 
+/// The value of the `#[path = "..."]` attribute of `module`, if it has one.
+fn path_attr_value(module: &ast::Module) -> Option<String> {
+    module.attrs().filter(|attr| attr.simple_name().as_deref() == Some("path")).find_map(|attr| {
+        match attr.expr()? {
+            ast::Expr::Literal(lit) => match lit.kind() {
+                ast::LiteralKind::String(it) => Some(it.value()?.into_owned()),
+                _ => None,
+            },
+            _ => None,
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use expect_test::expect;
 
-    use crate::tests::{check_diagnostics, check_expect};
+    use crate::tests::{check_diagnostics, check_expect, check_file_system_edit_fix};
 
     #[test]
     fn unresolved_module() {
@@ -153,4 +180,55 @@ fn test_unresolved_module_diagnostic() {
             "#]],
         );
     }
+
+    #[test]
+    fn create_nested_module_file() {
+        // The module file is created next to the file declaring the module, in a directory
+        // named after the declaring module.
+        check_file_system_edit_fix(
+            r#"
+//- /lib.rs
+mod bar;
+//- /bar.rs
+mod foo$0;
+"#,
+            expect![[r#"
+                [
+                    CreateFile {
+                        dst: AnchoredPathBuf {
+                            anchor: FileId(
+                                1,
+                            ),
+                            path: "bar/foo.rs",
+                        },
+                        initial_contents: "",
+                    },
+                ]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn create_path_attr_module() {
+        check_file_system_edit_fix(
+            r#"
+//- /lib.rs
+#[path = "x/y.rs"]
+mod $0z;
+"#,
+            expect![[r#"
+                [
+                    CreateFile {
+                        dst: AnchoredPathBuf {
+                            anchor: FileId(
+                                0,
+                            ),
+                            path: "x/y.rs",
+                        },
+                        initial_contents: "// The `z` module, declared with `#[path = \"x/y.rs\"]`.\n",
+                    },
+                ]
+            "#]],
+        );
+    }
 }
@@ -0,0 +1,327 @@
+//! Diagnostic emitted for module files none of whose items are used.
+
+use hir::{HasVisibility, Module, ModuleDef, Visibility};
+use ide_db::{
+    base_db::FileId,
+    defs::Definition,
+    source_change::{FileSystemEdit, SourceChange},
+};
+use syntax::{
+    ast::{self, HasModuleItem, HasName},
+    AstNode,
+};
+use text_edit::TextEdit;
+
+use crate::{
+    fix, handlers::duplicate_mod_decl::mod_decl_delete_range, Diagnostic, DiagnosticsContext,
+    Severity,
+};
+
+/// Items with these attributes are used without being named.
+const USED_ATTRS: &[&str] = &["test", "bench", "no_mangle", "export_name", "used"];
+
+// Diagnostic: unused-module-file
+//
+// This diagnostic is shown on `mod` declarations of module files none of whose items are
+// referenced anywhere else in the workspace, so the file could just as well be removed from the
+// module tree. It is the counterpart of `unlinked-file`, which is shown for files that are not
+// part of the module tree.
+pub(crate) fn unused_module_file(
+    ctx: &DiagnosticsContext<'_>,
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+) {
+    let source_file = ctx.sema.parse(file_id);
+    for decl in source_file.syntax().descendants().filter_map(ast::Module::cast) {
+        if decl.item_list().is_some() {
+            continue;
+        }
+        let Some(module) = ctx.sema.to_def(&decl) else { continue };
+        let Some(module_file_id) = module.definition_source(ctx.sema.db).file_id.file_id() else {
+            continue;
+        };
+        if module_file_id == file_id || !is_unused_module_file(ctx, module, module_file_id) {
+            continue;
+        }
+
+        let name = decl.name().map_or_else(String::new, |name| name.to_string());
+        let range = decl.syntax().text_range();
+        let remove_decl = TextEdit::delete(mod_decl_delete_range(&decl));
+        let mut remove_and_delete = SourceChange::from_text_edit(file_id, remove_decl.clone());
+        remove_and_delete.push_file_system_edit(FileSystemEdit::DeleteFile { src: module_file_id });
+        let fixes = vec![
+            fix(
+                "remove_unused_mod_decl",
+                &format!("Remove `mod {name};`"),
+                SourceChange::from_text_edit(file_id, remove_decl),
+                range,
+            ),
+            fix(
+                "remove_unused_mod_decl_and_file",
+                &format!("Remove `mod {name};` and delete the module file"),
+                remove_and_delete,
+                range,
+            ),
+        ];
+
+        acc.push(
+            Diagnostic::new(
+                "unused-module-file",
+                format!("none of the items of module `{name}` are used"),
+                range,
+            )
+            .severity(Severity::WeakWarning)
+            .with_unused(true)
+            .experimental()
+            .with_fixes(Some(fixes)),
+        );
+    }
+}
+
+/// Whether none of the items of `module`, which is defined by the whole file `file_id`, are
+/// referenced outside of that file.
+///
+/// The public API of a library can be used by crates outside of the workspace, so such items
+/// always count as used.
+fn is_unused_module_file(ctx: &DiagnosticsContext<'_>, module: Module, file_id: FileId) -> bool {
+    let db = ctx.sema.db;
+    let in_library = is_library(ctx, module);
+
+    // Impls, trait methods and macros can be used without naming anything in the file, and we
+    // can't see through item macros or nested modules, so such files are never reported.
+    let source_file = ctx.sema.parse(file_id);
+    if source_file.items().any(|item| {
+        matches!(
+            item,
+            ast::Item::Impl(_)
+                | ast::Item::Trait(_)
+                | ast::Item::MacroCall(_)
+                | ast::Item::MacroRules(_)
+                | ast::Item::MacroDef(_)
+                | ast::Item::Module(_)
+                | ast::Item::ExternBlock(_)
+        )
+    }) {
+        return false;
+    }
+
+    let items = module.declarations(db);
+    !items.is_empty()
+        && items.into_iter().all(|item| {
+            let can_be_unused = match item {
+                ModuleDef::Function(_)
+                | ModuleDef::Adt(_)
+                | ModuleDef::Static(_)
+                | ModuleDef::TypeAlias(_) => true,
+                // `const _` items are only there for their side effects.
+                ModuleDef::Const(it) => it.name(db).is_some(),
+                _ => false,
+            };
+            let has_used_attr = item
+                .attrs(db)
+                .map_or(false, |attrs| USED_ATTRS.iter().any(|&key| attrs.by_key(key).exists()));
+            // Items of the file can use each other, that doesn't count.
+            can_be_unused
+                && !has_used_attr
+                && !(in_library && is_public_api(ctx, item))
+                && Definition::from(item)
+                    .usages(&ctx.sema)
+                    .all()
+                    .iter()
+                    .all(|(&usage_file_id, _)| usage_file_id == file_id)
+        })
+}
+
+/// Whether the crate of `module` is a library, i.e. has no `main` function in its root module.
+fn is_library(ctx: &DiagnosticsContext<'_>, module: Module) -> bool {
+    let db = ctx.sema.db;
+    !module
+        .krate()
+        .root_module(db)
+        .declarations(db)
+        .into_iter()
+        .any(|def| matches!(def, ModuleDef::Function(it) if it.name(db).to_smol_str() == "main"))
+}
+
+/// Whether `item` and all of its parent modules are public.
+fn is_public_api(ctx: &DiagnosticsContext<'_>, item: ModuleDef) -> bool {
+    let db = ctx.sema.db;
+    if item.visibility(db) != Visibility::Public {
+        return false;
+    }
+    let mut module = item.module(db);
+    while let Some(it) = module {
+        if it.parent(db).is_some() && it.visibility(db) != Visibility::Public {
+            return false;
+        }
+        module = it.parent(db);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use ide_db::{
+        assists::AssistResolveStrategy, base_db::fixture::WithFixture,
+        source_change::FileSystemEdit, RootDatabase,
+    };
+
+    use crate::{
+        tests::{check_diagnostics, check_fix, check_nth_fix},
+        DiagnosticsConfig,
+    };
+
+    #[test]
+    fn unused_module_file() {
+        check_diagnostics(
+            r#"
+//- /main.rs
+  mod foo;
+//^^^^^^^^ 💡 weak: none of the items of module `foo` are used
+fn main() {}
+//- /foo.rs
+fn unused() {}
+struct Unused;
+"#,
+        );
+    }
+
+    #[test]
+    fn uses_within_the_file_do_not_count() {
+        check_diagnostics(
+            r#"
+//- /main.rs
+  mod foo;
+//^^^^^^^^ 💡 weak: none of the items of module `foo` are used
+//- /foo.rs
+fn a() { b() }
+fn b() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn module_with_used_item() {
+        check_diagnostics(
+            r#"
+//- /main.rs
+mod foo;
+fn main() { foo::used() }
+//- /foo.rs
+pub(crate) fn used() {}
+fn unused() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn module_used_from_other_crate() {
+        check_diagnostics(
+            r#"
+//- /main.rs crate:main deps:lib
+fn main() { lib::foo::used() }
+//- /lib.rs crate:lib
+pub mod foo;
+//- /foo.rs
+pub fn used() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn public_api_of_library() {
+        check_diagnostics(
+            r#"
+//- /lib.rs crate:lib
+pub mod api;
+  pub mod private_items;
+//^^^^^^^^^^^^^^^^^^^^^^ 💡 weak: none of the items of module `private_items` are used
+
+  mod private_module;
+//^^^^^^^^^^^^^^^^^^^ 💡 weak: none of the items of module `private_module` are used
+//- /api.rs
+pub fn api() {}
+//- /private_items.rs
+fn unused() {}
+//- /private_module.rs
+pub fn unreachable() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn modules_with_implicitly_used_items() {
+        check_diagnostics(
+            r#"
+//- /main.rs
+mod exports;
+mod impls;
+mod empty;
+struct S;
+fn main() { S; }
+//- /exports.rs
+#[no_mangle]
+fn exported() {}
+//- /impls.rs
+impl super::S {}
+//- /empty.rs
+"#,
+        );
+    }
+
+    #[test]
+    fn remove_mod_decl() {
+        check_fix(
+            r#"
+//- /main.rs
+mod foo$0;
+fn main() {}
+//- /foo.rs
+fn unused() {}
+"#,
+            r#"
+fn main() {}
+"#,
+        );
+        check_nth_fix(
+            1,
+            r#"
+//- /main.rs
+fn main() {}
+mod foo$0;
+//- /foo.rs
+fn unused() {}
+"#,
+            r#"
+fn main() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn remove_mod_decl_and_delete_file() {
+        let (db, files) = RootDatabase::with_many_files(
+            r#"
+//- /main.rs
+mod foo;
+//- /foo.rs
+fn unused() {}
+"#,
+        );
+        let diagnostic = crate::diagnostics(
+            &db,
+            &DiagnosticsConfig::test_sample(),
+            &AssistResolveStrategy::All,
+            files[0],
+        )
+        .pop()
+        .unwrap();
+        let fix = &diagnostic.fixes.unwrap()[1];
+        let source_change = fix.source_change.as_ref().unwrap();
+        assert!(source_change.source_file_edits.contains_key(&files[0]));
+        assert!(matches!(
+            source_change.file_system_edits[..],
+            [FileSystemEdit::DeleteFile { src }] if src == files[1]
+        ));
+    }
+}
@@ -27,6 +27,8 @@
 
 mod handlers {
     pub(crate) mod break_outside_of_loop;
+    pub(crate) mod const_pattern_refers_to_static;
+    pub(crate) mod cyclic_module;
     pub(crate) mod expected_function;
     pub(crate) mod inactive_code;
     pub(crate) mod incoherent_impl;
@@ -60,7 +62,9 @@ mod handlers {
     // The handlers below are unusual, the implement the diagnostics as well.
     pub(crate) mod field_shorthand;
     pub(crate) mod useless_braces;
+    pub(crate) mod duplicate_mod_decl;
     pub(crate) mod unlinked_file;
+    pub(crate) mod unused_module_file;
     pub(crate) mod json_is_not_rust;
 }
 
@@ -70,7 +74,7 @@ mod handlers {
 use hir::{diagnostics::AnyDiagnostic, InFile, Semantics};
 use ide_db::{
     assists::{Assist, AssistId, AssistKind, AssistResolveStrategy},
-    base_db::{FileId, FileRange, SourceDatabase},
+    base_db::{FileId, FileRange, SourceDatabase, SourceDatabaseExt},
     imports::insert_use::InsertUseConfig,
     label::Label,
     source_change::SourceChange,
@@ -78,6 +82,8 @@ mod handlers {
 };
 use syntax::{algo::find_node_at_range, ast::AstNode, SyntaxNodePtr, TextRange};
 
+pub use crate::handlers::unlinked_file::UnlinkedFile;
+
 // FIXME: Make this an enum
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct DiagnosticCode(pub &'static str);
@@ -134,11 +140,11 @@ fn with_unused(mut self, unused: bool) -> Diagnostic {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Severity {
     Error,
-    // We don't actually emit this one yet, but we should at some point.
-    // Warning,
+    /// Not emitted by any diagnostic itself, only through a [`DiagnosticsOverride`].
+    Warning,
     WeakWarning,
 }
 
@@ -153,13 +159,34 @@ fn default() -> Self {
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InsertModDeclsMode {
+    #[default]
+    AfterExisting,
+    Alphabetical,
+    Top,
+}
+
+/// Changes the severity of the diagnostics with a given code in the files matching a glob.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiagnosticsOverride {
+    /// Supports `*`, `?` and `**`. Globs without a leading `/` can match at any directory.
+    pub glob: String,
+    pub code: String,
+    /// `None` disables the diagnostic.
+    pub severity: Option<Severity>,
+}
+
 #[derive(Debug, Clone)]
 pub struct DiagnosticsConfig {
     pub proc_macros_enabled: bool,
     pub proc_attr_macros_enabled: bool,
     pub disable_experimental: bool,
     pub disabled: FxHashSet<String>,
+    /// Later overrides take precedence over earlier ones.
+    pub overrides: Vec<DiagnosticsOverride>,
     pub expr_fill_default: ExprFillDefaultMode,
+    pub insert_mod_decls: InsertModDeclsMode,
     // FIXME: We may want to include a whole `AssistConfig` here
     pub insert_use: InsertUseConfig,
     pub prefer_no_std: bool,
@@ -175,7 +202,9 @@ pub fn test_sample() -> Self {
             proc_attr_macros_enabled: Default::default(),
             disable_experimental: Default::default(),
             disabled: Default::default(),
+            overrides: Default::default(),
             expr_fill_default: Default::default(),
+            insert_mod_decls: Default::default(),
             insert_use: InsertUseConfig {
                 granularity: ImportGranularity::Preserve,
                 enforce_granularity: false,
@@ -216,6 +245,18 @@ fn resolve_precise_location(
     }
 }
 
+/// Lists the files in the workspace that are not part of any crate, along with the files that the
+/// fixes of their `unlinked-file` diagnostics would declare them in.
+pub fn unlinked_files(db: &RootDatabase, config: &DiagnosticsConfig) -> Vec<UnlinkedFile> {
+    let _p = profile::span("unlinked_files");
+    let ctx = DiagnosticsContext {
+        config,
+        sema: Semantics::new(db),
+        resolve: &AssistResolveStrategy::All,
+    };
+    handlers::unlinked_file::unlinked_files(&ctx)
+}
+
 pub fn diagnostics(
     db: &RootDatabase,
     config: &DiagnosticsConfig,
@@ -247,6 +288,12 @@ pub fn diagnostics(
     let ctx = DiagnosticsContext { config, sema, resolve };
     if module.is_none() {
         handlers::unlinked_file::unlinked_file(&ctx, &mut res, file_id);
+    } else {
+        handlers::duplicate_mod_decl::duplicate_mod_decl(&ctx, &mut res, file_id);
+        // This searches the whole workspace, so only do it when the result would be shown.
+        if !ctx.config.disable_experimental && !ctx.config.disabled.contains("unused-module-file") {
+            handlers::unused_module_file::unused_module_file(&ctx, &mut res, file_id);
+        }
     }
 
     let mut diags = Vec::new();
@@ -257,6 +304,8 @@ pub fn diagnostics(
     for diag in diags {
         #[rustfmt::skip]
         let d = match diag {
+            AnyDiagnostic::ConstPatternRefersToStatic(d) => handlers::const_pattern_refers_to_static::const_pattern_refers_to_static(&ctx, &d),
+            AnyDiagnostic::CyclicModule(d) => handlers::cyclic_module::cyclic_module(&ctx, &d),
             AnyDiagnostic::ExpectedFunction(d) => handlers::expected_function::expected_function(&ctx, &d),
             AnyDiagnostic::InactiveCode(d) => match handlers::inactive_code::inactive_code(&ctx, &d) {
                 Some(it) => it,
@@ -313,10 +362,64 @@ pub fn diagnostics(
         !ctx.config.disabled.contains(d.code.as_str())
             && !(ctx.config.disable_experimental && d.experimental)
     });
+    apply_overrides(db, ctx.config, file_id, &mut res);
 
     res
 }
 
+fn apply_overrides(
+    db: &RootDatabase,
+    config: &DiagnosticsConfig,
+    file_id: FileId,
+    res: &mut Vec<Diagnostic>,
+) {
+    if config.overrides.is_empty() {
+        return;
+    }
+    let source_root = db.source_root(db.file_source_root(file_id));
+    let Some(path) = source_root.path_for_file(&file_id) else { return };
+    let path = path.to_string().replace('\\', "/");
+    let overrides: Vec<_> =
+        config.overrides.iter().filter(|it| glob_matches(&it.glob, &path)).collect();
+
+    res.retain_mut(|d| match overrides.iter().rev().find(|it| it.code == d.code.as_str()) {
+        Some(DiagnosticsOverride { severity: Some(severity), .. }) => {
+            d.severity = *severity;
+            true
+        }
+        Some(DiagnosticsOverride { severity: None, .. }) => false,
+        None => true,
+    });
+}
+
+/// Matches `path` against `glob`. `*` and `?` don't match `/`, while `**` matches any number of
+/// path components.
+fn glob_matches(glob: &str, path: &str) -> bool {
+    fn matches(glob: &[u8], path: &[u8]) -> bool {
+        match glob {
+            [] => path.is_empty(),
+            [b'*', b'*', rest @ ..] => match rest {
+                [] => true,
+                [b'/', rest @ ..] => (0..=path.len())
+                    .filter(|&i| i == 0 || path[i - 1] == b'/')
+                    .any(|i| matches(rest, &path[i..])),
+                _ => (0..=path.len()).any(|i| matches(rest, &path[i..])),
+            },
+            [b'*', rest @ ..] => (0..=path.len())
+                .take_while(|&i| i == 0 || path[i - 1] != b'/')
+                .any(|i| matches(rest, &path[i..])),
+            [b'?', rest @ ..] => matches!(path, [c, ..] if *c != b'/') && matches(rest, &path[1..]),
+            [c, rest @ ..] => path.first() == Some(c) && matches(rest, &path[1..]),
+        }
+    }
+
+    if glob.starts_with('/') {
+        matches(glob.as_bytes(), path.as_bytes())
+    } else {
+        matches(format!("**/{glob}").as_bytes(), path.as_bytes())
+    }
+}
+
 fn fix(id: &'static str, label: &str, source_change: SourceChange, target: TextRange) -> Assist {
     let mut res = unresolved_fix(id, label, target);
     res.source_change = Some(source_change);
@@ -10,7 +10,7 @@
 use stdx::trim_indent;
 use test_utils::{assert_eq_text, extract_annotations, MiniCore};
 
-use crate::{DiagnosticsConfig, ExprFillDefaultMode, Severity};
+use crate::{DiagnosticsConfig, DiagnosticsOverride, ExprFillDefaultMode, Severity};
 
 /// Takes a multi-file input fixture with annotated cursor positions,
 /// and checks that:
@@ -33,12 +33,31 @@ pub(crate) fn check_fixes(ra_fixture_before: &str, ra_fixtures_after: Vec<&str>)
 }
 
 #[track_caller]
-fn check_nth_fix(nth: usize, ra_fixture_before: &str, ra_fixture_after: &str) {
+pub(crate) fn check_nth_fix(nth: usize, ra_fixture_before: &str, ra_fixture_after: &str) {
+    let mut conf = DiagnosticsConfig::test_sample();
+    conf.expr_fill_default = ExprFillDefaultMode::Default;
+    check_nth_fix_with_config(conf, nth, ra_fixture_before, ra_fixture_after)
+}
+
+#[track_caller]
+pub(crate) fn check_fix_with_config(
+    config: DiagnosticsConfig,
+    ra_fixture_before: &str,
+    ra_fixture_after: &str,
+) {
+    check_nth_fix_with_config(config, 0, ra_fixture_before, ra_fixture_after)
+}
+
+#[track_caller]
+fn check_nth_fix_with_config(
+    conf: DiagnosticsConfig,
+    nth: usize,
+    ra_fixture_before: &str,
+    ra_fixture_after: &str,
+) {
     let after = trim_indent(ra_fixture_after);
 
     let (db, file_position) = RootDatabase::with_position(ra_fixture_before);
-    let mut conf = DiagnosticsConfig::test_sample();
-    conf.expr_fill_default = ExprFillDefaultMode::Default;
     let diagnostic =
         super::diagnostics(&db, &conf, &AssistResolveStrategy::All, file_position.file_id)
             .pop()
@@ -64,6 +83,32 @@ fn check_nth_fix(nth: usize, ra_fixture_before: &str, ra_fixture_after: &str) {
     assert_eq_text!(&after, &actual);
 }
 
+/// Like [`check_fix`], but for fixes which only consist of file system edits, which are
+/// checked against `expect`.
+#[track_caller]
+pub(crate) fn check_file_system_edit_fix(ra_fixture: &str, expect: Expect) {
+    let (db, file_position) = RootDatabase::with_position(ra_fixture);
+    let diagnostic = super::diagnostics(
+        &db,
+        &DiagnosticsConfig::test_sample(),
+        &AssistResolveStrategy::All,
+        file_position.file_id,
+    )
+    .pop()
+    .expect("no diagnostics");
+    let fix = &diagnostic.fixes.expect("diagnostic misses fixes")[0];
+    let source_change = fix.source_change.as_ref().unwrap();
+
+    assert!(
+        fix.target.contains_inclusive(file_position.offset),
+        "diagnostic fix range {:?} does not touch cursor position {:?}",
+        fix.target,
+        file_position.offset
+    );
+    assert!(source_change.source_file_edits.is_empty());
+    expect.assert_debug_eq(&source_change.file_system_edits);
+}
+
 /// Checks that there's a diagnostic *without* fix at `$0`.
 pub(crate) fn check_no_fix(ra_fixture: &str) {
     let (db, file_position) = RootDatabase::with_position(ra_fixture);
@@ -113,6 +158,7 @@ pub(crate) fn check_diagnostics_with_config(config: DiagnosticsConfig, ra_fixtur
                 }
                 annotation.push_str(match d.severity {
                     Severity::Error => "error",
+                    Severity::Warning => "warn",
                     Severity::WeakWarning => "weak",
                 });
                 annotation.push_str(": ");
@@ -153,6 +199,74 @@ fn test_disabled_diagnostics() {
     assert!(!diagnostics.is_empty());
 }
 
+#[test]
+fn test_diagnostics_overrides() {
+    let (db, files) = RootDatabase::with_many_files(
+        r#"
+//- /lib.rs
+mod foo;
+mod benches;
+//- /benches/mod.rs
+mod bar;
+//- /benches/nested/baz.rs
+"#,
+    );
+    let codes_and_severities = |config: &DiagnosticsConfig| {
+        files
+            .iter()
+            .map(|&file_id| {
+                super::diagnostics(&db, config, &AssistResolveStrategy::All, file_id)
+                    .into_iter()
+                    .map(|d| (d.code.0, d.severity))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut config = DiagnosticsConfig::test_sample();
+    config.overrides.push(DiagnosticsOverride {
+        glob: "benches/**".into(),
+        code: "unlinked-file".into(),
+        severity: None,
+    });
+    config.overrides.push(DiagnosticsOverride {
+        glob: "benches/*.rs".into(),
+        code: "unresolved-module".into(),
+        severity: Some(Severity::Warning),
+    });
+    assert_eq!(
+        codes_and_severities(&config),
+        [
+            vec![("unresolved-module", Severity::Error)],
+            vec![("unresolved-module", Severity::Warning)],
+            vec![],
+        ]
+    );
+
+    // Later overrides win.
+    config.overrides.push(DiagnosticsOverride {
+        glob: "/benches/nested/*".into(),
+        code: "unlinked-file".into(),
+        severity: Some(Severity::WeakWarning),
+    });
+    assert_eq!(codes_and_severities(&config)[2], [("unlinked-file", Severity::WeakWarning)]);
+}
+
+#[test]
+fn test_glob_matches() {
+    use crate::glob_matches;
+
+    assert!(glob_matches("benches/**", "/home/user/project/benches/foo/bar.rs"));
+    assert!(glob_matches("benches/**", "/benches/foo.rs"));
+    assert!(!glob_matches("benches/**", "/src/benches.rs"));
+    assert!(glob_matches("src/**/mod.rs", "/project/src/mod.rs"));
+    assert!(glob_matches("src/**/mod.rs", "/project/src/a/b/mod.rs"));
+    assert!(!glob_matches("src/**/mod.rs", "/project/src/a/xmod.rs"));
+    assert!(glob_matches("*.rs", "/project/build.rs"));
+    assert!(!glob_matches("/*.rs", "/project/build.rs"));
+    assert!(glob_matches("/project/?uild.rs", "/project/build.rs"));
+}
+
 #[test]
 fn minicore_smoke_test() {
     fn check(minicore: MiniCore) {
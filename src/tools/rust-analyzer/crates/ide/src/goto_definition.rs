@@ -1951,6 +1951,78 @@ fn f() {
         );
     }
 
+    #[test]
+    fn goto_const_trait_method_in_const_context() {
+        check(
+            r#"
+#[const_trait]
+trait Trait {
+    fn f(&self);
+}
+struct S;
+struct T;
+impl const Trait for S {
+    fn f(&self) {}
+     //^
+}
+impl Trait for T {
+    fn f(&self) {}
+}
+
+const fn f() {
+    S.f$0();
+}
+"#,
+        );
+        check(
+            r#"
+#[const_trait]
+trait Trait {
+    fn f(&self);
+}
+struct S;
+impl const Trait for S {
+    fn f(&self) {}
+     //^
+}
+
+const C: () = <S as Trait>::f$0(&S);
+"#,
+        );
+        check(
+            r#"
+//- minicore: add
+struct S;
+impl const core::ops::Add for S {
+    type Output = S;
+    fn add(self, _: S) -> S {
+     //^^^
+        self
+    }
+}
+
+const C: S = S +$0 S;
+"#,
+        );
+    }
+
+    #[test]
+    fn goto_const_trait_method_through_tilde_const_bound() {
+        check(
+            r#"
+#[const_trait]
+trait Trait {
+    fn f(&self);
+     //^
+}
+
+const fn f<T: ~const Trait>(t: &T) {
+    t.f$0();
+}
+"#,
+        );
+    }
+
     #[test]
     fn path_call_multiple_trait_impl() {
         check(
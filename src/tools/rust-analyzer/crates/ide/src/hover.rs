@@ -31,6 +31,7 @@ pub struct HoverConfig {
     pub documentation: bool,
     pub keywords: bool,
     pub format: HoverDocFormat,
+    pub expand_trait_bounds: bool,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -295,6 +296,21 @@ pub(crate) fn hover_for_definition(
         _ => None,
     };
     render::definition(sema.db, definition, famous_defs.as_ref(), config).map(|markup| {
+        let markup = match definition {
+            Definition::Function(it) => match render::const_call(sema, it, node) {
+                Some(note) => Markup::from(format!("{}\n___\n\n{note}", markup.as_str())),
+                None => markup,
+            },
+            Definition::GenericParam(hir::GenericParam::TypeParam(it))
+                if config.expand_trait_bounds =>
+            {
+                match render::implied_bounds(sema.db, it) {
+                    Some(bounds) => Markup::from(format!("{}\n___\n\n{bounds}", markup.as_str())),
+                    None => markup,
+                }
+            }
+            _ => markup,
+        };
         HoverResult {
             markup: render::process_markup(sema.db, definition, &markup, config),
             actions: [
@@ -18,10 +18,10 @@
 use stdx::format_to;
 use syntax::{
     algo,
-    ast::{self, RecordPat},
+    ast::{self, HasGenericParams, HasName, HasTypeBounds, RecordPat},
     match_ast, AstNode, Direction,
     SyntaxKind::{LET_EXPR, LET_STMT},
-    SyntaxToken, T,
+    SyntaxNode, SyntaxToken, T,
 };
 
 use crate::{
@@ -495,6 +495,157 @@ pub(super) fn definition(
     markup(docs, label, mod_path)
 }
 
+/// Describes how the `~const` bounds of a called function are treated at the call site `node`,
+/// and whether a method of a `#[const_trait]` trait resolves to a `const` impl.
+pub(super) fn const_call(
+    sema: &Semantics<'_, RootDatabase>,
+    func: hir::Function,
+    node: &SyntaxNode,
+) -> Option<String> {
+    let db = sema.db;
+    if !is_callee(node) {
+        return None;
+    }
+
+    let source = func.source(db)?.value;
+    let tilde_const = |bounds: Option<ast::TypeBoundList>| {
+        bounds
+            .into_iter()
+            .flat_map(|it| it.bounds())
+            .filter(|bound| bound.tilde_token().is_some() && bound.const_token().is_some())
+            .collect::<Vec<_>>()
+    };
+    let mut bounds = Vec::new();
+    for param in source.generic_param_list().into_iter().flat_map(|it| it.type_or_const_params()) {
+        if let ast::TypeOrConstParam::Type(param) = param {
+            let Some(name) = param.name() else { continue };
+            bounds.extend(
+                tilde_const(param.type_bound_list()).into_iter().map(|it| format!("{name}: {it}")),
+            );
+        }
+    }
+    for pred in source.where_clause().into_iter().flat_map(|it| it.predicates()) {
+        let Some(ty) = pred.ty() else { continue };
+        bounds.extend(
+            tilde_const(pred.type_bound_list()).into_iter().map(|it| format!("{ty}: {it}")),
+        );
+    }
+
+    let const_impl = match func.as_assoc_item(db).map(|it| it.container(db)) {
+        Some(hir::AssocItemContainer::Impl(impl_)) => impl_
+            .trait_(db)
+            .filter(|trait_| trait_.attrs(db).by_key("const_trait").exists())
+            .and_then(|trait_| {
+                let is_const = impl_.source(db)?.value.const_token().is_some();
+                Some((trait_, is_const))
+            }),
+        Some(hir::AssocItemContainer::Trait(_)) | None => None,
+    };
+    if bounds.is_empty() && const_impl.is_none() {
+        return None;
+    }
+
+    let in_const_context = is_in_const_context(node);
+    let mut buf = String::from(if in_const_context {
+        "Called in a const context"
+    } else {
+        "Called outside of a const context"
+    });
+    if let Some((trait_, is_const)) = const_impl {
+        let trait_ = trait_.name(db);
+        match (is_const, in_const_context) {
+            (true, _) => {
+                format_to!(buf, ", resolves to a `const` impl of `{}`", trait_.display(db))
+            }
+            (false, true) => format_to!(
+                buf,
+                ", resolves to a non-const impl of `{}`, which cannot be called here",
+                trait_.display(db)
+            ),
+            (false, false) => {
+                format_to!(buf, ", resolves to a non-const impl of `{}`", trait_.display(db))
+            }
+        }
+    }
+    buf.push('.');
+    if !bounds.is_empty() {
+        let bounds = bounds.iter().map(|it| format!("`{it}`")).join(", ");
+        if in_const_context {
+            format_to!(buf, "\n\n`~const` bounds must be satisfied by `const` impls: {bounds}");
+        } else {
+            format_to!(buf, "\n\n`~const` bounds are treated as plain bounds: {bounds}");
+        }
+    }
+    Some(buf)
+}
+
+/// Lists the bounds that the trait bounds on `param` imply, like `T: PartialEq` for `T: Eq`.
+pub(super) fn implied_bounds(db: &RootDatabase, param: hir::TypeParam) -> Option<String> {
+    let bounds = param.implied_bounds(db);
+    if bounds.is_empty() {
+        return None;
+    }
+    let name = param.name(db);
+    let bounds = bounds
+        .iter()
+        .map(|bound| match bound {
+            hir::ImpliedBound::Trait(trait_ref) => trait_ref.display(db).to_string(),
+            hir::ImpliedBound::Static => format!("{}: 'static", name.display(db)),
+        })
+        .join(",\n");
+    Some(format!("Implied bounds:\n```rust\n{bounds}\n```"))
+}
+
+/// Returns whether `node` names the function of a call or method call expression.
+fn is_callee(node: &SyntaxNode) -> bool {
+    let Some(name_ref) = ast::NameRef::cast(node.clone()) else { return false };
+    if let Some(call) = name_ref.syntax().parent().and_then(ast::MethodCallExpr::cast) {
+        return call.name_ref().as_ref() == Some(&name_ref);
+    }
+    let Some(path_expr) = name_ref.syntax().ancestors().find_map(ast::PathExpr::cast) else {
+        return false;
+    };
+    path_expr
+        .syntax()
+        .parent()
+        .and_then(ast::CallExpr::cast)
+        .and_then(|call| call.expr())
+        .map_or(false, |callee| callee.syntax() == path_expr.syntax())
+}
+
+/// Returns whether `node` is evaluated at compile time, i.e. is part of a `const fn`, the
+/// initializer of a `const` or `static`, a const argument or a `const` block.
+fn is_in_const_context(node: &SyntaxNode) -> bool {
+    for ancestor in node.ancestors() {
+        match_ast! {
+            match ancestor {
+                ast::Fn(it) => return it.const_token().is_some() || is_in_const_impl(&it),
+                ast::ClosureExpr(_) => return false,
+                ast::Const(_) => return true,
+                ast::Static(_) => return true,
+                ast::ConstArg(_) => return true,
+                ast::Variant(_) => return true,
+                ast::BlockExpr(it) => if it.const_token().is_some() {
+                    return true;
+                },
+                _ => (),
+            }
+        }
+    }
+    false
+}
+
+/// Whether `func` is an associated function of an `impl const Trait for T`, whose bodies are
+/// const-checked just like those of `const fn`s.
+fn is_in_const_impl(func: &ast::Fn) -> bool {
+    func.syntax()
+        .parent()
+        .and_then(ast::AssocItemList::cast)
+        .and_then(|it| it.syntax().parent())
+        .and_then(ast::Impl::cast)
+        .map_or(false, |it| it.const_token().is_some())
+}
+
 fn type_info(
     sema: &Semantics<'_, RootDatabase>,
     config: &HoverConfig,
@@ -17,6 +17,7 @@
     documentation: true,
     format: HoverDocFormat::Markdown,
     keywords: true,
+    expand_trait_bounds: false,
 };
 
 fn check_hover_no_result(ra_fixture: &str) {
@@ -82,6 +83,23 @@ fn check_hover_no_memory_layout(ra_fixture: &str, expect: Expect) {
     expect.assert_eq(&actual)
 }
 
+fn check_hover_expand_trait_bounds(ra_fixture: &str, expect: Expect) {
+    let (analysis, position) = fixture::position(ra_fixture);
+    let hover = analysis
+        .hover(
+            &HoverConfig { expand_trait_bounds: true, ..HOVER_BASE_CONFIG },
+            FileRange { file_id: position.file_id, range: TextRange::empty(position.offset) },
+        )
+        .unwrap()
+        .unwrap();
+
+    let content = analysis.db.file_text(position.file_id);
+    let hovered_element = &content[hover.range];
+
+    let actual = format!("*{hovered_element}*\n{}\n", hover.info.markup);
+    expect.assert_eq(&actual)
+}
+
 fn check_hover_no_markdown(ra_fixture: &str, expect: Expect) {
     let (analysis, position) = fixture::position(ra_fixture);
     let hover = analysis
@@ -3575,6 +3593,89 @@ impl<T: 'static> Foo<T$0> {}
     );
 }
 
+#[test]
+fn hover_type_param_implied_bounds() {
+    check_hover_expand_trait_bounds(
+        r#"
+//- minicore: sized
+trait Base: 'static {}
+trait Super<U>: Base {}
+trait Trait: Super<u32> + Base {}
+trait Other {}
+struct Foo<T>(T);
+impl<T: Trait + Other> Foo<T$0> {}
+"#,
+        expect![[r#"
+            *T*
+
+            ```rust
+            T: Trait + Other
+            ```
+
+            ---
+
+            Implied bounds:
+
+            ```rust
+            T: Super<u32>,
+            T: Base,
+            T: 'static
+            ```
+        "#]],
+    );
+}
+
+#[test]
+fn hover_type_param_implied_bounds_with_different_substitutions() {
+    check_hover_expand_trait_bounds(
+        r#"
+//- minicore: sized
+trait Sup<X> {}
+trait Tr<X>: Sup<X> {}
+trait Trait: Tr<u8> + Tr<u16> {}
+struct Foo<T>(T);
+impl<T: Trait> Foo<T$0> {}
+"#,
+        expect![[r#"
+            *T*
+
+            ```rust
+            T: Trait
+            ```
+
+            ---
+
+            Implied bounds:
+
+            ```rust
+            T: Tr<u8>,
+            T: Tr<u16>,
+            T: Sup<u8>,
+            T: Sup<u16>
+            ```
+        "#]],
+    );
+}
+
+#[test]
+fn hover_type_param_no_implied_bounds() {
+    check_hover_expand_trait_bounds(
+        r#"
+//- minicore: sized
+trait Trait {}
+struct Foo<T>(T);
+impl<T: Trait> Foo<T$0> {}
+"#,
+        expect![[r#"
+            *T*
+
+            ```rust
+            T: Trait
+            ```
+        "#]],
+    );
+}
+
 #[test]
 fn hover_type_param_sized_bounds() {
     // implicit `: Sized` bound
@@ -6375,3 +6476,198 @@ fn test() {
         "#]],
     );
 }
+
+#[test]
+fn hover_const_trait_method_const_impl_in_const_fn() {
+    check(
+        r#"
+#[const_trait]
+trait Tr {
+    fn func(&self);
+}
+struct S;
+impl const Tr for S {
+    fn func(&self) {}
+}
+const fn f() {
+    S.fu$0nc();
+}
+"#,
+        expect![[r#"
+            *func*
+
+            ```rust
+            test::S
+            ```
+
+            ```rust
+            fn func(&self)
+            ```
+
+            ---
+
+            Called in a const context, resolves to a `const` impl of `Tr`.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_const_trait_method_non_const_impl_in_const_fn() {
+    check(
+        r#"
+#[const_trait]
+trait Tr {
+    fn func(&self);
+}
+struct S;
+impl Tr for S {
+    fn func(&self) {}
+}
+const fn f() {
+    S.fu$0nc();
+}
+"#,
+        expect![[r#"
+            *func*
+
+            ```rust
+            test::S
+            ```
+
+            ```rust
+            fn func(&self)
+            ```
+
+            ---
+
+            Called in a const context, resolves to a non-const impl of `Tr`, which cannot be called here.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_const_trait_method_in_const_impl() {
+    check(
+        r#"
+#[const_trait]
+trait Tr {
+    fn func(&self);
+}
+#[const_trait]
+trait Other {
+    fn other(&self);
+}
+struct S;
+impl const Tr for S {
+    fn func(&self) {}
+}
+impl const Other for S {
+    fn other(&self) {
+        self.fu$0nc();
+    }
+}
+"#,
+        expect![[r#"
+            *func*
+
+            ```rust
+            test::S
+            ```
+
+            ```rust
+            fn func(&self)
+            ```
+
+            ---
+
+            Called in a const context, resolves to a `const` impl of `Tr`.
+        "#]],
+    );
+}
+
+#[test]
+fn hover_tilde_const_bounds() {
+    check(
+        r#"
+#[const_trait]
+trait Bar {}
+const fn foo<T: ~const Bar>(t: T) {}
+fn main() {
+    fo$0o(());
+}
+"#,
+        expect![[r#"
+            *foo*
+
+            ```rust
+            test
+            ```
+
+            ```rust
+            const fn foo<T>(t: T)
+            where
+                T: Bar,
+            ```
+
+            ---
+
+            Called outside of a const context.
+
+            `~const` bounds are treated as plain bounds: `T: ~const Bar`
+        "#]],
+    );
+    check(
+        r#"
+#[const_trait]
+trait Bar {}
+const fn foo<T>(t: T) where T: ~const Bar {}
+const C: () = fo$0o(());
+"#,
+        expect![[r#"
+            *foo*
+
+            ```rust
+            test
+            ```
+
+            ```rust
+            const fn foo<T>(t: T)
+            where
+                T: Bar,
+            ```
+
+            ---
+
+            Called in a const context.
+
+            `~const` bounds must be satisfied by `const` impls: `T: ~const Bar`
+        "#]],
+    );
+}
+
+#[test]
+fn hover_const_trait_method_definition_has_no_const_note() {
+    check(
+        r#"
+#[const_trait]
+trait Tr {
+    fn func(&self);
+}
+struct S;
+impl const Tr for S {
+    fn fu$0nc(&self) {}
+}
+"#,
+        expect![[r#"
+            *func*
+
+            ```rust
+            test::S
+            ```
+
+            ```rust
+            fn func(&self)
+            ```
+        "#]],
+    );
+}
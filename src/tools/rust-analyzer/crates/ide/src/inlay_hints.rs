@@ -29,6 +29,7 @@
 mod closure_captures;
 mod discriminant;
 mod fn_lifetime_fn;
+mod implicit_sized;
 mod implicit_static;
 mod param_name;
 
@@ -45,6 +46,7 @@ pub struct InlayHintsConfig {
     pub closure_return_type_hints: ClosureReturnTypeHints,
     pub closure_capture_hints: bool,
     pub binding_mode_hints: bool,
+    pub implicit_sized_bound_hints: bool,
     pub lifetime_elision_hints: LifetimeElisionHints,
     pub param_names_for_lifetime_elision_hints: bool,
     pub hide_named_constructor_hints: bool,
@@ -99,6 +101,7 @@ pub enum InlayKind {
     ClosureCapture,
     Discriminant,
     GenericParamList,
+    ImplicitSizedBound,
     Lifetime,
     Parameter,
     Type,
@@ -471,6 +474,7 @@ fn hints(
                 }
                 Some(())
             },
+            ast::GenericParamList(it) => implicit_sized::hints(hints, config, it),
             ast::Item(it) => match it {
                 // FIXME: record impl lifetimes so they aren't being reused in assoc item lifetime inlay hints
                 ast::Item::Impl(_) => None,
@@ -479,6 +483,7 @@ fn hints(
                 ast::Item::Static(it) => implicit_static::hints(hints, config, Either::Left(it)),
                 ast::Item::Const(it) => implicit_static::hints(hints, config, Either::Right(it)),
                 ast::Item::Enum(it) => discriminant::enum_hints(hints, famous_defs, config, file_id, it),
+                ast::Item::TypeAlias(it) => implicit_sized::assoc_type_hints(hints, config, it),
                 _ => None,
             },
             // FIXME: fn-ptr type, dyn fn type, and trait object type elisions
@@ -553,6 +558,7 @@ mod tests {
         adjustment_hints_mode: AdjustmentHintsMode::Prefix,
         adjustment_hints_hide_outside_unsafe: false,
         binding_mode_hints: false,
+        implicit_sized_bound_hints: false,
         hide_named_constructor_hints: false,
         hide_closure_initialization_hints: false,
         closure_style: ClosureStyle::ImplFn,
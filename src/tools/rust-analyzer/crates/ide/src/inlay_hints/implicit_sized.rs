@@ -0,0 +1,178 @@
+//! Implementation of implicit `Sized` bound inlay hints:
+//! ```no_run
+//! fn foo<T/*: Sized*/, U: Clone/* + Sized*/, V: ?Sized>() {}
+//! trait Tr {
+//!     type Assoc/*: Sized*/;
+//! }
+//! ```
+use syntax::ast::{self, AstNode, HasGenericParams, HasName, HasTypeBounds};
+
+use crate::{
+    InlayHint, InlayHintLabel, InlayHintPosition, InlayHintsConfig, InlayKind, InlayTooltip,
+};
+
+pub(super) fn hints(
+    acc: &mut Vec<InlayHint>,
+    config: &InlayHintsConfig,
+    generic_param_list: ast::GenericParamList,
+) -> Option<()> {
+    if !config.implicit_sized_bound_hints {
+        return None;
+    }
+
+    let where_clause = generic_param_list
+        .syntax()
+        .parent()
+        .and_then(ast::AnyHasGenericParams::cast)
+        .and_then(|owner| owner.where_clause());
+    for param in generic_param_list.type_or_const_params() {
+        let ast::TypeOrConstParam::Type(param) = param else { continue };
+        let Some(name) = param.name() else { continue };
+        if mentions_sized(param.type_bound_list()) {
+            continue;
+        }
+        // `T: ?Sized` may also be written in the where clause.
+        let relaxed_in_where_clause = where_clause
+            .iter()
+            .flat_map(|it| it.predicates())
+            .any(|pred| is_param(pred.ty(), &name) && mentions_sized(pred.type_bound_list()));
+        if !relaxed_in_where_clause {
+            push_hint(acc, &name, &param);
+        }
+    }
+
+    Some(())
+}
+
+pub(super) fn assoc_type_hints(
+    acc: &mut Vec<InlayHint>,
+    config: &InlayHintsConfig,
+    type_alias: ast::TypeAlias,
+) -> Option<()> {
+    if !config.implicit_sized_bound_hints {
+        return None;
+    }
+
+    // Only associated types declared in traits are `Sized` by default, the ones in impls are
+    // normal type aliases.
+    let assoc_item_list = type_alias.syntax().parent().and_then(ast::AssocItemList::cast)?;
+    ast::Trait::cast(assoc_item_list.syntax().parent()?)?;
+    if type_alias.ty().is_some() || mentions_sized(type_alias.type_bound_list()) {
+        return None;
+    }
+    push_hint(acc, &type_alias.name()?, &type_alias);
+
+    Some(())
+}
+
+fn push_hint(acc: &mut Vec<InlayHint>, name: &ast::Name, bounded: &impl HasTypeBounds) {
+    let bounds = bounded.type_bound_list().filter(|it| it.bounds().next().is_some());
+    let (range, label) = match (bounds, bounded.colon_token()) {
+        (Some(bounds), _) => (bounds.syntax().text_range(), " + Sized"),
+        (None, Some(colon)) => (colon.text_range(), " Sized"),
+        (None, None) => (name.syntax().text_range(), ": Sized"),
+    };
+    acc.push(InlayHint {
+        range,
+        kind: InlayKind::ImplicitSizedBound,
+        label: InlayHintLabel::simple(
+            label,
+            Some(InlayTooltip::String("implicit `Sized` bound".into())),
+            None,
+        ),
+        text_edit: None,
+        position: InlayHintPosition::After,
+        pad_left: false,
+        pad_right: false,
+    });
+}
+
+/// Whether the bounds contain `Sized` or `?Sized`, in which case there is no implicit bound to
+/// show.
+fn mentions_sized(bounds: Option<ast::TypeBoundList>) -> bool {
+    bounds.iter().flat_map(|it| it.bounds()).any(|bound| match bound.kind() {
+        ast::TypeBoundKind::PathType(path_type) => path_type
+            .path()
+            .and_then(|path| path.segment())
+            .and_then(|segment| segment.name_ref())
+            .map_or(false, |name_ref| name_ref.text() == "Sized"),
+        _ => false,
+    })
+}
+
+fn is_param(ty: Option<ast::Type>, name: &ast::Name) -> bool {
+    let Some(ast::Type::PathType(path_type)) = ty else { return false };
+    let Some(path) = path_type.path() else { return false };
+    path.qualifier().is_none()
+        && path
+            .segment()
+            .and_then(|segment| segment.name_ref())
+            .map_or(false, |name_ref| name_ref.text() == name.text())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        inlay_hints::tests::{check_with_config, DISABLED_CONFIG},
+        InlayHintsConfig,
+    };
+
+    #[track_caller]
+    fn check_implicit_sized(ra_fixture: &str) {
+        check_with_config(
+            InlayHintsConfig { implicit_sized_bound_hints: true, ..DISABLED_CONFIG },
+            ra_fixture,
+        );
+    }
+
+    #[test]
+    fn generic_params() {
+        check_implicit_sized(
+            r#"
+trait Trait {}
+fn foo<T, U: Trait, V: ?Sized, W: Sized, X:, const N: usize>() {}
+     //^: Sized
+           //^^^^^+ Sized
+                                        //^Sized
+struct S<'a, T>(&'a T);
+           //^: Sized
+impl<T: Trait> S<'static, T> {}
+      //^^^^^+ Sized
+"#,
+        );
+    }
+
+    #[test]
+    fn relaxed_in_where_clause() {
+        check_implicit_sized(
+            r#"
+fn foo<T, U>()
+        //^: Sized
+where
+    T: ?Sized,
+{
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn assoc_types() {
+        check_implicit_sized(
+            r#"
+trait Trait {
+    type Plain;
+       //^^^^^: Sized
+    type Bounded: Trait;
+                //^^^^^+ Sized
+    type Unsized: ?Sized;
+}
+impl Trait for () {
+    type Plain = ();
+    type Bounded = ();
+    type Unsized = str;
+}
+"#,
+        );
+    }
+}
@@ -129,7 +129,10 @@ macro_rules! eprintln {
     symbol_index::Query,
     RootDatabase, SymbolKind,
 };
-pub use ide_diagnostics::{Diagnostic, DiagnosticsConfig, ExprFillDefaultMode, Severity};
+pub use ide_diagnostics::{
+    Diagnostic, DiagnosticsConfig, DiagnosticsOverride, ExprFillDefaultMode, InsertModDeclsMode,
+    Severity, UnlinkedFile,
+};
 pub use ide_ssr::SsrError;
 pub use syntax::{TextRange, TextSize};
 pub use text_edit::{Indel, TextEdit};
@@ -628,6 +631,11 @@ pub fn diagnostics(
         self.with_db(|db| ide_diagnostics::diagnostics(db, config, &resolve, file_id))
     }
 
+    /// Lists all files of the workspace that are not part of any crate.
+    pub fn unlinked_files(&self, config: &DiagnosticsConfig) -> Cancellable<Vec<UnlinkedFile>> {
+        self.with_db(|db| ide_diagnostics::unlinked_files(db, config))
+    }
+
     /// Convenience function to return assists + quick fixes for diagnostics
     pub fn assists_with_fixes(
         &self,
@@ -41,6 +41,8 @@ pub(crate) fn shuffle_crate_graph(db: &mut RootDatabase) {
             data.target_layout.clone(),
             data.channel,
         );
+        new_graph.set_excluded_files(new_id, data.excluded_files.clone());
+        new_graph.set_module_roots(new_id, data.module_roots.clone());
         new_proc_macros.insert(new_id, proc_macros[&old_id].clone());
         map.insert(old_id, new_id);
     }
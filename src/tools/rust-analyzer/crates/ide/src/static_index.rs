@@ -121,6 +121,7 @@ fn add_file(&mut self, file_id: FileId) {
                     closure_style: hir::ClosureStyle::ImplFn,
                     param_names_for_lifetime_elision_hints: false,
                     binding_mode_hints: false,
+                    implicit_sized_bound_hints: false,
                     max_length: Some(25),
                     closure_capture_hints: false,
                     closing_brace_hints_min_lines: Some(25),
@@ -142,6 +143,7 @@ fn add_file(&mut self, file_id: FileId) {
             documentation: true,
             keywords: true,
             format: crate::HoverDocFormat::Markdown,
+            expand_trait_bounds: false,
         };
         let tokens = tokens.filter(|token| {
             matches!(
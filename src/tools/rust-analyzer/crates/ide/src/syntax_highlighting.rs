@@ -60,6 +60,8 @@ pub struct HighlightConfig {
     pub macro_bang: bool,
     /// Whether to highlight unresolved things be their syntax
     pub syntactic_name_ref_highlighting: bool,
+    /// Whether to mark method calls that resolve through a supertrait of the receiver's bounds
+    pub supertrait_methods: bool,
 }
 
 // Feature: Semantic Syntax Highlighting
@@ -174,6 +176,7 @@ pub struct HighlightConfig {
 // public:: Emitted for items that are from the current crate and are `pub`.
 // reference:: Emitted for locals behind a reference and functions taking `self` by reference.
 // static:: Emitted for "static" functions, also known as functions that do not take a `self` param, as well as statics and consts.
+// supertrait:: Emitted for method calls on generic parameters, trait objects and `impl Trait` types that resolve to a method of a supertrait of their trait bounds, if enabled.
 // trait:: Emitted for associated trait items.
 // unsafe:: Emitted for unsafe operations, like unsafe function calls, as well as the `unsafe` token.
 //
@@ -472,6 +475,7 @@ fn item(&self) -> &ast::Item {
                 krate,
                 &mut bindings_shadow_count,
                 config.syntactic_name_ref_highlighting,
+                config.supertrait_methods,
                 name_like,
             ),
             NodeOrToken::Token(token) => highlight::token(sema, token).zip(Some(None)),
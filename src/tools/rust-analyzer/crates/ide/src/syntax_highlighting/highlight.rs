@@ -50,6 +50,7 @@ pub(super) fn name_like(
     krate: hir::Crate,
     bindings_shadow_count: &mut FxHashMap<hir::Name, u32>,
     syntactic_name_ref_highlighting: bool,
+    supertrait_methods: bool,
     name_like: ast::NameLike,
 ) -> Option<(Highlight, Option<u64>)> {
     let mut binding_hash = None;
@@ -60,6 +61,7 @@ pub(super) fn name_like(
             bindings_shadow_count,
             &mut binding_hash,
             syntactic_name_ref_highlighting,
+            supertrait_methods,
             name_ref,
         ),
         ast::NameLike::Name(name) => {
@@ -201,17 +203,19 @@ fn highlight_name_ref(
     bindings_shadow_count: &mut FxHashMap<hir::Name, u32>,
     binding_hash: &mut Option<u64>,
     syntactic_name_ref_highlighting: bool,
+    supertrait_methods: bool,
     name_ref: ast::NameRef,
 ) -> Highlight {
     let db = sema.db;
-    if let Some(res) = highlight_method_call_by_name_ref(sema, krate, &name_ref) {
+    if let Some(res) = highlight_method_call_by_name_ref(sema, krate, supertrait_methods, &name_ref)
+    {
         return res;
     }
 
     let name_class = match NameRefClass::classify(sema, &name_ref) {
         Some(name_kind) => name_kind,
         None if syntactic_name_ref_highlighting => {
-            return highlight_name_ref_by_syntax(name_ref, sema, krate)
+            return highlight_name_ref_by_syntax(name_ref, sema, krate, supertrait_methods)
         }
         // FIXME: This is required for helper attributes used by proc-macros, as those do not map down
         // to anything when used.
@@ -502,15 +506,17 @@ fn highlight_def(
 fn highlight_method_call_by_name_ref(
     sema: &Semantics<'_, RootDatabase>,
     krate: hir::Crate,
+    supertrait_methods: bool,
     name_ref: &ast::NameRef,
 ) -> Option<Highlight> {
     let mc = name_ref.syntax().parent().and_then(ast::MethodCallExpr::cast)?;
-    highlight_method_call(sema, krate, &mc)
+    highlight_method_call(sema, krate, supertrait_methods, &mc)
 }
 
 fn highlight_method_call(
     sema: &Semantics<'_, RootDatabase>,
     krate: hir::Crate,
+    supertrait_methods: bool,
     method_call: &ast::MethodCallExpr,
 ) -> Option<Highlight> {
     let func = sema.resolve_method_call(method_call)?;
@@ -531,6 +537,19 @@ fn highlight_method_call(
     {
         h |= HlMod::Trait;
     }
+    // Walking the supertraits of every bound is not free, so skip it if the modifier is disabled.
+    if supertrait_methods {
+        if let Some(hir::AssocItemContainer::Trait(trait_)) =
+            func.as_assoc_item(sema.db).map(|it| it.container(sema.db))
+        {
+            let receiver_ty = method_call.receiver().and_then(|it| sema.type_of_expr(&it));
+            if receiver_ty
+                .map_or(false, |ty| is_supertrait_of_bounds(sema.db, &ty.original, trait_))
+            {
+                h |= HlMod::Supertrait;
+            }
+        }
+    }
 
     let def_crate = func.module(sema.db).krate();
     let is_from_other_crate = def_crate != krate;
@@ -568,6 +587,23 @@ fn highlight_method_call(
     Some(h)
 }
 
+/// Whether `trait_` is not one of the trait bounds of `receiver_ty`, a generic parameter, trait
+/// object or `impl Trait` type, but a supertrait of one of them.
+fn is_supertrait_of_bounds(db: &RootDatabase, receiver_ty: &hir::Type, trait_: hir::Trait) -> bool {
+    let receiver_ty = receiver_ty.strip_references();
+    let bounds = if let Some(param) = receiver_ty.as_type_param(db) {
+        param.trait_bounds(db)
+    } else if let Some(bound) = receiver_ty.as_dyn_trait() {
+        vec![bound]
+    } else if let Some(bounds) = receiver_ty.as_impl_traits(db) {
+        bounds.collect()
+    } else {
+        return false;
+    };
+    !bounds.contains(&trait_)
+        && bounds.iter().any(|bound| bound.all_supertraits(db).contains(&trait_))
+}
+
 fn highlight_name_by_syntax(name: ast::Name) -> Highlight {
     let default = HlTag::UnresolvedReference;
 
@@ -600,6 +636,7 @@ fn highlight_name_ref_by_syntax(
     name: ast::NameRef,
     sema: &Semantics<'_, RootDatabase>,
     krate: hir::Crate,
+    supertrait_methods: bool,
 ) -> Highlight {
     let default = HlTag::UnresolvedReference;
 
@@ -610,7 +647,7 @@ fn highlight_name_ref_by_syntax(
 
     match parent.kind() {
         METHOD_CALL_EXPR => ast::MethodCallExpr::cast(parent)
-            .and_then(|it| highlight_method_call(sema, krate, &it))
+            .and_then(|it| highlight_method_call(sema, krate, supertrait_methods, &it))
             .unwrap_or_else(|| SymbolKind::Function.into()),
         FIELD_EXPR => {
             let h = HlTag::Symbol(SymbolKind::Field);
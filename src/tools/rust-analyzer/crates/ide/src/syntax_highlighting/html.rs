@@ -34,6 +34,7 @@ fn rainbowify(seed: u64) -> String {
             inject_doc_comment: true,
             macro_bang: true,
             syntactic_name_ref_highlighting: false,
+            supertrait_methods: false,
         },
         file_id,
         None,
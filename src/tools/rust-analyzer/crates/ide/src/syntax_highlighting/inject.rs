@@ -79,6 +79,7 @@ pub(super) fn ra_fixture(
                 specialize_operator: config.operator,
                 inject_doc_comment: config.inject_doc_comment,
                 macro_bang: config.macro_bang,
+                supertrait_methods: config.supertrait_methods,
             },
             tmp_file_id,
         )
@@ -240,6 +241,7 @@ pub(super) fn doc_comment(
                 specialize_operator: config.operator,
                 inject_doc_comment: config.inject_doc_comment,
                 macro_bang: config.macro_bang,
+                supertrait_methods: config.supertrait_methods,
             },
             tmp_file_id,
             None,
@@ -82,6 +82,9 @@ pub enum HlMod {
     Reference,
     /// Used for associated functions.
     Static,
+    /// Used for method calls that resolve to a method of a supertrait of the trait bounds of
+    /// their receiver.
+    Supertrait,
     /// Used for items in traits and trait impls.
     Trait,
     // Keep this last!
@@ -221,6 +224,7 @@ impl HlMod {
         HlMod::Public,
         HlMod::Reference,
         HlMod::Static,
+        HlMod::Supertrait,
         HlMod::Trait,
         HlMod::Unsafe,
     ];
@@ -245,6 +249,7 @@ fn as_str(self) -> &'static str {
             HlMod::Public => "public",
             HlMod::Reference => "reference",
             HlMod::Static => "static",
+            HlMod::Supertrait => "supertrait",
             HlMod::Trait => "trait",
             HlMod::Unsafe => "unsafe",
         }
@@ -343,6 +348,10 @@ pub fn contains(self, m: HlMod) -> bool {
         self.0 & m.mask() == m.mask()
     }
 
+    pub fn remove(&mut self, m: HlMod) {
+        self.0 &= !m.mask();
+    }
+
     pub fn iter(self) -> impl Iterator<Item = HlMod> {
         HlMod::ALL.iter().copied().filter(move |it| self.0 & it.mask() == it.mask())
     }
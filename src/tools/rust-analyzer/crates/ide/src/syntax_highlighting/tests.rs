@@ -4,7 +4,7 @@
 use ide_db::SymbolKind;
 use test_utils::{bench, bench_fixture, skip_slow_tests, AssertLinear};
 
-use crate::{fixture, FileRange, HighlightConfig, HlTag, TextRange};
+use crate::{fixture, FileRange, HighlightConfig, HlMod, HlTag, TextRange};
 
 const HL_CONFIG: HighlightConfig = HighlightConfig {
     strings: true,
@@ -15,6 +15,7 @@
     inject_doc_comment: true,
     macro_bang: true,
     syntactic_name_ref_highlighting: false,
+    supertrait_methods: false,
 };
 
 #[test]
@@ -1027,6 +1028,55 @@ struct Foo {
     assert_eq!(&highlights[0].highlight.to_string(), "field.declaration.public");
 }
 
+#[test]
+fn supertrait_methods() {
+    let (analysis, file_id) = fixture::file(
+        r#"
+trait Super {
+    fn sup(&self);
+}
+trait Sub: Super {
+    fn sub(&self);
+}
+
+fn generic<T: Sub>(t: T, d: &dyn Sub, i: impl Sub) {
+    t.sup();
+    t.sub();
+    d.sup();
+    d.sub();
+    i.sup();
+}
+fn named<T: Sub + Super>(t: &T) {
+    t.sup();
+}
+"#,
+    );
+    let text = analysis.file_text(file_id).unwrap();
+    let method_calls = |config| {
+        analysis
+            .highlight(config, file_id)
+            .unwrap()
+            .into_iter()
+            .filter(|it| it.highlight.tag == HlTag::Symbol(SymbolKind::Function))
+            .filter(|it| !it.highlight.mods.contains(HlMod::Definition))
+            .map(|it| format!("{} {}", &text[it.range], it.highlight))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        method_calls(HighlightConfig { supertrait_methods: true, ..HL_CONFIG }),
+        [
+            "sup function.associated.reference.supertrait.trait",
+            "sub function.associated.reference.trait",
+            "sup function.associated.reference.supertrait.trait",
+            "sub function.associated.reference.trait",
+            "sup function.associated.reference.supertrait.trait",
+            "sup function.associated.reference.trait",
+        ]
+    );
+    assert!(method_calls(HL_CONFIG).iter().all(|it| !it.contains("supertrait")));
+}
+
 #[test]
 fn ranges_sorted() {
     let (analysis, file_id) = fixture::file(
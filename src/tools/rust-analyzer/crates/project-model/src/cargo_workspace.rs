@@ -1,6 +1,6 @@
 //! See [`CargoWorkspace`].
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::from_utf8;
 use std::{ops, process::Command};
 
@@ -131,6 +131,10 @@ pub struct PackageData {
     pub id: String,
     /// The contents of [package.metadata.rust-analyzer]
     pub metadata: RustAnalyzerPackageMetaData,
+    /// Files that Cargo would discover as targets on its own, but that are not the root of any
+    /// target of this package, because target auto-discovery is turned off (`autobins = false`,
+    /// ...) or the targets are declared elsewhere
+    pub excluded_target_files: Vec<AbsPathBuf>,
 }
 
 #[derive(Deserialize, Default, Debug, Clone, Eq, PartialEq)]
@@ -355,6 +359,7 @@ pub fn new(mut meta: cargo_metadata::Metadata) -> CargoWorkspace {
                 features: features.into_iter().collect(),
                 active_features: Vec::new(),
                 metadata: meta.rust_analyzer.unwrap_or_default(),
+                excluded_target_files: Vec::new(),
             });
             let pkg_data = &mut packages[pkg];
             pkg_by_id.insert(id, pkg);
@@ -371,6 +376,11 @@ pub fn new(mut meta: cargo_metadata::Metadata) -> CargoWorkspace {
                 });
                 pkg_data.targets.push(tgt);
             }
+            if is_member {
+                let roots: Vec<_> = pkg_data.targets.iter().map(|&it| &targets[it].root).collect();
+                pkg_data.excluded_target_files =
+                    excluded_target_files(pkg_data.manifest.parent(), &roots);
+            }
         }
         let resolve = meta.resolve.expect("metadata executed with deps");
         for mut node in resolve.nodes {
@@ -474,6 +484,32 @@ fn is_unique(&self, name: &str) -> bool {
     }
 }
 
+/// Lists the files in `package_root` that Cargo's target auto-discovery would pick up, but that
+/// are not one of the target `roots`.
+fn excluded_target_files(package_root: &AbsPath, roots: &[&AbsPathBuf]) -> Vec<AbsPathBuf> {
+    let package_root: &Path = package_root.as_ref();
+    let mut candidates = vec![package_root.join("src/lib.rs"), package_root.join("src/main.rs")];
+    for dir in ["src/bin", "examples", "tests", "benches"] {
+        let Ok(entries) = std::fs::read_dir(package_root.join(dir)) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            match path.extension() {
+                Some(ext) if ext == "rs" => candidates.push(path),
+                Some(_) => (),
+                None => candidates.push(path.join("main.rs")),
+            }
+        }
+    }
+    let mut res: Vec<_> = candidates
+        .into_iter()
+        .filter(|path| path.is_file())
+        .map(AbsPathBuf::assert)
+        .filter(|path| !roots.contains(&path))
+        .collect();
+    res.sort();
+    res
+}
+
 fn find_list_of_build_targets(config: &CargoConfig, cargo_toml: &ManifestPath) -> Vec<String> {
     if let Some(target) = &config.target {
         return [target.into()].to_vec();
@@ -87,6 +87,16 @@ pub struct Crate {
     pub(crate) exclude: Vec<AbsPathBuf>,
     pub(crate) is_proc_macro: bool,
     pub(crate) repository: Option<String>,
+    pub(crate) module_roots: Vec<ModuleRoot>,
+}
+
+/// A directory whose files are submodules of a module of the crate, like generated code that the
+/// crate includes with `#[path]` attributes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ModuleRoot {
+    pub(crate) path: AbsPathBuf,
+    /// The names of the modules from the crate root to the parent module of the files.
+    pub(crate) module_path: Vec<String>,
 }
 
 impl ProjectJson {
@@ -111,7 +121,7 @@ pub fn new(base: &AbsPath, data: ProjectJsonData) -> ProjectJson {
                     let is_workspace_member = crate_data
                         .is_workspace_member
                         .unwrap_or_else(|| root_module.starts_with(base));
-                    let (include, exclude) = match crate_data.source {
+                    let (mut include, exclude) = match crate_data.source {
                         Some(src) => {
                             let absolutize = |dirs: Vec<PathBuf>| {
                                 dirs.into_iter().map(absolutize_on_base).collect::<Vec<_>>()
@@ -120,6 +130,27 @@ pub fn new(base: &AbsPath, data: ProjectJsonData) -> ProjectJson {
                         }
                         None => (vec![root_module.parent().unwrap().to_path_buf()], Vec::new()),
                     };
+                    let module_roots: Vec<_> = crate_data
+                        .module_roots
+                        .into_iter()
+                        .map(|root| ModuleRoot {
+                            path: absolutize_on_base(root.path),
+                            module_path: root
+                                .module
+                                .split("::")
+                                .filter(|segment| !segment.is_empty())
+                                .skip_while(|&segment| segment == "crate")
+                                .map(ToOwned::to_owned)
+                                .collect(),
+                        })
+                        .collect();
+                    // The files of module roots are part of the crate, so they have to be loaded
+                    // even if they live outside of the crate's source directories.
+                    for root in &module_roots {
+                        if !include.iter().any(|dir| root.path.starts_with(dir)) {
+                            include.push(root.path.clone());
+                        }
+                    }
 
                     Crate {
                         display_name: crate_data
@@ -149,6 +180,7 @@ pub fn new(base: &AbsPath, data: ProjectJsonData) -> ProjectJson {
                         exclude,
                         is_proc_macro: crate_data.is_proc_macro,
                         repository: crate_data.repository,
+                        module_roots,
                     }
                 })
                 .collect(),
@@ -201,6 +233,8 @@ struct CrateData {
     is_proc_macro: bool,
     #[serde(default)]
     repository: Option<String>,
+    #[serde(default)]
+    module_roots: Vec<ModuleRootData>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -233,6 +267,15 @@ struct DepData {
     name: CrateName,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+struct ModuleRootData {
+    /// A directory containing submodules of `module`.
+    path: PathBuf,
+    /// Path of the parent module from the crate root, e.g. `generated::proto`, or `""` for the
+    /// crate root itself.
+    module: String,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 struct CrateSource {
     include_dirs: Vec<PathBuf>,
@@ -3,7 +3,7 @@
     path::{Path, PathBuf},
 };
 
-use base_db::{CrateGraph, FileId, ProcMacroPaths};
+use base_db::{CrateGraph, FileId, ProcMacroPaths, VfsPath};
 use cfg::{CfgAtom, CfgDiff};
 use expect_test::{expect_file, ExpectFile};
 use paths::{AbsPath, AbsPathBuf};
@@ -222,6 +222,22 @@ fn rust_project_is_proc_macro_has_proc_macro_dep() {
     crate_data.dependencies.iter().find(|&dep| dep.name.deref() == "proc_macro").unwrap();
 }
 
+#[test]
+fn rust_project_module_roots() {
+    let project = rooted_project_json(get_test_json_file("module-roots-project.json"));
+    let (_, krate) = project.crates().next().unwrap();
+    let gen_dir = krate.module_roots[0].path.clone();
+    // The module root lies outside of `src/`, so it has to be loaded in addition.
+    assert!(krate.include.contains(&gen_dir));
+
+    let (crate_graph, _proc_macros) = load_rust_project("module-roots-project.json");
+    let crate_id = crate_graph.iter().max().unwrap();
+    let module_roots = &crate_graph[crate_id].module_roots;
+    assert_eq!(module_roots.len(), 1);
+    assert_eq!(module_roots[0].dir, VfsPath::from(gen_dir));
+    assert_eq!(module_roots[0].module_path, ["generated", "proto"]);
+}
+
 #[test]
 fn crate_graph_dedup_identical() {
     let (mut crate_graph, proc_macros) =
@@ -7,7 +7,8 @@
 use anyhow::{format_err, Context, Result};
 use base_db::{
     CrateDisplayName, CrateGraph, CrateId, CrateName, CrateOrigin, Dependency, Edition, Env,
-    FileId, LangCrateOrigin, ProcMacroPaths, ReleaseChannel, TargetLayoutLoadResult,
+    FileId, LangCrateOrigin, ModuleRoot, ProcMacroPaths, ReleaseChannel, TargetLayoutLoadResult,
+    VfsPath,
 };
 use cfg::{CfgDiff, CfgOptions};
 use paths::{AbsPath, AbsPathBuf};
@@ -754,6 +755,7 @@ fn project_json_to_crate_graph(
                     proc_macro_dylib_path,
                     is_proc_macro,
                     repository,
+                    module_roots,
                     ..
                 },
                 file_id,
@@ -787,6 +789,16 @@ fn project_json_to_crate_graph(
                     target_layout.clone(),
                     channel,
                 );
+                if !module_roots.is_empty() {
+                    let module_roots = module_roots
+                        .iter()
+                        .map(|root| ModuleRoot {
+                            dir: VfsPath::from(root.path.clone()),
+                            module_path: root.module_path.clone(),
+                        })
+                        .collect();
+                    crate_graph.set_module_roots(crate_graph_crate_id, module_roots);
+                }
                 if *is_proc_macro {
                     if let Some(path) = proc_macro_dylib_path.clone() {
                         let node = Ok((
@@ -945,6 +957,14 @@ fn cargo_to_crate_graph(
             pkg_crates.entry(pkg).or_insert_with(Vec::new).push((crate_id, kind));
         }
 
+        let excluded_files: Vec<_> =
+            cargo[pkg].excluded_target_files.iter().filter_map(|path| load(path)).collect();
+        if !excluded_files.is_empty() {
+            for &(krate, _) in pkg_crates.get(&pkg).into_iter().flatten() {
+                crate_graph.set_excluded_files(krate, excluded_files.clone());
+            }
+        }
+
         // Set deps to the core, std and to the lib target of the current package
         for &(from, kind) in pkg_crates.get(&pkg).into_iter().flatten() {
             // Add sysroot deps first so that a lib target named `core` etc. can overwrite them.
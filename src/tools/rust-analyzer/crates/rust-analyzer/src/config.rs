@@ -12,9 +12,10 @@
 use cfg::{CfgAtom, CfgDiff};
 use flycheck::FlycheckConfig;
 use ide::{
-    AssistConfig, CallableSnippets, CompletionConfig, DiagnosticsConfig, ExprFillDefaultMode,
-    HighlightConfig, HighlightRelatedConfig, HoverConfig, HoverDocFormat, InlayHintsConfig,
-    JoinLinesConfig, MemoryLayoutHoverConfig, MemoryLayoutHoverRenderKind, Snippet, SnippetScope,
+    AssistConfig, CallableSnippets, CompletionConfig, DiagnosticsConfig, DiagnosticsOverride,
+    ExprFillDefaultMode, HighlightConfig, HighlightRelatedConfig, HoverConfig, HoverDocFormat,
+    InlayHintsConfig, InsertModDeclsMode, JoinLinesConfig, MemoryLayoutHoverConfig,
+    MemoryLayoutHoverRenderKind, Severity, Snippet, SnippetScope,
 };
 use ide_db::{
     imports::insert_use::{ImportGranularity, InsertUseConfig, PrefixKind},
@@ -261,6 +262,15 @@ struct ConfigData {
         /// Whether to show experimental rust-analyzer diagnostics that might
         /// have more false positives than usual.
         diagnostics_experimental_enable: bool    = "false",
+        /// Where to insert new `mod` declarations when fixing unlinked files.
+        diagnostics_insertModDecls: InsertModDeclsDef = "\"after_existing\"",
+        /// Severities of rust-analyzer diagnostics in the files matching a glob, e.g.
+        /// `[{ "glob": "benches/**", "severity": { "unlinked-file": "off" } }]`.
+        ///
+        /// Severities can be `"error"`, `"warning"`, `"hint"` or `"off"`. Globs support
+        /// `*`, `?` and `**`, and can match at any directory unless they start with `/`.
+        /// Later entries take precedence over earlier ones.
+        diagnostics_overrides: Vec<DiagnosticsOverrideDef> = "[]",
         /// Map of prefixes to be substituted when parsing diagnostic file paths.
         /// This should be the reverse mapping of what is passed to `rustc` as `--remap-path-prefix`.
         diagnostics_remapPrefix: FxHashMap<String, String> = "{}",
@@ -315,6 +325,9 @@ struct ConfigData {
         /// Whether to show keyword hover popups. Only applies when
         /// `#rust-analyzer.hover.documentation.enable#` is set.
         hover_documentation_keywords_enable: bool  = "true",
+        /// Whether to show the bounds implied by the trait bounds of a type parameter, like
+        /// the supertraits of these bounds, when hovering it.
+        hover_expandTraitBounds_enable: bool = "false",
         /// Use markdown syntax for links on hover.
         hover_links_enable: bool = "true",
         /// How to render the align information in a memory layout hover.
@@ -364,6 +377,9 @@ struct ConfigData {
         inlayHints_expressionAdjustmentHints_hideOutsideUnsafe: bool = "false",
         /// Whether to show inlay hints as postfix ops (`.*` instead of `*`, etc).
         inlayHints_expressionAdjustmentHints_mode: AdjustmentHintsModeDef = "\"prefix\"",
+        /// Whether to show inlay hints for the implicit `Sized` bound on generic parameters and
+        /// associated types.
+        inlayHints_implicitSizedBoundHints_enable: bool = "false",
         /// Whether to show inlay type hints for elided lifetimes in function signatures.
         inlayHints_lifetimeElisionHints_enable: LifetimeElisionDef = "\"never\"",
         /// Whether to prefer using parameter names as the name for elided lifetime hints if possible.
@@ -524,6 +540,10 @@ struct ConfigData {
         /// By disabling semantic tokens for strings, other grammars can be used to highlight
         /// their contents.
         semanticHighlighting_strings_enable: bool = "true",
+        /// Emit the `supertrait` semantic token modifier for method calls on generic parameters,
+        /// trait objects and `impl Trait` types that resolve to a method of a supertrait of their
+        /// trait bounds, rather than of one of the bounds themselves.
+        semanticHighlighting_supertraitMethods_enable: bool = "false",
 
         /// Show full signature of the callable. Only shows parameters if disabled.
         signatureInfo_detail: SignatureDetail                           = "\"full\"",
@@ -1083,10 +1103,32 @@ pub fn diagnostics(&self) -> DiagnosticsConfig {
             proc_macros_enabled: self.data.procMacro_enable,
             disable_experimental: !self.data.diagnostics_experimental_enable,
             disabled: self.data.diagnostics_disabled.clone(),
+            overrides: self
+                .data
+                .diagnostics_overrides
+                .iter()
+                .flat_map(|it| {
+                    it.severity.iter().map(|(code, severity)| DiagnosticsOverride {
+                        glob: it.glob.clone(),
+                        code: code.clone(),
+                        severity: match severity {
+                            DiagnosticSeverityDef::Error => Some(Severity::Error),
+                            DiagnosticSeverityDef::Warning => Some(Severity::Warning),
+                            DiagnosticSeverityDef::Hint => Some(Severity::WeakWarning),
+                            DiagnosticSeverityDef::Off => None,
+                        },
+                    })
+                })
+                .collect(),
             expr_fill_default: match self.data.assist_expressionFillDefault {
                 ExprFillDefaultDef::Todo => ExprFillDefaultMode::Todo,
                 ExprFillDefaultDef::Default => ExprFillDefaultMode::Default,
             },
+            insert_mod_decls: match self.data.diagnostics_insertModDecls {
+                InsertModDeclsDef::AfterExisting => InsertModDeclsMode::AfterExisting,
+                InsertModDeclsDef::Alphabetical => InsertModDeclsMode::Alphabetical,
+                InsertModDeclsDef::Top => InsertModDeclsMode::Top,
+            },
             insert_use: self.insert_use_config(),
             prefer_no_std: self.data.imports_prefer_no_std,
         }
@@ -1380,6 +1422,7 @@ pub fn inlay_hints(&self) -> InlayHintsConfig {
                 .data
                 .inlayHints_expressionAdjustmentHints_hideOutsideUnsafe,
             binding_mode_hints: self.data.inlayHints_bindingModeHints_enable,
+            implicit_sized_bound_hints: self.data.inlayHints_implicitSizedBoundHints_enable,
             param_names_for_lifetime_elision_hints: self
                 .data
                 .inlayHints_lifetimeElisionHints_useParameterNames,
@@ -1518,6 +1561,7 @@ pub fn highlighting_config(&self) -> HighlightConfig {
             specialize_operator: self.data.semanticHighlighting_operator_specialization_enable,
             inject_doc_comment: self.data.semanticHighlighting_doc_comment_inject_enable,
             syntactic_name_ref_highlighting: false,
+            supertrait_methods: self.data.semanticHighlighting_supertraitMethods_enable,
         }
     }
 
@@ -1554,6 +1598,7 @@ pub fn hover(&self) -> HoverConfig {
                 }
             },
             keywords: self.data.hover_documentation_keywords_enable,
+            expand_trait_bounds: self.data.hover_expandTraitBounds_enable,
         }
     }
 
@@ -1823,6 +1868,29 @@ enum ExprFillDefaultDef {
     Default,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+enum InsertModDeclsDef {
+    AfterExisting,
+    Alphabetical,
+    Top,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct DiagnosticsOverrideDef {
+    glob: String,
+    severity: FxHashMap<String, DiagnosticSeverityDef>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+enum DiagnosticSeverityDef {
+    Error,
+    Warning,
+    Hint,
+    Off,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 enum ImportGranularityDef {
@@ -2153,6 +2221,23 @@ macro_rules! set {
             "type": ["null", "array"],
             "items": { "type": "string" },
         },
+        "Vec<DiagnosticsOverrideDef>" => set! {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "glob": { "type": "string" },
+                    "severity": {
+                        "type": "object",
+                        "additionalProperties": {
+                            "type": "string",
+                            "enum": ["error", "warning", "hint", "off"],
+                        },
+                    },
+                },
+                "required": ["glob", "severity"],
+            },
+        },
         "ExprFillDefaultDef" => set! {
             "type": "string",
             "enum": ["todo", "default"],
@@ -2161,6 +2246,15 @@ macro_rules! set {
                 "Fill missing expressions with reasonable defaults, `new` or `default` constructors."
             ],
         },
+        "InsertModDeclsDef" => set! {
+            "type": "string",
+            "enum": ["after_existing", "alphabetical", "top"],
+            "enumDescriptions": [
+                "Insert after the first group of existing `mod` declarations.",
+                "Insert among the existing `mod` declarations, keeping them sorted by name.",
+                "Insert before the first item of the parent module."
+            ],
+        },
         "ImportGranularityDef" => set! {
             "type": "string",
             "enum": ["preserve", "crate", "module", "item"],
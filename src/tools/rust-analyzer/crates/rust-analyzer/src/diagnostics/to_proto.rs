@@ -5,11 +5,15 @@
 use flycheck::{Applicability, DiagnosticLevel, DiagnosticSpan};
 use itertools::Itertools;
 use stdx::format_to;
+use syntax::{ast, AstNode};
 use vfs::{AbsPath, AbsPathBuf};
 
 use crate::{
-    global_state::GlobalStateSnapshot, line_index::PositionEncoding, lsp_ext,
-    to_proto::url_from_abs_path,
+    from_proto,
+    global_state::GlobalStateSnapshot,
+    line_index::PositionEncoding,
+    lsp_ext,
+    to_proto::{self, url_from_abs_path},
 };
 
 use super::{DiagnosticsMapConfig, Fix};
@@ -132,6 +136,56 @@ fn primary_location(
     location(config, workspace_root, last_span, snap)
 }
 
+/// Narrows a location covering the definition of a `const` or `static` item, i.e. `const NAME: Ty`,
+/// to the expression that produces its value.
+///
+/// rustc points errors about the final value of a constant, e.g. it being undefined behavior, at
+/// the definition. Highlighting the value instead shows what the error is about.
+fn const_value_location(
+    snap: &GlobalStateSnapshot,
+    location: lsp_types::Location,
+) -> lsp_types::Location {
+    let value_range = || {
+        let file_id = snap.url_to_file_id(&location.uri).ok()?;
+        let line_index = snap.file_line_index(file_id).ok()?;
+        let range = from_proto::text_range(&line_index, location.range).ok()?;
+        let source_file = snap.analysis.parse(file_id).ok()?;
+        let token = source_file.syntax().token_at_offset(range.start()).right_biased()?;
+        let item = token.parent_ancestors().find_map(ast::Item::cast)?;
+        let (ty, mut value) = match &item {
+            ast::Item::Const(it) => (it.ty()?, it.body()?),
+            ast::Item::Static(it) => (it.ty()?, it.body()?),
+            _ => return None,
+        };
+        // The definition starts after the attributes, at the visibility or the keyword, and ends
+        // with the type.
+        if !item.syntax().text_range().contains_range(range)
+            || range.end() != ty.syntax().text_range().end()
+        {
+            return None;
+        }
+        // The value of a block, e.g. an `unsafe` one, is the value of its tail expression.
+        loop {
+            value = match &value {
+                ast::Expr::BlockExpr(block) => match block.tail_expr() {
+                    Some(tail) => tail,
+                    None => break,
+                },
+                ast::Expr::ParenExpr(paren) => match paren.expr() {
+                    Some(expr) => expr,
+                    None => break,
+                },
+                _ => break,
+            };
+        }
+        Some(to_proto::range(&line_index, value.syntax().text_range()))
+    };
+    match value_range() {
+        Some(range) => lsp_types::Location { range, ..location },
+        None => location,
+    }
+}
+
 /// Converts a secondary Rust span to a LSP related information
 ///
 /// If the span is unlabelled this will return `None`.
@@ -301,8 +355,14 @@ pub(crate) fn map_rust_diagnostic_to_lsp(
         }
     }
 
+    // Errors from evaluating a constant point at the whole `const` item when they are about its
+    // final value. Point at the value instead, with rustc's notes, like the raw bytes of the
+    // value, as related information.
+    let is_const_eval_error = rd.code.as_ref().map_or(false, |code| code.code == "E0080");
+
     let mut needs_primary_span_label = true;
     let mut subdiagnostics = Vec::new();
+    let mut notes = Vec::new();
     let mut tags = Vec::new();
 
     for secondary_span in rd.spans.iter().filter(|s| !s.is_primary) {
@@ -319,6 +379,9 @@ pub(crate) fn map_rust_diagnostic_to_lsp(
             MappedRustChildDiagnostic::SubDiagnostic(sub) => {
                 subdiagnostics.push(sub);
             }
+            MappedRustChildDiagnostic::MessageLine(message_line) if is_const_eval_error => {
+                notes.push(message_line);
+            }
             MappedRustChildDiagnostic::MessageLine(message_line) => {
                 format_to!(message, "\n{}", message_line);
 
@@ -358,7 +421,10 @@ pub(crate) fn map_rust_diagnostic_to_lsp(
     primary_spans
         .iter()
         .flat_map(|primary_span| {
-            let primary_location = primary_location(config, workspace_root, primary_span, snap);
+            let mut primary_location = primary_location(config, workspace_root, primary_span, snap);
+            if is_const_eval_error {
+                primary_location = const_value_location(snap, primary_location);
+            }
             let message = {
                 let mut message = message.clone();
                 if needs_primary_span_label {
@@ -389,7 +455,10 @@ pub(crate) fn map_rust_diagnostic_to_lsp(
                 // generated that code.
                 let is_in_macro_call = i != 0;
 
-                let secondary_location = location(config, workspace_root, span, snap);
+                let mut secondary_location = location(config, workspace_root, span, snap);
+                if is_const_eval_error {
+                    secondary_location = const_value_location(snap, secondary_location);
+                }
                 if secondary_location == primary_location {
                     continue;
                 }
@@ -442,6 +511,12 @@ pub(crate) fn map_rust_diagnostic_to_lsp(
                             .iter()
                             .cloned()
                             .chain(subdiagnostics.iter().map(|sub| sub.related.clone()))
+                            .chain(notes.iter().map(|note| {
+                                lsp_types::DiagnosticRelatedInformation {
+                                    location: primary_location.clone(),
+                                    message: note.clone(),
+                                }
+                            }))
                             .collect::<Vec<_>>();
                         if info.is_empty() {
                             None
@@ -520,19 +595,36 @@ mod tests {
 
     use expect_test::{expect_file, ExpectFile};
     use lsp_types::ClientCapabilities;
+    use vfs::VfsPath;
 
     fn check(diagnostics_json: &str, expect: ExpectFile) {
         check_with_config(DiagnosticsMapConfig::default(), diagnostics_json, expect)
     }
 
     fn check_with_config(config: DiagnosticsMapConfig, diagnostics_json: &str, expect: ExpectFile) {
+        check_with_files(config, &[], diagnostics_json, expect)
+    }
+
+    /// Like `check_with_config`, but with `files` loaded into the workspace, for the mappings that
+    /// look at the source code.
+    fn check_with_files(
+        config: DiagnosticsMapConfig,
+        files: &[(&str, &str)],
+        diagnostics_json: &str,
+        expect: ExpectFile,
+    ) {
         let diagnostic: flycheck::Diagnostic = serde_json::from_str(diagnostics_json).unwrap();
         let workspace_root: &AbsPath = Path::new("/test/").try_into().unwrap();
         let (sender, _) = crossbeam_channel::unbounded();
-        let state = GlobalState::new(
+        let mut state = GlobalState::new(
             sender,
             Config::new(workspace_root.to_path_buf(), ClientCapabilities::default(), Vec::new()),
         );
+        for &(path, text) in files {
+            let path = VfsPath::from(workspace_root.join(path));
+            state.vfs.write().0.set_file_contents(path, Some(text.as_bytes().to_vec()));
+        }
+        state.process_changes();
         let snap = state.snapshot();
         let mut actual = map_rust_diagnostic_to_lsp(&config, &diagnostic, workspace_root, &snap);
         actual.iter_mut().for_each(|diag| diag.diagnostic.data = None);
@@ -1841,4 +1933,63 @@ fn reasonable_line_numbers_from_empty_file() {
             expect_file!["./test_data/reasonable_line_numbers_from_empty_file.txt"],
         );
     }
+
+    #[test]
+    fn rustc_const_eval_error() {
+        check_with_files(
+            DiagnosticsMapConfig::default(),
+            &[("src/lib.rs", "static mut S: i32 = 0;\nconst C: &i32 = unsafe { &S };\n")],
+            r##"{
+    "rendered": "error[E0080]: it is undefined behavior to use this value\n --> src/lib.rs:2:1\n  |\n2 | const C: &i32 = unsafe { &S };\n  | ^^^^^^^^^^^^^ constructing invalid value: encountered reference to mutable memory in `const`\n  |\n  = note: The rules on what exactly is undefined behavior aren't clear, so this check might be overzealous. Please open an issue on the rustc repository if you believe it should not be considered undefined behavior.\n  = note: the raw bytes of the constant (size: 8, align: 8) {\n              alloc1\n          }\n\n",
+    "children": [
+        {
+            "children": [],
+            "code": null,
+            "level": "note",
+            "message": "The rules on what exactly is undefined behavior aren't clear, so this check might be overzealous. Please open an issue on the rustc repository if you believe it should not be considered undefined behavior.",
+            "rendered": null,
+            "spans": []
+        },
+        {
+            "children": [],
+            "code": null,
+            "level": "note",
+            "message": "the raw bytes of the constant (size: 8, align: 8) {\n    alloc1\n}",
+            "rendered": null,
+            "spans": []
+        }
+    ],
+    "code": {
+        "code": "E0080",
+        "explanation": null
+    },
+    "level": "error",
+    "message": "it is undefined behavior to use this value",
+    "spans": [
+        {
+            "byte_end": 36,
+            "byte_start": 23,
+            "column_end": 14,
+            "column_start": 1,
+            "expansion": null,
+            "file_name": "src/lib.rs",
+            "is_primary": true,
+            "label": "constructing invalid value: encountered reference to mutable memory in `const`",
+            "line_end": 2,
+            "line_start": 2,
+            "suggested_replacement": null,
+            "suggestion_applicability": null,
+            "text": [
+                {
+                    "highlight_end": 14,
+                    "highlight_start": 1,
+                    "text": "const C: &i32 = unsafe { &S };"
+                }
+            ]
+        }
+    ]
+}"##,
+            expect_file!["./test_data/rustc_const_eval_error.txt"],
+        );
+    }
 }
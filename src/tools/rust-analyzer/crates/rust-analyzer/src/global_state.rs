@@ -435,6 +435,22 @@ pub(crate) fn anchored_path(&self, path: &AnchoredPathBuf) -> Url {
         url_from_abs_path(path)
     }
 
+    /// Returns the contents of the file at `path`. Files that aren't part of the VFS, like a
+    /// `Cargo.toml` that the client doesn't have open, are read from disk instead.
+    pub(crate) fn anchored_path_contents(&self, path: &AnchoredPathBuf) -> Option<String> {
+        let path = {
+            let vfs = &self.vfs.read().0;
+            let mut base = vfs.file_path(path.anchor);
+            base.pop();
+            let path = base.join(&path.path)?;
+            if let Some(file_id) = vfs.file_id(&path) {
+                return String::from_utf8(vfs.file_contents(file_id).to_vec()).ok();
+            }
+            path
+        };
+        std::fs::read_to_string(path.as_path()?).ok()
+    }
+
     pub(crate) fn file_id_to_file_path(&self, file_id: FileId) -> vfs::VfsPath {
         self.vfs.read().0.file_path(file_id)
     }
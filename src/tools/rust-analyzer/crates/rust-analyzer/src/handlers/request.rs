@@ -41,7 +41,8 @@
     line_index::LineEndings,
     lsp_ext::{
         self, CrateInfoResult, ExternalDocsPair, ExternalDocsResponse, FetchDependencyListParams,
-        FetchDependencyListResult, PositionOrRange, ViewCrateGraphParams, WorkspaceSymbolParams,
+        FetchDependencyListResult, PositionOrRange, UnlinkedFilesParams, UnlinkedFilesResult,
+        ViewCrateGraphParams, WorkspaceSymbolParams,
     },
     lsp_utils::{all_edits_are_disjoint, invalid_params_error},
     to_proto, LspError, Result,
@@ -1337,7 +1338,7 @@ pub(crate) fn handle_ssr(
         position,
         selections,
     )??;
-    to_proto::workspace_edit(&snap, source_change).map_err(Into::into)
+    to_proto::workspace_edit(&snap, source_change)
 }
 
 pub(crate) fn handle_inlay_hints(
@@ -1958,6 +1959,27 @@ pub(crate) fn fetch_dependency_list(
     Ok(FetchDependencyListResult { crates: crate_infos })
 }
 
+pub(crate) fn handle_unlinked_files(
+    snap: GlobalStateSnapshot,
+    _params: UnlinkedFilesParams,
+) -> Result<UnlinkedFilesResult> {
+    let _p = profile::span("handle_unlinked_files");
+    let files = snap
+        .analysis
+        .unlinked_files(&snap.config.diagnostics())?
+        .into_iter()
+        .map(|it| lsp_ext::UnlinkedFile {
+            uri: to_proto::url(&snap, it.file_id),
+            suggested_parents: it
+                .suggested_parents
+                .into_iter()
+                .map(|parent| to_proto::url(&snap, parent))
+                .collect(),
+        })
+        .collect();
+    Ok(UnlinkedFilesResult { files })
+}
+
 /// Searches for the directory of a Rust crate given this crate's root file path.
 ///
 /// # Arguments
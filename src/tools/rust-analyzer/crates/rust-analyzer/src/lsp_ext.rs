@@ -52,6 +52,31 @@ pub struct FetchDependencyListResult {
     pub crates: Vec<CrateInfoResult>,
 }
 
+pub enum UnlinkedFiles {}
+
+impl Request for UnlinkedFiles {
+    type Params = UnlinkedFilesParams;
+    type Result = UnlinkedFilesResult;
+    const METHOD: &'static str = "rust-analyzer/unlinkedFiles";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlinkedFilesParams {}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlinkedFilesResult {
+    pub files: Vec<UnlinkedFile>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlinkedFile {
+    pub uri: Url,
+    pub suggested_parents: Vec<Url>,
+}
+
 pub enum MemoryUsage {}
 
 impl Request for MemoryUsage {
@@ -705,6 +705,7 @@ fn on_request(&mut self, req: Request) {
             )
             // All other request handlers
             .on::<lsp_ext::FetchDependencyList>(handlers::fetch_dependency_list)
+            .on::<lsp_ext::UnlinkedFiles>(handlers::handle_unlinked_files)
             .on::<lsp_ext::AnalyzerStatus>(handlers::handle_analyzer_status)
             .on::<lsp_ext::SyntaxTree>(handlers::handle_syntax_tree)
             .on::<lsp_ext::ViewHir>(handlers::handle_view_hir)
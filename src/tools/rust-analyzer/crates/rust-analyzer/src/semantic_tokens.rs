@@ -145,6 +145,7 @@ macro_rules! define_semantic_token_modifiers {
         (MUTABLE, "mutable"),
         (PUBLIC, "public"),
         (REFERENCE, "reference"),
+        (SUPERTRAIT, "supertrait"),
         (TRAIT_MODIFIER, "trait"),
         (UNSAFE, "unsafe"),
     }
@@ -5,6 +5,7 @@
     sync::atomic::{AtomicU32, Ordering},
 };
 
+use anyhow::format_err;
 use ide::{
     Annotation, AnnotationKind, Assist, AssistKind, Cancellable, CompletionItem,
     CompletionItemKind, CompletionRelevance, Documentation, FileId, FileRange, FileSystemEdit,
@@ -25,6 +26,7 @@
     lsp_ext,
     lsp_utils::invalid_params_error,
     semantic_tokens::{self, standard_fallback_type},
+    Result,
 };
 
 pub(crate) fn position(line_index: &LineIndex, offset: TextSize) -> lsp_types::Position {
@@ -94,6 +96,7 @@ pub(crate) fn document_highlight_kind(
 pub(crate) fn diagnostic_severity(severity: Severity) -> lsp_types::DiagnosticSeverity {
     match severity {
         Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
+        Severity::Warning => lsp_types::DiagnosticSeverity::WARNING,
         Severity::WeakWarning => lsp_types::DiagnosticSeverity::HINT,
     }
 }
@@ -683,6 +686,7 @@ fn semantic_token_type_and_modifiers(
             HlMod::Public => semantic_tokens::PUBLIC,
             HlMod::Reference => semantic_tokens::REFERENCE,
             HlMod::Static => semantic_tokens::STATIC,
+            HlMod::Supertrait => semantic_tokens::SUPERTRAIT,
             HlMod::Trait => semantic_tokens::TRAIT_MODIFIER,
             HlMod::Unsafe => semantic_tokens::UNSAFE,
         };
@@ -903,7 +907,7 @@ pub(crate) fn snippet_text_document_edit(
 pub(crate) fn snippet_text_document_ops(
     snap: &GlobalStateSnapshot,
     file_system_edit: FileSystemEdit,
-) -> Cancellable<Vec<lsp_ext::SnippetDocumentChangeOperation>> {
+) -> Result<Vec<lsp_ext::SnippetDocumentChangeOperation>> {
     let mut ops = Vec::new();
     match file_system_edit {
         FileSystemEdit::CreateFile { dst, initial_contents } => {
@@ -956,14 +960,58 @@ pub(crate) fn snippet_text_document_ops(
                 rename_file,
             )))
         }
+        FileSystemEdit::AppendToFile { dst, contents } => {
+            let uri = snap.anchored_path(&dst);
+            let text = snap.anchored_path_contents(&dst);
+            let edit_file = append_to_file_edit(uri, text.as_deref(), contents)?;
+            ops.push(lsp_ext::SnippetDocumentChangeOperation::Edit(edit_file));
+        }
+        FileSystemEdit::DeleteFile { src } => {
+            let uri = snap.file_id_to_url(src);
+            let mut delete_file = lsp_types::DeleteFile { uri, options: None };
+            if snap.analysis.is_library_file(src).ok() == Some(true)
+                && snap.config.change_annotation_support()
+            {
+                delete_file.options = Some(lsp_types::DeleteFileOptions {
+                    recursive: None,
+                    ignore_if_not_exists: None,
+                    annotation_id: Some(outside_workspace_annotation_id()),
+                })
+            }
+            ops.push(lsp_ext::SnippetDocumentChangeOperation::Op(lsp_types::ResourceOp::Delete(
+                delete_file,
+            )))
+        }
     }
     Ok(ops)
 }
 
+/// Appends `contents` to the end of the file at `uri`, whose current contents are `text`.
+///
+/// The current contents are needed to find out where the file ends, so this fails if they
+/// couldn't be read, rather than dropping the edit.
+fn append_to_file_edit(
+    uri: lsp_types::Url,
+    text: Option<&str>,
+    contents: String,
+) -> Result<lsp_ext::SnippetTextDocumentEdit> {
+    let text = text.ok_or_else(|| format_err!("cannot read {uri} to append to it"))?;
+    let (line, last_line) = text.split('\n').enumerate().last().unwrap_or((0, ""));
+    let end = lsp_types::Position::new(line as u32, last_line.encode_utf16().count() as u32);
+    let text_document = lsp_types::OptionalVersionedTextDocumentIdentifier { uri, version: None };
+    let text_edit = lsp_ext::SnippetTextEdit {
+        range: lsp_types::Range::new(end, end),
+        new_text: contents,
+        insert_text_format: Some(lsp_types::InsertTextFormat::PLAIN_TEXT),
+        annotation_id: None,
+    };
+    Ok(lsp_ext::SnippetTextDocumentEdit { text_document, edits: vec![text_edit] })
+}
+
 pub(crate) fn snippet_workspace_edit(
     snap: &GlobalStateSnapshot,
     source_change: SourceChange,
-) -> Cancellable<lsp_ext::SnippetWorkspaceEdit> {
+) -> Result<lsp_ext::SnippetWorkspaceEdit> {
     let mut document_changes: Vec<lsp_ext::SnippetDocumentChangeOperation> = Vec::new();
 
     for op in source_change.file_system_edits {
@@ -1000,7 +1048,7 @@ pub(crate) fn snippet_workspace_edit(
 pub(crate) fn workspace_edit(
     snap: &GlobalStateSnapshot,
     source_change: SourceChange,
-) -> Cancellable<lsp_types::WorkspaceEdit> {
+) -> Result<lsp_types::WorkspaceEdit> {
     assert!(!source_change.is_snippet);
     snippet_workspace_edit(snap, source_change).map(|it| it.into())
 }
@@ -1085,7 +1133,7 @@ pub(crate) fn code_action(
     snap: &GlobalStateSnapshot,
     assist: Assist,
     resolve_data: Option<(usize, lsp_types::CodeActionParams)>,
-) -> Cancellable<lsp_ext::CodeAction> {
+) -> Result<lsp_ext::CodeAction> {
     let mut res = lsp_ext::CodeAction {
         title: assist.label.to_string(),
         group: assist.group.filter(|_| snap.config.code_action_group()).map(|gr| gr.0),
@@ -1451,6 +1499,29 @@ fn main() {
         }
     }
 
+    #[test]
+    fn append_to_file() {
+        let uri = lsp_types::Url::parse("file:///ws/Cargo.toml").unwrap();
+        let text = "[package]\nname = \"ws\"\n\n[[bin]]\nname = \"ä\"";
+        let edit = append_to_file_edit(uri.clone(), Some(text), "\n[[bin]]\n".to_owned()).unwrap();
+        assert_eq!(edit.text_document.uri, uri);
+        let [text_edit] = &edit.edits[..] else { panic!("expected a single edit") };
+        let end = lsp_types::Position::new(4, 10);
+        assert_eq!(text_edit.range, lsp_types::Range::new(end, end));
+        assert_eq!(text_edit.new_text, "\n[[bin]]\n");
+
+        let edit = append_to_file_edit(uri, Some("[package]\n"), String::new()).unwrap();
+        let end = lsp_types::Position::new(1, 0);
+        assert_eq!(edit.edits[0].range, lsp_types::Range::new(end, end));
+    }
+
+    #[test]
+    fn append_to_unreadable_file() {
+        let uri = lsp_types::Url::parse("file:///ws/Cargo.toml").unwrap();
+        let err = append_to_file_edit(uri, None, "\n[[bin]]\n".to_owned()).unwrap_err();
+        assert_eq!(err.to_string(), "cannot read file:///ws/Cargo.toml to append to it");
+    }
+
     // `Url` is not able to parse windows paths on unix machines.
     #[test]
     #[cfg(target_os = "windows")]
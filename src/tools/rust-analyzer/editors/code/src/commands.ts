@@ -274,6 +274,38 @@ export function openCargoToml(ctx: CtxInit): Cmd {
     };
 }
 
+export function showUnlinkedFiles(ctx: CtxInit): Cmd {
+    return async () => {
+        const client = ctx.client;
+        const response = await client.sendRequest(ra.unlinkedFiles, {});
+        if (response.files.length === 0) {
+            await vscode.window.showInformationMessage("All files are linked");
+            return;
+        }
+
+        const items = response.files.map((file) => {
+            const uri = client.protocol2CodeConverter.asUri(file.uri);
+            const parents = file.suggestedParents.map((parent) =>
+                vscode.workspace.asRelativePath(client.protocol2CodeConverter.asUri(parent))
+            );
+            return {
+                label: vscode.workspace.asRelativePath(uri),
+                description:
+                    parents.length === 0 ? "no suggested parent" : `in ${parents.join(", ")}`,
+                uri,
+            };
+        });
+        const item = await vscode.window.showQuickPick(items, {
+            title: `${items.length} files not included in any crate`,
+            matchOnDescription: true,
+        });
+        if (!item) return;
+
+        const doc = await vscode.workspace.openTextDocument(item.uri);
+        await vscode.window.showTextDocument(doc);
+    };
+}
+
 export function revealDependency(ctx: CtxInit): Cmd {
     return async (editor: RustEditor) => {
         if (!ctx.dependencies?.isInitialized()) {
@@ -86,6 +86,19 @@ export const fetchDependencyList = new lc.RequestType<
     void
 >("rust-analyzer/fetchDependencyList");
 
+export interface UnlinkedFilesParams {}
+
+export interface UnlinkedFilesResult {
+    files: {
+        uri: string;
+        suggestedParents: string[];
+    }[];
+}
+
+export const unlinkedFiles = new lc.RequestType<UnlinkedFilesParams, UnlinkedFilesResult, void>(
+    "rust-analyzer/unlinkedFiles"
+);
+
 export interface FetchDependencyGraphParams {}
 
 export interface FetchDependencyGraphResult {
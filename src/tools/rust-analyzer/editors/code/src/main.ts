@@ -171,6 +171,7 @@ function createCommands(): Record<string, CommandFactory> {
         newDebugConfig: { enabled: commands.newDebugConfig },
         openDocs: { enabled: commands.openDocs },
         openCargoToml: { enabled: commands.openCargoToml },
+        showUnlinkedFiles: { enabled: commands.showUnlinkedFiles },
         peekTests: { enabled: commands.peekTests },
         moveItemUp: { enabled: commands.moveItemUp },
         moveItemDown: { enabled: commands.moveItemDown },
@@ -0,0 +1,21 @@
+// ignore-wasm32-bare compiled with panic=abort by default
+// needs-unwind - this test checks that unneeded cleanup blocks are not codegened
+// compile-flags: -C no-prepopulate-passes -Copt-level=0
+
+#![crate_type = "lib"]
+
+// `_b` is dropped before `_a`, and if dropping it unwound, `_a` would be dropped in a cleanup
+// block. As `Option<&u8>` has no drop glue, dropping it does nothing, so that cleanup block is
+// unreachable once this function is monomorphized, and must not be codegened.
+
+// CHECK-LABEL: define{{.*}}drop_in_reverse_order
+// CHECK-NOT: resume
+// CHECK: {{(call|invoke) void @.*}}drop_in_place
+// CHECK-NOT: resume
+// CHECK-LABEL: {{^[}]}}
+fn drop_in_reverse_order<T, U>(_a: T, _b: U) {}
+
+#[no_mangle]
+pub fn no_glue(a: String, b: Option<&u8>) {
+    drop_in_reverse_order(a, b)
+}
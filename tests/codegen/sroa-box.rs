@@ -0,0 +1,51 @@
+// compile-flags: -O -Zmir-opt-level=3 -Cno-prepopulate-passes
+// ignore-debug: the debug assertions get in the way
+#![crate_type = "lib"]
+
+// Boxes of small types that don't escape the function are replaced by stack locals in MIR, so
+// there is no allocation left for LLVM to remove.
+
+pub struct Point {
+    x: u32,
+    y: u32,
+}
+
+// CHECK-LABEL: @local_box
+#[no_mangle]
+pub fn local_box(x: u32, y: u32) -> u32 {
+    // CHECK-NOT: exchange_malloc
+    // CHECK-NOT: __rust_alloc
+    // CHECK-NOT: __rust_dealloc
+    // CHECK: ret i32
+    let point = Box::new(Point { x, y });
+    point.x + point.y
+}
+
+// CHECK-LABEL: @local_box_moved
+#[no_mangle]
+pub fn local_box_moved(x: u32, y: u32) -> u32 {
+    // CHECK-NOT: exchange_malloc
+    // CHECK-NOT: __rust_alloc
+    // CHECK-NOT: __rust_dealloc
+    // CHECK: ret i32
+    let point = Box::new(Point { x, y });
+    let moved = point;
+    moved.x * moved.y
+}
+
+// The box is returned, so it has to be allocated.
+// CHECK-LABEL: @escaping_box
+#[no_mangle]
+pub fn escaping_box(x: u32, y: u32) -> Box<Point> {
+    // CHECK: exchange_malloc
+    Box::new(Point { x, y })
+}
+
+// Large types stay on the heap.
+// CHECK-LABEL: @large_box
+#[no_mangle]
+pub fn large_box(x: u32) -> u32 {
+    // CHECK: exchange_malloc
+    let array = Box::new([x; 64]);
+    array[0] + array[63]
+}
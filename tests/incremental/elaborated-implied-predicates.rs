@@ -0,0 +1,46 @@
+// Check that the `elaborated_implied_predicates` of a trait are only recomputed
+// to something different when the predicates of the trait change, and not on
+// other changes to the trait.
+
+// revisions: cfail1 cfail2 cfail3
+// compile-flags: -Z query-dep-graph -Ztrait-solver=next
+// build-pass
+
+#![feature(rustc_attrs)]
+#![crate_type = "rlib"]
+
+pub trait Super {}
+pub trait Other {}
+
+fn needs_super<T: Super + ?Sized>() {}
+
+// Add a supertrait
+#[cfg(cfail1)]
+pub trait AddSuperTrait: Super {}
+
+#[cfg(not(cfail1))]
+#[rustc_clean(
+    cfg = "cfail2",
+    except = "hir_owner,hir_owner_nodes,predicates_of,elaborated_implied_predicates"
+)]
+#[rustc_clean(cfg = "cfail3")]
+pub trait AddSuperTrait: Super + Other {}
+
+pub fn add_super_trait() {
+    needs_super::<dyn AddSuperTrait>();
+}
+
+// Add a method
+#[cfg(cfail1)]
+pub trait AddMethod: Super {}
+
+#[cfg(not(cfail1))]
+#[rustc_clean(cfg = "cfail2", except = "hir_owner,hir_owner_nodes,associated_item_def_ids")]
+#[rustc_clean(cfg = "cfail3")]
+pub trait AddMethod: Super {
+    fn method(&self);
+}
+
+pub fn add_method() {
+    needs_super::<dyn AddMethod>();
+}
@@ -0,0 +1,30 @@
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+// unit-test: Devirtualize
+
+pub trait Foo {
+    fn foo(&self) -> u32;
+}
+
+pub struct S(u32);
+
+impl Foo for S {
+    fn foo(&self) -> u32 {
+        self.0
+    }
+}
+
+// EMIT_MIR devirtualize.known.Devirtualize.diff
+pub fn known(s: &S) -> u32 {
+    let d: &dyn Foo = s;
+    d.foo()
+}
+
+// EMIT_MIR devirtualize.unknown.Devirtualize.diff
+pub fn unknown(d: &dyn Foo) -> u32 {
+    d.foo()
+}
+
+fn main() {
+    known(&S(0));
+    unknown(&S(1));
+}
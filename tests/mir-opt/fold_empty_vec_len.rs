@@ -0,0 +1,111 @@
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+// unit-test: FoldEmptyVecLen
+
+#![feature(custom_mir, core_intrinsics)]
+
+extern crate core;
+use core::intrinsics::mir::*;
+
+// EMIT_MIR fold_empty_vec_len.new_len.FoldEmptyVecLen.diff
+pub fn new_len() -> usize {
+    let v: Vec<u32> = Vec::new();
+    v.len()
+}
+
+// EMIT_MIR fold_empty_vec_len.with_capacity_is_empty.FoldEmptyVecLen.diff
+pub fn with_capacity_is_empty(n: usize) -> bool {
+    let v: Vec<u32> = Vec::with_capacity(n);
+    v.is_empty()
+}
+
+fn fill(mut v: Vec<u32>) -> Vec<u32> {
+    v.push(1);
+    v
+}
+
+fn opaque() {}
+
+// The vector is pushed to through a mutable borrow, so its length must not be folded.
+// EMIT_MIR fold_empty_vec_len.pushed_through_mut_ref.FoldEmptyVecLen.diff
+#[custom_mir(dialect = "analysis", phase = "post-cleanup")]
+pub fn pushed_through_mut_ref() -> usize {
+    mir!(
+        let v: Vec<u32>;
+        let m: &mut Vec<u32>;
+        let unit: ();
+        let r: &Vec<u32>;
+        {
+            Call(v, push, Vec::new())
+        }
+        push = {
+            m = &mut v;
+            Call(unit, len, Vec::push(Move(m), 1))
+        }
+        len = {
+            r = &v;
+            Call(RET, ret, Vec::len(Move(r)))
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// The vector is moved out and replaced by a non-empty one, so its length must not be folded.
+// EMIT_MIR fold_empty_vec_len.moved.FoldEmptyVecLen.diff
+#[custom_mir(dialect = "analysis", phase = "post-cleanup")]
+pub fn moved() -> usize {
+    mir!(
+        let v: Vec<u32>;
+        let w: Vec<u32>;
+        let r: &Vec<u32>;
+        {
+            Call(v, refill, Vec::new())
+        }
+        refill = {
+            Call(w, len, fill(Move(v)))
+        }
+        len = {
+            v = Move(w);
+            r = &v;
+            Call(RET, ret, Vec::len(Move(r)))
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+// The receiver is borrowed from the empty vector, but borrowed again from `w` before the call.
+// The last borrow in block order is the one of the empty vector, so this must not be folded.
+// EMIT_MIR fold_empty_vec_len.reborrowed_twice.FoldEmptyVecLen.diff
+#[custom_mir(dialect = "analysis", phase = "post-cleanup")]
+pub fn reborrowed_twice(w: Vec<u32>) -> usize {
+    mir!(
+        let v: Vec<u32>;
+        let r: &Vec<u32>;
+        let unit: ();
+        {
+            Call(v, borrow_empty, Vec::new())
+        }
+        len = {
+            r = &w;
+            Call(RET, ret, Vec::len(Move(r)))
+        }
+        borrow_empty = {
+            r = &v;
+            Call(unit, len, opaque())
+        }
+        ret = {
+            Return()
+        }
+    )
+}
+
+fn main() {
+    new_len();
+    with_capacity_is_empty(4);
+    pushed_through_mut_ref();
+    moved();
+    reborrowed_twice(vec![]);
+}
@@ -0,0 +1,15 @@
+// compile-flags: -O -Zcross-crate-inline-threshold=10
+
+#![crate_type = "lib"]
+
+pub fn leaf() {}
+
+pub fn not_leaf() {
+    opaque();
+}
+
+#[inline(never)]
+pub fn never() {}
+
+#[inline(never)]
+fn opaque() {}
@@ -0,0 +1,18 @@
+#![crate_type = "lib"]
+
+pub struct D;
+
+impl Drop for D {
+    #[inline(never)]
+    fn drop(&mut self) {}
+}
+
+// With `-Cpanic=unwind`, the cleanup path drops `_d` and resumes unwinding.
+#[inline]
+pub fn with_landing_pad() {
+    let _d = D;
+    opaque();
+}
+
+#[inline(never)]
+pub fn opaque() {}
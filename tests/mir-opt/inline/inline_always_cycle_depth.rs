@@ -0,0 +1,20 @@
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+// compile-flags: -Zinline-mir-cycle-depth=2
+// Checks that a cycle of `#[inline(always)]` functions is unrolled up to the given depth before
+// the inliner leaves a call in place.
+
+#![allow(unconditional_recursion)]
+
+#[inline(always)]
+fn rec() {
+    rec()
+}
+
+// EMIT_MIR inline_always_cycle_depth.caller.Inline.diff
+pub fn caller() {
+    rec();
+}
+
+fn main() {
+    caller();
+}
@@ -0,0 +1,50 @@
+// Checks that functions using inline assembly are not inlined into callers with a different
+// instruction set, as the assembly may not be valid in the caller's instruction set.
+//
+// compile-flags: --target thumbv4t-none-eabi
+// needs-llvm-components: arm
+
+#![crate_type = "lib"]
+#![feature(rustc_attrs)]
+#![feature(no_core, lang_items)]
+#![feature(isa_attribute)]
+#![no_core]
+
+#[rustc_builtin_macro]
+#[macro_export]
+macro_rules! asm {
+    ("assembly template",
+        $(operands,)*
+        $(options($(option),*))?
+    ) => {
+        /* compiler built-in */
+    };
+}
+
+#[lang = "sized"]
+trait Sized {}
+#[lang = "copy"]
+trait Copy {}
+
+#[inline(always)]
+fn nop() {
+    unsafe { asm!("nop") };
+}
+
+#[inline(always)]
+fn clobber_reg_class() {
+    unsafe { asm!("/* {} */", out(reg) _) };
+}
+
+#[inline(always)]
+fn clobber_r0() {
+    unsafe { asm!("", out("r0") _) };
+}
+
+// EMIT_MIR inline_asm_instruction_set.a32.Inline.diff
+#[instruction_set(arm::a32)]
+pub fn a32() {
+    nop();
+    clobber_reg_class();
+    clobber_r0();
+}
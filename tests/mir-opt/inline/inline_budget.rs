@@ -0,0 +1,31 @@
+// Tests that `#[inline_budget]` replaces the inlining threshold of a function.
+//
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+#![crate_type = "lib"]
+#![feature(inline_budget)]
+
+// EMIT_MIR inline_budget.caller.Inline.diff
+pub fn caller() {
+    within_budget();
+    over_budget();
+}
+
+// Cost is 3 * 25 + 2 * 5 = 85.
+#[inline]
+#[inline_budget(100)]
+fn within_budget() {
+    g();
+    g();
+    g();
+}
+
+#[inline]
+#[inline_budget(70)]
+fn over_budget() {
+    g();
+    g();
+    g();
+}
+
+#[inline(never)]
+fn g() {}
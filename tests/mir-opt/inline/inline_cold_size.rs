@@ -0,0 +1,58 @@
+// Tests that cold callees are only inlined if they are hinted `#[inline]`, and then get a much
+// smaller inlining threshold, and that callers optimized for size only inline the callees that are
+// hinted `#[inline]`.
+//
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+#![crate_type = "lib"]
+#![feature(optimize_attribute)]
+
+// EMIT_MIR inline_cold_size.cold_callees.Inline.diff
+pub fn cold_callees() {
+    empty_cold();
+    calls_cold();
+    plain_cold::<()>();
+    always_cold();
+}
+
+// EMIT_MIR inline_cold_size.size_caller.Inline.diff
+#[optimize(size)]
+pub fn size_caller() {
+    hinted();
+    unhinted::<()>();
+}
+
+// EMIT_MIR inline_cold_size.speed_caller.Inline.diff
+pub fn speed_caller() {
+    unhinted::<()>();
+}
+
+#[inline]
+#[cold]
+fn empty_cold() {}
+
+#[inline]
+#[cold]
+fn calls_cold() {
+    g();
+}
+
+#[cold]
+fn plain_cold<T>() {}
+
+#[inline(always)]
+#[cold]
+fn always_cold() {
+    g();
+}
+
+#[inline]
+fn hinted() {
+    g();
+}
+
+fn unhinted<T>() {
+    g();
+}
+
+#[inline(never)]
+fn g() {}
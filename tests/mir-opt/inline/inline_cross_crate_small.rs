@@ -0,0 +1,16 @@
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+// Checks that small leaf functions without `#[inline]` are inlined across crates
+// when the defining crate is compiled with -Zcross-crate-inline-threshold.
+//
+// aux-build:cross_crate_small.rs
+
+#![crate_type = "lib"]
+
+extern crate cross_crate_small;
+
+// EMIT_MIR inline_cross_crate_small.caller.Inline.diff
+pub fn caller() {
+    cross_crate_small::leaf();
+    cross_crate_small::not_leaf();
+    cross_crate_small::never();
+}
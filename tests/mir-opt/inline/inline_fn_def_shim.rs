@@ -0,0 +1,20 @@
+// Tests that calls of `Fn*` trait methods on function items are turned into direct calls,
+// so that the function item can be inlined without going through its shim first.
+//
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+#![crate_type = "lib"]
+
+// EMIT_MIR inline_fn_def_shim.caller.Inline.diff
+pub fn caller(x: u32) -> u32 {
+    apply(&add_one, x)
+}
+
+#[inline]
+fn apply(f: &impl Fn(u32) -> u32, x: u32) -> u32 {
+    f(x)
+}
+
+#[inline]
+fn add_one(x: u32) -> u32 {
+    x | 1
+}
@@ -0,0 +1,36 @@
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+// The target of the call through `f` is only known once const-prop has propagated the arguments
+// of the inlined `apply`, so `double` gets inlined by the inliner run after const-prop.
+
+#[inline]
+fn apply(f: fn(i32) -> i32, x: i32) -> i32 {
+    f(x)
+}
+
+#[inline]
+fn apply2(f: fn(fn(i32) -> i32, i32) -> i32, g: fn(i32) -> i32, x: i32) -> i32 {
+    f(g, x)
+}
+
+#[inline]
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+// EMIT_MIR inline_fn_ptr_after_const_prop.caller.ConstProp.diff
+// EMIT_MIR inline_fn_ptr_after_const_prop.caller.InlineAfterConstProp.diff
+pub fn caller() -> i32 {
+    apply(double, 21)
+}
+
+// The call through `g` only shows up once `apply` is inlined, so this takes a second round of
+// const-prop and inlining.
+// EMIT_MIR inline_fn_ptr_after_const_prop.caller_twice.InlineAfterConstProp.diff
+pub fn caller_twice() -> i32 {
+    apply2(apply, double, 21)
+}
+
+fn main() {
+    caller();
+    caller_twice();
+}
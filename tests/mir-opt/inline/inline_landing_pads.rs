@@ -0,0 +1,16 @@
+// Checks that the landing pads of a callee are charged when unwinding: with
+// `-Cpanic=unwind` the callee costs 75 + 25 + 5 + 25 + 45 = 175, which is above the
+// `#[inline]` threshold. See `inline_landing_pads_abort.rs` for `-Cpanic=abort`, where
+// it only costs 25 + 25 + 5 = 55.
+//
+// needs-unwind
+// aux-build:landing_pads.rs
+
+#![crate_type = "lib"]
+
+extern crate landing_pads;
+
+// EMIT_MIR inline_landing_pads.caller.Inline.diff
+pub fn caller() {
+    landing_pads::with_landing_pad();
+}
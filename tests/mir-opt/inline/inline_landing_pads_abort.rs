@@ -0,0 +1,15 @@
+// Checks that the landing pads of a callee built with `-Cpanic=unwind` aren't charged
+// when inlining it into a crate built with `-Cpanic=abort`, as they are dead code there.
+//
+// needs-unwind
+// aux-build:landing_pads.rs
+// compile-flags: -Cpanic=abort
+
+#![crate_type = "lib"]
+
+extern crate landing_pads;
+
+// EMIT_MIR inline_landing_pads_abort.caller.Inline.diff
+pub fn caller() {
+    landing_pads::with_landing_pad();
+}
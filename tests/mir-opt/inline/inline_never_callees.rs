@@ -0,0 +1,32 @@
+// Tests that `#[inline(never_callees)]` prevents inlining into a function, while the function
+// itself can still be inlined.
+//
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+#![crate_type = "lib"]
+#![feature(inline_never_callees)]
+
+// EMIT_MIR inline_never_callees.frozen.Inline.diff
+#[inline(never_callees)]
+pub fn frozen() {
+    g();
+}
+
+// EMIT_MIR inline_never_callees.caller.Inline.diff
+pub fn caller() {
+    frozen_leaf();
+}
+
+// Combined with `#[inline]`, which is needed for a private function to be inlined.
+#[inline]
+#[inline(never_callees)]
+fn frozen_leaf() {
+    h();
+}
+
+#[inline]
+fn g() {
+    h();
+}
+
+#[inline(never)]
+fn h() {}
@@ -0,0 +1,22 @@
+// Tests that the MIR inliner stops inlining into a caller once the total cost of the callees it
+// inlined into it would exceed `-Zinline-mir-total-threshold`.
+//
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+// compile-flags: -Zinline-mir-total-threshold=70
+#![crate_type = "lib"]
+
+// EMIT_MIR inline_total_threshold.caller.Inline.diff
+pub fn caller() {
+    step();
+    step();
+    step();
+}
+
+// Cost is 25 + 2 * 5 = 35, so only the first two calls fit in the total threshold.
+#[inline]
+fn step() {
+    g();
+}
+
+#[inline(never)]
+fn g() {}
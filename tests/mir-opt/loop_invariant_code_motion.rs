@@ -0,0 +1,17 @@
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+// unit-test: LoopInvariantCodeMotion
+
+// EMIT_MIR loop_invariant_code_motion.hoist.LoopInvariantCodeMotion.diff
+pub fn hoist(n: u32, a: u32, b: u32) -> u32 {
+    let mut acc = 0;
+    let mut i = 0;
+    while i < n {
+        acc ^= a * b;
+        i += 1;
+    }
+    acc
+}
+
+fn main() {
+    hoist(3, 4, 5);
+}
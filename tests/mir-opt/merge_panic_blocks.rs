@@ -0,0 +1,50 @@
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+// unit-test: MergePanicBlocks
+// compile-flags: -Zinline-mir
+
+#[inline(never)]
+fn fail() -> ! {
+    loop {}
+}
+
+#[inline(never)]
+fn fail_with(_code: u32) -> ! {
+    loop {}
+}
+
+#[inline]
+fn check(x: bool) {
+    if x {
+        fail();
+    }
+}
+
+#[inline]
+fn check_with_code(x: bool) {
+    if x {
+        let code = 7;
+        fail_with(code);
+    }
+}
+
+// Both inlined copies of `check` call `fail` from the same place, so only one of the blocks
+// calling it is kept.
+// EMIT_MIR merge_panic_blocks.merged.MergePanicBlocks.diff
+pub fn merged(a: bool, b: bool) {
+    check(a);
+    check(b);
+}
+
+// Each inlined copy of `check_with_code` has its own `code` variable with debuginfo, so the blocks
+// calling `fail_with` are kept apart.
+// EMIT_MIR merge_panic_blocks.not_merged_debuginfo.MergePanicBlocks.diff
+#[inline(never)]
+pub fn not_merged_debuginfo(a: bool, b: bool) {
+    check_with_code(a);
+    check_with_code(b);
+}
+
+fn main() {
+    merged(false, false);
+    not_merged_debuginfo(false, false);
+}
@@ -0,0 +1,17 @@
+// EMIT_MIR_FOR_EACH_PANIC_STRATEGY
+// unit-test: RemoveRedundantBoundsChecks
+
+// EMIT_MIR remove_redundant_bounds_checks.twice.RemoveRedundantBoundsChecks.diff
+pub fn twice(a: &[u32], i: usize) -> (u32, u32) {
+    (a[i], a[i])
+}
+
+// EMIT_MIR remove_redundant_bounds_checks.different.RemoveRedundantBoundsChecks.diff
+pub fn different(a: &[u32], i: usize, j: usize) -> (u32, u32) {
+    (a[i], a[j])
+}
+
+fn main() {
+    twice(&[1, 2], 1);
+    different(&[1, 2], 0, 1);
+}
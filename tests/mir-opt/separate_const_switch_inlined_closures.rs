@@ -0,0 +1,34 @@
+// compile-flags: -Zinline-mir
+
+// Checks that switches over enum values that the caller builds are threaded
+// through the bodies of inlined closures.
+
+#[inline]
+fn with<T, R>(value: T, f: impl FnOnce(T) -> R) -> R {
+    f(value)
+}
+
+// EMIT_MIR separate_const_switch_inlined_closures.unwrap_or.SeparateConstSwitch.diff
+pub fn unwrap_or(x: u32, y: u32) -> u32 {
+    // The `Some` built in one branch of the `if` is matched on inside the closure.
+    let wrapped = if x > y { Some(x) } else { None };
+    with(wrapped, |wrapped| match wrapped {
+        Some(x) => x,
+        None => y,
+    })
+}
+
+// EMIT_MIR separate_const_switch_inlined_closures.nested.SeparateConstSwitch.diff
+pub fn nested(x: bool) -> u8 {
+    // The value built by the first closure is matched on by the second one.
+    let wrapped = with(x, |x| if x { Ok(1u8) } else { Err(2u8) });
+    with(wrapped, |wrapped| match wrapped {
+        Ok(v) => v,
+        Err(e) => e,
+    })
+}
+
+fn main() {
+    unwrap_or(1, 2);
+    nested(true);
+}
@@ -0,0 +1,17 @@
+// unit-test: ScalarReplacementOfAggregates
+// compile-flags: -Cpanic=abort -Zmir-opt-level=3 -Zinline-mir=no
+// no-prefer-dynamic
+
+#![feature(rustc_attrs, stmt_expr_attributes)]
+
+// The box is replaced with a local, and its debuginfo now refers to that local.
+// EMIT_MIR boxes.local_box.ScalarReplacementOfAggregates.diff
+pub fn local_box(x: u32) -> u32 {
+    let b = #[rustc_box]
+    Box::new(x);
+    *b
+}
+
+fn main() {
+    local_box(5);
+}
@@ -0,0 +1,13 @@
+#[inline]
+fn small(x: u32) -> u32 {
+    x + 1
+}
+
+#[inline(never)]
+fn recursive(x: u32) -> u32 {
+    if x == 0 { 0 } else { recursive(x - 1) }
+}
+
+pub fn caller(x: u32) -> u32 {
+    small(x) + recursive(x)
+}
@@ -0,0 +1,24 @@
+// check-pass
+// Tests that `#[inline(never_callees)]` can be combined with another `#[inline]` attribute without
+// either being reported as unused.
+
+#![feature(inline_never_callees)]
+#![deny(unused_attributes)]
+
+#[inline]
+#[inline(never_callees)]
+pub fn hint() {}
+
+#[inline(never_callees)]
+#[inline(always)]
+pub fn always() {}
+
+#[inline(never)]
+#[inline(never_callees)]
+pub fn never() {}
+
+fn main() {
+    hint();
+    always();
+    never();
+}
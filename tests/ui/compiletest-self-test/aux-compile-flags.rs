@@ -0,0 +1,17 @@
+// Check that the arguments provided through `// aux-compile-flags` are only passed to the
+// auxiliary crates of the revisions they are given for, and not to the test crate itself.
+//
+// revisions: plain with_cfg
+// aux-build: aux-compile-flags.rs
+//[with_cfg] aux-compile-flags: --cfg aux_cfg
+//[with_cfg] check-pass
+
+extern crate aux_compile_flags;
+
+#[cfg(aux_cfg)]
+compile_error!("`aux-compile-flags` must not be passed to the test crate");
+
+fn main() {
+    aux_compile_flags::only_with_aux_cfg();
+    //[plain]~^ ERROR cannot find function `only_with_aux_cfg` in crate `aux_compile_flags`
+}
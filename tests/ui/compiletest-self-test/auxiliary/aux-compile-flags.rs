@@ -0,0 +1,2 @@
+#[cfg(aux_cfg)]
+pub fn only_with_aux_cfg() {}
@@ -0,0 +1,29 @@
+// check-pass
+#![feature(generic_const_exprs)]
+#![allow(incomplete_features)]
+
+// Elaborating `[(); N + 1 + 1]:` yields `N + 1` as well, which is fully concrete
+// once `N` is substituted when building the vtable of `dyn Trait<3>`. We can't
+// evaluate concrete `ConstKind::Expr`s yet, so those must not be elaborated, as
+// checking them for impossible predicates would ICE.
+
+trait Trait<const N: usize> {
+    fn method(&self)
+    where
+        [(); N + 1 + 1]:;
+}
+
+struct Foo;
+
+impl<const N: usize> Trait<N> for Foo {
+    fn method(&self)
+    where
+        [(); N + 1 + 1]:,
+    {
+    }
+}
+
+fn main() {
+    let foo: &dyn Trait<3> = &Foo;
+    foo.method();
+}
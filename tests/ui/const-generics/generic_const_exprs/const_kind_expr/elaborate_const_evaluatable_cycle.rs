@@ -0,0 +1,59 @@
+// run-pass
+#![feature(generic_const_exprs)]
+#![allow(incomplete_features)]
+
+// Elaborating the `ConstEvaluatable` bounds of an item while building its param env
+// needs the abstract representation of their constants, which needs that param env
+// to type-check the constants, causing a query cycle. Such bounds are only elaborated
+// by callers that opt in.
+
+fn free<const N: usize>() -> [u8; N + 1 + 1]
+where
+    [u8; N + 1 + 1]:,
+{
+    [0; N + 1 + 1]
+}
+
+struct Wrapper<const N: usize>
+where
+    [(); N * 2 + 1]:,
+{
+    data: [u8; N * 2 + 1],
+}
+
+impl<const N: usize> Wrapper<N>
+where
+    [(); N * 2 + 1]:,
+{
+    fn new() -> Self {
+        Wrapper { data: [0; N * 2 + 1] }
+    }
+}
+
+trait Trait<const N: usize> {
+    fn method(&self) -> usize
+    where
+        [(); (N + 1) * 2]:;
+}
+
+impl<const N: usize> Trait<N> for () {
+    fn method(&self) -> usize
+    where
+        [(); (N + 1) * 2]:,
+    {
+        [(); (N + 1) * 2].len()
+    }
+}
+
+fn caller<const N: usize>() -> usize
+where
+    [u8; N + 1 + 1]:,
+    [(); N * 2 + 1]:,
+    [(); (N + 1) * 2]:,
+{
+    free::<N>().len() + Wrapper::<N>::new().data.len() + Trait::<N>::method(&())
+}
+
+fn main() {
+    assert_eq!(caller::<1>(), 3 + 3 + 4);
+}
@@ -0,0 +1,27 @@
+// Checks that allocating more memory than `-Zconst-eval-memory-limit` allows is reported at the
+// allocation site, and that deallocated memory doesn't count towards the limit.
+// compile-flags: -Zconst-eval-memory-limit=1024
+#![feature(core_intrinsics)]
+#![feature(const_heap)]
+use std::intrinsics;
+
+const FITS: () = unsafe {
+    let mut i = 0;
+    while i < 4 {
+        let ptr = intrinsics::const_allocate(1000, 1);
+        intrinsics::const_deallocate(ptr, 1000, 1);
+        i += 1;
+    }
+};
+
+const TOO_BIG: i32 = too_big();
+const fn too_big() -> i32 {
+    unsafe {
+        let _ = intrinsics::const_allocate(512, 1);
+        let _ = intrinsics::const_allocate(1024, 1);
+        //~^ ERROR evaluation of constant value failed
+    }
+    1
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+// compile-flags: -Zunleash-the-miri-inside-of-you -Zextra-const-ub-info
+// stderr-per-bitwidth
+// Like `const-points-to-static.rs`, but also shows the raw bytes of the constant.
+#![allow(dead_code)]
+
+const TEST: &u8 = &MY_STATIC;
+//~^ ERROR it is undefined behavior to use this value
+//~| encountered a reference pointing to a static variable
+
+static MY_STATIC: u8 = 4;
+
+fn main() {
+}
@@ -0,0 +1,21 @@
+// When the crate uses `SyncUnsafeCell`, `const_refs_to_static_mut` mentions it as an alternative.
+
+#![feature(sync_unsafe_cell)]
+
+static mut COUNTER: u64 = 0;
+fn increment() { unsafe { COUNTER += 1 } }
+const A: &u64 = unsafe { &COUNTER };
+//~^ ERROR constant takes a reference to the mutable static `COUNTER`
+//~| ERROR constants cannot refer to statics
+//~| ERROR constants cannot refer to statics
+
+// Raw pointers are not `Sync`, so this can't be a `static`.
+static mut PTR: *const u8 = std::ptr::null();
+const B: &*const u8 = unsafe { &PTR };
+//~^ ERROR constant takes a reference to the mutable static `PTR`
+//~| ERROR constants cannot refer to statics
+//~| ERROR constants cannot refer to statics
+
+fn main() {
+    increment();
+}
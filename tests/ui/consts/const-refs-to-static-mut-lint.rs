@@ -0,0 +1,24 @@
+// Constants taking references to a `static mut` are linted, in addition to the errors that const
+// checking reports for them.
+
+static mut COUNTER: i32 = 0;
+static mut FLAGS: [bool; 2] = [false; 2];
+static mut NAME: &str = "";
+
+const A: &i32 = unsafe { &COUNTER };
+//~^ ERROR constant takes a reference to the mutable static `COUNTER`
+//~| ERROR constants cannot refer to statics
+//~| ERROR constants cannot refer to statics
+
+const B: &bool = unsafe { &FLAGS[1] };
+//~^ ERROR constant takes a reference to the mutable static `FLAGS`
+//~| ERROR constants cannot refer to statics
+//~| ERROR constants cannot refer to statics
+//~| ERROR constants cannot refer to statics
+
+const C: &mut &str = unsafe { &mut NAME };
+//~^ ERROR constant takes a reference to the mutable static `NAME`
+//~| ERROR constants cannot refer to statics
+//~| ERROR constants cannot refer to statics
+
+fn main() {}
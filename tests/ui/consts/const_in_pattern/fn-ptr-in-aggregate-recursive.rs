@@ -0,0 +1,18 @@
+// check-pass
+
+// Check that looking for function pointers in the type of a constant pattern terminates for
+// polymorphically recursive types, whose nested types never repeat.
+
+#![warn(pointer_structural_match)]
+
+#[derive(PartialEq, Eq)]
+struct Nested<T: 'static>(T, Option<&'static Nested<(T, T)>>);
+
+const LEAF: Nested<u8> = Nested(0, None);
+
+fn main() {
+    match Nested(0, None) {
+        LEAF => {}
+        _ => {}
+    }
+}
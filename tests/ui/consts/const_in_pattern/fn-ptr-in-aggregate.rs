@@ -0,0 +1,48 @@
+// run-pass
+
+// Check that function pointers nested in the value of a constant pattern are reported, as they
+// are compared by address.
+
+#![warn(pointer_structural_match)]
+
+use std::marker::PhantomData;
+
+fn foo() {}
+
+#[derive(PartialEq, Eq)]
+struct Callbacks {
+    id: u32,
+    on_event: fn(),
+}
+
+#[derive(PartialEq, Eq)]
+struct Marker(PhantomData<fn()>);
+
+const CALLBACKS: Callbacks = Callbacks { id: 0, on_event: foo };
+const SOME_FN: Option<fn()> = Some(foo);
+const TUPLE_REF: &(u8, fn()) = &(0, foo);
+const MARKER: Marker = Marker(PhantomData);
+
+fn main() {
+    match (Callbacks { id: 0, on_event: foo }) {
+        CALLBACKS => {}
+        //~^ WARN function pointers in patterns behave unpredictably
+        //~| WARN this was previously accepted by the compiler but is being phased out
+        _ => {}
+    }
+    match Some(foo as fn()) {
+        SOME_FN => {}
+        //~^ WARN function pointers in patterns behave unpredictably
+        //~| WARN this was previously accepted by the compiler but is being phased out
+        _ => {}
+    }
+    match &(0, foo as fn()) {
+        TUPLE_REF => {}
+        //~^ WARN function pointers in patterns behave unpredictably
+        //~| WARN this was previously accepted by the compiler but is being phased out
+        _ => {}
+    }
+    match Marker(PhantomData) {
+        MARKER => {}
+    }
+}
@@ -0,0 +1,33 @@
+// run-pass
+#![feature(const_refs_to_static)]
+
+static S: i32 = 42;
+const C: &i32 = &S;
+
+static ARR: [u8; 3] = [1, 2, 3];
+const SLICE: &[u8] = &ARR;
+
+const fn get() -> &'static i32 {
+    &S
+}
+const C2: &i32 = get();
+
+fn main() {
+    assert_eq!(*C, 42);
+    assert!(std::ptr::eq(C, &S));
+    assert_eq!(*C2, 42);
+
+    // The pointed-to statics are read-only, so their values can be used in patterns.
+    match &42 {
+        C => {}
+        _ => panic!(),
+    }
+    match &0 {
+        C => panic!(),
+        _ => {}
+    }
+    match &[1, 2, 3][..] {
+        SLICE => {}
+        _ => panic!(),
+    }
+}
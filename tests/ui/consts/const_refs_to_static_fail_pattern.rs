@@ -0,0 +1,15 @@
+#![feature(const_refs_to_static)]
+#![allow(const_refs_to_static_mut)]
+
+// The value of a `static mut` may change at runtime, so constants may never refer to it, even
+// with `const_refs_to_static`.
+static mut S_MUT: i32 = 0;
+
+const C: &i32 = unsafe { &S_MUT };
+//~^ ERROR constants cannot refer to statics
+//~| ERROR constants cannot refer to statics
+
+fn main() {
+    let C = &0;
+    //~^ ERROR could not evaluate constant pattern
+}
@@ -0,0 +1,38 @@
+// run-pass
+#![feature(const_refs_to_static)]
+
+// Constants may refer to statics that are neither `static mut` nor have interior mutability,
+// whatever their type is otherwise.
+
+#[derive(Debug, PartialEq, Eq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+static ORIGIN: Point = Point { x: 0, y: 0 };
+const ORIGIN_REF: &Point = &ORIGIN;
+const ORIGIN_X: &i32 = &ORIGIN.x;
+
+static PAIR: (u8, &str) = (1, "one");
+const PAIR_REF: &(u8, &str) = &PAIR;
+
+// The static itself may contain references, as long as they are shared.
+static NESTED: &[i32] = &[1, 2, 3];
+const NESTED_REF: &&[i32] = &NESTED;
+
+const fn pick<T>(first: bool, a: &'static T, b: &'static T) -> &'static T {
+    if first { a } else { b }
+}
+static ONE: u64 = 1;
+static TWO: u64 = 2;
+const PICKED: &u64 = pick(false, &ONE, &TWO);
+
+fn main() {
+    assert_eq!(*ORIGIN_REF, Point { x: 0, y: 0 });
+    assert!(std::ptr::eq(ORIGIN_REF, &ORIGIN));
+    assert_eq!(*ORIGIN_X, 0);
+    assert_eq!(*PAIR_REF, (1, "one"));
+    assert_eq!(**NESTED_REF, [1, 2, 3]);
+    assert!(std::ptr::eq(PICKED, &TWO));
+}
@@ -0,0 +1,26 @@
+#![feature(const_refs_to_static)]
+
+// A constant may refer to an immutable static that itself points to mutable memory. The value
+// behind the constant may then change at runtime, so it cannot be used as a pattern.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+struct Wrapper(AtomicU32);
+
+impl PartialEq for Wrapper {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.load(Ordering::Relaxed) == other.0.load(Ordering::Relaxed)
+    }
+}
+
+impl Eq for Wrapper {}
+
+static W: Wrapper = Wrapper(AtomicU32::new(0));
+static S: &Wrapper = &W;
+
+const C: &&Wrapper = &S;
+
+fn main() {
+    let C = &&W;
+    //~^ ERROR constant pattern depends on mutable memory
+}
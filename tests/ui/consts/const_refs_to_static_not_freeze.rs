@@ -0,0 +1,18 @@
+#![feature(const_refs_to_static)]
+#![allow(const_refs_to_static_mut)]
+
+// Constants may not refer to statics whose value may change at runtime, even with
+// `const_refs_to_static`: neither to a `static mut`, nor to a static with interior mutability.
+
+use std::sync::atomic::AtomicI32;
+
+static mut S_MUT: i32 = 0;
+const C_MUT: &i32 = unsafe { &S_MUT };
+//~^ ERROR constants cannot refer to statics
+//~| ERROR constants cannot refer to statics
+
+static ATOMIC: AtomicI32 = AtomicI32::new(0);
+const C_ATOMIC: &AtomicI32 = &ATOMIC;
+//~^ ERROR constants cannot refer to statics
+
+fn main() {}
@@ -1,3 +1,5 @@
+#![allow(const_refs_to_static_mut)]
+
 const C1: &'static mut [usize] = &mut [];
 //~^ ERROR: mutable references are not allowed
 
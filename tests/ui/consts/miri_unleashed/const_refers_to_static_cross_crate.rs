@@ -2,6 +2,7 @@
 // aux-build:static_cross_crate.rs
 // stderr-per-bitwidth
 #![feature(exclusive_range_pattern, half_open_range_patterns_in_slices)]
+#![allow(const_refs_to_static_mut)]
 
 extern crate static_cross_crate;
 
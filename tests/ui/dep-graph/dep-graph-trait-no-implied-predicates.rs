@@ -0,0 +1,26 @@
+// Test that elaborating a bound on a trait without supertraits or where clauses
+// doesn't need the implied predicates of that trait.
+
+// incremental
+// compile-flags: -Z query-dep-graph
+
+#![feature(rustc_attrs)]
+#![allow(dead_code)]
+
+fn main() {}
+
+#[rustc_if_this_changed(implied_predicates_of)]
+trait Marker {}
+
+#[rustc_if_this_changed(implied_predicates_of)]
+trait WithSuper: Clone {}
+
+#[rustc_then_this_would_need(typeck)] //~ ERROR no path from `Marker` to `typeck`
+//~| ERROR no path from `WithSuper` to `typeck`
+fn uses_marker<T: Marker>(_: T) {}
+
+#[rustc_then_this_would_need(typeck)] //~ ERROR no path from `Marker` to `typeck`
+//~| ERROR OK
+fn uses_with_super<T: WithSuper>(t: &T) -> T {
+    t.clone()
+}
@@ -0,0 +1,5 @@
+static S: i32 = 0;
+
+const C: &i32 = &S; //~ ERROR constants cannot refer to statics
+
+fn main() {}
@@ -0,0 +1,7 @@
+#![crate_type = "lib"]
+
+pub trait Super {}
+pub trait Sub: Super {}
+
+pub fn f<#[do_not_elaborate] T: Sub>() {}
+//~^ ERROR the `#[do_not_elaborate]` attribute is an experimental feature
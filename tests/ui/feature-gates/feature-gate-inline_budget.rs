@@ -0,0 +1,4 @@
+#![crate_type = "lib"]
+
+#[inline_budget(100)] //~ ERROR the `#[inline_budget]` attribute is an experimental feature
+pub fn f() {}
@@ -0,0 +1,4 @@
+#![crate_type = "lib"]
+
+#[inline(never_callees)] //~ ERROR `#[inline(never_callees)]` is currently unstable
+pub fn f() {}
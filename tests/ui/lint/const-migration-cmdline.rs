@@ -0,0 +1,9 @@
+// check-pass
+// compile-flags: -W missing-const-fn
+
+pub fn double(x: u32) -> u32 {
+    //~^ WARNING this function could be a `const fn`
+    x * 2
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+// The `const_migration` lints that const-check function bodies also work when they are only
+// enabled for some items.
+
+#[deny(missing_const_fn)]
+pub fn double(x: u32) -> u32 {
+    //~^ ERROR this function could be a `const fn`
+    x * 2
+}
+
+pub fn triple(x: u32) -> u32 {
+    x * 3
+}
+
+fn main() {}
@@ -0,0 +1,68 @@
+#![feature(const_trait_impl)]
+#![deny(const_migration)]
+
+use std::sync::atomic::AtomicU32;
+
+pub fn double(x: u32) -> u32 {
+    //~^ ERROR this function could be a `const fn`
+    x * 2
+}
+
+pub const fn already_const(x: u32) -> u32 {
+    x * 2
+}
+
+pub fn boxed(x: u32) -> Box<u32> {
+    Box::new(x)
+}
+
+pub fn cloned<T: Clone>(x: &T) -> T {
+    x.clone()
+}
+
+pub struct S(u32);
+
+impl S {
+    pub fn get(&self) -> u32 {
+        //~^ ERROR this function could be a `const fn`
+        self.0
+    }
+
+    pub fn set(&mut self, x: u32) {
+        self.0 = x;
+    }
+}
+
+#[const_trait]
+pub trait Double {
+    fn double(&self) -> Self;
+}
+
+impl Double for u32 {
+    //~^ ERROR this impl could be an `impl const`
+    fn double(&self) -> u32 {
+        *self * 2
+    }
+}
+
+impl Double for String {
+    fn double(&self) -> String {
+        self.repeat(2)
+    }
+}
+
+pub static LIMIT: u32 = 100;
+//~^ ERROR this static could be a `const`
+
+pub static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+#[no_mangle]
+pub static EXPORTED: u32 = 1;
+
+pub static mut MUTABLE: u32 = 0;
+
+// Constants can't refer to statics.
+pub static LIMIT_REF: &u32 = &LIMIT;
+pub static LIMIT_COPY: u32 = LIMIT;
+
+fn main() {}
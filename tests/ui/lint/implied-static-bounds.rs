@@ -0,0 +1,37 @@
+// run-rustfix
+
+#![deny(implied_static_bounds)]
+#![allow(dead_code)]
+
+trait Service: Send + 'static {}
+
+trait Handler: Service {}
+//~^ ERROR bound `Self: Service` implicitly requires `Self: 'static`
+
+fn spawn<S: Service>(_: S) {}
+//~^ ERROR bound `S: Service` implicitly requires `S: 'static`
+
+fn handle<H>(_: H)
+where
+    H: Handler,
+    //~^ ERROR bound `H: Handler` implicitly requires `H: 'static`
+{
+}
+
+fn wrapped<T>()
+where
+    Vec<T>: Service,
+    //~^ ERROR bound `Vec<T>: Service` implicitly requires `T: 'static`
+{
+}
+
+// The `'static` requirement is already visible.
+fn explicit<S: Service + 'static>(_: S) {}
+
+fn explicit_component<T>()
+where
+    Vec<T>: Service + 'static,
+{
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+// Checks that calls to recursive `#[inline(always)]` functions are reported once the MIR inliner
+// has to leave a call in place to break the cycle, whether or not the cycle is unrolled.
+//
+// revisions: default unrolled
+// build-fail
+// compile-flags: -Zinline-mir
+//[unrolled] compile-flags: -Zinline-mir-cycle-depth=3
+
+#![deny(inline_always_cycle)]
+
+#[inline(always)]
+fn countdown(n: u32) {
+    if n > 0 {
+        countdown(n - 1); //~ ERROR was not fully inlined, as it is part of a call cycle
+    }
+}
+
+fn main() {
+    countdown(3); //~ ERROR was not fully inlined, as it is part of a call cycle
+}
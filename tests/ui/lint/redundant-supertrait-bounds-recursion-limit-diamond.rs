@@ -0,0 +1,21 @@
+// run-rustfix
+
+// Supertraits reachable through several paths are searched for redundant bounds
+// up to the recursion limit along their shortest path.
+
+#![deny(redundant_supertrait_bounds)]
+#![recursion_limit = "3"]
+#![allow(dead_code)]
+
+trait Top: Short + Long {}
+trait Short: Shared {}
+trait Long: Mid {}
+trait Mid: Shared {}
+trait Shared: Leaf {}
+trait Leaf {}
+
+// `Leaf` is three supertraits away from `Top` through `Short`, but four through `Long`.
+fn diamond<T: Top + Leaf>() {}
+//~^ ERROR bound `T: Leaf` is redundant
+
+fn main() {}
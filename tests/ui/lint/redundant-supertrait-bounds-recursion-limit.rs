@@ -0,0 +1,28 @@
+// run-rustfix
+
+// Supertraits are only searched for redundant bounds up to the recursion limit, bounds that
+// may be implied beyond it are reported as such.
+
+#![deny(redundant_supertrait_bounds)]
+#![recursion_limit = "2"]
+#![allow(dead_code)]
+
+trait A: B {}
+trait B: C {}
+trait C: D {}
+trait D {}
+
+fn within_limit<T: A + C>() {}
+//~^ ERROR bound `T: C` is redundant
+
+fn beyond_limit<T: A + D>() {}
+//~^ ERROR reached the recursion limit while checking whether bound `T: D` is redundant
+
+trait X: Y {}
+trait Y: Z {}
+trait Z {}
+
+// `Z` is at the recursion limit, but has no supertraits that could be cut off.
+fn at_limit<T: X + Send>() {}
+
+fn main() {}
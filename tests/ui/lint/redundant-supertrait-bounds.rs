@@ -0,0 +1,48 @@
+// run-rustfix
+
+#![deny(redundant_supertrait_bounds)]
+#![allow(dead_code)]
+
+trait Foo: Ord where Self: PartialOrd {}
+//~^ ERROR bound `Self: PartialOrd` is redundant
+
+fn copy<T: Copy + Clone>() {}
+//~^ ERROR bound `T: Clone` is redundant
+
+fn ord<T>()
+where
+    T: PartialEq + Ord,
+    //~^ ERROR bound `T: PartialEq` is redundant
+{
+}
+
+struct S;
+
+impl S {
+    fn method<T: Eq + PartialEq>() {}
+    //~^ ERROR bound `T: PartialEq` is redundant
+}
+
+// Duplicated bounds don't imply each other through supertraits.
+fn duplicated<T: Clone + Clone>() {}
+
+fn unrelated<T: PartialOrd + Eq>() {}
+
+trait A {}
+trait B: A {}
+trait C: B {}
+
+fn chain<T: C + A>() {}
+//~^ ERROR bound `T: A` is redundant
+
+// With several chains, one of the shortest is named.
+trait Base {}
+trait Left: Mid {}
+trait Mid: Base {}
+trait Right: Base {}
+trait Top: Left + Right {}
+
+fn diamond<T: Top + Base>() {}
+//~^ ERROR bound `T: Base` is redundant
+
+fn main() {}
@@ -0,0 +1,12 @@
+// A trait that names itself as a supertrait is a cycle error, which
+// `redundant_supertrait_elaboration` leaves alone.
+
+#![deny(redundant_supertrait_elaboration)]
+
+trait Foo: Foo {}
+//~^ ERROR cycle detected when computing the super predicates of `Foo`
+
+trait Bar: Clone + Bar + Clone {}
+//~^ ERROR cycle detected when computing the super predicates of `Bar`
+
+fn main() {}
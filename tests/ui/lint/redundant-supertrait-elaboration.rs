@@ -0,0 +1,45 @@
+// run-rustfix
+
+#![deny(redundant_supertrait_elaboration)]
+#![allow(dead_code)]
+
+trait Twice: Clone + Clone {}
+//~^ ERROR supertrait bound `Self: Clone` is declared more than once
+
+trait Thrice: Clone + Send + Clone + Clone {}
+//~^ ERROR supertrait bound `Self: Clone` is declared more than once
+//~| ERROR supertrait bound `Self: Clone` is declared more than once
+
+trait WhereClause: Copy
+where
+    Self: Copy + Send,
+    //~^ ERROR supertrait bound `Self: Copy` is declared more than once
+{
+}
+
+trait Outlives: 'static + Send + 'static {}
+//~^ ERROR supertrait bound `Self: 'static` is declared more than once
+
+// Supertraits with different generic arguments are different supertraits.
+trait Generic<T>: AsRef<T> + AsRef<[T]> {}
+
+// Bounds that constrain associated types are not compared, their constraints may differ.
+trait Base {
+    type Output;
+}
+trait Id {
+    type This;
+}
+impl<T> Id for T {
+    type This = T;
+}
+trait Constrained: Base<Output = u8> + Base<Output = <u8 as Id>::This> {}
+
+// Bounds on other types than `Self` don't declare supertraits.
+trait NotSelf<T: Clone>
+where
+    T: Clone,
+{
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+// Used to cause a query cycle: `ConstProp` turns the calls through the function pointers into
+// direct calls, which the inliner's call graph did not know about, so `a` and `b` were inlined
+// into each other.
+//
+// compile-flags: --crate-type=lib -Zmir-opt-level=3
+// build-pass
+
+pub fn a() {
+    let f: fn() = b;
+    f()
+}
+
+pub fn b() {
+    let f: fn() = a;
+    f()
+}
@@ -0,0 +1,35 @@
+// Used to cause a query cycle: `Devirtualize` turns the virtual calls into direct calls to the
+// other impl, which the inliner's call graph did not know about, so both methods were inlined
+// into each other.
+//
+// compile-flags: --crate-type=lib -O
+// build-pass
+
+pub trait Trait {
+    fn m(&self) -> u32;
+}
+
+pub struct A(pub u32);
+pub struct B(pub u32);
+
+impl Trait for A {
+    fn m(&self) -> u32 {
+        if self.0 == 0 {
+            return 0;
+        }
+        let b = B(self.0 - 1);
+        let d: &dyn Trait = &b;
+        d.m()
+    }
+}
+
+impl Trait for B {
+    fn m(&self) -> u32 {
+        if self.0 == 0 {
+            return 1;
+        }
+        let a = A(self.0 - 1);
+        let d: &dyn Trait = &a;
+        d.m()
+    }
+}
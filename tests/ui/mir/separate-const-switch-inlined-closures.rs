@@ -0,0 +1,56 @@
+// run-pass
+// compile-flags: -Zmir-opt-level=3 -Zinline-mir
+// Checks that threading switches over enum discriminants through the
+// bodies of inlined closures keeps the branches that were taken.
+
+#[derive(Debug, PartialEq)]
+enum Shape {
+    Circle(u32),
+    Square(u32),
+    Empty,
+}
+
+#[inline]
+fn with<T, R>(value: T, f: impl FnOnce(T) -> R) -> R {
+    f(value)
+}
+
+fn area(shape: Shape) -> u32 {
+    with(shape, |shape| match shape {
+        Shape::Circle(r) => 3 * r * r,
+        Shape::Square(s) => s * s,
+        Shape::Empty => 0,
+    })
+}
+
+fn wrap(x: u32) -> Option<u32> {
+    let wrapped = with(x, |x| if x > 10 { Some(x) } else { None });
+    with(wrapped, |wrapped| match wrapped {
+        Some(x) => Some(x - 10),
+        None => None,
+    })
+}
+
+fn classify(x: Result<u32, u32>) -> Shape {
+    let shape = with(x, |x| match x {
+        Ok(r) => Shape::Circle(r),
+        Err(0) => Shape::Empty,
+        Err(s) => Shape::Square(s),
+    });
+    with(shape, |shape| match shape {
+        Shape::Circle(0) => Shape::Empty,
+        shape => shape,
+    })
+}
+
+fn main() {
+    assert_eq!(area(Shape::Circle(2)), 12);
+    assert_eq!(area(Shape::Square(3)), 9);
+    assert_eq!(area(Shape::Empty), 0);
+    assert_eq!(wrap(5), None);
+    assert_eq!(wrap(15), Some(5));
+    assert_eq!(classify(Ok(0)), Shape::Empty);
+    assert_eq!(classify(Ok(4)), Shape::Circle(4));
+    assert_eq!(classify(Err(0)), Shape::Empty);
+    assert_eq!(classify(Err(7)), Shape::Square(7));
+}
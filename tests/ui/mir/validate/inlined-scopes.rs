@@ -0,0 +1,24 @@
+// Checks that the source scopes and argument debuginfo of inlined callees, including nested
+// and cross-crate ones, pass validation.
+//
+// build-pass
+// compile-flags: -Zvalidate-mir -Zmir-opt-level=3 -Zinline-mir -Cdebuginfo=2
+
+#[inline]
+fn add(a: u32, b: u32) -> u32 {
+    a.wrapping_add(b)
+}
+
+#[inline]
+fn add_twice(a: u32, b: u32) -> u32 {
+    let once = add(a, b);
+    add(once, b)
+}
+
+struct Wrapper(u32);
+
+fn main() {
+    let closure = |x: u32| add_twice(x, 1);
+    let wrapped = Some(3).map(Wrapper).map(|Wrapper(x)| closure(x));
+    assert_eq!(wrapped, Some(5));
+}
@@ -0,0 +1,24 @@
+// run-pass
+// revisions: default mir-opt
+//[default] compile-flags: -Zinline-mir=no
+//[mir-opt] compile-flags: -Zmir-opt-level=4
+
+// Calling a function item through the `Fn*` traits goes through its shim, so `#[track_caller]`
+// functions see a location in `core` rather than the call in `apply`. The MIR inliner usually
+// calls the function item directly instead, which must not change the reported location.
+
+#[inline]
+fn apply(f: impl Fn(u32) -> u32, x: u32) -> u32 {
+    f(x)
+}
+
+#[track_caller]
+fn tracked(x: u32) -> u32 {
+    let location = std::panic::Location::caller();
+    assert_ne!(location.file(), file!(), "call shims report a location in `core`");
+    x + 1
+}
+
+fn main() {
+    assert_eq!(apply(tracked, 1), 2);
+}
@@ -0,0 +1,26 @@
+#![feature(const_trait_impl)]
+
+#[const_trait]
+pub trait Bar {
+    fn bar(&self) -> u8;
+}
+
+#[const_trait]
+pub trait Foo {
+    fn foo(&self) -> u8
+    where
+        Self: ~const Bar,
+    {
+        self.bar() * 2
+    }
+}
+
+pub struct S;
+
+impl const Bar for S {
+    fn bar(&self) -> u8 {
+        21
+    }
+}
+
+impl const Foo for S {}
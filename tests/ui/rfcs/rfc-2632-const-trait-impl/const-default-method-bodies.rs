@@ -22,7 +22,7 @@ fn b(self) {}
 
 const fn test() {
     NonConstImpl.a();
-    //~^ ERROR the trait bound
+    //~^ ERROR calling non-const `ConstDefaultFn::a`
     ConstImpl.a();
 }
 
@@ -0,0 +1,27 @@
+// Check that the error for dropping a value at compile-time points out what keeps it from being
+// dropped in a const context.
+
+#![feature(const_trait_impl, const_mut_refs)]
+
+struct NonTrivialDrop;
+
+impl Drop for NonTrivialDrop {
+    fn drop(&mut self) {}
+}
+
+struct ConstImplWithDropGlue(NonTrivialDrop);
+
+impl const Drop for ConstImplWithDropGlue {
+    fn drop(&mut self) {}
+}
+
+const fn drop_direct(_x: NonTrivialDrop) {}
+//~^ ERROR destructor of `NonTrivialDrop` cannot be evaluated at compile-time
+
+const fn drop_glue(_x: ConstImplWithDropGlue) {}
+//~^ ERROR destructor of `ConstImplWithDropGlue` cannot be evaluated at compile-time
+
+const fn drop_param<T>(_x: T) {}
+//~^ ERROR destructor of `T` cannot be evaluated at compile-time
+
+fn main() {}
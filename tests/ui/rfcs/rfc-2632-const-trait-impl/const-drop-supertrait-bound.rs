@@ -0,0 +1,24 @@
+// check-pass
+// Check that a `~const Destruct` supertrait bound lets const fns drop values of types bounded
+// by the subtrait.
+
+#![feature(const_trait_impl, const_mut_refs)]
+
+use std::marker::Destruct;
+
+#[const_trait]
+trait Resource: ~const Destruct {}
+
+struct Handle;
+
+impl const Drop for Handle {
+    fn drop(&mut self) {}
+}
+
+impl const Resource for Handle {}
+
+const fn release<T: ~const Resource>(_resource: T) {}
+
+const _: () = release(Handle);
+
+fn main() {}
@@ -0,0 +1,25 @@
+// Default method bodies of a `#[const_trait]` from another crate, whose `~const` where-clauses
+// are only known through that crate's metadata, can be called in const contexts.
+
+// revisions: stock gated
+// [gated] run-pass
+#![cfg_attr(gated, feature(const_trait_impl))]
+
+// aux-build: cross-crate-default-method-bounds.rs
+extern crate cross_crate_default_method_bounds;
+
+use cross_crate_default_method_bounds::*;
+
+const fn const_context() -> u8 {
+    S.foo()
+    //[stock]~^ ERROR: cannot call
+}
+
+#[cfg(gated)]
+const ANS: u8 = const_context();
+
+fn main() {
+    assert_eq!(S.foo(), 42);
+    #[cfg(gated)]
+    assert_eq!(ANS, 42);
+}
@@ -0,0 +1,20 @@
+// check-run-pass-in-const
+// aux-build: cross-crate.rs
+#![feature(const_trait_impl)]
+
+extern crate cross_crate;
+
+use cross_crate::*;
+
+const fn const_context() -> bool {
+    Const.func();
+    true
+}
+
+#[cfg(run_pass_in_const)]
+const _: () = assert!(const_context());
+
+fn main() {
+    NonConst.func();
+    assert!(const_context());
+}
@@ -15,8 +15,8 @@ fn non_const_context() {
 const fn const_context() {
     #[cfg(any(stocknc, gatednc))]
     NonConst.func();
-    //[stocknc]~^ ERROR: the trait bound
-    //[gatednc]~^^ ERROR: the trait bound
+    //[stocknc]~^ ERROR: calling non-const `cross_crate::MyTrait::func`
+    //[gatednc]~^^ ERROR: calling non-const `cross_crate::MyTrait::func`
     Const.func();
     //[stock]~^ ERROR: cannot call
 }
@@ -6,7 +6,7 @@ fn a(&self) {}
 
     fn b(&self) {
         ().a()
-        //~^ ERROR the trait bound
+        //~^ ERROR calling non-const `Tr::a`
     }
 }
 
@@ -0,0 +1,41 @@
+// run-pass
+// Associated functions of traits that are not `#[const_trait]` can still declare `~const`
+// bounds of their own. Outside of a const context, they behave like plain bounds.
+
+#![feature(const_trait_impl)]
+
+#[const_trait]
+trait Bar {
+    fn bar(&self) -> u8;
+}
+
+trait Foo {
+    fn foo<T: ~const Bar>(t: &T) -> u8 {
+        t.bar()
+    }
+
+    fn foo_dup<T: Bar>(t: &T) -> u8
+    where
+        T: ~const Bar,
+    {
+        t.bar()
+    }
+}
+
+struct S;
+
+impl Bar for S {
+    fn bar(&self) -> u8 {
+        21
+    }
+}
+
+impl Foo for S {
+    fn foo<T: ~const Bar>(t: &T) -> u8 {
+        t.bar() + 1
+    }
+}
+
+fn main() {
+    assert_eq!(S::foo(&S) + S::foo_dup(&S), 43);
+}
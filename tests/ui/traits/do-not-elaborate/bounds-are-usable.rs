@@ -0,0 +1,31 @@
+// check-pass
+// Tests that the bounds on `#[do_not_elaborate]` parameters can still be used directly, and
+// that they are well-formed even though their supertraits are not in the param-env.
+
+#![feature(do_not_elaborate)]
+
+trait Super {}
+trait Sub: Super {}
+
+fn needs_sub<T: Sub>(_: &T) {}
+
+struct S;
+impl Super for S {}
+impl Sub for S {}
+
+fn f<#[do_not_elaborate] T: Sub>(x: &T) {
+    needs_sub(x);
+}
+
+struct Wrapper<#[do_not_elaborate] T: Sub>(T);
+
+impl<#[do_not_elaborate] T: Sub> Wrapper<T> {
+    fn g(&self) {
+        needs_sub(&self.0);
+    }
+}
+
+fn main() {
+    f(&S);
+    Wrapper(S).g();
+}
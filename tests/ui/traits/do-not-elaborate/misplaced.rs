@@ -0,0 +1,11 @@
+#![feature(do_not_elaborate)]
+#![crate_type = "lib"]
+
+#[do_not_elaborate] //~ ERROR attribute should be applied to a type parameter
+pub fn f<#[do_not_elaborate] T>() {}
+
+pub fn g<#[do_not_elaborate] 'a>() {}
+//~^ ERROR attribute should be applied to a type parameter
+
+pub fn h<#[do_not_elaborate] const N: usize>() {}
+//~^ ERROR attribute should be applied to a type parameter
@@ -0,0 +1,32 @@
+// Tests that the supertraits of the bounds on `#[do_not_elaborate]` parameters have to be
+// spelled out, and that the error points at the attribute.
+
+#![feature(do_not_elaborate)]
+
+trait Super {}
+trait Sub: Super {
+    fn sub(&self) {}
+}
+
+fn needs_super<T: Super>(_: &T) {}
+
+fn elaborated<T: Sub>(x: &T) {
+    needs_super(x);
+}
+
+fn not_elaborated<#[do_not_elaborate] T: Sub>(x: &T) {
+    needs_super(x);
+    //~^ ERROR the trait bound `T: Super` is not satisfied
+}
+
+// Methods of `Sub` require `Self: Super` as well.
+fn method<#[do_not_elaborate] T: Sub>(x: &T) {
+    x.sub();
+    //~^ ERROR the trait bound `T: Super` is not satisfied
+}
+
+fn explicit<#[do_not_elaborate] T: Sub + Super>(x: &T) {
+    needs_super(x);
+}
+
+fn main() {}
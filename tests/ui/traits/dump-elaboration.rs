@@ -0,0 +1,14 @@
+// Checks the per-predicate statistics printed with `-Zdump-elaboration`. `Base` is implied
+// twice by `Diamond`, so one of them is deduplicated.
+//
+// check-pass
+// compile-flags: -Zdump-elaboration
+
+#![crate_type = "lib"]
+
+trait Base {}
+trait Left: Base {}
+trait Right: Base {}
+trait Diamond: Left + Right {}
+
+fn diamond<T: Diamond>() {}
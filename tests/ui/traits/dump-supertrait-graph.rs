@@ -0,0 +1,13 @@
+// Checks the output of `-Zdump-supertrait-graph`, which only includes the traits matching the
+// filter, and their transitive supertraits.
+//
+// check-pass
+// compile-flags: -Zdump-supertrait-graph=Sub
+
+trait Base: 'static {}
+trait Left<T>: Base {}
+trait Right: Base {}
+trait Sub: Left<u32> + Right {}
+trait Unrelated {}
+
+fn main() {}
@@ -0,0 +1,20 @@
+// compare-solvers
+// compare-solvers-allow: the trait bound `u32: Other` is not satisfied
+// Checks that the diagnostics matching `compare-solvers-allow`, and the error count that
+// includes them, are left out of the comparison of the old and the new trait solver.
+
+trait Trait {}
+trait Other {}
+
+impl Trait for u8 {}
+impl Other for u8 {}
+
+fn needs<T: Trait>() {}
+fn needs_other<T: Other>() {}
+
+fn main() {
+    needs::<u32>();
+    //~^ ERROR the trait bound `u32: Trait` is not satisfied
+    needs_other::<u32>();
+    //~^ ERROR the trait bound `u32: Other` is not satisfied
+}
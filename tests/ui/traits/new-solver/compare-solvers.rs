@@ -0,0 +1,13 @@
+// compare-solvers
+// Checks that the old and the new trait solver report the same unsatisfied bound.
+
+trait Trait {}
+
+impl Trait for u8 {}
+
+fn needs<T: Trait>() {}
+
+fn main() {
+    needs::<u32>();
+    //~^ ERROR the trait bound `u32: Trait` is not satisfied
+}
@@ -0,0 +1,17 @@
+// compile-flags: -Ztrait-solver=next
+
+// Check that overflow caused by an inductive cycle lists every goal
+// involved in that cycle.
+
+trait A {}
+trait B {}
+
+impl<T: B> A for T {}
+impl<T: A> B for T {}
+
+fn needs_a<T: A>() {}
+
+fn main() {
+    needs_a::<()>();
+    //~^ ERROR overflow evaluating the requirement `(): A`
+}
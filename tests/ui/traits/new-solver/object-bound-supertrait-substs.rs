@@ -0,0 +1,25 @@
+// compile-flags: -Ztrait-solver=next
+// check-pass
+
+// The supertraits of the principal of a trait object are elaborated once per
+// trait and then substituted with the actual trait ref. Check that generic,
+// higher-ranked and duplicated supertraits are still found.
+
+trait Base<T> {}
+trait Hr<'a> {}
+
+trait Mid<T>: Base<T> + Base<u32> + for<'a> Hr<'a> {}
+trait Top<T, U>: Mid<T> + Mid<U> {}
+
+fn needs_base<T, B: Base<T> + ?Sized>() {}
+fn needs_hr<B: for<'a> Hr<'a> + ?Sized>() {}
+
+fn foo<T, U>() {
+    needs_base::<T, dyn Top<T, U>>();
+    needs_base::<U, dyn Top<T, U>>();
+    needs_base::<u32, dyn Top<T, U>>();
+    needs_base::<u32, dyn Top<u32, u32>>();
+    needs_hr::<dyn Top<T, U>>();
+}
+
+fn main() {}
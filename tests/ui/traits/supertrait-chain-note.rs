@@ -0,0 +1,16 @@
+// Check that the chain of supertrait bounds from a bound to an unsatisfied
+// supertrait bound is pointed at, from the unsatisfied bound up.
+
+trait Base {}
+trait C: Base {}
+trait B: C {}
+trait A: B {}
+
+impl<T> C for T {}
+//~^ ERROR the trait bound `T: Base` is not satisfied
+impl<T> B for T {}
+//~^ ERROR the trait bound `T: Base` is not satisfied
+impl<T> A for T {}
+//~^ ERROR the trait bound `T: Base` is not satisfied
+
+fn main() {}
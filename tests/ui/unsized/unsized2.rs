@@ -68,6 +68,8 @@ trait T5<X: ?Sized> {
     fn dummy(&self) { }
     // not an error (for now)
     fn m1(&self, x: &dyn T4<X>);
+    //~^ WARN the trait object in `&dyn T4<X>` requires `X: Sized`
+    //~| WARN this was previously accepted
     fn m2(&self, x: &dyn T5<X>);
 }
 
@@ -80,6 +82,8 @@ trait T7<X: ?Sized+T> {
     fn dummy(&self) { }
     // not an error (for now)
     fn m1(&self, x: &dyn T4<X>);
+    //~^ WARN the trait object in `&dyn T4<X>` requires `X: Sized`
+    //~| WARN this was previously accepted
     fn m2(&self, x: &dyn T5<X>);
 }
 
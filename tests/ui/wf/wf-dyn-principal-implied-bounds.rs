@@ -0,0 +1,20 @@
+// check-pass
+// Checks that trait object types whose principal trait has where clauses that don't hold for its
+// generic arguments are linted, and not rejected yet.
+
+trait Trait<T: Copy> {}
+
+trait Sub<T: 'static>: Super<T> {}
+trait Super<T: 'static> {}
+
+fn copy(_: &dyn Trait<String>) {}
+//~^ WARN the trait object in `&dyn Trait<String>` requires `String: Copy`
+//~| WARN this was previously accepted
+
+fn outlives<'a>(_: &dyn Sub<&'a u8>) {}
+//~^ WARN the trait object in `&dyn Sub<&'a u8>` requires `&'a u8: 'static`
+//~| WARN this was previously accepted
+
+fn satisfied(_: &dyn Trait<u8>, _: &dyn Sub<&'static u8>) {}
+
+fn main() {}